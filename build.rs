@@ -0,0 +1,140 @@
+//! Composes every WGSL shader and validates it with naga before the crate is
+//! compiled, so a broken shader fails the build at its file and line instead
+//! of panicking at pipeline creation. Also generates the module table behind
+//! `rendering::shaders::SHADER_MODULES`.
+
+use std::fs;
+use std::path::Path;
+
+#[path = "src/rendering/preprocessor.rs"]
+mod preprocessor;
+
+use preprocessor::ComposedShader;
+
+/// Root files, one per shader; everything under `src/shaders` is a module.
+const ROOTS: &[&str] = &["shader.wgsl", "grid.wgsl", "plots.wgsl", "post.wgsl"];
+
+/// Values each `override` constant is also validated with, besides its default.
+const VARIANTS: &[(&str, &[&str])] = &[
+    ("WORKGROUP_SIZE", &["32u", "256u"]),
+    ("SPIN_MARKERS", &["false"]),
+    ("CONTACTS", &["false"]),
+    ("POST_NEWTONIAN", &["false"]),
+];
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/shaders");
+    println!("cargo::rerun-if-changed=src/rendering/preprocessor.rs");
+
+    let mut modules: Vec<(String, String)> = fs::read_dir("src/shaders")
+        .expect("src/shaders is readable")
+        .map(|entry| {
+            let path = entry.expect("src/shaders is readable").path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, read(&path))
+        })
+        .collect();
+    modules.sort();
+    let modules: Vec<(&str, &str)> = modules
+        .iter()
+        .map(|(name, source)| (name.as_str(), source.as_str()))
+        .collect();
+
+    // The runtime module table, so every file here is also includable in the app.
+    let table: String = modules
+        .iter()
+        .map(|(name, _)| {
+            format!("    ({name:?}, include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/src/shaders/{name}\"))),\n")
+        })
+        .collect();
+    let out_dir = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(
+        Path::new(&out_dir).join("shader_modules.rs"),
+        format!("&[\n{table}]\n"),
+    )
+    .expect("OUT_DIR is writable");
+
+    let mut failed = false;
+    for root in ROOTS {
+        let path = format!("src/{root}");
+        println!("cargo::rerun-if-changed={path}");
+        let composed = match preprocessor::compose(root, &read(Path::new(&path)), &modules) {
+            Ok(composed) => composed,
+            Err(error) => {
+                println!("cargo::error={error}");
+                failed = true;
+                continue;
+            }
+        };
+        // Variants only differ in the overrides, so the first broken one is
+        // reported rather than the same error once per variant.
+        for (variant, source) in variants(&composed.source) {
+            if let Err(error) = validate(&composed, &source) {
+                println!("cargo::error={root} ({variant}): {error}");
+                failed = true;
+                break;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|error| panic!("{}: {error}", path.display()))
+}
+
+/// The composed source with every override at its default, then once per
+/// entry of `VARIANTS` it declares. Defaults are rewritten in place, so line
+/// numbers stay those of the composed source.
+fn variants(source: &str) -> Vec<(String, String)> {
+    let mut variants = vec![("defaults".to_owned(), source.to_owned())];
+    for (name, values) in VARIANTS {
+        let declaration = format!("override {name}:");
+        let Some(line) = source
+            .lines()
+            .find(|line| line.trim_start().starts_with(&declaration))
+        else {
+            continue;
+        };
+        let (head, _) = line.split_once('=').expect("overrides have a default");
+        for value in *values {
+            variants.push((
+                format!("{name} = {value}"),
+                source.replacen(line, &format!("{head}= {value};"), 1),
+            ));
+        }
+    }
+    variants
+}
+
+/// Parses and validates `source`, reporting the first error at the file and
+/// line it came from before preprocessing.
+fn validate(composed: &ComposedShader, source: &str) -> Result<(), String> {
+    let at = |location: Option<naga::SourceLocation>| {
+        location
+            .and_then(|location| {
+                let (file, line) = composed.origin(location.line_number)?;
+                let directory = if ROOTS.contains(&file) {
+                    "src"
+                } else {
+                    "src/shaders"
+                };
+                Some(format!(
+                    "{directory}/{file}:{line}:{}",
+                    location.line_position
+                ))
+            })
+            .unwrap_or_else(|| "<composed>".to_owned())
+    };
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|error| format!("{}: {}", at(error.location(source)), error.message()))?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|error| format!("{}: {}", at(error.location(source)), error.as_inner()))?;
+    Ok(())
+}
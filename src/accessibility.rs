@@ -0,0 +1,74 @@
+//! Screen-reader output through AccessKit. The window is a single node with
+//! one polite live region holding the latest announcements from
+//! `ui::announcements::Announcer`; the host hands these updates to its
+//! platform adapter (e.g. `accesskit_winit`).
+
+use accesskit::{Live, Node, NodeId, Role, Tree, TreeId, TreeUpdate};
+
+const WINDOW: NodeId = NodeId(0);
+/// The live region alternates between these ids, so repeating the same text
+/// (pausing twice, say) still reads as a change.
+const ANNOUNCEMENTS: [NodeId; 2] = [NodeId(1), NodeId(2)];
+
+/// The accessibility tree the app exposes and the slot of its live region.
+#[derive(Clone, Debug, Default)]
+pub struct AccessibilityTree {
+    slot: usize,
+    /// Latest announcement, repeated in the full tree for a newly attached
+    /// screen reader.
+    latest: Option<String>,
+}
+
+impl AccessibilityTree {
+    /// The whole tree, for the adapter's first request.
+    pub fn full(&self, window_label: &str) -> TreeUpdate {
+        let mut nodes = vec![(WINDOW, self.window(window_label, self.latest.is_some()))];
+        if let Some(text) = &self.latest {
+            nodes.push((ANNOUNCEMENTS[self.slot], announcement(text)));
+        }
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(WINDOW)),
+            tree_id: TreeId::ROOT,
+            focus: WINDOW,
+        }
+    }
+
+    /// Replaces the live region with `announcements`, one per line; `None`
+    /// when there is nothing new to say.
+    pub fn announce(&mut self, window_label: &str, announcements: &[String]) -> Option<TreeUpdate> {
+        if announcements.is_empty() {
+            return None;
+        }
+        let text = announcements.join("\n");
+        self.slot = (self.slot + 1) % ANNOUNCEMENTS.len();
+        let nodes = vec![
+            (ANNOUNCEMENTS[self.slot], announcement(&text)),
+            (WINDOW, self.window(window_label, true)),
+        ];
+        self.latest = Some(text);
+        Some(TreeUpdate {
+            nodes,
+            tree: None,
+            tree_id: TreeId::ROOT,
+            focus: WINDOW,
+        })
+    }
+
+    /// The window, parenting the live region once there is one.
+    fn window(&self, label: &str, has_announcement: bool) -> Node {
+        let mut node = Node::new(Role::Window);
+        node.set_label(label);
+        if has_announcement {
+            node.set_children(vec![ANNOUNCEMENTS[self.slot]]);
+        }
+        node
+    }
+}
+
+fn announcement(text: &str) -> Node {
+    let mut node = Node::new(Role::Label);
+    node.set_value(text);
+    node.set_live(Live::Polite);
+    node
+}
@@ -0,0 +1,1766 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use glam::Vec2;
+
+use crate::camera::Camera;
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::formats::body_json::BodyState;
+use crate::formats::gltf::{self, GltfOptions};
+use crate::formats::ply;
+use crate::formats::{FormatError, ParticleSnapshot, SnapshotFormat};
+use crate::input::{InputState, KeyCode, MouseAction, MouseButton, MouseScheme};
+use crate::localization::{Language, Localizer};
+use crate::recording::{VideoCodec, VideoConfig, VideoRecorder};
+use crate::rendering::accumulation;
+use crate::rendering::config::RenderConfig;
+use crate::rendering::debug_targets::DebugChannel;
+use crate::rendering::minimap::{Minimap, MinimapView};
+use crate::rendering::orbit_outlines::OrbitOutlines;
+use crate::rendering::palette::ColorPalette;
+use crate::rendering::picture_in_picture::ScissorRect;
+use crate::rendering::reference::{LengthUnit, LineVertex, ScaleBar};
+use crate::rendering::render_scale::{AdaptiveResolution, RenderScale};
+use crate::rendering::resize::{ResizeCoalescer, SurfaceSize};
+use crate::rendering::shaders::ShaderSources;
+use crate::rendering::texture::TextureFormat;
+use crate::rendering::trails;
+use crate::rendering::uniforms::ColorMode;
+use crate::share::{CameraPose, ShareError, SharedScenario};
+use crate::simulation::worker::{SimulationFrame, SimulationWorker, WorkerConfig};
+use crate::simulation::{
+    BinaryFrame, Body, GroupMask, ReferenceFrame, RotatingView, SimulationError, SimulationManager,
+    ThrustInput,
+};
+use crate::tools::gravity_well::GravityWellTool;
+use crate::tools::measure::MeasureTool;
+use crate::tools::picking::{PICK_RADIUS, pick_body};
+use crate::tools::point_cloud::PointCloudSeries;
+use crate::tools::spawn::SpawnTool;
+use crate::tools::trails::TrailHistory;
+use crate::tools::trajectory::TrajectoryRecorder;
+use crate::ui::inspector::BodyInspector;
+use crate::ui::measurement::Measurement;
+use crate::ui::orbit_hud::{self, OrbitHud};
+use crate::ui::shader_editor::ShaderEditor;
+use crate::ui::simulation_palette::SimulationPalette;
+use crate::ui::theme::{TextSize, UiTheme};
+use crate::ui::toasts::Toasts;
+use crate::ui::tooltip::BodyTooltip;
+
+/// Camera turn per pixel of rotate-drag, in radians.
+const ROTATE_PER_PIXEL: f32 = 0.005;
+/// Camera turn per rotate nudge, in radians (2°).
+const NUDGE_ROTATE: f32 = 0.035;
+/// Pan per nudge, as a fraction of the view distance.
+const NUDGE_PAN: f32 = 0.02;
+/// Zoom per nudge, as a fraction of the view distance.
+const NUDGE_ZOOM: f32 = 0.05;
+/// Scale on every camera delta while the precision modifier (Alt) is held.
+const PRECISION_SCALE: f32 = 0.1;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AppSettings {
+    /// Keep camera, time scale and pause state when switching simulations.
+    pub hot_swap: bool,
+    pub visible_groups: GroupMask,
+    /// Show the bodies in the co-rotating frame picked by `rotating_view`.
+    pub rotating_frame: bool,
+    pub rotating_view: RotatingView,
+    /// Origin the bodies are drawn relative to; the physics is unaffected.
+    pub reference_frame: ReferenceFrame,
+    /// Phase-space, mass and density plots over the scene.
+    pub diagnostics_panel: bool,
+    pub color_mode: ColorMode,
+    /// Colormap and kind tints of the bodies, and the UI theme.
+    pub color_palette: ColorPalette,
+    /// Label size on top of the platform's scale factor.
+    pub text_size: TextSize,
+    /// Auxiliary render target shown over the frame, if any.
+    pub debug_channel: DebugChannel,
+    /// Fraction of the previous frame kept under the current one, for
+    /// streaking trails without the trail system; zero turns it off.
+    pub accumulation_decay: f32,
+    /// Draw bodies through `rendering::lod`: impostors near, points far.
+    pub level_of_detail: bool,
+    /// Ground grid, axes and scale bar from `rendering::reference`.
+    pub reference_overlay: bool,
+    /// Drop-lines from stars and planets to the ecliptic plane.
+    pub ecliptic_guides: bool,
+    /// Outlines of the named planets' full orbits.
+    pub orbit_outlines: bool,
+    /// Top-down inset of the whole system.
+    pub minimap: Minimap,
+    /// Internal resolution relative to the window.
+    pub render_scale: RenderScale,
+    /// Which mouse button rotates, pans and zooms the camera.
+    pub mouse_scheme: MouseScheme,
+    /// Keep integrating while the window is minimized or occluded, instead of
+    /// pausing until it is shown again.
+    pub simulate_while_hidden: bool,
+    /// Language of toasts, labels and overlays; change with `App::set_language`.
+    pub language: Language,
+}
+
+/// Camera plus whether it should be re-fitted once the next scenario's bodies arrive.
+struct View {
+    camera: Camera,
+    refit_pending: bool,
+    /// Simulation whose first frame the refit should wait for.
+    awaiting_simulation: Option<usize>,
+    /// Distance of the last refit, which remote zoom controls scale.
+    fitted_distance: f32,
+}
+
+impl EventHandler for View {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        match event {
+            AppEvent::SimulationSwitched {
+                index,
+                hot_swap: false,
+            } => {
+                self.refit_pending = true;
+                self.awaiting_simulation = Some(*index);
+                self.camera.follow = None;
+            }
+            AppEvent::SimulationSwitched { hot_swap: true, .. } => self.camera.follow = None,
+            AppEvent::SnapshotImported(_) => {
+                self.refit_pending = true;
+                self.awaiting_simulation = None;
+                self.camera.follow = None;
+            }
+            // Removal reorders the bodies, so the followed index goes stale.
+            AppEvent::SupernovaTriggered(_) => self.camera.follow = None,
+            _ => {}
+        }
+    }
+}
+
+/// Window-independent application core: owns the simulation thread, the camera
+/// and the event queue that connects them.
+pub struct App {
+    pub settings: AppSettings,
+    events: EventQueue,
+    view: View,
+    worker: SimulationWorker,
+    frame: Option<SimulationFrame>,
+    /// The frame's bodies as shown, i.e. after any reference-frame transform.
+    display: Vec<Body>,
+    input: InputState,
+    resize: ResizeCoalescer,
+    pub gravity_well: GravityWellTool,
+    pub spawn: SpawnTool,
+    pub measure: MeasureTool,
+    pub toasts: Toasts,
+    /// User-facing text in `settings.language`, shared with `toasts`.
+    locale: Arc<Localizer>,
+    pub shader_editor: ShaderEditor,
+    pub simulation_palette: SimulationPalette,
+    pub inspector: BodyInspector,
+    shader_sources: ShaderSources,
+    /// Trail of the followed body, for export from the inspector.
+    trajectory: TrajectoryRecorder,
+    /// Recent positions of every body, for the glTF export.
+    trails: TrailHistory,
+    /// Recent positions of every body as shown, for on-screen trails; its
+    /// length follows the render config.
+    shown_trails: TrailHistory,
+    /// Frame `shown_trails` were recorded in; changing it restarts them.
+    shown_trails_view: Option<(ReferenceFrame, bool, RotatingView)>,
+    orbit_outlines: OrbitOutlines,
+    /// PLY batch export, between `start_point_cloud_series` and `stop_point_cloud_series`.
+    point_clouds: Option<PointCloudSeries>,
+    /// ffmpeg capture, between `start_recording` and `stop_recording`.
+    recording: Option<VideoRecorder>,
+    simulation_names: Vec<String>,
+    /// Camera drag in progress: the button held and what it does.
+    camera_drag: Option<(MouseButton, MouseAction)>,
+    /// Body being dragged across the camera plane while paused.
+    body_drag: Option<usize>,
+    /// Bodies changed locally since `upload_body_edits`, by index.
+    body_writes: Vec<usize>,
+    /// Set by `set_occluded`; minimization is tracked by `resize`.
+    occluded: bool,
+    /// Whether the app paused the simulation itself when the window was
+    /// hidden, so it knows to resume it.
+    paused_while_hidden: bool,
+    /// Render-scale controller fed by `record_gpu_frame`.
+    adaptive_resolution: AdaptiveResolution,
+    /// Looks the user changed, by simulation index; the rest keep the scenario's defaults.
+    render_configs: HashMap<usize, RenderConfig>,
+    /// WebSocket feed of the frames, once `start_streaming` has bound it.
+    #[cfg(feature = "net")]
+    stream: Option<crate::net::stream::StreamServer>,
+    /// JSON-RPC endpoint, once `start_control_server` has bound it.
+    #[cfg(feature = "net")]
+    control: Option<crate::net::control::ControlServer>,
+    /// Show-control cues, once `start_osc` has bound the socket.
+    #[cfg(feature = "net")]
+    osc: Option<crate::net::osc::OscServer>,
+    /// Prometheus endpoint, once `start_metrics` has bound it.
+    #[cfg(feature = "metrics")]
+    metrics: Option<crate::metrics::MetricsExporter>,
+    /// Knobs and faders, once `connect_midi` has opened a port.
+    #[cfg(feature = "midi")]
+    midi: Option<crate::midi::MidiController>,
+    /// Guided tour on screen, from `start_tour` until past its last step.
+    #[cfg(feature = "tour")]
+    tour: Option<crate::tour::TourPlayer>,
+    /// What screen readers have yet to be told.
+    #[cfg(feature = "accesskit")]
+    announcer: crate::ui::announcements::Announcer,
+    #[cfg(feature = "accesskit")]
+    accessibility: crate::accessibility::AccessibilityTree,
+}
+
+impl App {
+    pub fn new(
+        manager: SimulationManager,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, SimulationError> {
+        let mut camera = Camera::new(1.0);
+        camera.set_viewport_size(width, height);
+        let simulation_names: Vec<String> = manager.names().map(String::from).collect();
+        let locale = Arc::new(Localizer::default());
+        Ok(Self {
+            settings: AppSettings::default(),
+            events: EventQueue::new(),
+            view: View {
+                camera,
+                refit_pending: true,
+                awaiting_simulation: None,
+                fitted_distance: camera.distance,
+            },
+            worker: SimulationWorker::spawn(manager, WorkerConfig::default())?,
+            frame: None,
+            display: Vec::new(),
+            input: InputState::default(),
+            resize: ResizeCoalescer::new(width, height),
+            gravity_well: GravityWellTool::default(),
+            spawn: SpawnTool::default(),
+            measure: MeasureTool::default(),
+            toasts: Toasts::new(simulation_names.clone(), Arc::clone(&locale)),
+            locale,
+            shader_editor: ShaderEditor::default(),
+            simulation_palette: SimulationPalette::default(),
+            inspector: BodyInspector::default(),
+            shader_sources: ShaderSources::default(),
+            trajectory: TrajectoryRecorder::default(),
+            trails: TrailHistory::default(),
+            shown_trails: TrailHistory::default(),
+            shown_trails_view: None,
+            orbit_outlines: OrbitOutlines::default(),
+            point_clouds: None,
+            recording: None,
+            simulation_names,
+            camera_drag: None,
+            body_drag: None,
+            body_writes: Vec::new(),
+            occluded: false,
+            paused_while_hidden: false,
+            adaptive_resolution: AdaptiveResolution::default(),
+            render_configs: HashMap::new(),
+            #[cfg(feature = "net")]
+            stream: None,
+            #[cfg(feature = "net")]
+            control: None,
+            #[cfg(feature = "net")]
+            osc: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            #[cfg(feature = "midi")]
+            midi: None,
+            #[cfg(feature = "tour")]
+            tour: None,
+            #[cfg(feature = "accesskit")]
+            announcer: crate::ui::announcements::Announcer::default(),
+            #[cfg(feature = "accesskit")]
+            accessibility: crate::accessibility::AccessibilityTree::default(),
+        })
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.view.camera
+    }
+
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.view.camera
+    }
+
+    pub fn events(&mut self) -> &mut EventQueue {
+        &mut self.events
+    }
+
+    /// Latest bodies and state received from the simulation thread.
+    pub fn frame(&self) -> Option<&SimulationFrame> {
+        self.frame.as_ref()
+    }
+
+    /// Shader sources the pipelines should be built from.
+    pub fn shader_sources(&self) -> &ShaderSources {
+        &self.shader_sources
+    }
+
+    pub fn trajectory(&self) -> &TrajectoryRecorder {
+        &self.trajectory
+    }
+
+    /// Writes the followed body's recorded trail to `path`, as CSV for a
+    /// `.csv` extension and JSON otherwise.
+    pub fn export_trajectory(&mut self, path: &Path) -> crate::error::Result<()> {
+        self.trajectory.export(path)?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-saved-samples",
+                &[
+                    ("count", self.trajectory.samples().len().into()),
+                    ("path", path.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    /// Replaces the running bodies with a Tipsy or NEMO snapshot.
+    pub fn import_snapshot(&mut self, path: &Path) -> crate::error::Result<()> {
+        let snapshot = ParticleSnapshot::decode(&std::fs::read(path)?)?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-imported-bodies",
+                &[
+                    ("count", snapshot.bodies.len().into()),
+                    ("path", path.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        self.events
+            .push(AppEvent::SnapshotImported(Arc::new(snapshot)));
+        Ok(())
+    }
+
+    /// Writes the latest frame as NEMO for a `.nemo` or `.snap` path, Tipsy otherwise.
+    pub fn export_snapshot(&mut self, path: &Path) -> crate::error::Result<()> {
+        let Some(frame) = &self.frame else {
+            return Ok(());
+        };
+        let snapshot = ParticleSnapshot::from_frame(frame);
+        std::fs::write(path, snapshot.encode(SnapshotFormat::from_path(path)))?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-saved-bodies",
+                &[
+                    ("count", snapshot.bodies.len().into()),
+                    ("path", path.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    /// Writes the latest bodies as spheres and their recent trails as tubes to
+    /// a binary glTF (`.glb`) file, for rendering offline in e.g. Blender.
+    pub fn export_gltf(&mut self, path: &Path, options: &GltfOptions) -> crate::error::Result<()> {
+        let Some(frame) = &self.frame else {
+            return Ok(());
+        };
+        std::fs::write(
+            path,
+            gltf::encode(&frame.bodies, self.trails.trails(), options),
+        )?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-saved-bodies-and-trails",
+                &[
+                    ("count", frame.bodies.len().into()),
+                    ("path", path.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    /// Writes the latest body positions, colors, masses and radii as a PLY point cloud.
+    pub fn export_point_cloud(&mut self, path: &Path) -> crate::error::Result<()> {
+        let Some(frame) = &self.frame else {
+            return Ok(());
+        };
+        std::fs::write(path, ply::encode(&frame.bodies))?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-saved-bodies",
+                &[
+                    ("count", frame.bodies.len().into()),
+                    ("path", path.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    /// Writes a PLY point cloud into `directory` every `every` simulation steps.
+    pub fn start_point_cloud_series(
+        &mut self,
+        directory: &Path,
+        every: u64,
+    ) -> crate::error::Result<()> {
+        self.point_clouds = Some(PointCloudSeries::new(directory, every)?);
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-point-cloud-series",
+                &[
+                    ("every", every.into()),
+                    ("path", directory.display().to_string().into()),
+                ],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    pub fn stop_point_cloud_series(&mut self) {
+        if let Some(series) = self.point_clouds.take() {
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-saved-point-clouds",
+                    &[
+                        ("count", series.written().into()),
+                        ("path", series.directory().display().to_string().into()),
+                    ],
+                ),
+                duration: None,
+            });
+        }
+    }
+
+    /// Starts piping frames to ffmpeg: H.264 MP4, or VP9 for a `.webm` path.
+    /// The video runs at 60 fps in simulated time, however fast frames render.
+    pub fn start_recording(
+        &mut self,
+        path: &Path,
+        format: TextureFormat,
+        bytes_per_row: u32,
+    ) -> crate::error::Result<()> {
+        let size = self.resize.current();
+        let mut config = VideoConfig {
+            bytes_per_row,
+            ..VideoConfig::new(size.width, size.height, format, VideoCodec::from_path(path))
+        };
+        let worker = self.worker.config();
+        let ticks_per_frame = (1.0
+            / (f64::from(config.frame_rate) * worker.tick_interval.as_secs_f64()))
+        .round()
+        .max(1.0) as u64;
+        let substeps = self
+            .frame
+            .as_ref()
+            .map_or(1, |frame| frame.state.substeps.max(1));
+        config.steps_per_frame = ticks_per_frame * u64::from(worker.steps_per_tick * substeps);
+        self.recording = Some(VideoRecorder::start(path, config)?);
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-recording",
+                &[("path", path.display().to_string().into())],
+            ),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Feeds the frame just rendered, read back as `bytes_per_row * height`
+    /// bytes. A failed write, e.g. after a resize, ends the recording.
+    pub fn record_frame(&mut self, pixels: &[u8]) -> crate::error::Result<()> {
+        let (Some(recording), Some(frame)) = (&mut self.recording, &self.frame) else {
+            return Ok(());
+        };
+        if let Err(error) = recording.push(frame.state.step, pixels) {
+            self.recording = None;
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    /// Waits for ffmpeg to finish the file.
+    pub fn stop_recording(&mut self) -> crate::error::Result<()> {
+        let Some(recording) = self.recording.take() else {
+            return Ok(());
+        };
+        let frames = recording.finish()?;
+        self.events.push(AppEvent::ToastRequested {
+            message: self
+                .locale
+                .format("toast-recorded-frames", &[("count", frames.into())]),
+            duration: None,
+        });
+        Ok(())
+    }
+
+    /// How the current simulation is drawn: the user's changes if any, else
+    /// the scenario's defaults.
+    pub fn render_config(&self) -> RenderConfig {
+        let Some(frame) = &self.frame else {
+            return RenderConfig::default();
+        };
+        self.render_configs
+            .get(&frame.simulation_index)
+            .copied()
+            .unwrap_or(frame.render_config)
+    }
+
+    /// Changes the current simulation's look; switching back to it later restores it.
+    pub fn set_render_config(&mut self, config: RenderConfig) {
+        if let Some(frame) = &self.frame {
+            self.render_configs
+                .insert(frame.simulation_index, config.sanitized());
+        }
+    }
+
+    /// Turns on the rotating view locked to the followed body and whatever it
+    /// orbits, or back to the scenario's own frame when nothing is followed.
+    fn lock_rotation_to_followed_binary(&mut self) {
+        let binary = self.view.camera.follow.and_then(|secondary| {
+            let frame = self.frame.as_ref()?;
+            let primary = orbit_hud::dominant_body(&frame.bodies, secondary)?;
+            Some(BinaryFrame { primary, secondary })
+        });
+        let message = match binary {
+            Some(binary) => {
+                self.settings.rotating_view = RotatingView::Binary(binary);
+                self.settings.rotating_frame = true;
+                self.locale.format(
+                    "toast-rotating-binary",
+                    &[
+                        ("primary", binary.primary.into()),
+                        ("secondary", binary.secondary.into()),
+                    ],
+                )
+            }
+            None => {
+                self.settings.rotating_view = RotatingView::Scenario;
+                self.locale.text("toast-rotating-scenario")
+            }
+        };
+        self.events.push(AppEvent::ToastRequested {
+            message,
+            duration: None,
+        });
+    }
+
+    /// Changes the internal resolution, e.g. to 2x while taking a screenshot.
+    /// While adaptive resolution is on this sets its ceiling instead.
+    pub fn set_render_scale(&mut self, scale: RenderScale) {
+        if self.adaptive_resolution.enabled {
+            self.adaptive_resolution.enable(scale);
+        }
+        if scale == self.settings.render_scale {
+            return;
+        }
+        self.settings.render_scale = scale;
+        self.events.push(AppEvent::ToastRequested {
+            message: self
+                .locale
+                .format("toast-render-scale", &[("scale", scale.label().into())]),
+            duration: None,
+        });
+    }
+
+    /// Switches toasts, labels and overlays to `language`.
+    pub fn set_language(&mut self, language: Language) {
+        if language == self.locale.language() {
+            return;
+        }
+        self.settings.language = language;
+        self.locale = Arc::new(Localizer::new(language));
+        self.toasts.locale = Arc::clone(&self.locale);
+    }
+
+    /// Text in the selected language, for labels the UI layer draws itself.
+    pub fn locale(&self) -> &Localizer {
+        &self.locale
+    }
+
+    /// Lets GPU load drive the render scale, never above the current one.
+    /// Enabling it again keeps the ceiling taken when it was first enabled.
+    pub fn set_adaptive_resolution(&mut self, enabled: bool) {
+        if enabled == self.adaptive_resolution.enabled {
+            return;
+        }
+        if enabled {
+            self.adaptive_resolution.enable(self.settings.render_scale);
+        } else {
+            self.adaptive_resolution.enabled = false;
+            self.settings.render_scale = self.adaptive_resolution.ceiling;
+        }
+    }
+
+    /// Reports the GPU time of a whole frame, summed from timestamp queries
+    /// around its passes, for adaptive resolution.
+    pub fn record_gpu_frame(&mut self, duration: std::time::Duration) {
+        if let Some(scale) = self
+            .adaptive_resolution
+            .observe(duration, self.settings.render_scale)
+        {
+            self.settings.render_scale = scale;
+        }
+    }
+
+    /// Status bar text for the render scale while it adapts to GPU load.
+    pub fn resolution_indicator(&self) -> Option<String> {
+        self.adaptive_resolution
+            .indicator(self.settings.render_scale, &self.locale)
+    }
+
+    /// Size of the targets rendered before the tone-mapping pass.
+    pub fn render_size(&self) -> SurfaceSize {
+        self.settings
+            .render_scale
+            .internal_size(self.resize.current())
+    }
+
+    /// Scale bar for the reference overlay, in the current scenario's units.
+    pub fn scale_bar(&self) -> Option<ScaleBar> {
+        let unit = match &self.frame {
+            Some(frame) => LengthUnit::new(frame.units.as_ref(), frame.length_label),
+            None => LengthUnit::new(None, ""),
+        };
+        ScaleBar::new(&self.view.camera, self.viewport().y, self.ui_scale(), unit)
+    }
+
+    /// Osculating elements of the followed body, for the orbit HUD.
+    pub fn orbit_hud(&self) -> Option<OrbitHud> {
+        OrbitHud::new(self.frame.as_ref()?, self.view.camera.follow?)
+    }
+
+    /// Tooltip for the body under the cursor, within `PICK_RADIUS`; hidden
+    /// while dragging the camera.
+    pub fn hovered_body(&self) -> Option<BodyTooltip> {
+        if self.camera_drag.is_some() {
+            return None;
+        }
+        let frame = self.frame.as_ref()?;
+        let cursor = self.input.cursor;
+        let index = pick_body(
+            &self.view.camera,
+            &self.display,
+            cursor,
+            self.viewport(),
+            PICK_RADIUS * self.ui_scale(),
+        )?;
+        BodyTooltip::new(frame, &self.display, index, cursor)
+    }
+
+    /// Inset rectangle and region of the minimap, when it is on and fits.
+    pub fn minimap(&self) -> Option<(ScissorRect, MinimapView)> {
+        let minimap = Minimap {
+            margin: (self.settings.minimap.margin as f32 * self.ui_scale()).round() as u32,
+            ..self.settings.minimap
+        };
+        if !minimap.enabled || self.display.is_empty() {
+            return None;
+        }
+        let size = self.resize.current();
+        let rect = minimap.viewport(size.width, size.height)?;
+        Some((rect, minimap.view(&self.display, &self.view.camera)))
+    }
+
+    /// Sends the inspector's values to the simulation; see `BodyInspector`.
+    pub fn commit_inspector(&mut self) {
+        if let Some((index, properties)) = self.inspector.commit(&mut self.events, &self.locale)
+            && let Some(body) = self
+                .frame
+                .as_mut()
+                .and_then(|frame| frame.bodies.get_mut(index))
+        {
+            properties.apply(body);
+            self.body_writes.push(index);
+        }
+    }
+
+    /// Writes bodies changed by the inspector or by dragging into the bodies
+    /// buffer, each at its own offset; hosts call it after uploading the
+    /// frame's bodies.
+    pub fn upload_body_edits(
+        &mut self,
+        resources: &crate::simulation::SimulationResources,
+        upload: &mut crate::rendering::upload::UploadBelt,
+    ) {
+        let bodies = self.frame.as_ref().map_or(&[][..], |frame| &frame.bodies);
+        for index in self.body_writes.drain(..) {
+            if let Some(body) = bodies.get(index) {
+                resources.update_body(upload, index, body);
+            }
+        }
+    }
+
+    /// Triangle list of the on-screen trails, for `rendering::trails`'s
+    /// pass; only groups both trailed and visible get one.
+    pub fn trail_vertices(&self) -> Vec<LineVertex> {
+        let Some(frame) = &self.frame else {
+            return Vec::new();
+        };
+        let mut config = self.render_config().trails;
+        config.groups = GroupMask(config.groups.0 & self.settings.visible_groups.0);
+        trails::trail_ribbons(
+            self.shown_trails.trails(),
+            &self.display,
+            &frame.flags,
+            &config,
+            &self.view.camera,
+            self.viewport().y,
+            self.ui_scale(),
+        )
+    }
+
+    /// Line list of the planets' orbit outlines, for the reference pass;
+    /// empty while off or in the rotating view, where the inertial ellipses
+    /// would spin.
+    pub fn orbit_outline_vertices(&self) -> Vec<LineVertex> {
+        if !self.settings.orbit_outlines || self.settings.rotating_frame {
+            return Vec::new();
+        }
+        self.orbit_outlines.vertices(&self.display)
+    }
+
+    /// Distance between the two bodies picked with the measure tool.
+    pub fn measurement(&self) -> Option<Measurement> {
+        Measurement::new(
+            self.frame.as_ref()?,
+            &self.display,
+            self.measure.pair()?,
+            &self.view.camera,
+            self.viewport(),
+        )
+    }
+
+    /// Line list of the measure tool's connecting line, for the reference pass.
+    pub fn measure_vertices(&self) -> Vec<LineVertex> {
+        self.measure.vertices(&self.display)
+    }
+
+    /// Bodies to draw and to aim the camera at.
+    pub fn display_bodies(&self) -> &[Body] {
+        &self.display
+    }
+
+    pub fn switch_simulation(&mut self, index: usize) {
+        self.adaptive_resolution.reset();
+        self.events.push(AppEvent::SimulationSwitched {
+            index,
+            hot_swap: self.settings.hot_swap,
+        });
+    }
+
+    /// Starts streaming body positions to WebSocket clients.
+    #[cfg(feature = "net")]
+    pub fn start_streaming(
+        &mut self,
+        config: crate::net::stream::StreamConfig,
+    ) -> std::io::Result<()> {
+        self.stream = Some(crate::net::stream::StreamServer::bind(config)?);
+        Ok(())
+    }
+
+    /// Starts accepting remote-control calls; see `net::control`.
+    #[cfg(feature = "net")]
+    pub fn start_control_server(&mut self, address: std::net::SocketAddr) -> std::io::Result<()> {
+        self.control = Some(crate::net::control::ControlServer::bind(address)?);
+        Ok(())
+    }
+
+    /// Turns a remote-control call into the events a key press would send.
+    #[cfg(feature = "net")]
+    fn answer(
+        &mut self,
+        request: crate::net::control::ControlRequest,
+    ) -> Result<serde_json::Value, String> {
+        use crate::net::control::ControlRequest;
+
+        match request {
+            ControlRequest::Pause(paused) => self.events.push(AppEvent::PauseChanged(paused)),
+            ControlRequest::SetTimeScale(scale) => {
+                self.events.push(AppEvent::TimeScaleChanged(scale))
+            }
+            ControlRequest::SwitchSimulation(simulation) => {
+                let index = self
+                    .simulation_index(&simulation)
+                    .ok_or_else(|| format!("no simulation {simulation:?}"))?;
+                self.switch_simulation(index);
+            }
+            ControlRequest::SpawnBody(body) => self.events.push(AppEvent::BodySpawned(body)),
+            ControlRequest::SaveSnapshot => self.events.push(AppEvent::SnapshotRequested),
+            ControlRequest::Status => {
+                let Some(frame) = &self.frame else {
+                    return Ok(serde_json::Value::Null);
+                };
+                return Ok(serde_json::json!({
+                    "simulation": frame.simulation_index,
+                    "name": self.simulation_names.get(frame.simulation_index),
+                    "time": frame.state.time,
+                    "bodies": frame.bodies.len(),
+                    "paused": frame.state.paused,
+                    "time_scale": frame.state.time_scale,
+                    "reversed": frame.state.reversed,
+                }));
+            }
+        }
+        Ok(serde_json::Value::Bool(true))
+    }
+
+    /// Starts serving `/metrics` for Prometheus.
+    #[cfg(feature = "metrics")]
+    pub fn start_metrics(&mut self, config: crate::metrics::MetricsConfig) -> std::io::Result<()> {
+        self.metrics = Some(crate::metrics::MetricsExporter::bind(config)?);
+        Ok(())
+    }
+
+    /// Reports a render graph pass's GPU time to the metrics endpoint, if any.
+    #[cfg(feature = "metrics")]
+    pub fn record_gpu_pass(&mut self, pass: &'static str, duration: std::time::Duration) {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.record_pass(pass, duration);
+        }
+    }
+
+    /// Follows the body a VR controller points at, or stops following when it
+    /// points at nothing.
+    #[cfg(feature = "xr")]
+    pub fn xr_select(&mut self, ray: &crate::xr::ControllerRay) {
+        let picked = ray.pick(&self.display);
+        self.view.camera.follow_body(picked);
+    }
+
+    /// Maps a MIDI controller's knobs and faders onto live parameters; see
+    /// `midi::MidiMapping` for the default layout.
+    #[cfg(feature = "midi")]
+    pub fn connect_midi(
+        &mut self,
+        port: Option<&str>,
+        mapping: crate::midi::MidiMapping,
+    ) -> Result<(), crate::midi::MidiError> {
+        self.midi = Some(crate::midi::MidiController::connect(port, mapping)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "midi")]
+    fn apply_midi(&mut self) {
+        use crate::midi::MidiControl;
+
+        let Some(midi) = &self.midi else {
+            return;
+        };
+        for control in midi.controls() {
+            match control {
+                MidiControl::TimeScale(scale) => {
+                    self.events.push(AppEvent::TimeScaleChanged(scale))
+                }
+                MidiControl::Softening(softening) => {
+                    self.events.push(AppEvent::SofteningChanged(softening))
+                }
+                MidiControl::CameraDistance(factor) => {
+                    self.view.camera.distance = self.view.fitted_distance * factor;
+                    self.events.push(AppEvent::CameraChanged);
+                }
+                MidiControl::ColorMode(mode) => self.settings.color_mode = mode,
+            }
+        }
+    }
+
+    /// Starts accepting OSC cues over UDP; see `net::osc`.
+    #[cfg(feature = "net")]
+    pub fn start_osc(&mut self, address: std::net::SocketAddr) -> std::io::Result<()> {
+        self.osc = Some(crate::net::osc::OscServer::bind(address)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "net")]
+    fn apply_osc(&mut self) {
+        use crate::net::osc::OscCommand;
+
+        let Some(osc) = &self.osc else {
+            return;
+        };
+        let commands: Vec<_> = osc.commands().collect();
+        for command in commands {
+            match command {
+                OscCommand::SetTimeScale(scale) => {
+                    self.events.push(AppEvent::TimeScaleChanged(scale))
+                }
+                OscCommand::SwitchSimulation(simulation) => {
+                    match self.simulation_index(&simulation) {
+                        Some(index) => self.switch_simulation(index),
+                        None => tracing::warn!(?simulation, "OSC cue for unknown simulation"),
+                    }
+                }
+                OscCommand::Orbit {
+                    yaw,
+                    pitch,
+                    distance,
+                } => {
+                    let camera = &mut self.view.camera;
+                    camera.rotate(yaw - camera.yaw, pitch - camera.pitch);
+                    if let Some(distance) = distance {
+                        camera.distance = self.view.fitted_distance * distance;
+                    }
+                    self.events.push(AppEvent::CameraChanged);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "net")]
+    fn simulation_index(&self, simulation: &crate::net::control::SimulationRef) -> Option<usize> {
+        use crate::net::control::SimulationRef;
+
+        match simulation {
+            SimulationRef::Index(index) => {
+                Some(*index).filter(|index| *index < self.simulation_names.len())
+            }
+            SimulationRef::Name(name) => {
+                self.simulation_names.iter().position(|known| known == name)
+            }
+        }
+    }
+
+    /// Share code for the scenario and view on screen, for the clipboard.
+    pub fn share_code(&self) -> Option<String> {
+        let frame = self.frame.as_ref()?;
+        let shared = SharedScenario {
+            simulation: self.simulation_names.get(frame.simulation_index)?.clone(),
+            seed: frame.seed,
+            parameters: frame
+                .parameters
+                .iter()
+                .filter(|parameter| parameter.value != parameter.default)
+                .map(|parameter| (parameter.name.to_owned(), parameter.value))
+                .collect(),
+            camera: CameraPose::of(&self.view.camera),
+            time_scale: frame.state.time_scale,
+        };
+        Some(shared.encode())
+    }
+
+    /// Restores a scenario and view from a pasted share code.
+    pub fn load_share_code(&mut self, code: &str) -> Result<(), ShareError> {
+        let shared = SharedScenario::decode(code)?;
+        let index = self
+            .simulation_names
+            .iter()
+            .position(|name| *name == shared.simulation)
+            .ok_or_else(|| ShareError::UnknownSimulation(shared.simulation.clone()))?;
+        self.events.push(AppEvent::SharedScenarioLoaded {
+            index,
+            seed: shared.seed,
+            parameters: shared.parameters,
+        });
+        self.events
+            .push(AppEvent::TimeScaleChanged(shared.time_scale));
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.format(
+                "toast-loaded-shared",
+                &[("simulation", shared.simulation.as_str().into())],
+            ),
+            duration: None,
+        });
+        shared.camera.apply(&mut self.view.camera);
+        // Keep the shared pose instead of fitting the new bodies.
+        self.view.refit_pending = false;
+        self.view.awaiting_simulation = None;
+        Ok(())
+    }
+
+    /// The followed body's state as a JSON snippet for the host to put on
+    /// the clipboard; `None` without a followed body or when its state has
+    /// diverged to NaN or infinity.
+    pub fn copy_body_state(&mut self) -> Option<String> {
+        let frame = self.frame.as_ref()?;
+        let index = self.view.camera.follow?;
+        let state = BodyState {
+            name: frame
+                .names
+                .iter()
+                .find(|(named, _)| *named == index)
+                .map(|(_, name)| name.clone()),
+            body: *frame.bodies.get(index)?,
+        };
+        let json = state.to_json();
+        self.events.push(AppEvent::ToastRequested {
+            message: self.locale.text(if json.is_some() {
+                "toast-copied-body"
+            } else {
+                "toast-body-not-finite"
+            }),
+            duration: None,
+        });
+        json
+    }
+
+    /// Spawns a body from a pasted snippet, as made by `copy_body_state`.
+    pub fn paste_body_state(&mut self, text: &str) -> Result<(), FormatError> {
+        let BodyState { name, body } = BodyState::from_json(text)?;
+        self.events.push(AppEvent::ToastRequested {
+            message: match &name {
+                Some(name) => self
+                    .locale
+                    .format("toast-pasted-body", &[("name", name.into())]),
+                None => self.locale.text("toast-pasted-unnamed-body"),
+            },
+            duration: None,
+        });
+        self.events.push(AppEvent::BodyPasted { body, name });
+        Ok(())
+    }
+
+    /// Plays a guided tour from its first step; Enter moves on and Escape
+    /// leaves it.
+    #[cfg(feature = "tour")]
+    pub fn start_tour(&mut self, tour: crate::tour::Tour) -> Result<(), crate::tour::TourError> {
+        tour.check_simulations(&self.simulation_names)?;
+        self.tour = Some(crate::tour::TourPlayer::new(tour, Instant::now()));
+        self.show_tour_step();
+        Ok(())
+    }
+
+    #[cfg(feature = "tour")]
+    pub fn stop_tour(&mut self) {
+        self.tour = None;
+    }
+
+    /// Caption of the tour step on screen, for the UI layer to draw.
+    #[cfg(feature = "tour")]
+    pub fn tour_caption(&self) -> Option<crate::tour::TourCaption<'_>> {
+        self.tour.as_ref().map(crate::tour::TourPlayer::caption)
+    }
+
+    /// Goes to the tour's next step, ending the tour after the last.
+    #[cfg(feature = "tour")]
+    fn advance_tour(&mut self) {
+        let Some(tour) = &mut self.tour else {
+            return;
+        };
+        if tour.next(Instant::now()) {
+            self.show_tour_step();
+        } else {
+            self.tour = None;
+        }
+    }
+
+    #[cfg(feature = "tour")]
+    fn show_tour_step(&mut self) {
+        let Some(step) = self.tour.as_ref().map(|tour| tour.step().clone()) else {
+            return;
+        };
+        if let Some(index) = step
+            .simulation
+            .as_ref()
+            .and_then(|name| self.simulation_names.iter().position(|known| known == name))
+        {
+            self.adaptive_resolution.reset();
+            self.events.push(AppEvent::SharedScenarioLoaded {
+                index,
+                seed: step.seed,
+                parameters: step.parameters,
+            });
+            if step.camera.is_none() {
+                self.view.camera.follow = None;
+                self.view.refit_pending = true;
+                self.view.awaiting_simulation = Some(index);
+            }
+        }
+        if let Some(camera) = step.camera {
+            camera.apply(&mut self.view.camera);
+            self.view.refit_pending = false;
+            self.view.awaiting_simulation = None;
+            self.events.push(AppEvent::CameraChanged);
+        }
+        if let Some(time_scale) = step.time_scale {
+            self.events.push(AppEvent::TimeScaleChanged(time_scale));
+        }
+        if let Some(paused) = step.paused {
+            self.events.push(AppEvent::PauseChanged(paused));
+        }
+    }
+
+    /// The whole accessibility tree, for the AccessKit adapter's first
+    /// request.
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_tree(&self) -> accesskit::TreeUpdate {
+        self.accessibility
+            .full(&self.locale.text("accessibility-window"))
+    }
+
+    /// Simulation switches, pause changes and the selected body since the
+    /// last call, for the host to pass to its AccessKit adapter after
+    /// `update`; `None` when nothing changed.
+    #[cfg(feature = "accesskit")]
+    pub fn accessibility_update(&mut self) -> Option<accesskit::TreeUpdate> {
+        let announcements = self.announcer.take();
+        self.accessibility
+            .announce(&self.locale.text("accessibility-window"), &announcements)
+    }
+
+    /// The window's new size in physical pixels.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.resize.request(width, height);
+    }
+
+    /// Handles `ScaleFactorChanged`. The cursor is kept in physical pixels,
+    /// so its last position is rescaled to stay over the same spot.
+    pub fn scale_factor_changed(&mut self, scale_factor: f64, width: u32, height: u32) {
+        let previous = self.resize.scale_factor();
+        self.resize
+            .request_scale_factor(scale_factor, width, height);
+        let ratio = (self.resize.scale_factor() / previous) as f32;
+        self.input.cursor *= ratio;
+    }
+
+    /// Handles winit's `Occluded`: the window is fully covered or on another
+    /// workspace.
+    pub fn set_occluded(&mut self, occluded: bool) {
+        self.occluded = occluded;
+    }
+
+    /// Whether to draw this frame. While false, hosts skip acquiring and
+    /// configuring the surface, which panics at zero size when minimized.
+    pub fn should_render(&self) -> bool {
+        !self.occluded && !self.resize.is_minimized()
+    }
+
+    /// Pauses the simulation when the window is hidden, unless
+    /// `simulate_while_hidden`, and resumes it on restore if it was running.
+    fn update_visibility(&mut self) {
+        let hidden = !self.should_render();
+        if hidden && !self.paused_while_hidden {
+            let running = self.frame.as_ref().is_some_and(|frame| !frame.state.paused);
+            if running && !self.settings.simulate_while_hidden {
+                self.events.push(AppEvent::PauseChanged(true));
+                self.paused_while_hidden = true;
+            }
+        } else if !hidden && self.paused_while_hidden {
+            self.events.push(AppEvent::PauseChanged(false));
+            self.paused_while_hidden = false;
+        }
+    }
+
+    /// Physical pixels per logical pixel; UI text and margins are specified
+    /// in logical pixels and multiplied by this.
+    pub fn ui_scale(&self) -> f32 {
+        self.resize.scale_factor() as f32
+    }
+
+    /// Physical pixels per logical pixel of label text: `ui_scale` enlarged
+    /// by `settings.text_size`.
+    pub fn text_scale(&self) -> f32 {
+        self.ui_scale() * self.settings.text_size.scale()
+    }
+
+    /// Colors for the UI layer, following `settings.color_palette`.
+    pub fn ui_theme(&self) -> UiTheme {
+        UiTheme::new(self.settings.color_palette)
+    }
+
+    fn viewport(&self) -> Vec2 {
+        let size = self.resize.current();
+        Vec2::new(size.width as f32, size.height as f32)
+    }
+
+    pub fn key_event(&mut self, key: KeyCode, pressed: bool) {
+        self.input.handle_key_state(key, pressed);
+        if key == KeyCode::F2 && pressed {
+            self.shader_editor.open = !self.shader_editor.open;
+        }
+        // Keys are typing while the editor is open; only Ctrl+Enter is a command.
+        if self.shader_editor.open {
+            if key == KeyCode::Enter && pressed && self.input.is_key_held(KeyCode::ControlLeft) {
+                self.shader_editor.compile(&mut self.events, &self.locale);
+            }
+            return;
+        }
+        if key == KeyCode::KeyK && pressed && self.input.is_key_held(KeyCode::ControlLeft) {
+            self.simulation_palette.toggle();
+            return;
+        }
+        // Likewise while the palette is open, except for its navigation keys.
+        if self.simulation_palette.open {
+            if pressed {
+                self.palette_key(key);
+            }
+            return;
+        }
+        #[cfg(feature = "tour")]
+        if pressed && self.tour.is_some() {
+            match key {
+                KeyCode::Enter => return self.advance_tour(),
+                KeyCode::Escape => return self.stop_tour(),
+                _ => {}
+            }
+        }
+        if pressed && self.nudge_camera(key) {
+            return;
+        }
+        if pressed
+            && let Some(index) = key
+                .simulation_slot()
+                .filter(|&index| index < self.simulation_names.len())
+        {
+            self.switch_simulation(index);
+        }
+        if key == KeyCode::F3 && pressed {
+            self.settings.debug_channel = self.settings.debug_channel.next();
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-debug-view",
+                    &[(
+                        "channel",
+                        self.settings.debug_channel.label(&self.locale).into(),
+                    )],
+                ),
+                duration: None,
+            });
+        }
+        if key == KeyCode::KeyM && pressed && self.input.is_key_held(KeyCode::ShiftLeft) {
+            self.settings.minimap.enabled = !self.settings.minimap.enabled;
+        } else if key == KeyCode::KeyM && pressed {
+            let mut config = self.render_config();
+            config.blend_mode = config.blend_mode.toggled();
+            self.set_render_config(config);
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-blend-mode",
+                    &[("mode", config.blend_mode.label(&self.locale).into())],
+                ),
+                duration: None,
+            });
+        }
+        if key == KeyCode::F4 && pressed {
+            let mut config = self.render_config();
+            config.tone_mapper = config.tone_mapper.next();
+            self.set_render_config(config);
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-tone-mapping",
+                    &[("mapper", config.tone_mapper.label(&self.locale).into())],
+                ),
+                duration: None,
+            });
+        }
+        if key == KeyCode::KeyL && pressed {
+            self.settings.accumulation_decay = if self.settings.accumulation_decay > 0.0 {
+                0.0
+            } else {
+                accumulation::DEFAULT_DECAY
+            };
+            let enabled = self.settings.accumulation_decay > 0.0;
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-long-exposure",
+                    &[("enabled", enabled.to_string().into())],
+                ),
+                duration: None,
+            });
+        }
+        if key == KeyCode::KeyO && pressed {
+            if self.input.is_key_held(KeyCode::ShiftLeft) {
+                self.settings.ecliptic_guides = !self.settings.ecliptic_guides;
+            } else if self.input.is_key_held(KeyCode::AltLeft) {
+                self.settings.orbit_outlines = !self.settings.orbit_outlines;
+            } else {
+                self.settings.reference_overlay = !self.settings.reference_overlay;
+            }
+        }
+        if key == KeyCode::KeyB && pressed {
+            self.spawn.enabled = !self.spawn.enabled;
+            self.spawn.cancel();
+            self.measure.enabled &= !self.spawn.enabled;
+        }
+        if key == KeyCode::KeyD && pressed {
+            self.measure.enabled = !self.measure.enabled;
+            self.measure.clear();
+            if self.measure.enabled {
+                self.spawn.enabled = false;
+                self.spawn.cancel();
+            }
+        }
+        if key == KeyCode::KeyG && self.gravity_well.active != pressed {
+            self.gravity_well.active = pressed;
+            if !pressed {
+                self.events.push(AppEvent::GravityWellChanged(None));
+            }
+        }
+        if key == KeyCode::KeyF && pressed {
+            self.settings.reference_frame =
+                self.settings.reference_frame.next(self.view.camera.follow);
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-reference-frame",
+                    &[(
+                        "frame",
+                        self.settings.reference_frame.label(&self.locale).into(),
+                    )],
+                ),
+                duration: None,
+            });
+        }
+        if key == KeyCode::KeyR && pressed {
+            if self.input.is_key_held(KeyCode::ShiftLeft) {
+                self.lock_rotation_to_followed_binary();
+            } else {
+                self.settings.rotating_frame = !self.settings.rotating_frame;
+            }
+        }
+        if key == KeyCode::KeyP && pressed {
+            self.settings.diagnostics_panel = !self.settings.diagnostics_panel;
+            self.events.push(AppEvent::DiagnosticsToggled(
+                self.settings.diagnostics_panel,
+            ));
+        }
+        if key == KeyCode::KeyT && pressed && self.input.is_key_held(KeyCode::ShiftLeft) {
+            let mut config = self.render_config();
+            config.trails.fade = config.trails.fade.next();
+            self.set_render_config(config);
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-trail-fade",
+                    &[("fade", config.trails.fade.label(&self.locale).into())],
+                ),
+                duration: None,
+            });
+        } else if key == KeyCode::KeyT
+            && pressed
+            && let Some(frame) = &self.frame
+        {
+            self.events
+                .push(AppEvent::ReverseChanged(!frame.state.reversed));
+        }
+        if key == KeyCode::BracketLeft && pressed {
+            self.events.push(AppEvent::RewindRequested);
+        }
+        if pressed && self.input.is_key_held(KeyCode::ControlLeft) {
+            let redo = key == KeyCode::KeyY
+                || (key == KeyCode::KeyZ && self.input.is_key_held(KeyCode::ShiftLeft));
+            if redo {
+                self.events.push(AppEvent::RedoRequested);
+            } else if key == KeyCode::KeyZ {
+                self.events.push(AppEvent::UndoRequested);
+            }
+        }
+        if key == KeyCode::Delete
+            && pressed
+            && let Some(index) = self.view.camera.follow
+        {
+            self.view.camera.follow_body(None);
+            self.events.push(AppEvent::BodyDeleted(index));
+        }
+        if key == KeyCode::KeyN && pressed {
+            self.events
+                .push(AppEvent::SupernovaTriggered(self.view.camera.follow));
+        }
+        if matches!(
+            key,
+            KeyCode::ArrowUp | KeyCode::ArrowDown | KeyCode::ArrowLeft | KeyCode::ArrowRight
+        ) {
+            let thrust = self.thrust_input();
+            self.events.push(AppEvent::ThrustChanged(thrust));
+        }
+    }
+
+    fn palette_key(&mut self, key: KeyCode) {
+        let palette = &mut self.simulation_palette;
+        match key {
+            KeyCode::Escape => palette.toggle(),
+            KeyCode::ArrowUp => palette.move_selection(-1, &self.simulation_names),
+            KeyCode::ArrowDown => palette.move_selection(1, &self.simulation_names),
+            KeyCode::Enter => {
+                if let Some(index) = palette.choice(&self.simulation_names) {
+                    palette.toggle();
+                    self.switch_simulation(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Digit key and name of each simulation that has one, for the palette
+    /// and help overlay to show next to the names.
+    pub fn simulation_hotkeys(&self) -> impl Iterator<Item = (KeyCode, &str)> {
+        self.simulation_names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| Some((KeyCode::for_simulation(index)?, name.as_str())))
+    }
+
+    /// Names of the simulations, by index.
+    pub fn simulation_names(&self) -> &[String] {
+        &self.simulation_names
+    }
+
+    /// Arrow keys fire the spacecraft: up/down along the orbit, right/left
+    /// radially. Not while Ctrl is held, when they nudge the camera instead.
+    fn thrust_input(&self) -> ThrustInput {
+        if self.input.is_key_held(KeyCode::ControlLeft) {
+            return ThrustInput::default();
+        }
+        let axis = |positive, negative| {
+            f32::from(u8::from(self.input.is_key_held(positive)))
+                - f32::from(u8::from(self.input.is_key_held(negative)))
+        };
+        ThrustInput {
+            prograde: axis(KeyCode::ArrowUp, KeyCode::ArrowDown),
+            radial: axis(KeyCode::ArrowRight, KeyCode::ArrowLeft),
+        }
+    }
+
+    /// The cursor's position in physical pixels, like winit's `CursorMoved`.
+    pub fn cursor_moved(&mut self, x: f32, y: f32) {
+        let delta = Vec2::new(x, y) - self.input.cursor;
+        self.input.cursor = Vec2::new(x, y);
+        if let Some((_, action)) = self.camera_drag {
+            self.drag_camera(action, delta);
+        }
+        if let Some(index) = self.body_drag {
+            self.drag_body(index, self.input.cursor - delta);
+        }
+        if self.spawn.is_dragging()
+            && let Some(frame) = &self.frame
+        {
+            let viewport = self.viewport();
+            self.spawn.drag(
+                &self.view.camera,
+                self.input.cursor,
+                viewport,
+                &frame.bodies,
+                &frame.state,
+            );
+        }
+    }
+
+    /// With the measure tool on, a left press on a body makes it the next end
+    /// of the measurement.
+    fn pick_measured(&mut self) -> bool {
+        let Some(index) = pick_body(
+            &self.view.camera,
+            &self.display,
+            self.input.cursor,
+            self.viewport(),
+            PICK_RADIUS * self.ui_scale(),
+        ) else {
+            return false;
+        };
+        self.measure.pick(index);
+        true
+    }
+
+    /// While paused, a left press on a body grabs it. Not in a rotating view,
+    /// whose shown axes turn relative to the simulation's.
+    fn begin_body_drag(&mut self) -> bool {
+        let paused = self.frame.as_ref().is_some_and(|frame| frame.state.paused);
+        if !paused || self.settings.rotating_frame {
+            return false;
+        }
+        let Some(index) = pick_body(
+            &self.view.camera,
+            &self.display,
+            self.input.cursor,
+            self.viewport(),
+            PICK_RADIUS * self.ui_scale(),
+        ) else {
+            return false;
+        };
+        // Following the body would drag the camera along with it.
+        if self.view.camera.follow == Some(index) {
+            self.view.camera.follow_body(None);
+        }
+        self.body_drag = Some(index);
+        true
+    }
+
+    /// Moves the grabbed body by the cursor's motion since `previous`,
+    /// unprojected onto the plane through the body facing the camera.
+    fn drag_body(&mut self, index: usize, previous: Vec2) {
+        let viewport = self.viewport();
+        let camera = &self.view.camera;
+        let Some(anchor) = self.display.get(index).map(|body| body.position) else {
+            return;
+        };
+        let (Some(from), Some(to)) = (
+            camera.cursor_on_plane(previous, viewport, anchor),
+            camera.cursor_on_plane(self.input.cursor, viewport, anchor),
+        ) else {
+            return;
+        };
+        let delta = (to - from) * self.camera_precision();
+        let Some(body) = self
+            .frame
+            .as_mut()
+            .and_then(|frame| frame.bodies.get_mut(index))
+        else {
+            return;
+        };
+        // Reference frames only translate, so the shown delta is the real one.
+        body.position += delta;
+        let position = body.position;
+        self.display[index].position += delta;
+        self.body_writes.push(index);
+        self.events.push(AppEvent::BodyMoved { index, position });
+    }
+
+    /// Scale on camera deltas: `PRECISION_SCALE` while Alt is held, for
+    /// fine framing during recording.
+    fn camera_precision(&self) -> f32 {
+        if self.input.is_key_held(KeyCode::AltLeft) {
+            PRECISION_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// Numpad 4/6 and 8/2 rotate, numpad +/− zoom and Ctrl+arrows pan, by a
+    /// small step per press (or key repeat). Returns whether `key` was one.
+    fn nudge_camera(&mut self, key: KeyCode) -> bool {
+        let arrows = self.input.is_key_held(KeyCode::ControlLeft);
+        let scale = self.camera_precision();
+        let camera = &mut self.view.camera;
+        match key {
+            KeyCode::Numpad4 => camera.rotate(NUDGE_ROTATE * scale, 0.0),
+            KeyCode::Numpad6 => camera.rotate(-NUDGE_ROTATE * scale, 0.0),
+            KeyCode::Numpad8 => camera.rotate(0.0, NUDGE_ROTATE * scale),
+            KeyCode::Numpad2 => camera.rotate(0.0, -NUDGE_ROTATE * scale),
+            KeyCode::NumpadAdd => camera.zoom(NUDGE_ZOOM * scale),
+            KeyCode::NumpadSubtract => camera.zoom(-NUDGE_ZOOM * scale),
+            KeyCode::ArrowLeft if arrows => camera.pan(NUDGE_PAN * scale, 0.0),
+            KeyCode::ArrowRight if arrows => camera.pan(-NUDGE_PAN * scale, 0.0),
+            KeyCode::ArrowUp if arrows => camera.pan(0.0, NUDGE_PAN * scale),
+            KeyCode::ArrowDown if arrows => camera.pan(0.0, -NUDGE_PAN * scale),
+            _ => return false,
+        }
+        self.events.push(AppEvent::CameraChanged);
+        true
+    }
+
+    /// Rotates by `ROTATE_PER_PIXEL` per pixel; pans and zooms by the
+    /// fraction of the viewport height dragged across.
+    fn drag_camera(&mut self, action: MouseAction, delta: Vec2) {
+        let delta = delta * self.camera_precision();
+        let height = self.viewport().y.max(1.0);
+        let camera = &mut self.view.camera;
+        match action {
+            MouseAction::Rotate => {
+                camera.rotate(-delta.x * ROTATE_PER_PIXEL, delta.y * ROTATE_PER_PIXEL)
+            }
+            MouseAction::Pan => camera.pan(delta.x / height, delta.y / height),
+            MouseAction::Zoom => camera.zoom(-2.0 * delta.y / height),
+        }
+        self.events.push(AppEvent::CameraChanged);
+    }
+
+    /// Handles focus changes. On losing focus, held keys, buttons and any
+    /// drag are dropped, since their releases will not be delivered.
+    pub fn focus_changed(&mut self, focused: bool) {
+        if !focused {
+            self.input.release_all();
+            self.camera_drag = None;
+            if self.body_drag.take().is_some() {
+                self.events.push(AppEvent::BodyMoveFinished);
+            }
+            self.spawn.cancel();
+        }
+    }
+
+    /// Handles winit's `ModifiersChanged`.
+    pub fn modifiers_changed(&mut self, shift: bool, control: bool, alt: bool) {
+        self.input.sync_modifiers(shift, control, alt);
+    }
+
+    pub fn mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        self.input.handle_mouse_button(button, pressed);
+        if !pressed && self.camera_drag.is_some_and(|(held, _)| held == button) {
+            self.camera_drag = None;
+            return;
+        }
+        if button == MouseButton::Left && !pressed && self.body_drag.take().is_some() {
+            self.events.push(AppEvent::BodyMoveFinished);
+            return;
+        }
+        if button == MouseButton::Left && pressed && self.measure.enabled && self.pick_measured() {
+            return;
+        }
+        if button == MouseButton::Left && pressed && !self.spawn.enabled && self.begin_body_drag() {
+            return;
+        }
+        // The spawn tool owns the left button while it is on.
+        if button != MouseButton::Left || !self.spawn.enabled {
+            if pressed && self.camera_drag.is_none() {
+                self.camera_drag = self
+                    .settings
+                    .mouse_scheme
+                    .action(button, &self.input)
+                    .map(|action| (button, action));
+            }
+            return;
+        }
+        if pressed {
+            let viewport = self.viewport();
+            self.spawn
+                .begin(&self.view.camera, self.input.cursor, viewport);
+        } else if let Some(frame) = &self.frame
+            && let Some(body) = self
+                .spawn
+                .release(&self.view.camera, &frame.bodies, &frame.state)
+        {
+            self.events.push(AppEvent::BodySpawned(body));
+        }
+    }
+
+    /// Scroll zooms the camera, or tunes the gravity well while it is held.
+    pub fn mouse_wheel(&mut self, steps: f32) {
+        if self.gravity_well.active {
+            self.gravity_well.adjust_strength(steps);
+        } else {
+            let scale = self.camera_precision();
+            self.view.camera.zoom(steps * 0.1 * scale);
+            self.events.push(AppEvent::CameraChanged);
+        }
+    }
+
+    /// Dispatches queued events and picks up the newest simulation frame.
+    pub fn update(&mut self) {
+        #[cfg(feature = "net")]
+        if let Some(control) = &self.control {
+            let calls: Vec<_> = control.pending().collect();
+            for call in calls {
+                let result = self.answer(call.request.clone());
+                call.reply(result);
+            }
+        }
+        #[cfg(feature = "midi")]
+        self.apply_midi();
+        #[cfg(feature = "net")]
+        self.apply_osc();
+        self.resize.apply(&mut [&mut self.view.camera]);
+        self.update_visibility();
+        if self.gravity_well.active
+            && let Some(frame) = &self.frame
+        {
+            let viewport = self.viewport();
+            self.gravity_well
+                .track_cursor(&self.view.camera, self.input.cursor, viewport);
+            let total_mass = frame.bodies.iter().map(|body| body.mass).sum();
+            let well = self.gravity_well.potential(total_mass, &self.view.camera);
+            self.events.push(AppEvent::GravityWellChanged(well));
+        }
+
+        self.events.dispatch(&mut [
+            &mut self.worker,
+            &mut self.view,
+            &mut self.toasts,
+            &mut self.shader_sources,
+            &mut self.trails,
+            &mut self.shown_trails,
+            &mut self.orbit_outlines,
+            &mut self.measure,
+        ]);
+        self.toasts.expire(Instant::now());
+        #[cfg(feature = "tour")]
+        if self
+            .tour
+            .as_ref()
+            .is_some_and(|tour| tour.is_due(Instant::now()))
+        {
+            self.advance_tour();
+        }
+
+        if let Some(frame) = self.worker.latest_frame()
+            && let Some(previous) = self.frame.replace(frame)
+        {
+            self.worker.recycle(previous);
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &mut self.metrics {
+            metrics.update(Instant::now(), self.frame.as_ref());
+        }
+
+        let Some(frame) = &self.frame else {
+            return;
+        };
+        self.inspector
+            .select(self.view.camera.follow, &frame.bodies);
+        self.trajectory
+            .record(self.view.camera.follow, &frame.bodies, frame.state.time);
+        self.trails.record(&frame.bodies, frame.state.time);
+        if let Some(series) = &mut self.point_clouds
+            && let Err(error) = series.observe(frame)
+        {
+            tracing::warn!(%error, "stopping point cloud export");
+            self.events.push(AppEvent::ToastRequested {
+                message: self.locale.format(
+                    "toast-point-cloud-stopped",
+                    &[("error", error.to_string().into())],
+                ),
+                duration: None,
+            });
+            self.point_clouds = None;
+        }
+        #[cfg(feature = "net")]
+        if let Some(stream) = &mut self.stream {
+            stream.publish(frame);
+        }
+        self.display.clear();
+        self.display.extend_from_slice(&frame.bodies);
+        self.settings.reference_frame.transform(&mut self.display);
+        if self.settings.rotating_frame {
+            self.settings.rotating_view.transform(
+                &mut self.display,
+                frame.state.time,
+                frame.rotating_frame,
+            );
+        }
+        let arrived = self
+            .view
+            .awaiting_simulation
+            .is_none_or(|index| index == frame.simulation_index);
+        if self.view.refit_pending && arrived {
+            self.view.camera.fit_to_bodies(&self.display);
+            self.view.fitted_distance = self.view.camera.distance;
+            self.view.refit_pending = false;
+            self.view.awaiting_simulation = None;
+        }
+        if let Some(body) = self
+            .view
+            .camera
+            .follow
+            .and_then(|index| self.display.get(index))
+        {
+            self.view.camera.target = body.position;
+        }
+        let view = (
+            self.settings.reference_frame,
+            self.settings.rotating_frame,
+            self.settings.rotating_view,
+        );
+        if self.shown_trails_view.replace(view) != Some(view) {
+            self.shown_trails.clear();
+        }
+        self.shown_trails
+            .set_length(self.render_config().trails.length as usize);
+        self.shown_trails.record(&self.display, frame.state.time);
+        self.orbit_outlines.observe(frame);
+        #[cfg(feature = "accesskit")]
+        self.announcer.observe(
+            frame,
+            &self.display,
+            self.view.camera.follow,
+            &self.simulation_names,
+            &self.locale,
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use glam::{Mat4, Vec2, Vec3, Vec4Swizzles};
+
+use crate::simulation::Body;
+
+/// Orbit camera looking at `target` from `distance` along the yaw/pitch direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub target: Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub fovy: f32,
+    pub aspect: f32,
+    pub znear: f32,
+    pub zfar: f32,
+    /// Index of the body the camera keeps centered, if any.
+    pub follow: Option<usize>,
+}
+
+const MIN_DISTANCE: f32 = 1e-3;
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+impl Camera {
+    pub fn new(aspect: f32) -> Self {
+        Self {
+            target: Vec3::ZERO,
+            distance: 10.0,
+            yaw: 0.0,
+            pitch: 0.3,
+            fovy: 45f32.to_radians(),
+            aspect,
+            znear: 0.01,
+            zfar: 10_000.0,
+            follow: None,
+        }
+    }
+
+    pub fn eye(&self) -> Vec3 {
+        let direction = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
+        self.target + direction * self.distance
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.eye(), self.target, Vec3::Y)
+    }
+
+    pub fn projection_matrix(&self) -> Mat4 {
+        Mat4::perspective_rh(self.fovy, self.aspect, self.znear, self.zfar)
+    }
+
+    pub fn view_projection(&self) -> Mat4 {
+        self.projection_matrix() * self.view_matrix()
+    }
+
+    pub fn set_viewport_size(&mut self, width: u32, height: u32) {
+        if width > 0 && height > 0 {
+            self.aspect = width as f32 / height as f32;
+        }
+    }
+
+    pub fn rotate(&mut self, delta_yaw: f32, delta_pitch: f32) {
+        self.yaw += delta_yaw;
+        self.pitch = (self.pitch + delta_pitch).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Moves the target in the camera plane; deltas are fractions of the view distance.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let forward = (self.target - self.eye()).normalize();
+        let right = forward.cross(Vec3::Y).normalize();
+        let up = right.cross(forward);
+        self.target += (right * -dx + up * dy) * self.distance;
+        self.follow = None;
+    }
+
+    /// Scales the view distance; positive deltas move closer.
+    pub fn zoom(&mut self, delta: f32) {
+        self.distance = (self.distance * (1.0 - delta)).max(MIN_DISTANCE);
+    }
+
+    pub fn follow_body(&mut self, index: Option<usize>) {
+        self.follow = index;
+    }
+
+    /// Re-centers on the followed body given the current body positions.
+    pub fn update_follow(&mut self, positions: &[Vec3]) {
+        if let Some(position) = self.follow.and_then(|index| positions.get(index)) {
+            self.target = *position;
+        }
+    }
+
+    /// Projects a world point to the pixel it lands on (physical pixels,
+    /// origin top-left) and its clip-space w, the distance along the view
+    /// direction; `None` behind the camera.
+    pub fn project(&self, point: Vec3, viewport: Vec2) -> Option<(Vec2, f32)> {
+        let clip = self.view_projection() * point.extend(1.0);
+        if clip.w <= self.znear {
+            return None;
+        }
+        let ndc = clip.xy() / clip.w;
+        let pixel = Vec2::new(
+            (ndc.x + 1.0) * 0.5 * viewport.x,
+            (1.0 - ndc.y) * 0.5 * viewport.y,
+        );
+        Some((pixel, clip.w))
+    }
+
+    /// Unprojects a cursor position (physical pixels, origin top-left) onto the
+    /// plane through `target` facing the camera.
+    pub fn cursor_on_target_plane(&self, cursor: Vec2, viewport: Vec2) -> Option<Vec3> {
+        self.cursor_on_plane(cursor, viewport, self.target)
+    }
+
+    /// Like `cursor_on_target_plane`, for the parallel plane through `point`.
+    pub fn cursor_on_plane(&self, cursor: Vec2, viewport: Vec2, point: Vec3) -> Option<Vec3> {
+        if viewport.x <= 0.0 || viewport.y <= 0.0 {
+            return None;
+        }
+        let ndc = Vec2::new(
+            2.0 * cursor.x / viewport.x - 1.0,
+            1.0 - 2.0 * cursor.y / viewport.y,
+        );
+        let inverse = self.view_projection().inverse();
+        let near = inverse * ndc.extend(0.0).extend(1.0);
+        let far = inverse * ndc.extend(1.0).extend(1.0);
+        let origin = near.xyz() / near.w;
+        let direction = (far.xyz() / far.w - origin).normalize();
+
+        let normal = (self.target - self.eye()).normalize();
+        let denominator = direction.dot(normal);
+        if denominator.abs() < 1e-6 {
+            return None;
+        }
+        let t = (point - origin).dot(normal) / denominator;
+        (t > 0.0).then(|| origin + direction * t)
+    }
+
+    /// Centers on the bodies' center of mass and backs off until most of them fit.
+    pub fn fit_to_bodies(&mut self, bodies: &[Body]) {
+        let total_mass: f32 = bodies.iter().map(|body| body.mass).sum();
+        if bodies.is_empty() || total_mass <= 0.0 {
+            return;
+        }
+        let center = bodies
+            .iter()
+            .map(|body| body.position * body.mass)
+            .sum::<Vec3>()
+            / total_mass;
+
+        // Ignore the outermost 5% so a few stragglers don't shrink everything else.
+        let mut extents: Vec<f32> = bodies
+            .iter()
+            .map(|body| (body.position - center).length() + body.radius)
+            .collect();
+        extents.sort_by(f32::total_cmp);
+        let extent = extents[(extents.len() - 1) * 95 / 100];
+
+        self.target = center;
+        self.distance = (1.2 * extent / (0.5 * self.fovy).tan()).max(MIN_DISTANCE);
+        self.znear = self.distance * 1e-4;
+        self.zfar = self.distance * 100.0;
+        self.follow = None;
+    }
+}
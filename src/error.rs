@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use crate::formats::FormatError;
+use crate::recording::RecordingError;
+use crate::rendering::graph::GraphError;
+use crate::simulation::SimulationError;
+
+/// Top-level error wrapping each layer's error type.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Render(#[from] GraphError),
+    #[error(transparent)]
+    Simulation(#[from] SimulationError),
+    #[error(transparent)]
+    Format(#[from] FormatError),
+    #[error(transparent)]
+    Recording(#[from] RecordingError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
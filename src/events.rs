@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use glam::Vec3;
+
+use crate::formats::ParticleSnapshot;
+use crate::rendering::shaders::ShaderId;
+use crate::simulation::{Body, BodyProperties, ExternalPotential, ThrustInput};
+
+/// Everything that crosses subsystem boundaries goes through one of these
+/// instead of direct calls between the app, renderer and simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppEvent {
+    CameraChanged,
+    SimulationSwitched {
+        index: usize,
+        hot_swap: bool,
+    },
+    PauseChanged(bool),
+    TimeScaleChanged(f32),
+    /// Gravitational softening length, in simulation units.
+    SofteningChanged(f32),
+    /// Run the integrator backwards (`true`) or forwards.
+    ReverseChanged(bool),
+    ResetRequested,
+    ParameterChanged {
+        name: &'static str,
+        value: f32,
+    },
+    BodySpawned(Body),
+    /// A body pasted from a copied snippet, keeping its name.
+    BodyPasted {
+        body: Body,
+        name: Option<String>,
+    },
+    /// Remove the body at this index.
+    BodyDeleted(usize),
+    /// The body at `index`, dragged while paused, is now at `position`.
+    BodyMoved {
+        index: usize,
+        position: Vec3,
+    },
+    /// The drag ended; the whole move becomes one undo step.
+    BodyMoveFinished,
+    /// New mass, velocity and color for the body at `index`, from the inspector.
+    BodyEdited {
+        index: usize,
+        properties: BodyProperties,
+    },
+    /// Step back through the spawns, deletions and parameter changes.
+    UndoRequested,
+    RedoRequested,
+    GravityWellChanged(Option<ExternalPotential>),
+    ThrustChanged(ThrustInput),
+    /// Explode a body; `None` picks the most massive one.
+    SupernovaTriggered(Option<usize>),
+    /// Record a rewind point now rather than at the next interval.
+    SnapshotRequested,
+    /// Step back through the recent history.
+    RewindRequested,
+    /// The diagnostics panel opened (`true`) or closed; plots are only binned while open.
+    DiagnosticsToggled(bool),
+    /// Show a transient message; `None` uses the configured toast duration.
+    ToastRequested {
+        message: String,
+        duration: Option<Duration>,
+    },
+    /// Restore a scenario from a share code; the app applies the camera and
+    /// time scale itself.
+    SharedScenarioLoaded {
+        index: usize,
+        seed: Option<u64>,
+        parameters: Vec<(String, f32)>,
+    },
+    /// Replace the running bodies with ones read from a Tipsy or NEMO file.
+    SnapshotImported(Arc<ParticleSnapshot>),
+    /// A validated root file replaced the active one; `None` restores the built-in shader.
+    ShaderChanged {
+        shader: ShaderId,
+        text: Option<Arc<str>>,
+    },
+}
+
+impl AppEvent {
+    /// Whether the event regenerates, reorders or removes bodies, so state
+    /// kept per body index no longer matches. Undo and redo may replay a
+    /// deletion.
+    pub fn invalidates_bodies(&self) -> bool {
+        matches!(
+            self,
+            Self::SimulationSwitched { .. }
+                | Self::SharedScenarioLoaded { .. }
+                | Self::SnapshotImported(_)
+                | Self::ResetRequested
+                | Self::ParameterChanged { .. }
+                | Self::SupernovaTriggered(_)
+                | Self::RewindRequested
+                | Self::BodyDeleted(_)
+                | Self::UndoRequested
+                | Self::RedoRequested
+        )
+    }
+}
+
+pub trait EventHandler {
+    /// Reacts to `event`; follow-up events pushed to `events` are dispatched in the same pass.
+    fn handle(&mut self, event: &AppEvent, events: &mut EventQueue);
+}
+
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    pending: VecDeque<AppEvent>,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, event: AppEvent) {
+        self.pending.push_back(event);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Delivers every queued event to every handler, in order.
+    pub fn dispatch(&mut self, handlers: &mut [&mut dyn EventHandler]) {
+        while let Some(event) = self.pending.pop_front() {
+            for handler in handlers.iter_mut() {
+                handler.handle(&event, self);
+            }
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! One body as a JSON snippet, for copying a body's state to the clipboard
+//! and pasting it back as a new body, here or in another run:
+//!
+//! `{"name":"Sun","position":[0,0,0],"velocity":[0,0,0],"mass":1,"radius":0.05,"color":[1,0.9,0.6,1]}`
+//!
+//! `name`, `radius` and `color` may be missing or `null` when pasting; other
+//! keys are ignored. Bodies with a non-finite value are not copied. Written
+//! and read by hand, so the core needs no JSON crate.
+
+use std::fmt::Write;
+
+use glam::{Vec3, Vec4};
+
+use super::{FormatError, kind_color, radius_or_default};
+use crate::simulation::{Body, BodyKind};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BodyState {
+    pub name: Option<String>,
+    pub body: Body,
+}
+
+impl BodyState {
+    /// `None` if any value is NaN or infinite, which JSON cannot carry.
+    pub fn to_json(&self) -> Option<String> {
+        let body = &self.body;
+        let finite = body.position.is_finite()
+            && body.velocity.is_finite()
+            && body.mass.is_finite()
+            && body.radius.is_finite()
+            && body.color.is_finite();
+        if !finite {
+            return None;
+        }
+        let mut json = String::from("{\"name\":");
+        match &self.name {
+            Some(name) => write_string(&mut json, name),
+            None => json.push_str("null"),
+        }
+        write!(
+            json,
+            ",\"position\":{},\"velocity\":{},\"mass\":{},\"radius\":{},\"color\":{}}}",
+            number_array(&body.position.to_array()),
+            number_array(&body.velocity.to_array()),
+            number(body.mass),
+            number(body.radius),
+            number_array(&body.color.to_array()),
+        )
+        .unwrap();
+        Some(json)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, FormatError> {
+        let mut parser = Parser {
+            text: text.as_bytes(),
+            cursor: 0,
+        };
+        let mut name = None;
+        let mut position = None;
+        let mut velocity = None;
+        let mut mass = None;
+        let mut radius = None;
+        let mut color = None;
+        parser.expect(b'{')?;
+        if !parser.eat(b'}') {
+            loop {
+                let key = parser.string()?;
+                parser.expect(b':')?;
+                let value = parser.value()?;
+                match key.as_str() {
+                    "name" => name = value.text(),
+                    "position" => position = Some(value.vec3("position")?),
+                    "velocity" => velocity = Some(value.vec3("velocity")?),
+                    "mass" => mass = Some(value.number("mass")?),
+                    "radius" => radius = value.optional_number("radius")?,
+                    "color" => color = value.optional_vec4("color")?,
+                    _ => {}
+                }
+                if parser.eat(b'}') {
+                    break;
+                }
+                parser.expect(b',')?;
+            }
+        }
+        if parser.peek().is_some() {
+            return Err(FormatError::Invalid(
+                "trailing characters after body JSON".to_owned(),
+            ));
+        }
+        let missing = |key: &str| FormatError::Invalid(format!("body JSON has no `{key}`"));
+        let mass = mass.ok_or_else(|| missing("mass"))?;
+        if mass < 0.0 {
+            return Err(FormatError::Invalid("negative mass".to_owned()));
+        }
+        Ok(Self {
+            name,
+            body: Body::new(
+                position.ok_or_else(|| missing("position"))?,
+                velocity.ok_or_else(|| missing("velocity"))?,
+                mass,
+                radius_or_default(radius.unwrap_or(0.0)),
+                color.unwrap_or_else(|| kind_color(BodyKind::Massive)),
+            ),
+        })
+    }
+}
+
+/// Finite numbers as Rust prints them, which is valid JSON.
+fn number(value: f32) -> String {
+    value.to_string()
+}
+
+fn number_array(values: &[f32]) -> String {
+    let items: Vec<String> = values.iter().copied().map(number).collect();
+    format!("[{}]", items.join(","))
+}
+
+fn write_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// The JSON values the snippet uses; nested objects are not.
+enum Value {
+    Null,
+    Number(f32),
+    Text(String),
+    Array(Vec<f32>),
+}
+
+impl Value {
+    fn text(self) -> Option<String> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn number(self, key: &str) -> Result<f32, FormatError> {
+        self.optional_number(key)?
+            .ok_or_else(|| FormatError::Invalid(format!("`{key}` must be a number")))
+    }
+
+    fn optional_number(self, key: &str) -> Result<Option<f32>, FormatError> {
+        match self {
+            Self::Number(value) => Ok(Some(value)),
+            Self::Null => Ok(None),
+            _ => Err(FormatError::Invalid(format!("`{key}` must be a number"))),
+        }
+    }
+
+    fn array<const N: usize>(self, key: &str) -> Result<Option<[f32; N]>, FormatError> {
+        match self {
+            Self::Array(values) => values.try_into().map(Some).map_err(|_| {
+                FormatError::Invalid(format!("`{key}` must be an array of {N} numbers"))
+            }),
+            Self::Null => Ok(None),
+            _ => Err(FormatError::Invalid(format!(
+                "`{key}` must be an array of {N} numbers"
+            ))),
+        }
+    }
+
+    fn vec3(self, key: &str) -> Result<Vec3, FormatError> {
+        self.array::<3>(key)?
+            .map(Vec3::from_array)
+            .ok_or_else(|| FormatError::Invalid(format!("`{key}` must not be null")))
+    }
+
+    fn optional_vec4(self, key: &str) -> Result<Option<Vec4>, FormatError> {
+        Ok(self.array::<4>(key)?.map(Vec4::from_array))
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    cursor: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .text
+            .get(self.cursor)
+            .is_some_and(|byte| byte.is_ascii_whitespace())
+        {
+            self.cursor += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.get(self.cursor).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.cursor += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), FormatError> {
+        if self.eat(byte) {
+            return Ok(());
+        }
+        match self.peek() {
+            None => Err(FormatError::Truncated),
+            Some(found) => Err(FormatError::Invalid(format!(
+                "expected `{}` in body JSON, found `{}`",
+                byte as char, found as char
+            ))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, FormatError> {
+        match self.peek().ok_or(FormatError::Truncated)? {
+            b'"' => Ok(Value::Text(self.string()?)),
+            b'[' => {
+                self.cursor += 1;
+                let mut values = Vec::new();
+                if !self.eat(b']') {
+                    loop {
+                        values.push(self.number()?);
+                        if self.eat(b']') {
+                            break;
+                        }
+                        self.expect(b',')?;
+                    }
+                }
+                Ok(Value::Array(values))
+            }
+            b'n' if self.text[self.cursor..].starts_with(b"null") => {
+                self.cursor += 4;
+                Ok(Value::Null)
+            }
+            _ => Ok(Value::Number(self.number()?)),
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, FormatError> {
+        self.skip_whitespace();
+        let start = self.cursor;
+        while self
+            .text
+            .get(self.cursor)
+            .is_some_and(|byte| byte.is_ascii_digit() || b"+-.eE".contains(byte))
+        {
+            self.cursor += 1;
+        }
+        std::str::from_utf8(&self.text[start..self.cursor])
+            .ok()
+            .and_then(|text| text.parse::<f32>().ok())
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| FormatError::Invalid("bad number in body JSON".to_owned()))
+    }
+
+    fn string(&mut self) -> Result<String, FormatError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = *self.text.get(self.cursor).ok_or(FormatError::Truncated)?;
+            self.cursor += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = *self.text.get(self.cursor).ok_or(FormatError::Truncated)?;
+                    self.cursor += 1;
+                    let unescaped = match escape {
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(bad_escape()),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(unescaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes)
+            .map_err(|_| FormatError::Invalid("body JSON is not UTF-8".to_owned()))
+    }
+
+    /// The character of a `\u` escape, after the `u`; a UTF-16 surrogate pair
+    /// spans two escapes.
+    fn unicode_escape(&mut self) -> Result<char, FormatError> {
+        let high = self.hex_code()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high).ok_or_else(bad_escape);
+        }
+        if self.text.get(self.cursor..self.cursor + 2) != Some(b"\\u") {
+            return Err(bad_escape());
+        }
+        self.cursor += 2;
+        let low = self.hex_code()?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return Err(bad_escape());
+        }
+        char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)).ok_or_else(bad_escape)
+    }
+
+    fn hex_code(&mut self) -> Result<u32, FormatError> {
+        let hex = self
+            .text
+            .get(self.cursor..self.cursor + 4)
+            .ok_or(FormatError::Truncated)?;
+        self.cursor += 4;
+        std::str::from_utf8(hex)
+            .ok()
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .ok_or_else(bad_escape)
+    }
+}
+
+fn bad_escape() -> FormatError {
+    FormatError::Invalid("bad escape in body JSON".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> BodyState {
+        BodyState {
+            name: Some("Halley \"1P\"\n☄".to_owned()),
+            body: Body::new(
+                Vec3::new(1.5, -2.25, 0.125),
+                Vec3::new(0.1, 0.2, -0.3),
+                3.0e-4,
+                0.02,
+                Vec4::new(1.0, 0.9, 0.6, 1.0),
+            ),
+        }
+    }
+
+    fn invalid(text: &str) -> bool {
+        matches!(BodyState::from_json(text), Err(FormatError::Invalid(_)))
+    }
+
+    #[test]
+    fn from_json_inverts_to_json() {
+        let state = state();
+        let json = state.to_json().unwrap();
+        assert_eq!(BodyState::from_json(&json).unwrap(), state);
+
+        let unnamed = BodyState {
+            name: None,
+            ..state
+        };
+        assert_eq!(
+            BodyState::from_json(&unnamed.to_json().unwrap()).unwrap(),
+            unnamed
+        );
+    }
+
+    #[test]
+    fn non_finite_bodies_are_not_copied() {
+        for value in [f32::NAN, f32::INFINITY] {
+            let mut state = state();
+            state.body.position.y = value;
+            assert_eq!(state.to_json(), None);
+            let mut state = self::state();
+            state.body.mass = value;
+            assert_eq!(state.to_json(), None);
+        }
+    }
+
+    #[test]
+    fn optional_keys_may_be_missing_or_null() {
+        let minimal = r#"{"position":[1,2,3],"velocity":[0,0,0],"mass":2}"#;
+        let nulls = r#"{"name":null,"position":[1,2,3],"velocity":[0,0,0],"mass":2,"radius":null,"color":null,"extra":"ignored"}"#;
+        for text in [minimal, nulls] {
+            let state = BodyState::from_json(text).unwrap();
+            assert_eq!(state.name, None);
+            assert_eq!(state.body.position, Vec3::new(1.0, 2.0, 3.0));
+            assert_eq!(state.body.mass, 2.0);
+            assert_eq!(state.body.radius, radius_or_default(0.0));
+            assert_eq!(state.body.color, kind_color(BodyKind::Massive));
+        }
+    }
+
+    #[test]
+    fn required_keys_must_be_present_and_not_null() {
+        assert!(invalid(r#"{"position":[1,2,3],"velocity":[0,0,0]}"#));
+        assert!(invalid(r#"{"position":null,"velocity":[0,0,0],"mass":1}"#));
+        assert!(invalid(r#"{"position":[1,2],"velocity":[0,0,0],"mass":1}"#));
+        assert!(invalid(
+            r#"{"position":[1,2,3],"velocity":[0,0,0],"mass":-1}"#
+        ));
+        assert!(invalid(
+            r#"{"position":[1,2,3],"velocity":[0,0,0],"mass":1e99}"#
+        ));
+    }
+
+    #[test]
+    fn truncated_snippets_are_errors() {
+        let json = state().to_json().unwrap();
+        for (length, _) in json.char_indices() {
+            assert!(
+                BodyState::from_json(&json[..length]).is_err(),
+                "{length} bytes parsed"
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_characters_are_rejected() {
+        let json = state().to_json().unwrap();
+        assert!(BodyState::from_json(&format!("  {json}\n")).is_ok());
+        assert!(invalid(&format!("{json}}}")));
+        assert!(invalid(&format!("{json} garbage")));
+    }
+
+    #[test]
+    fn escapes_are_decoded() {
+        let name = |escaped: &str| {
+            let text =
+                format!(r#"{{"name":"{escaped}","position":[0,0,0],"velocity":[0,0,0],"mass":1}}"#);
+            BodyState::from_json(&text).map(|state| state.name.unwrap())
+        };
+        assert_eq!(name(r#"a\"b\\c\/d\te"#).unwrap(), "a\"b\\c/d\te");
+        assert_eq!(name(r"\u00e9\u2604").unwrap(), "é☄");
+        assert_eq!(name(r"\ud83d\ude00").unwrap(), "😀");
+        assert_eq!(name(r"\uD83D\uDE00").unwrap(), "😀");
+    }
+
+    #[test]
+    fn bad_escapes_are_rejected() {
+        for escaped in [
+            r"\x", r"\u12g4", r"\u+123", r"\ud83d", r"\ud83dx", r"\ud83dA", r"\ude00",
+        ] {
+            let text =
+                format!(r#"{{"name":"{escaped}","position":[0,0,0],"velocity":[0,0,0],"mass":1}}"#);
+            assert!(invalid(&text), "{escaped} accepted");
+        }
+    }
+}
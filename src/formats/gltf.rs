@@ -0,0 +1,312 @@
+//! Binary glTF 2.0 (`.glb`) scenes for offline rendering, e.g. in Blender:
+//! every body as a sphere scaled to its radius and every trail as a tube or
+//! line strip, in simulation units with materials taken from the body colors.
+
+use std::collections::{HashMap, VecDeque};
+use std::f32::consts::{PI, TAU};
+use std::fmt::Write as _;
+
+use glam::Vec3;
+
+use crate::simulation::Body;
+
+const GLB_MAGIC: u32 = 0x4654_6c67;
+const JSON_CHUNK: u32 = 0x4e4f_534a;
+const BIN_CHUNK: u32 = 0x004e_4942;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const TRIANGLES: u32 = 4;
+const LINE_STRIP: u32 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrailMesh {
+    /// Bare polylines; Blender imports them as edges that need a bevel to render.
+    Lines,
+    /// Closed tubes around each trail, `relative_radius` times its body's radius.
+    Tubes { relative_radius: f32, sides: u32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GltfOptions {
+    pub trails: TrailMesh,
+    /// Segments around the sphere's equator; half as many rings pole to pole.
+    pub sphere_segments: u32,
+}
+
+impl Default for GltfOptions {
+    fn default() -> Self {
+        Self {
+            trails: TrailMesh::Tubes {
+                relative_radius: 0.25,
+                sides: 8,
+            },
+            sphere_segments: 24,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    bin: Vec<u8>,
+    views: Vec<String>,
+    accessors: Vec<String>,
+    materials: Vec<String>,
+    meshes: Vec<String>,
+    nodes: Vec<String>,
+    /// Material index by color bits.
+    material_of: HashMap<[u32; 4], usize>,
+}
+
+impl Builder {
+    fn view(&mut self, bytes: &[u8], target: u32) -> usize {
+        let offset = self.bin.len();
+        self.bin.extend_from_slice(bytes);
+        self.bin.resize(self.bin.len().next_multiple_of(4), 0);
+        self.views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{offset},\"byteLength\":{},\"target\":{target}}}",
+            bytes.len()
+        ));
+        self.views.len() - 1
+    }
+
+    fn vec3s(&mut self, values: &[Vec3]) -> usize {
+        let (min, max) = values.iter().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(min, max), &value| (min.min(value), max.max(value)),
+        );
+        let view = self.view(bytemuck::cast_slice(values), ARRAY_BUFFER);
+        self.accessors.push(format!(
+            "{{\"bufferView\":{view},\"componentType\":{FLOAT},\"count\":{},\"type\":\"VEC3\",\"min\":[{},{},{}],\"max\":[{},{},{}]}}",
+            values.len(),
+            min.x,
+            min.y,
+            min.z,
+            max.x,
+            max.y,
+            max.z
+        ));
+        self.accessors.len() - 1
+    }
+
+    fn indices(&mut self, indices: &[u32]) -> usize {
+        let view = self.view(bytemuck::cast_slice(indices), ELEMENT_ARRAY_BUFFER);
+        self.accessors.push(format!(
+            "{{\"bufferView\":{view},\"componentType\":{UNSIGNED_INT},\"count\":{},\"type\":\"SCALAR\"}}",
+            indices.len()
+        ));
+        self.accessors.len() - 1
+    }
+
+    fn material(&mut self, body: &Body) -> usize {
+        let color = body.color.clamp(glam::Vec4::ZERO, glam::Vec4::ONE);
+        let key = color.to_array().map(f32::to_bits);
+        if let Some(&index) = self.material_of.get(&key) {
+            return index;
+        }
+        let blend = if color.w < 1.0 {
+            ",\"alphaMode\":\"BLEND\""
+        } else {
+            ""
+        };
+        self.materials.push(format!(
+            "{{\"pbrMetallicRoughness\":{{\"baseColorFactor\":[{},{},{},{}],\"metallicFactor\":0,\"roughnessFactor\":0.8}}{blend}}}",
+            color.x, color.y, color.z, color.w
+        ));
+        self.material_of.insert(key, self.materials.len() - 1);
+        self.materials.len() - 1
+    }
+
+    fn mesh(&mut self, primitive: String) -> usize {
+        self.meshes
+            .push(format!("{{\"primitives\":[{primitive}]}}"));
+        self.meshes.len() - 1
+    }
+
+    fn node(&mut self, name: &str, mesh: usize, transform: &str) {
+        self.nodes.push(format!(
+            "{{\"name\":\"{name}\",\"mesh\":{mesh}{transform}}}"
+        ));
+    }
+
+    fn into_glb(self) -> Vec<u8> {
+        let nodes = (0..self.nodes.len())
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut json = String::new();
+        write!(
+            json,
+            "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"n-body-problem-webgpu\"}},\"scene\":0,\"scenes\":[{{\"nodes\":[{nodes}]}}]"
+        )
+        .unwrap();
+        for (name, items) in [
+            ("nodes", &self.nodes),
+            ("meshes", &self.meshes),
+            ("materials", &self.materials),
+            ("accessors", &self.accessors),
+            ("bufferViews", &self.views),
+        ] {
+            if !items.is_empty() {
+                write!(json, ",\"{name}\":[{}]", items.join(",")).unwrap();
+            }
+        }
+        if !self.bin.is_empty() {
+            write!(json, ",\"buffers\":[{{\"byteLength\":{}}}]", self.bin.len()).unwrap();
+        }
+        json.push('}');
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+
+        let bin_chunk = if self.bin.is_empty() {
+            0
+        } else {
+            8 + self.bin.len()
+        };
+        let total = 12 + 8 + json.len() + bin_chunk;
+        let mut glb = Vec::with_capacity(total);
+        for word in [GLB_MAGIC, 2, total as u32, json.len() as u32, JSON_CHUNK] {
+            glb.extend_from_slice(&word.to_le_bytes());
+        }
+        glb.extend_from_slice(&json);
+        if !self.bin.is_empty() {
+            glb.extend_from_slice(&(self.bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&BIN_CHUNK.to_le_bytes());
+            glb.extend_from_slice(&self.bin);
+        }
+        glb
+    }
+}
+
+/// Unit UV sphere: positions (also its normals) and indices.
+fn sphere(segments: u32) -> (Vec<Vec3>, Vec<u32>) {
+    let segments = segments.max(3);
+    let rings = (segments / 2).max(2);
+    let mut positions = Vec::new();
+    for ring in 0..=rings {
+        let theta = PI * ring as f32 / rings as f32;
+        for segment in 0..=segments {
+            let phi = TAU * segment as f32 / segments as f32;
+            positions.push(Vec3::new(
+                theta.sin() * phi.cos(),
+                theta.cos(),
+                theta.sin() * phi.sin(),
+            ));
+        }
+    }
+    let mut indices = Vec::new();
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let a = ring * (segments + 1) + segment;
+            let b = a + segments + 1;
+            indices.extend_from_slice(&[a, a + 1, b, a + 1, b + 1, b]);
+        }
+    }
+    (positions, indices)
+}
+
+/// Tube around `points`, with rings kept from twisting by parallel transport.
+fn tube(points: &[Vec3], radius: f32, sides: u32) -> (Vec<Vec3>, Vec<Vec3>, Vec<u32>) {
+    let sides = sides.max(3);
+    let last = points.len() - 1;
+    let mut positions = Vec::with_capacity(points.len() * sides as usize);
+    let mut normals = Vec::with_capacity(positions.capacity());
+    let mut normal = None;
+    for (index, &point) in points.iter().enumerate() {
+        let tangent =
+            (points[(index + 1).min(last)] - points[index.saturating_sub(1)]).normalize_or(Vec3::X);
+        let previous = normal.unwrap_or_else(|| tangent.any_orthonormal_vector());
+        let transported = (previous - tangent * previous.dot(tangent))
+            .try_normalize()
+            .unwrap_or_else(|| tangent.any_orthonormal_vector());
+        normal = Some(transported);
+        let binormal = tangent.cross(transported);
+        for side in 0..sides {
+            let angle = TAU * side as f32 / sides as f32;
+            let direction = transported * angle.cos() + binormal * angle.sin();
+            positions.push(point + direction * radius);
+            normals.push(direction);
+        }
+    }
+    let mut indices = Vec::new();
+    for segment in 0..last as u32 {
+        for side in 0..sides {
+            let a = segment * sides + side;
+            let b = segment * sides + (side + 1) % sides;
+            indices.extend_from_slice(&[a, b, a + sides, b, b + sides, a + sides]);
+        }
+    }
+    (positions, normals, indices)
+}
+
+/// Writes `bodies` and their `trails` (parallel, oldest point first; missing
+/// or short trails are skipped) as a single-scene GLB.
+pub fn encode(bodies: &[Body], trails: &[VecDeque<Vec3>], options: &GltfOptions) -> Vec<u8> {
+    let mut builder = Builder::default();
+    let (sphere_positions, sphere_indices) = sphere(options.sphere_segments);
+    let sphere_position = builder.vec3s(&sphere_positions);
+    let sphere_index = builder.indices(&sphere_indices);
+    // One sphere mesh per material, all sharing the geometry.
+    let mut sphere_meshes = HashMap::new();
+
+    for (index, body) in bodies.iter().enumerate() {
+        if !body.position.is_finite() {
+            continue;
+        }
+        let material = builder.material(body);
+        let mesh = *sphere_meshes.entry(material).or_insert_with(|| {
+            builder.mesh(format!(
+                "{{\"attributes\":{{\"POSITION\":{sphere_position},\"NORMAL\":{sphere_position}}},\"indices\":{sphere_index},\"material\":{material},\"mode\":{TRIANGLES}}}"
+            ))
+        });
+        let p = body.position;
+        let r = body.radius;
+        builder.node(
+            &format!("body {index}"),
+            mesh,
+            &format!(
+                ",\"translation\":[{},{},{}],\"scale\":[{r},{r},{r}]",
+                p.x, p.y, p.z
+            ),
+        );
+    }
+
+    for (index, (body, trail)) in bodies.iter().zip(trails).enumerate() {
+        let mut points: Vec<Vec3> = trail
+            .iter()
+            .copied()
+            .filter(|point| point.is_finite())
+            .collect();
+        points.dedup();
+        if points.len() < 2 {
+            continue;
+        }
+        let material = builder.material(body);
+        let primitive = match options.trails {
+            TrailMesh::Lines => {
+                let position = builder.vec3s(&points);
+                format!(
+                    "{{\"attributes\":{{\"POSITION\":{position}}},\"material\":{material},\"mode\":{LINE_STRIP}}}"
+                )
+            }
+            TrailMesh::Tubes {
+                relative_radius,
+                sides,
+            } => {
+                let (positions, normals, indices) =
+                    tube(&points, body.radius * relative_radius, sides);
+                let position = builder.vec3s(&positions);
+                let normal = builder.vec3s(&normals);
+                let indices = builder.indices(&indices);
+                format!(
+                    "{{\"attributes\":{{\"POSITION\":{position},\"NORMAL\":{normal}}},\"indices\":{indices},\"material\":{material},\"mode\":{TRIANGLES}}}"
+                )
+            }
+        };
+        let mesh = builder.mesh(primitive);
+        builder.node(&format!("trail {index}"), mesh, "");
+    }
+
+    builder.into_glb()
+}
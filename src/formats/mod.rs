@@ -0,0 +1,175 @@
+//! Particle snapshot files shared with other N-body codes, and scene
+//! exports for other tools, plus the backdrop images scenarios draw behind
+//! them and the JSON snippets single bodies are copied as.
+
+pub mod body_json;
+pub mod gltf;
+pub mod nemo;
+pub mod ply;
+pub mod ppm;
+pub mod tipsy;
+
+use std::path::Path;
+
+use glam::Vec4;
+use thiserror::Error;
+
+use crate::simulation::worker::SimulationFrame;
+use crate::simulation::{Body, BodyKind};
+
+/// Radius given to imported bodies whose file carries no usable size.
+const DEFAULT_RADIUS: f32 = 0.01;
+
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("snapshot file is truncated")]
+    Truncated,
+    #[error("invalid snapshot: {0}")]
+    Invalid(String),
+}
+
+/// Bodies and clock as exchanged with Tipsy or NEMO, in the file's units.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParticleSnapshot {
+    pub time: f64,
+    pub bodies: Vec<Body>,
+    /// Parallel to `bodies`.
+    pub kinds: Vec<BodyKind>,
+}
+
+impl ParticleSnapshot {
+    pub fn from_frame(frame: &SimulationFrame) -> Self {
+        Self {
+            time: frame.state.time,
+            bodies: frame.bodies.clone(),
+            kinds: frame
+                .flags
+                .iter()
+                .map(|&flags| BodyKind::from_flags(flags))
+                .collect(),
+        }
+    }
+
+    /// Parses a NEMO or Tipsy file, told apart by NEMO's leading magic number.
+    pub fn decode(bytes: &[u8]) -> Result<Self, FormatError> {
+        if nemo::is_nemo(bytes) {
+            nemo::decode(bytes)
+        } else {
+            tipsy::decode(bytes)
+        }
+    }
+
+    pub fn encode(&self, format: SnapshotFormat) -> Vec<u8> {
+        match format {
+            SnapshotFormat::Tipsy => tipsy::encode(self),
+            SnapshotFormat::Nemo => nemo::encode(self),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Standard (big-endian XDR) Tipsy binary.
+    Tipsy,
+    /// NEMO binary structured file holding one `SnapShot`.
+    Nemo,
+}
+
+impl SnapshotFormat {
+    /// NEMO for a `.nemo` or `.snap` extension, Tipsy otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension)
+                if extension.eq_ignore_ascii_case("nemo")
+                    || extension.eq_ignore_ascii_case("snap") =>
+            {
+                Self::Nemo
+            }
+            _ => Self::Tipsy,
+        }
+    }
+}
+
+/// Imported bodies carry no color, so they are tinted by kind.
+fn kind_color(kind: BodyKind) -> Vec4 {
+    match kind {
+        BodyKind::Star => Vec4::new(1.0, 0.9, 0.6, 1.0),
+        BodyKind::Gas => Vec4::new(0.5, 0.7, 1.0, 0.6),
+        _ => Vec4::new(0.85, 0.85, 0.9, 1.0),
+    }
+}
+
+fn radius_or_default(radius: f32) -> f32 {
+    if radius.is_finite() && radius > 0.0 {
+        radius
+    } else {
+        DEFAULT_RADIUS
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// Cursor over a snapshot file in either byte order.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], endian: Endian) -> Self {
+        Self { bytes, endian }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], FormatError> {
+        if self.bytes.len() < count {
+            return Err(FormatError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(count);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], FormatError> {
+        let mut bytes: [u8; N] = self.take(N)?.try_into().expect("took N bytes");
+        if self.endian == Endian::Big {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, FormatError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, FormatError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, FormatError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, FormatError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    /// NUL-terminated string.
+    fn c_string(&mut self) -> Result<String, FormatError> {
+        let end = self
+            .bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or(FormatError::Truncated)?;
+        let text = String::from_utf8_lossy(&self.bytes[..end]).into_owned();
+        self.take(end + 1)?;
+        Ok(text)
+    }
+}
@@ -0,0 +1,331 @@
+//! NEMO binary structured files, as written by NEMO tools such as
+//! `mkplummer` and by gyrfalcON: tagged items nested in sets, read in either
+//! byte order.
+//!
+//! Export writes one little-endian `SnapShot` set:
+//!
+//! ```text
+//! set SnapShot
+//!   set Parameters
+//!     int Nobj
+//!     double Time
+//!   tes
+//!   set Particles
+//!     int CoordSystem
+//!     double Mass[N]
+//!     double PhaseSpace[N][2][3]
+//!   tes
+//! tes
+//! ```
+//!
+//! Import takes the first snapshot, with `PhaseSpace` or separate `Position`
+//! and `Velocity` arrays in single or double precision. NEMO has no particle
+//! types, so every body is `BodyKind::Massive`.
+
+use glam::Vec3;
+
+use super::{DEFAULT_RADIUS, Endian, FormatError, ParticleSnapshot, Reader, kind_color};
+use crate::simulation::{Body, BodyKind};
+
+/// Magic numbers opening a single-valued or an array item.
+const SINGLE_MAGIC: u16 = 0x0992;
+const PLURAL_MAGIC: u16 = 0x0b92;
+/// Cartesian coordinates, three dimensions, positions and velocities.
+const COORD_SYSTEM: i32 = 0x10302;
+
+/// Whether `bytes` opens with a NEMO item header.
+pub fn is_nemo(bytes: &[u8]) -> bool {
+    magic_endian(bytes).is_some()
+}
+
+fn magic_endian(bytes: &[u8]) -> Option<Endian> {
+    let magic = u16::from_le_bytes(bytes.get(..2)?.try_into().ok()?);
+    if magic == SINGLE_MAGIC || magic == PLURAL_MAGIC {
+        Some(Endian::Little)
+    } else if magic.swap_bytes() == SINGLE_MAGIC || magic.swap_bytes() == PLURAL_MAGIC {
+        Some(Endian::Big)
+    } else {
+        None
+    }
+}
+
+/// One item of the file, with nested items for sets.
+#[derive(Debug)]
+enum Item {
+    Set {
+        tag: String,
+        items: Vec<Item>,
+    },
+    Data {
+        tag: String,
+        values: Vec<f64>,
+    },
+    /// Characters and other data a snapshot does not need.
+    Other {
+        tag: String,
+    },
+}
+
+impl Item {
+    fn tag(&self) -> &str {
+        match self {
+            Self::Set { tag, .. } | Self::Data { tag, .. } | Self::Other { tag } => tag,
+        }
+    }
+}
+
+fn find<'a>(items: &'a [Item], wanted: &str) -> Option<&'a Item> {
+    items.iter().find(|item| item.tag() == wanted)
+}
+
+fn set_items<'a>(items: &'a [Item], wanted: &str) -> Option<&'a [Item]> {
+    match find(items, wanted)? {
+        Item::Set { items, .. } => Some(items),
+        _ => None,
+    }
+}
+
+fn values<'a>(items: &'a [Item], wanted: &str) -> Option<&'a [f64]> {
+    match find(items, wanted)? {
+        Item::Data { values, .. } => Some(values),
+        _ => None,
+    }
+}
+
+/// Reads items until the enclosing set's `tes`, or the end of the file at
+/// the top level; a `nested` set cut off before its `tes` is truncated.
+fn read_items(reader: &mut Reader, nested: bool) -> Result<Vec<Item>, FormatError> {
+    let mut items = Vec::new();
+    while !reader.is_empty() {
+        let magic = reader.u16()?;
+        if magic != SINGLE_MAGIC && magic != PLURAL_MAGIC {
+            return Err(FormatError::Invalid(format!("bad item magic {magic:#06x}")));
+        }
+        let kind = reader.c_string()?;
+        if kind == ")" {
+            return Ok(items);
+        }
+        let tag = reader.c_string()?;
+        if kind == "(" {
+            let nested = read_items(reader, true)?;
+            items.push(Item::Set { tag, items: nested });
+            continue;
+        }
+        let mut count = 1usize;
+        if magic == PLURAL_MAGIC {
+            loop {
+                let dimension = reader.i32()?;
+                if dimension == 0 {
+                    break;
+                }
+                let dimension = usize::try_from(dimension)
+                    .map_err(|_| FormatError::Invalid(format!("negative dimension in {tag}")))?;
+                count = count.saturating_mul(dimension);
+            }
+        }
+        let element = match kind.as_str() {
+            "a" | "c" | "b" => 1,
+            "s" | "h" => 2,
+            "i" | "f" => 4,
+            "l" | "d" => 8,
+            _ => {
+                return Err(FormatError::Invalid(format!(
+                    "unsupported item type `{kind}` in {tag}"
+                )));
+            }
+        };
+        let bytes = count
+            .checked_mul(element)
+            .ok_or_else(|| FormatError::Invalid(format!("{tag} is too large")))?;
+        let mut data = Reader::new(reader.take(bytes)?, reader.endian);
+        let values = match kind.as_str() {
+            "i" => (0..count)
+                .map(|_| data.i32().map(f64::from))
+                .collect::<Result<_, _>>()?,
+            "f" => (0..count)
+                .map(|_| data.f32().map(f64::from))
+                .collect::<Result<_, _>>()?,
+            "d" => (0..count).map(|_| data.f64()).collect::<Result<_, _>>()?,
+            _ => {
+                items.push(Item::Other { tag });
+                continue;
+            }
+        };
+        items.push(Item::Data { tag, values });
+    }
+    if nested {
+        return Err(FormatError::Truncated);
+    }
+    Ok(items)
+}
+
+pub fn decode(bytes: &[u8]) -> Result<ParticleSnapshot, FormatError> {
+    let endian =
+        magic_endian(bytes).ok_or_else(|| FormatError::Invalid("not a NEMO file".into()))?;
+    let items = read_items(&mut Reader::new(bytes, endian), false)?;
+    let snapshot = set_items(&items, "SnapShot")
+        .ok_or_else(|| FormatError::Invalid("no SnapShot set".into()))?;
+    let parameters = set_items(snapshot, "Parameters").unwrap_or_default();
+    let particles = set_items(snapshot, "Particles")
+        .ok_or_else(|| FormatError::Invalid("no Particles set".into()))?;
+    let time = values(parameters, "Time")
+        .and_then(|time| time.first().copied())
+        .unwrap_or(0.0);
+    let masses =
+        values(particles, "Mass").ok_or_else(|| FormatError::Invalid("no Mass array".into()))?;
+
+    let vector = |values: &[f64], stride: usize, offset: usize, index: usize| {
+        let start = index * stride + offset;
+        Vec3::new(
+            values[start] as f32,
+            values[start + 1] as f32,
+            values[start + 2] as f32,
+        )
+    };
+    let mut bodies = Vec::with_capacity(masses.len());
+    if let Some(phase_space) = values(particles, "PhaseSpace") {
+        if phase_space.len() != 6 * masses.len() {
+            return Err(FormatError::Invalid(
+                "PhaseSpace does not match Mass".into(),
+            ));
+        }
+        for (index, &mass) in masses.iter().enumerate() {
+            bodies.push((
+                vector(phase_space, 6, 0, index),
+                vector(phase_space, 6, 3, index),
+                mass,
+            ));
+        }
+    } else {
+        let (Some(positions), Some(velocities)) =
+            (values(particles, "Position"), values(particles, "Velocity"))
+        else {
+            return Err(FormatError::Invalid(
+                "no PhaseSpace or Position and Velocity".into(),
+            ));
+        };
+        if positions.len() != 3 * masses.len() || velocities.len() != 3 * masses.len() {
+            return Err(FormatError::Invalid(
+                "Position or Velocity does not match Mass".into(),
+            ));
+        }
+        for (index, &mass) in masses.iter().enumerate() {
+            bodies.push((
+                vector(positions, 3, 0, index),
+                vector(velocities, 3, 0, index),
+                mass,
+            ));
+        }
+    }
+
+    let color = kind_color(BodyKind::Massive);
+    Ok(ParticleSnapshot {
+        time,
+        kinds: vec![BodyKind::Massive; bodies.len()],
+        bodies: bodies
+            .into_iter()
+            .map(|(position, velocity, mass)| {
+                Body::new(position, velocity, mass as f32, DEFAULT_RADIUS, color)
+            })
+            .collect(),
+    })
+}
+
+/// Little-endian, double precision.
+pub fn encode(snapshot: &ParticleSnapshot) -> Vec<u8> {
+    let count = snapshot.bodies.len();
+    let mut bytes = Vec::with_capacity(128 + 56 * count);
+    let header = |bytes: &mut Vec<u8>, kind: &str, tag: Option<&str>, dims: &[i32]| {
+        let magic = if dims.is_empty() {
+            SINGLE_MAGIC
+        } else {
+            PLURAL_MAGIC
+        };
+        bytes.extend_from_slice(&magic.to_le_bytes());
+        for text in [Some(kind), tag].into_iter().flatten() {
+            bytes.extend_from_slice(text.as_bytes());
+            bytes.push(0);
+        }
+        if !dims.is_empty() {
+            for dimension in dims.iter().chain([&0]) {
+                bytes.extend_from_slice(&dimension.to_le_bytes());
+            }
+        }
+    };
+    let bodies = i32::try_from(count).unwrap_or(i32::MAX);
+
+    header(&mut bytes, "(", Some("SnapShot"), &[]);
+    header(&mut bytes, "(", Some("Parameters"), &[]);
+    header(&mut bytes, "i", Some("Nobj"), &[]);
+    bytes.extend_from_slice(&bodies.to_le_bytes());
+    header(&mut bytes, "d", Some("Time"), &[]);
+    bytes.extend_from_slice(&snapshot.time.to_le_bytes());
+    header(&mut bytes, ")", None, &[]);
+
+    header(&mut bytes, "(", Some("Particles"), &[]);
+    header(&mut bytes, "i", Some("CoordSystem"), &[]);
+    bytes.extend_from_slice(&COORD_SYSTEM.to_le_bytes());
+    header(&mut bytes, "d", Some("Mass"), &[bodies]);
+    for body in &snapshot.bodies {
+        bytes.extend_from_slice(&f64::from(body.mass).to_le_bytes());
+    }
+    header(&mut bytes, "d", Some("PhaseSpace"), &[bodies, 2, 3]);
+    for body in &snapshot.bodies {
+        for value in body
+            .position
+            .to_array()
+            .into_iter()
+            .chain(body.velocity.to_array())
+        {
+            bytes.extend_from_slice(&f64::from(value).to_le_bytes());
+        }
+    }
+    header(&mut bytes, ")", None, &[]);
+    header(&mut bytes, ")", None, &[]);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> ParticleSnapshot {
+        let color = kind_color(BodyKind::Massive);
+        ParticleSnapshot {
+            time: 3.75,
+            bodies: vec![
+                Body::new(
+                    Vec3::new(1.0, -2.0, 0.5),
+                    Vec3::new(0.1, 0.2, -0.3),
+                    0.5,
+                    DEFAULT_RADIUS,
+                    color,
+                ),
+                Body::new(
+                    Vec3::new(-4.0, 0.0, 8.0),
+                    Vec3::new(0.0, -1.5, 0.25),
+                    0.125,
+                    DEFAULT_RADIUS,
+                    color,
+                ),
+            ],
+            kinds: vec![BodyKind::Massive; 2],
+        }
+    }
+
+    #[test]
+    fn decode_inverts_encode() {
+        let snapshot = snapshot();
+        let bytes = encode(&snapshot);
+        assert!(is_nemo(&bytes));
+        assert_eq!(decode(&bytes).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn truncated_files_are_errors() {
+        let bytes = encode(&snapshot());
+        for length in 0..bytes.len() {
+            assert!(decode(&bytes[..length]).is_err(), "{length} bytes decoded");
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Binary little-endian PLY point clouds for CloudCompare or MeshLab: one
+//! vertex per body with its position, 8-bit color, mass and radius.
+
+use std::fmt::Write as _;
+
+use crate::simulation::Body;
+
+/// Bytes per vertex: three position floats, RGB, mass and radius.
+const VERTEX_BYTES: usize = 12 + 3 + 4 + 4;
+
+/// Bodies with non-finite positions are left out.
+pub fn encode(bodies: &[Body]) -> Vec<u8> {
+    let finite = bodies.iter().filter(|body| body.position.is_finite());
+    let mut header = String::from("ply\nformat binary_little_endian 1.0\n");
+    header.push_str("comment n-body-problem-webgpu frame, simulation units\n");
+    writeln!(header, "element vertex {}", finite.clone().count()).unwrap();
+    for property in [
+        "float x",
+        "float y",
+        "float z",
+        "uchar red",
+        "uchar green",
+        "uchar blue",
+        "float mass",
+        "float radius",
+    ] {
+        writeln!(header, "property {property}").unwrap();
+    }
+    header.push_str("end_header\n");
+
+    let mut bytes = header.into_bytes();
+    bytes.reserve(VERTEX_BYTES * bodies.len());
+    for body in finite {
+        for value in body.position.to_array() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for channel in body.color.truncate().to_array() {
+            bytes.push((channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+        bytes.extend_from_slice(&body.mass.to_le_bytes());
+        bytes.extend_from_slice(&body.radius.to_le_bytes());
+    }
+    bytes
+}
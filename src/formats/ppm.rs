@@ -0,0 +1,78 @@
+//! Binary PPM (`P6`) images, the one raster format read without an image
+//! crate: scenario backdrops are exported to it from any editor.
+
+use super::FormatError;
+
+/// 8-bit RGBA pixels, row-major from the top-left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Header fields are separated by whitespace and may be interleaved with
+/// `#` comments; a single whitespace byte separates the header from the
+/// pixels. Only 8-bit samples (max value up to 255) are supported.
+pub fn decode(bytes: &[u8]) -> Result<Image, FormatError> {
+    let mut cursor = 0;
+    let mut field = || -> Result<&[u8], FormatError> {
+        loop {
+            match bytes.get(cursor) {
+                Some(b'#') => {
+                    while bytes.get(cursor).is_some_and(|&byte| byte != b'\n') {
+                        cursor += 1;
+                    }
+                }
+                Some(byte) if byte.is_ascii_whitespace() => cursor += 1,
+                Some(_) => break,
+                None => return Err(FormatError::Truncated),
+            }
+        }
+        let start = cursor;
+        while bytes
+            .get(cursor)
+            .is_some_and(|byte| !byte.is_ascii_whitespace())
+        {
+            cursor += 1;
+        }
+        Ok(&bytes[start..cursor])
+    };
+    if field()? != b"P6" {
+        return Err(FormatError::Invalid(
+            "not a binary PPM (P6) image".to_owned(),
+        ));
+    }
+    let mut number = |name: &str| -> Result<u32, FormatError> {
+        std::str::from_utf8(field()?)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| FormatError::Invalid(format!("bad PPM {name}")))
+    };
+    let width = number("width")?;
+    let height = number("height")?;
+    let max_value = number("max value")?;
+    if !(1..=255).contains(&max_value) {
+        return Err(FormatError::Invalid(format!(
+            "unsupported PPM max value {max_value}"
+        )));
+    }
+    // The single whitespace byte after the max value.
+    cursor += 1;
+    let pixels = width as usize * height as usize;
+    let data = bytes
+        .get(cursor..cursor + 3 * pixels)
+        .ok_or(FormatError::Truncated)?;
+    let mut rgba = Vec::with_capacity(4 * pixels);
+    for pixel in data.chunks_exact(3) {
+        for &sample in pixel {
+            rgba.push((u32::from(sample).min(max_value) * 255 / max_value) as u8);
+        }
+        rgba.push(255);
+    }
+    Ok(Image {
+        width,
+        height,
+        rgba,
+    })
+}
@@ -0,0 +1,209 @@
+//! Tipsy binary snapshots: a header, then gas, dark and star particles in
+//! that order. Standard files are big-endian with a padded 32-byte header;
+//! native little-endian dumps, with or without the padding, are read too.
+//!
+//! Gas particles map to `BodyKind::Gas`, stars to `Star` and dark particles
+//! to `Massive`, so a pure dark-matter run is still drawn. On export `Gas`
+//! and `Star` keep their type and every other kind is written as dark. The
+//! smoothing length (gas) or softening (dark, star) is exchanged with the
+//! body radius.
+
+use glam::Vec3;
+
+use super::{Endian, FormatError, ParticleSnapshot, Reader, kind_color, radius_or_default};
+use crate::simulation::{Body, BodyKind};
+
+const HEADER_BYTES: usize = 32;
+/// Floats per particle: mass, position, velocity and the type's extra fields.
+const GAS_FLOATS: usize = 12;
+const DARK_FLOATS: usize = 9;
+const STAR_FLOATS: usize = 11;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Gas,
+    Dark,
+    Star,
+}
+
+impl Family {
+    fn floats(self) -> usize {
+        match self {
+            Self::Gas => GAS_FLOATS,
+            Self::Dark => DARK_FLOATS,
+            Self::Star => STAR_FLOATS,
+        }
+    }
+
+    fn kind(self) -> BodyKind {
+        match self {
+            Self::Gas => BodyKind::Gas,
+            Self::Dark => BodyKind::Massive,
+            Self::Star => BodyKind::Star,
+        }
+    }
+
+    fn of(kind: BodyKind) -> Self {
+        match kind {
+            BodyKind::Gas => Self::Gas,
+            BodyKind::Star => Self::Star,
+            _ => Self::Dark,
+        }
+    }
+}
+
+pub fn decode(bytes: &[u8]) -> Result<ParticleSnapshot, FormatError> {
+    // `ndim` is 3 in any valid file, which gives away the byte order.
+    let endian = match bytes.get(12..16) {
+        Some([0, 0, 0, 3]) => Endian::Big,
+        Some([3, 0, 0, 0]) => Endian::Little,
+        Some(_) => return Err(FormatError::Invalid("not a Tipsy file".into())),
+        None => return Err(FormatError::Truncated),
+    };
+    let mut reader = Reader::new(bytes, endian);
+    let time = reader.f64()?;
+    let mut counts = [0usize; 5];
+    for count in &mut counts {
+        *count = usize::try_from(reader.i32()?)
+            .map_err(|_| FormatError::Invalid("negative particle count".into()))?;
+    }
+    let [total, _, gas, dark, star] = counts;
+    if gas + dark + star != total {
+        return Err(FormatError::Invalid(format!(
+            "{total} bodies but {gas} gas, {dark} dark and {star} star particles"
+        )));
+    }
+    let payload = 4 * (gas * GAS_FLOATS + dark * DARK_FLOATS + star * STAR_FLOATS);
+    // Native dumps may lack the 4 bytes padding the header to 32; standard
+    // big-endian files always have them, so a cut-off one stays truncated.
+    let header = if endian == Endian::Little && bytes.len() == HEADER_BYTES - 4 + payload {
+        HEADER_BYTES - 4
+    } else {
+        HEADER_BYTES
+    };
+    let mut reader = Reader::new(bytes.get(header..).ok_or(FormatError::Truncated)?, endian);
+
+    let mut snapshot = ParticleSnapshot {
+        time,
+        bodies: Vec::with_capacity(total),
+        kinds: Vec::with_capacity(total),
+    };
+    for (family, count) in [
+        (Family::Gas, gas),
+        (Family::Dark, dark),
+        (Family::Star, star),
+    ] {
+        for _ in 0..count {
+            let mut fields = [0.0; GAS_FLOATS];
+            for field in &mut fields[..family.floats()] {
+                *field = reader.f32()?;
+            }
+            // hsmooth for gas, eps otherwise.
+            let size = match family {
+                Family::Gas => fields[9],
+                Family::Dark => fields[7],
+                Family::Star => fields[9],
+            };
+            let kind = family.kind();
+            snapshot.bodies.push(Body::new(
+                Vec3::new(fields[1], fields[2], fields[3]),
+                Vec3::new(fields[4], fields[5], fields[6]),
+                fields[0],
+                radius_or_default(size),
+                kind_color(kind),
+            ));
+            snapshot.kinds.push(kind);
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Standard big-endian Tipsy, particles regrouped by type.
+pub fn encode(snapshot: &ParticleSnapshot) -> Vec<u8> {
+    let family = |index: usize| {
+        Family::of(
+            snapshot
+                .kinds
+                .get(index)
+                .copied()
+                .unwrap_or(BodyKind::Massive),
+        )
+    };
+    let count = |wanted| {
+        (0..snapshot.bodies.len())
+            .filter(|&index| family(index) == wanted)
+            .count() as i32
+    };
+    let (gas, dark, star) = (count(Family::Gas), count(Family::Dark), count(Family::Star));
+
+    let mut bytes =
+        Vec::with_capacity(HEADER_BYTES + 4 * STAR_FLOATS.max(GAS_FLOATS) * snapshot.bodies.len());
+    bytes.extend_from_slice(&snapshot.time.to_be_bytes());
+    for value in [gas + dark + star, 3, gas, dark, star, 0] {
+        bytes.extend_from_slice(&value.to_be_bytes());
+    }
+    for wanted in [Family::Gas, Family::Dark, Family::Star] {
+        for (index, body) in snapshot.bodies.iter().enumerate() {
+            if family(index) != wanted {
+                continue;
+            }
+            let (p, v) = (body.position, body.velocity);
+            let mut fields = vec![body.mass, p.x, p.y, p.z, v.x, v.y, v.z];
+            match wanted {
+                // rho, temp, hsmooth, metals, phi
+                Family::Gas => fields.extend([0.0, 0.0, body.radius, 0.0, 0.0]),
+                // eps, phi
+                Family::Dark => fields.extend([body.radius, 0.0]),
+                // metals, tform, eps, phi
+                Family::Star => fields.extend([0.0, 0.0, body.radius, 0.0]),
+            }
+            for field in fields {
+                bytes.extend_from_slice(&field.to_be_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> ParticleSnapshot {
+        // One of each family, in file order, so the regrouping keeps them.
+        let kinds = vec![BodyKind::Gas, BodyKind::Massive, BodyKind::Star];
+        let bodies = kinds
+            .iter()
+            .enumerate()
+            .map(|(index, &kind)| {
+                let offset = index as f32;
+                Body::new(
+                    Vec3::new(1.0 + offset, -2.0, 0.5),
+                    Vec3::new(0.1, 0.2 * offset, -0.3),
+                    0.25 + offset,
+                    0.02 + 0.01 * offset,
+                    kind_color(kind),
+                )
+            })
+            .collect();
+        ParticleSnapshot {
+            time: 12.5,
+            bodies,
+            kinds,
+        }
+    }
+
+    #[test]
+    fn decode_inverts_encode() {
+        let snapshot = snapshot();
+        assert_eq!(decode(&encode(&snapshot)).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn truncated_files_are_errors() {
+        let bytes = encode(&snapshot());
+        for length in 0..bytes.len() {
+            assert!(decode(&bytes[..length]).is_err(), "{length} bytes decoded");
+        }
+    }
+}
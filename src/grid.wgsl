@@ -0,0 +1,102 @@
+// Uniform-grid build for neighbour queries, mirroring `simulation::grid`.
+// Dispatch order: cs_grid_clear (one thread per cell), cs_grid_assign (one per
+// body), cs_grid_scan (a single workgroup), cs_grid_reorder (one per body).
+// Consumers then walk `sorted[cell_start[c]..cell_start[c + 1]]`.
+
+#include "body.wgsl"
+#include "dispatch.wgsl"
+
+struct GridUniforms {
+    origin: vec3<f32>,
+    cell_size: f32,
+    dimensions: vec3<u32>,
+    body_count: u32,
+}
+
+const SCAN_WORKGROUP_SIZE: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> grid: GridUniforms;
+@group(0) @binding(1) var<storage, read> bodies: array<Body>;
+@group(0) @binding(2) var<storage, read_write> body_cells: array<u32>;
+// Slot of each body within its cell, handed out by the count pass.
+@group(0) @binding(3) var<storage, read_write> body_ranks: array<u32>;
+@group(0) @binding(4) var<storage, read_write> cell_counts: array<atomic<u32>>;
+// cell_count + 1 entries; the last one holds the body count.
+@group(0) @binding(5) var<storage, read_write> cell_start: array<u32>;
+@group(0) @binding(6) var<storage, read_write> sorted: array<u32>;
+
+var<workgroup> scan_totals: array<u32, SCAN_WORKGROUP_SIZE>;
+
+fn cell_count() -> u32 {
+    return grid.dimensions.x * grid.dimensions.y * grid.dimensions.z;
+}
+
+// Mirrors `GridUniforms::cell_coords` and `cell_index`.
+fn cell_of(position: vec3<f32>) -> u32 {
+    let scaled = max((position - grid.origin) / grid.cell_size, vec3<f32>(0.0));
+    let coords = min(vec3<u32>(scaled), grid.dimensions - vec3<u32>(1u));
+    return coords.x + grid.dimensions.x * (coords.y + grid.dimensions.y * coords.z);
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_grid_clear(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x < cell_count() {
+        atomicStore(&cell_counts[id.x], 0u);
+    }
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_grid_assign(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= grid.body_count {
+        return;
+    }
+    let cell = cell_of(bodies[i].position);
+    body_cells[i] = cell;
+    body_ranks[i] = atomicAdd(&cell_counts[cell], 1u);
+}
+
+// Exclusive prefix sum over the cell counts: each thread scans a contiguous
+// chunk serially, then the chunk totals are scanned in shared memory.
+@compute @workgroup_size(256)
+fn cs_grid_scan(@builtin(local_invocation_index) thread: u32) {
+    let cells = cell_count();
+    let chunk = (cells + SCAN_WORKGROUP_SIZE - 1u) / SCAN_WORKGROUP_SIZE;
+    let first = thread * chunk;
+    let last = min(first + chunk, cells);
+
+    var total = 0u;
+    for (var cell = first; cell < last; cell++) {
+        total += atomicLoad(&cell_counts[cell]);
+    }
+    scan_totals[thread] = total;
+    workgroupBarrier();
+
+    for (var offset = 1u; offset < SCAN_WORKGROUP_SIZE; offset *= 2u) {
+        var value = 0u;
+        if thread >= offset {
+            value = scan_totals[thread - offset];
+        }
+        workgroupBarrier();
+        scan_totals[thread] += value;
+        workgroupBarrier();
+    }
+
+    var running = scan_totals[thread] - total;
+    for (var cell = first; cell < last; cell++) {
+        cell_start[cell] = running;
+        running += atomicLoad(&cell_counts[cell]);
+    }
+    if thread == SCAN_WORKGROUP_SIZE - 1u {
+        cell_start[cells] = scan_totals[thread];
+    }
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_grid_reorder(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= grid.body_count {
+        return;
+    }
+    sorted[cell_start[body_cells[i]] + body_ranks[i]] = i;
+}
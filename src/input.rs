@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+
+use glam::Vec2;
+
+/// Keys the app reacts to, named after winit's `KeyCode` variants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    KeyB,
+    KeyD,
+    KeyF,
+    KeyG,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyR,
+    KeyT,
+    KeyY,
+    KeyZ,
+    F2,
+    F3,
+    F4,
+    Numpad2,
+    Numpad4,
+    Numpad6,
+    Numpad8,
+    NumpadAdd,
+    NumpadSubtract,
+    Enter,
+    Escape,
+    Delete,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    BracketLeft,
+    ControlLeft,
+    ShiftLeft,
+    AltLeft,
+}
+
+/// Digit keys in keyboard order: 1–9, then 0 for the tenth simulation.
+const SIMULATION_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Digit0,
+];
+
+impl KeyCode {
+    /// The simulation index a digit key selects, before checking it exists.
+    pub fn simulation_slot(self) -> Option<usize> {
+        SIMULATION_KEYS.iter().position(|&key| key == self)
+    }
+
+    /// The digit key bound to simulation `index`; only the first ten have one.
+    pub fn for_simulation(index: usize) -> Option<Self> {
+        SIMULATION_KEYS.get(index).copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// What dragging with a mouse button does to the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseAction {
+    Rotate,
+    Pan,
+    Zoom,
+}
+
+/// Camera action per mouse button, so no modifier keys are needed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseScheme {
+    pub left: Option<MouseAction>,
+    pub middle: Option<MouseAction>,
+    pub right: Option<MouseAction>,
+    /// Shift+left-drag pans and Ctrl+left-drag zooms too, for trackpads
+    /// without a middle or right button.
+    pub modifier_fallbacks: bool,
+}
+
+impl Default for MouseScheme {
+    fn default() -> Self {
+        Self {
+            left: Some(MouseAction::Rotate),
+            middle: Some(MouseAction::Pan),
+            right: Some(MouseAction::Zoom),
+            modifier_fallbacks: true,
+        }
+    }
+}
+
+impl MouseScheme {
+    /// The action a press of `button` starts, given the held keys.
+    pub fn action(&self, button: MouseButton, input: &InputState) -> Option<MouseAction> {
+        match button {
+            MouseButton::Left if self.modifier_fallbacks => {
+                if input.is_key_held(KeyCode::ShiftLeft) {
+                    Some(MouseAction::Pan)
+                } else if input.is_key_held(KeyCode::ControlLeft) {
+                    Some(MouseAction::Zoom)
+                } else {
+                    self.left
+                }
+            }
+            MouseButton::Left => self.left,
+            MouseButton::Middle => self.middle,
+            MouseButton::Right => self.right,
+        }
+    }
+}
+
+/// Currently held keys and buttons plus the cursor position in physical pixels.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    keys: HashSet<KeyCode>,
+    buttons: HashSet<MouseButton>,
+    pub cursor: Vec2,
+}
+
+impl InputState {
+    pub fn handle_key_state(&mut self, key: KeyCode, pressed: bool) {
+        if pressed {
+            self.keys.insert(key);
+        } else {
+            self.keys.remove(&key);
+        }
+    }
+
+    pub fn handle_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            self.buttons.insert(button);
+        } else {
+            self.buttons.remove(&button);
+        }
+    }
+
+    /// Forgets every held key and button, for when the window loses focus:
+    /// their releases go to another window and would never arrive.
+    pub fn release_all(&mut self) {
+        self.keys.clear();
+        self.buttons.clear();
+    }
+
+    /// Resyncs the modifier keys from winit's `ModifiersChanged`, which also
+    /// fires on regaining focus with modifiers already down.
+    pub fn sync_modifiers(&mut self, shift: bool, control: bool, alt: bool) {
+        for (key, held) in [
+            (KeyCode::ShiftLeft, shift),
+            (KeyCode::ControlLeft, control),
+            (KeyCode::AltLeft, alt),
+        ] {
+            self.handle_key_state(key, held);
+        }
+    }
+
+    pub fn is_key_held(&self, key: KeyCode) -> bool {
+        self.keys.contains(&key)
+    }
+
+    pub fn is_button_held(&self, button: MouseButton) -> bool {
+        self.buttons.contains(&button)
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(feature = "accesskit")]
+pub mod accessibility;
+pub mod app;
+pub mod camera;
+pub mod error;
+pub mod events;
+pub mod formats;
+pub mod input;
+pub mod localization;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "midi")]
+pub mod midi;
+#[cfg(feature = "net")]
+pub mod net;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod recording;
+pub mod rendering;
+pub mod share;
+pub mod simulation;
+pub mod tools;
+#[cfg(feature = "tour")]
+pub mod tour;
+pub mod ui;
+pub mod window_placement;
+#[cfg(feature = "xr")]
+pub mod xr;
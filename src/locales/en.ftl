@@ -0,0 +1,106 @@
+# English catalog, and the fallback for messages other catalogs lack.
+
+## Toasts
+
+toast-switched = Switched to { $simulation }
+toast-loaded-shared = Loaded shared { $simulation }
+toast-render-scale = Render scale: { $scale }
+toast-debug-view = Debug view: { $channel }
+toast-blend-mode = Blend mode: { $mode }
+toast-tone-mapping = Tone mapping: { $mapper }
+toast-trail-fade = Trail fade: { $fade }
+toast-long-exposure = Long exposure: { $enabled ->
+        [true] on
+       *[false] off
+    }
+toast-reference-frame = Reference frame: { $frame }
+toast-rotating-binary = Rotating with bodies { $primary } and { $secondary }
+toast-rotating-scenario = Rotating with the scenario's frame
+toast-saved-samples = Saved { $count } { $count ->
+        [one] sample
+       *[other] samples
+    } to { $path }
+toast-saved-bodies = Saved { $count } { $count ->
+        [one] body
+       *[other] bodies
+    } to { $path }
+toast-saved-bodies-and-trails = Saved { $count } { $count ->
+        [one] body
+       *[other] bodies
+    } and trails to { $path }
+toast-imported-bodies = Imported { $count } { $count ->
+        [one] body
+       *[other] bodies
+    } from { $path }
+toast-point-cloud-series = Exporting a point cloud every { $every } steps to { $path }
+toast-saved-point-clouds = Saved { $count } point { $count ->
+        [one] cloud
+       *[other] clouds
+    } to { $path }
+toast-point-cloud-stopped = Point cloud export stopped: { $error }
+toast-recording = Recording to { $path }
+toast-recorded-frames = Recorded { $count } { $count ->
+        [one] frame
+       *[other] frames
+    }
+toast-copied-body = Copied body state
+toast-body-not-finite = Cannot copy a body whose state is not finite
+toast-pasted-body = Pasted { $name }
+toast-pasted-unnamed-body = Pasted body
+toast-rebuilt-shader = Rebuilt { $shader }
+toast-invalid-properties = Mass must be non-negative and every value finite
+
+## Overlays
+
+resolution-auto = Resolution { $scale } (auto)
+orbit-hud-title = body { $body } about { $central }
+tooltip-title = body { $index }
+tooltip-title-named = { $name } (body { $index })
+measure-title = { $from } to { $to }
+measure-body = body { $index }
+measure-simulation-units = { $distance } simulation units
+
+## Screen reader
+
+accessibility-window = N-body simulation
+announcement-switched = Now showing { $simulation }
+announcement-paused = Simulation paused
+announcement-running = Simulation running
+announcement-selected = Selected { $body }: mass { $mass }, { $distance } from the origin, moving at { $speed }
+announcement-selection-cleared = Selection cleared
+
+## Labels
+
+debug-channel-off = off
+debug-channel-body-index = body index
+debug-channel-depth = depth
+debug-channel-speed = speed
+
+blend-mode-alpha = alpha
+blend-mode-additive = additive
+
+tone-mapper-clamp = clamp
+tone-mapper-reinhard = Reinhard
+tone-mapper-aces = ACES
+
+trail-fade-none = none
+trail-fade-linear = linear
+trail-fade-quadratic = quadratic
+
+palette-standard = standard
+palette-deuteranopia = deuteranopia-safe
+palette-protanopia = protanopia-safe
+palette-high-contrast = high contrast
+
+reference-frame-inertial = inertial
+reference-frame-barycentric = barycentric
+reference-frame-heliocentric = heliocentric
+reference-frame-body = body { $index }
+
+body-group-star = Stars
+body-group-planet = Planets
+body-group-moon = Moons
+body-group-asteroid = Asteroids
+body-group-kuiper-belt = Kuiper belt
+body-group-gas = Gas
+body-group-other = Other
@@ -0,0 +1,129 @@
+//! User-facing text, looked up by message id in Fluent catalogs compiled
+//! into the binary (`src/locales/*.ftl`). A message missing from the
+//! selected language's catalog falls back to English, and one missing there
+//! too shows as its id.
+//!
+//! Adding a language takes a catalog and a `Language` variant; the ids are
+//! those of `en.ftl`.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use tracing::debug;
+use unic_langid::LanguageIdentifier;
+
+/// Language of the UI, chosen in `AppSettings` and applied with
+/// `App::set_language`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+}
+
+impl Language {
+    pub const ALL: [Self; 1] = [Self::English];
+
+    /// BCP 47 tag, e.g. for a config file.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::English => "en",
+        }
+    }
+
+    /// The language's own name, for a language picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+        }
+    }
+
+    /// Language for a tag such as `en` or `en-GB`, matched on its primary
+    /// language subtag; `None` when there is no catalog for it.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let identifier: LanguageIdentifier = tag.parse().ok()?;
+        Self::ALL
+            .into_iter()
+            .find(|language| language.tag() == identifier.language.as_str())
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Self::English => include_str!("locales/en.ftl"),
+        }
+    }
+
+    fn bundle(self) -> FluentBundle<FluentResource> {
+        let identifier: LanguageIdentifier = self.tag().parse().expect("valid language tag");
+        let resource = FluentResource::try_new(self.catalog().to_owned())
+            .unwrap_or_else(|(_, errors)| panic!("{} catalog: {errors:?}", self.tag()));
+        let mut bundle = FluentBundle::new_concurrent(vec![identifier]);
+        // Bidi isolation marks would show as boxes in fonts without them.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errors| panic!("{} catalog: {errors:?}", self.tag()));
+        bundle
+    }
+}
+
+/// Formats messages in one language. Cheap to share behind an `Arc`;
+/// rebuilt when the language changes.
+pub struct Localizer {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+    /// English, for messages the selected catalog lacks.
+    fallback: Option<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            bundle: language.bundle(),
+            fallback: (language != Language::English).then(|| Language::English.bundle()),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// A message without arguments.
+    pub fn text(&self, id: &str) -> String {
+        self.format(id, &[])
+    }
+
+    /// A message with its `$name` arguments filled in.
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let args: FluentArgs = args
+            .iter()
+            .map(|(name, value)| (*name, value.clone()))
+            .collect();
+        for bundle in std::iter::once(&self.bundle).chain(&self.fallback) {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(&args), &mut errors);
+            if !errors.is_empty() {
+                debug!(id, ?errors, "message formatted with errors");
+            }
+            return text.into_owned();
+        }
+        debug!(id, "no such message");
+        id.to_owned()
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+impl std::fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Localizer")
+            .field("language", &self.language)
+            .finish_non_exhaustive()
+    }
+}
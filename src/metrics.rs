@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::simulation::worker::SimulationFrame;
+
+/// How often the listener checks for shutdown while no one scrapes.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug)]
+pub struct MetricsConfig {
+    pub address: SocketAddr,
+    /// An update interval longer than this counts as dropping frames, one per
+    /// whole budget it overran.
+    pub frame_budget: Duration,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], 9464)),
+            frame_budget: Duration::from_secs_f64(1.0 / 60.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Summary {
+    sum: f64,
+    count: u64,
+}
+
+impl Summary {
+    fn observe(&mut self, seconds: f64) {
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Values as of the last app update; rendered on each scrape.
+#[derive(Clone, Debug, Default)]
+struct Metrics {
+    frame_seconds: Summary,
+    dropped_frames: u64,
+    passes: BTreeMap<&'static str, Summary>,
+    simulation: usize,
+    bodies: usize,
+    simulated_time: f64,
+    energy_drift: Option<f32>,
+}
+
+impl Metrics {
+    /// Prometheus text exposition format, version 0.0.4.
+    fn render(&self) -> String {
+        let mut text = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str| {
+            writeln!(
+                text,
+                "# HELP nbody_{name} {help}\n# TYPE nbody_{name} {kind}"
+            )
+            .unwrap();
+        };
+        metric("frame_seconds", "summary", "Interval between app updates.");
+        metric(
+            "dropped_frames_total",
+            "counter",
+            "Frames lost to updates overrunning the frame budget.",
+        );
+        metric(
+            "gpu_pass_seconds",
+            "summary",
+            "GPU time of each render graph pass.",
+        );
+        metric("simulation", "gauge", "Index of the running scenario.");
+        metric("bodies", "gauge", "Bodies in the latest simulation frame.");
+        metric(
+            "simulated_time",
+            "gauge",
+            "Simulation clock of the latest frame.",
+        );
+        metric(
+            "energy_drift",
+            "gauge",
+            "Relative total-energy change since the scenario was loaded.",
+        );
+
+        let frame = self.frame_seconds;
+        writeln!(text, "nbody_frame_seconds_sum {}", frame.sum).unwrap();
+        writeln!(text, "nbody_frame_seconds_count {}", frame.count).unwrap();
+        writeln!(text, "nbody_dropped_frames_total {}", self.dropped_frames).unwrap();
+        for (pass, summary) in &self.passes {
+            writeln!(
+                text,
+                "nbody_gpu_pass_seconds_sum{{pass=\"{pass}\"}} {}",
+                summary.sum
+            )
+            .unwrap();
+            writeln!(
+                text,
+                "nbody_gpu_pass_seconds_count{{pass=\"{pass}\"}} {}",
+                summary.count
+            )
+            .unwrap();
+        }
+        writeln!(text, "nbody_simulation {}", self.simulation).unwrap();
+        writeln!(text, "nbody_bodies {}", self.bodies).unwrap();
+        writeln!(text, "nbody_simulated_time {}", self.simulated_time).unwrap();
+        if let Some(drift) = self.energy_drift {
+            writeln!(text, "nbody_energy_drift {}", prometheus_float(drift)).unwrap();
+        }
+        text
+    }
+}
+
+/// Prometheus spells non-finite values `NaN`, `+Inf` and `-Inf`.
+fn prometheus_float(value: f32) -> String {
+    match value {
+        value if value.is_nan() => "NaN".to_owned(),
+        f32::INFINITY => "+Inf".to_owned(),
+        f32::NEG_INFINITY => "-Inf".to_owned(),
+        value => value.to_string(),
+    }
+}
+
+/// Serves `GET /metrics` for Prometheus, so unattended kiosk deployments can
+/// be monitored and alerted on. The app feeds it once per update; scrapes are
+/// answered from a background thread.
+pub struct MetricsExporter {
+    config: MetricsConfig,
+    metrics: Arc<Mutex<Metrics>>,
+    last_update: Option<Instant>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsExporter {
+    pub fn bind(config: MetricsConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.address)?;
+        listener.set_nonblocking(true)?;
+        info!(address = %listener.local_addr()?, "serving Prometheus metrics");
+        let metrics = Arc::new(Mutex::new(Metrics::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = thread::Builder::new().name("metrics".into()).spawn({
+            let metrics = Arc::clone(&metrics);
+            let shutdown = Arc::clone(&shutdown);
+            move || serve(listener, &metrics, &shutdown)
+        })?;
+        Ok(Self {
+            config,
+            metrics,
+            last_update: None,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Records one app update at `now` and the frame it showed.
+    pub fn update(&mut self, now: Instant, frame: Option<&SimulationFrame>) {
+        let interval = self.last_update.map(|last| now.duration_since(last));
+        self.last_update = Some(now);
+        let mut metrics = self.metrics.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(interval) = interval {
+            metrics.frame_seconds.observe(interval.as_secs_f64());
+            let budgets = interval.as_secs_f64() / self.config.frame_budget.as_secs_f64();
+            metrics.dropped_frames += (budgets.floor() as u64).saturating_sub(1);
+        }
+        if let Some(frame) = frame {
+            metrics.simulation = frame.simulation_index;
+            metrics.bodies = frame.bodies.len();
+            metrics.simulated_time = frame.state.time;
+            metrics.energy_drift = frame.energy_drift;
+        }
+    }
+
+    /// Records a render graph pass's GPU time, e.g. from timestamp queries.
+    pub fn record_pass(&mut self, pass: &'static str, duration: Duration) {
+        self.metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .passes
+            .entry(pass)
+            .or_default()
+            .observe(duration.as_secs_f64());
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            warn!("metrics thread panicked");
+        }
+    }
+}
+
+fn serve(listener: TcpListener, metrics: &Mutex<Metrics>, shutdown: &AtomicBool) {
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if let Err(error) = respond(stream, metrics) {
+                    warn!(%peer, %error, "metrics scrape failed");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+            Err(error) => warn!(%error, "metrics accept failed"),
+        }
+    }
+}
+
+fn respond(stream: TcpStream, metrics: &Mutex<Metrics>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers are not needed, only consumed.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut stream = reader.into_inner();
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            metrics
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .render(),
+        ),
+        (Some("GET"), _) => ("404 Not Found", String::new()),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
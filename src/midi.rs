@@ -0,0 +1,193 @@
+use std::sync::mpsc::{self, Receiver};
+
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use thiserror::Error;
+use tracing::info;
+
+use crate::rendering::uniforms::ColorMode;
+
+const CLIENT_NAME: &str = "n-body-problem-webgpu";
+
+#[derive(Debug, Error)]
+pub enum MidiError {
+    #[error("MIDI is unavailable: {0}")]
+    Init(#[from] midir::InitError),
+    #[error("no MIDI input port")]
+    NoPorts,
+    #[error("no MIDI input port matches `{0}`")]
+    NoMatchingPort(String),
+    #[error("could not open MIDI port: {0}")]
+    Connect(String),
+}
+
+/// What a controller drives. Ranges are exponential, so a knob covers
+/// several orders of magnitude evenly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MidiTarget {
+    TimeScale {
+        min: f32,
+        max: f32,
+    },
+    Softening {
+        min: f32,
+        max: f32,
+    },
+    /// Multiple of the distance the camera was last fitted at.
+    CameraDistance {
+        min: f32,
+        max: f32,
+    },
+    /// The range is split evenly between the color modes.
+    ColorMode,
+}
+
+/// One control-change controller and the parameter it drives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MidiBinding {
+    /// `None` listens on every channel.
+    pub channel: Option<u8>,
+    pub controller: u8,
+    pub target: MidiTarget,
+}
+
+/// A value to apply, already scaled from the 7-bit controller value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MidiControl {
+    TimeScale(f32),
+    Softening(f32),
+    CameraDistance(f32),
+    ColorMode(ColorMode),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiMapping {
+    pub bindings: Vec<MidiBinding>,
+}
+
+impl Default for MidiMapping {
+    /// General-purpose controllers 1-4 (CC 16-19) on any channel.
+    fn default() -> Self {
+        let binding = |controller, target| MidiBinding {
+            channel: None,
+            controller,
+            target,
+        };
+        Self {
+            bindings: vec![
+                binding(
+                    16,
+                    MidiTarget::TimeScale {
+                        min: 0.05,
+                        max: 20.0,
+                    },
+                ),
+                binding(
+                    17,
+                    MidiTarget::Softening {
+                        min: 1e-3,
+                        max: 0.5,
+                    },
+                ),
+                binding(
+                    18,
+                    MidiTarget::CameraDistance {
+                        min: 0.1,
+                        max: 10.0,
+                    },
+                ),
+                binding(19, MidiTarget::ColorMode),
+            ],
+        }
+    }
+}
+
+impl MidiMapping {
+    /// Controls bound to a control-change `message`; other messages map to nothing.
+    pub fn map<'a>(&'a self, message: &[u8]) -> impl Iterator<Item = MidiControl> + use<'a> {
+        let change = match *message {
+            [status, controller, value] if status & 0xF0 == 0xB0 => {
+                Some((status & 0x0F, controller, value.min(127)))
+            }
+            _ => None,
+        };
+        self.bindings.iter().filter_map(move |binding| {
+            let (channel, controller, value) = change?;
+            if binding.controller != controller
+                || binding.channel.is_some_and(|bound| bound != channel)
+            {
+                return None;
+            }
+            let t = f32::from(value) / 127.0;
+            let exponential = |min: f32, max: f32| min * (max / min).powf(t);
+            Some(match binding.target {
+                MidiTarget::TimeScale { min, max } => MidiControl::TimeScale(exponential(min, max)),
+                MidiTarget::Softening { min, max } => MidiControl::Softening(exponential(min, max)),
+                MidiTarget::CameraDistance { min, max } => {
+                    MidiControl::CameraDistance(exponential(min, max))
+                }
+                MidiTarget::ColorMode => {
+                    let index = usize::from(value) * ColorMode::ALL.len() / 128;
+                    MidiControl::ColorMode(ColorMode::ALL[index])
+                }
+            })
+        })
+    }
+}
+
+/// Open MIDI input port; messages are queued by midir's thread and mapped
+/// when the app drains them with `controls`.
+pub struct MidiController {
+    pub mapping: MidiMapping,
+    messages: Receiver<[u8; 3]>,
+    _connection: MidiInputConnection<()>,
+}
+
+impl MidiController {
+    /// Connects to the first input port whose name contains `port`, or to
+    /// the first port at all.
+    pub fn connect(port: Option<&str>, mapping: MidiMapping) -> Result<Self, MidiError> {
+        let mut input = MidiInput::new(CLIENT_NAME)?;
+        input.ignore(Ignore::All);
+        let ports = input.ports();
+        let selected = match port {
+            Some(wanted) => ports
+                .iter()
+                .find(|candidate| {
+                    input
+                        .port_name(candidate)
+                        .is_ok_and(|name| name.contains(wanted))
+                })
+                .ok_or_else(|| MidiError::NoMatchingPort(wanted.to_owned()))?,
+            None => ports.first().ok_or(MidiError::NoPorts)?,
+        };
+        let name = input.port_name(selected).unwrap_or_default();
+        let (sender, messages) = mpsc::channel();
+        let connection = input
+            .connect(
+                selected,
+                CLIENT_NAME,
+                move |_timestamp, message, _| {
+                    if let Ok(message) = <[u8; 3]>::try_from(message) {
+                        // The app may be shutting down.
+                        let _ = sender.send(message);
+                    }
+                },
+                (),
+            )
+            .map_err(|error| MidiError::Connect(error.to_string()))?;
+        info!(port = %name, "MIDI input connected");
+        Ok(Self {
+            mapping,
+            messages,
+            _connection: connection,
+        })
+    }
+
+    /// Controls received since the last call, oldest first.
+    pub fn controls(&self) -> Vec<MidiControl> {
+        self.messages
+            .try_iter()
+            .flat_map(|message| self.mapping.map(&message))
+            .collect()
+    }
+}
@@ -0,0 +1,263 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use glam::{Vec3, Vec4};
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::simulation::Body;
+
+/// How often the listener checks for shutdown while no one connects.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a call waits for the app to pick it up; the app answers once per frame.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_BODY_BYTES: usize = 64 << 10;
+
+// JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const APPLICATION_ERROR: i64 = -32000;
+
+/// A scenario by position in the list or by name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationRef {
+    Index(usize),
+    Name(String),
+}
+
+/// Calls the control API accepts, already parsed from JSON-RPC:
+///
+/// - `pause {"paused": bool = true}`, `resume`
+/// - `set_time_scale {"time_scale": f32}`
+/// - `switch_simulation {"index": n}` or `{"name": "..."}`
+/// - `spawn_body {"position": [x, y, z], "velocity": [x, y, z], "mass": m, "radius": r = 0.05}`
+/// - `save_snapshot`, a rewind point
+/// - `status`, the scenario, time, body count and clock settings
+#[derive(Clone, Debug, PartialEq)]
+pub enum ControlRequest {
+    Pause(bool),
+    SetTimeScale(f32),
+    SwitchSimulation(SimulationRef),
+    SpawnBody(Body),
+    SaveSnapshot,
+    Status,
+}
+
+/// A request waiting for the app; dropping it unanswered reports a timeout.
+pub struct PendingCall {
+    pub request: ControlRequest,
+    reply: Sender<Result<Value, String>>,
+}
+
+impl PendingCall {
+    pub fn reply(self, result: Result<Value, String>) {
+        // The connection may already have given up.
+        let _ = self.reply.send(result);
+    }
+}
+
+/// Local HTTP endpoint taking JSON-RPC 2.0 requests as `POST` bodies, for
+/// scripts and remotes driving long demos. Connections are served one at a
+/// time on a background thread; each call is handed to the app through
+/// `pending` and answered on its next frame.
+pub struct ControlServer {
+    calls: Receiver<PendingCall>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ControlServer {
+    pub fn bind(address: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        info!(address = %listener.local_addr()?, "control API listening");
+        let (sender, receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let handle = thread::Builder::new()
+            .name("control-api".into())
+            .spawn(move || serve(listener, sender, stop))?;
+        Ok(Self {
+            calls: receiver,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Calls received since the last poll, oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = PendingCall> + '_ {
+        self.calls.try_iter()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            warn!("control API thread panicked");
+        }
+    }
+}
+
+fn serve(listener: TcpListener, calls: Sender<PendingCall>, shutdown: Arc<AtomicBool>) {
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if let Err(error) = respond(stream, &calls) {
+                    warn!(%peer, %error, "control request failed");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+            Err(error) => warn!(%error, "control API accept failed"),
+        }
+    }
+}
+
+fn respond(stream: TcpStream, calls: &Sender<PendingCall>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let mut stream = reader.get_ref().try_clone()?;
+    if !request_line.starts_with("POST ") {
+        return write_response(&mut stream, "405 Method Not Allowed", "");
+    }
+    if content_length > MAX_BODY_BYTES {
+        return write_response(&mut stream, "413 Payload Too Large", "");
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let response = call(&body, calls);
+    write_response(&mut stream, "200 OK", &response.to_string())
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// Runs one JSON-RPC request through the app and builds the response object.
+fn call(body: &[u8], calls: &Sender<PendingCall>) -> Value {
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(error) => return error_response(Value::Null, PARSE_ERROR, &error.to_string()),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return error_response(id, INVALID_REQUEST, "missing method");
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let request = match parse(method, &params) {
+        Ok(request) => request,
+        Err((code, message)) => return error_response(id, code, &message),
+    };
+
+    let (reply, result) = mpsc::channel();
+    if calls.send(PendingCall { request, reply }).is_err() {
+        return error_response(id, APPLICATION_ERROR, "app is shutting down");
+    }
+    match result.recv_timeout(REPLY_TIMEOUT) {
+        Ok(Ok(result)) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Ok(Err(message)) => error_response(id, APPLICATION_ERROR, &message),
+        Err(_) => error_response(id, APPLICATION_ERROR, "app did not answer in time"),
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
+
+fn parse(method: &str, params: &Value) -> Result<ControlRequest, (i64, String)> {
+    let invalid = |message: &str| (INVALID_PARAMS, message.to_owned());
+    let number = |name: &str| {
+        params
+            .get(name)
+            .and_then(Value::as_f64)
+            .map(|value| value as f32)
+    };
+    let vector = |name: &str| -> Result<Vec3, (i64, String)> {
+        let values: Vec<f32> = params
+            .get(name)
+            .and_then(Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(Value::as_f64)
+                    .map(|value| value as f32)
+                    .collect()
+            })
+            .unwrap_or_default();
+        <[f32; 3]>::try_from(values)
+            .map(Vec3::from_array)
+            .map_err(|_| invalid(&format!("`{name}` must be an array of three numbers")))
+    };
+
+    Ok(match method {
+        "pause" => ControlRequest::Pause(
+            params
+                .get("paused")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+        ),
+        "resume" => ControlRequest::Pause(false),
+        "set_time_scale" => ControlRequest::SetTimeScale(
+            number("time_scale")
+                .filter(|scale| scale.is_finite() && *scale > 0.0)
+                .ok_or_else(|| invalid("`time_scale` must be a positive number"))?,
+        ),
+        "switch_simulation" => {
+            if let Some(index) = params.get("index").and_then(Value::as_u64) {
+                ControlRequest::SwitchSimulation(SimulationRef::Index(index as usize))
+            } else if let Some(name) = params.get("name").and_then(Value::as_str) {
+                ControlRequest::SwitchSimulation(SimulationRef::Name(name.to_owned()))
+            } else {
+                return Err(invalid("expected `index` or `name`"));
+            }
+        }
+        "spawn_body" => ControlRequest::SpawnBody(Body::new(
+            vector("position")?,
+            vector("velocity")?,
+            number("mass")
+                .filter(|mass| mass.is_finite() && *mass >= 0.0)
+                .ok_or_else(|| invalid("`mass` must be a non-negative number"))?,
+            match number("radius") {
+                None => 0.05,
+                Some(radius) if radius.is_finite() && radius > 0.0 => radius,
+                Some(_) => return Err(invalid("`radius` must be a positive number")),
+            },
+            Vec4::ONE,
+        )),
+        "save_snapshot" => ControlRequest::SaveSnapshot,
+        "status" => ControlRequest::Status,
+        _ => return Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+    })
+}
@@ -0,0 +1,5 @@
+//! Optional network interfaces (feature `net`).
+
+pub mod control;
+pub mod osc;
+pub mod stream;
@@ -0,0 +1,326 @@
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use tracing::{debug, info, warn};
+
+use super::control::SimulationRef;
+
+/// How often the receiving thread checks for shutdown while no packet arrives.
+const RECEIVE_INTERVAL: Duration = Duration::from_millis(50);
+/// Largest datagram accepted; show-control cues are a few dozen bytes.
+const MAX_PACKET_BYTES: usize = 8 << 10;
+
+/// Cues the OSC endpoint understands:
+///
+/// - `/sim/timescale f`
+/// - `/sim/switch i` (index) or `/sim/switch s` (name)
+/// - `/camera/orbit f f [f]`: yaw and pitch in degrees, then optionally the
+///   distance as a multiple of the fitted one
+///
+/// Numeric arguments may be sent as int or float.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscCommand {
+    SetTimeScale(f32),
+    SwitchSimulation(SimulationRef),
+    Orbit {
+        yaw: f32,
+        pitch: f32,
+        distance: Option<f32>,
+    },
+}
+
+/// UDP endpoint for lighting and show-control software. Packets, including
+/// bundles, are decoded on a background thread; bundle time tags are ignored
+/// and their messages applied on arrival.
+pub struct OscServer {
+    commands: Receiver<OscCommand>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl OscServer {
+    pub fn bind(address: SocketAddr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_read_timeout(Some(RECEIVE_INTERVAL))?;
+        info!(address = %socket.local_addr()?, "OSC endpoint listening");
+        let (sender, receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let handle = thread::Builder::new()
+            .name("osc".into())
+            .spawn(move || serve(socket, sender, stop))?;
+        Ok(Self {
+            commands: receiver,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Commands received since the last poll, oldest first.
+    pub fn commands(&self) -> impl Iterator<Item = OscCommand> + '_ {
+        self.commands.try_iter()
+    }
+}
+
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            warn!("OSC thread panicked");
+        }
+    }
+}
+
+fn serve(socket: UdpSocket, commands: Sender<OscCommand>, shutdown: Arc<AtomicBool>) {
+    let mut buffer = vec![0; MAX_PACKET_BYTES];
+    while !shutdown.load(Ordering::Relaxed) {
+        let (length, peer) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue;
+            }
+            Err(error) => {
+                warn!(%error, "OSC receive failed");
+                continue;
+            }
+        };
+        let mut messages = Vec::new();
+        if decode_packet(&buffer[..length], &mut messages).is_none() {
+            warn!(%peer, "malformed OSC packet");
+            continue;
+        }
+        for message in messages {
+            match command(&message) {
+                Some(command) => {
+                    if commands.send(command).is_err() {
+                        return;
+                    }
+                }
+                None => debug!(%peer, address = %message.address, "ignored OSC message"),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Argument {
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Message {
+    address: String,
+    arguments: Vec<Argument>,
+}
+
+fn command(message: &Message) -> Option<OscCommand> {
+    let number = |index: usize| match message.arguments.get(index)? {
+        Argument::Number(value) => Some(*value as f32),
+        Argument::Text(_) => None,
+    };
+    match message.address.as_str() {
+        "/sim/timescale" => Some(OscCommand::SetTimeScale(
+            number(0).filter(|scale| scale.is_finite() && *scale > 0.0)?,
+        )),
+        "/sim/switch" => Some(OscCommand::SwitchSimulation(
+            match message.arguments.first()? {
+                Argument::Number(index) if *index >= 0.0 => SimulationRef::Index(*index as usize),
+                Argument::Number(_) => return None,
+                Argument::Text(name) => SimulationRef::Name(name.clone()),
+            },
+        )),
+        "/camera/orbit" => Some(OscCommand::Orbit {
+            yaw: number(0).filter(|yaw| yaw.is_finite())?.to_radians(),
+            pitch: number(1).filter(|pitch| pitch.is_finite())?.to_radians(),
+            distance: number(2).filter(|distance| distance.is_finite() && *distance > 0.0),
+        }),
+        _ => None,
+    }
+}
+
+/// Appends the messages of a packet, flattening bundles; `None` if malformed.
+fn decode_packet(packet: &[u8], messages: &mut Vec<Message>) -> Option<()> {
+    let mut reader = Reader(packet);
+    if packet.starts_with(b"#bundle\0") {
+        reader.take(16)?; // Tag and time tag.
+        while !reader.0.is_empty() {
+            let size = usize::try_from(reader.i32()?).ok()?;
+            decode_packet(reader.take(size)?, messages)?;
+        }
+        return Some(());
+    }
+    let address = reader.string()?;
+    // A missing type tag string is an old-style message without arguments.
+    let tags = if reader.0.is_empty() {
+        String::from(",")
+    } else {
+        reader.string()?
+    };
+    let arguments = tags
+        .strip_prefix(',')?
+        .chars()
+        .filter_map(|tag| {
+            Some(match tag {
+                'i' => reader.i32().map(|value| Argument::Number(value.into())),
+                'f' => reader
+                    .array()
+                    .map(|bytes| Argument::Number(f32::from_be_bytes(bytes).into())),
+                'h' => reader
+                    .array()
+                    .map(|bytes| Argument::Number(i64::from_be_bytes(bytes) as f64)),
+                'd' => reader
+                    .array()
+                    .map(|bytes| Argument::Number(f64::from_be_bytes(bytes))),
+                's' | 'S' => reader.string().map(Argument::Text),
+                'T' => Some(Argument::Number(1.0)),
+                'F' => Some(Argument::Number(0.0)),
+                // Nil and impulse carry no value.
+                'N' | 'I' => return None,
+                _ => None,
+            })
+        })
+        .collect::<Option<_>>()?;
+    messages.push(Message { address, arguments });
+    Some(())
+}
+
+/// Big-endian cursor over a packet; strings are NUL-terminated and padded to
+/// four bytes.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.0.len() < count {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(count);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.array().map(i32::from_be_bytes)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let end = self.0.iter().position(|&byte| byte == 0)?;
+        let text = std::str::from_utf8(&self.0[..end]).ok()?.to_owned();
+        self.take((end + 4) & !3)?;
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn osc_string(bytes: &mut Vec<u8>, text: &str) {
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.resize((bytes.len() + 4) & !3, 0);
+    }
+
+    fn float_message(address: &str, values: &[f32]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        osc_string(&mut bytes, address);
+        osc_string(&mut bytes, &format!(",{}", "f".repeat(values.len())));
+        for value in values {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn decode(packet: &[u8]) -> Option<Vec<Message>> {
+        let mut messages = Vec::new();
+        decode_packet(packet, &mut messages)?;
+        Some(messages)
+    }
+
+    fn orbit(values: &[f32]) -> Option<OscCommand> {
+        command(&decode(&float_message("/camera/orbit", values))?[0])
+    }
+
+    #[test]
+    fn camera_orbit_takes_degrees_and_an_optional_distance() {
+        assert_eq!(
+            orbit(&[90.0, -30.0, 2.0]),
+            Some(OscCommand::Orbit {
+                yaw: 90f32.to_radians(),
+                pitch: (-30f32).to_radians(),
+                distance: Some(2.0),
+            })
+        );
+        assert_eq!(
+            orbit(&[0.0, 0.0]),
+            Some(OscCommand::Orbit {
+                yaw: 0.0,
+                pitch: 0.0,
+                distance: None,
+            })
+        );
+        assert_eq!(
+            orbit(&[0.0, 0.0, -1.0]),
+            Some(OscCommand::Orbit {
+                yaw: 0.0,
+                pitch: 0.0,
+                distance: None,
+            })
+        );
+    }
+
+    #[test]
+    fn camera_orbit_rejects_non_finite_angles() {
+        assert_eq!(orbit(&[f32::NAN, 0.0]), None);
+        assert_eq!(orbit(&[0.0, f32::INFINITY, 1.0]), None);
+        assert_eq!(orbit(&[0.0]), None);
+    }
+
+    #[test]
+    fn bundles_are_flattened_in_order() {
+        let first = float_message("/sim/timescale", &[2.0]);
+        let mut nested = b"#bundle\0".to_vec();
+        nested.extend_from_slice(&[0; 8]);
+        let second = float_message("/camera/orbit", &[10.0, 20.0]);
+        nested.extend_from_slice(&(second.len() as i32).to_be_bytes());
+        nested.extend_from_slice(&second);
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&[0; 8]);
+        for element in [&first, &nested] {
+            bundle.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            bundle.extend_from_slice(element);
+        }
+        let messages = decode(&bundle).expect("well-formed bundle");
+        let addresses: Vec<&str> = messages
+            .iter()
+            .map(|message| message.address.as_str())
+            .collect();
+        assert_eq!(addresses, ["/sim/timescale", "/camera/orbit"]);
+        assert_eq!(command(&messages[0]), Some(OscCommand::SetTimeScale(2.0)));
+    }
+
+    #[test]
+    fn truncated_bundles_are_malformed() {
+        let message = float_message("/sim/timescale", &[2.0]);
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&[0; 8]);
+        bundle.extend_from_slice(&(message.len() as i32 + 4).to_be_bytes());
+        bundle.extend_from_slice(&message);
+        assert_eq!(decode(&bundle), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde_json::{Map, Value, json};
+use tracing::{info, warn};
+use tungstenite::{Message, WebSocket};
+
+use crate::simulation::worker::SimulationFrame;
+
+/// How often the streaming thread looks for new connections while idle.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+/// A client that cannot take a message within this long is dropped, so one
+/// stalled dashboard does not hold up the rest.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug)]
+pub struct StreamConfig {
+    pub address: SocketAddr,
+    /// Messages per second; frames in between are skipped.
+    pub rate: f32,
+    /// Bodies per message; larger systems are sent every `stride`-th body.
+    pub max_bodies: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], 9001)),
+            rate: 10.0,
+            max_bodies: 4096,
+        }
+    }
+}
+
+/// Broadcasts downsampled body positions and diagnostics to every connected
+/// WebSocket client as JSON text messages:
+///
+/// `{"simulation": 3, "time": 1.5, "body_count": 256, "stride": 1,
+///   "positions": [x0, y0, z0, x1, ...], "diagnostics": {...}}`
+///
+/// Encoding happens on the caller's thread at the configured rate; sockets are
+/// served by a background thread, and a message is dropped rather than queued
+/// when that thread is still busy with the previous one.
+pub struct StreamServer {
+    config: StreamConfig,
+    messages: Option<SyncSender<String>>,
+    last_sent: Option<Instant>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StreamServer {
+    pub fn bind(config: StreamConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.address)?;
+        listener.set_nonblocking(true)?;
+        info!(address = %listener.local_addr()?, "streaming bodies over WebSocket");
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let handle = thread::Builder::new()
+            .name("body-stream".into())
+            .spawn(move || serve(listener, receiver))?;
+        Ok(Self {
+            config,
+            messages: Some(sender),
+            last_sent: None,
+            handle: Some(handle),
+        })
+    }
+
+    /// Sends `frame` if the last message went out at least one period ago.
+    pub fn publish(&mut self, frame: &SimulationFrame) {
+        let now = Instant::now();
+        let period = Duration::from_secs_f32(1.0 / self.config.rate.max(f32::EPSILON));
+        if self
+            .last_sent
+            .is_some_and(|last| now.duration_since(last) < period)
+        {
+            return;
+        }
+        self.last_sent = Some(now);
+        let Some(messages) = &self.messages else {
+            return;
+        };
+        match messages.try_send(encode(frame, self.config.max_bodies)) {
+            Ok(()) | Err(TrySendError::Full(_)) => {}
+            Err(TrySendError::Disconnected(_)) => self.messages = None,
+        }
+    }
+}
+
+impl Drop for StreamServer {
+    fn drop(&mut self) {
+        self.messages = None;
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            warn!("body stream thread panicked");
+        }
+    }
+}
+
+fn serve(listener: TcpListener, messages: Receiver<String>) {
+    let mut clients: Vec<WebSocket<TcpStream>> = Vec::new();
+    loop {
+        match messages.recv_timeout(ACCEPT_INTERVAL) {
+            Ok(message) => {
+                let message = Message::text(message);
+                clients.retain_mut(|client| client.send(message.clone()).is_ok());
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+        while let Ok((stream, peer)) = listener.accept() {
+            match handshake(stream) {
+                Ok(client) => {
+                    info!(%peer, "stream client connected");
+                    clients.push(client);
+                }
+                Err(error) => warn!(%peer, %error, "rejected stream client"),
+            }
+        }
+    }
+}
+
+fn handshake(stream: TcpStream) -> Result<WebSocket<TcpStream>, Box<dyn std::error::Error>> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    Ok(tungstenite::accept(stream).map_err(|error| error.to_string())?)
+}
+
+fn encode(frame: &SimulationFrame, max_bodies: usize) -> String {
+    let count = frame.bodies.len();
+    let stride = count.div_ceil(max_bodies.max(1)).max(1);
+    let positions: Vec<Value> = frame
+        .bodies
+        .iter()
+        .step_by(stride)
+        .flat_map(|body| body.position.to_array())
+        .map(|value| finite(value.into()))
+        .collect();
+    let mut diagnostics = Map::new();
+    if let Some(sample) = frame.cluster_history.last() {
+        diagnostics.insert(
+            "virial_ratio".to_owned(),
+            finite(sample.virial_ratio.into()),
+        );
+        diagnostics.insert(
+            "lagrangian_radii".to_owned(),
+            sample
+                .lagrangian_radii
+                .iter()
+                .map(|&radius| finite(radius.into()))
+                .collect(),
+        );
+    }
+    if let Some(readout) = &frame.precession {
+        diagnostics.insert(
+            "precession_per_orbit".to_owned(),
+            finite(readout.measured.into()),
+        );
+    }
+    if let Some(divergence) = &frame.divergence {
+        diagnostics.insert("diverged_body".to_owned(), divergence.body.into());
+    }
+    json!({
+        "simulation": frame.simulation_index,
+        "time": finite(frame.state.time),
+        "body_count": count,
+        "stride": stride,
+        "positions": positions,
+        "diagnostics": diagnostics,
+    })
+    .to_string()
+}
+
+/// JSON has no NaN or infinity.
+fn finite(value: f64) -> Value {
+    if value.is_finite() {
+        value.into()
+    } else {
+        Value::Null
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Vec3, Vec4};
+
+    use super::*;
+    use crate::simulation::Body;
+
+    #[test]
+    fn non_finite_values_are_sent_as_null() {
+        let mut frame = SimulationFrame::default();
+        frame.state.time = f64::NAN;
+        frame.bodies = vec![
+            Body::new(Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO, 1.0, 0.1, Vec4::ONE),
+            Body::new(Vec3::splat(f32::INFINITY), Vec3::ZERO, 1.0, 0.1, Vec4::ONE),
+        ];
+        let message: Value = serde_json::from_str(&encode(&frame, 4096)).expect("valid JSON");
+        assert_eq!(message["time"], Value::Null);
+        assert_eq!(message["body_count"], 2);
+        assert_eq!(
+            message["positions"],
+            json!([1.0, 2.0, 3.0, null, null, null])
+        );
+    }
+}
@@ -0,0 +1,72 @@
+// Binning passes for the diagnostics panel, mirroring `simulation::plots`.
+// Dispatch order: cs_plots_clear (one thread per bin), cs_plots_bin (one per
+// body); the three count buffers are then copied out and read back without
+// stalling the frame.
+
+#include "body.wgsl"
+#include "dispatch.wgsl"
+
+struct PlotUniforms {
+    center: vec3<f32>,
+    body_count: u32,
+    bulk_velocity: vec3<f32>,
+    max_radius: f32,
+    max_speed: f32,
+    min_log_mass: f32,
+    max_log_mass: f32,
+}
+
+const PLOT_BINS: u32 = 32u;
+const INV_LN_10: f32 = 0.4342944819;
+
+@group(0) @binding(0) var<uniform> plots: PlotUniforms;
+@group(0) @binding(1) var<storage, read> bodies: array<Body>;
+// Radius-major (radius, speed) counts, PLOT_BINS² entries.
+@group(0) @binding(2) var<storage, read_write> phase_space: array<atomic<u32>>;
+@group(0) @binding(3) var<storage, read_write> mass_histogram: array<atomic<u32>>;
+@group(0) @binding(4) var<storage, read_write> shell_counts: array<atomic<u32>>;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_plots_clear(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i < PLOT_BINS * PLOT_BINS {
+        atomicStore(&phase_space[i], 0u);
+    }
+    if i < PLOT_BINS {
+        atomicStore(&mass_histogram[i], 0u);
+        atomicStore(&shell_counts[i], 0u);
+    }
+}
+
+// Bin of `value` in [0, max), or PLOT_BINS when outside; mirrors `PlotUniforms::bin`.
+fn linear_bin(value: f32, max: f32) -> u32 {
+    let scaled = value / max * f32(PLOT_BINS);
+    if scaled < 0.0 || scaled >= f32(PLOT_BINS) {
+        return PLOT_BINS;
+    }
+    return u32(scaled);
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_plots_bin(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= plots.body_count {
+        return;
+    }
+    let body = bodies[i];
+    if body.mass > 0.0 {
+        let scaled = (log(body.mass) * INV_LN_10 - plots.min_log_mass)
+            / (plots.max_log_mass - plots.min_log_mass);
+        let mass_bin = min(u32(max(scaled * f32(PLOT_BINS), 0.0)), PLOT_BINS - 1u);
+        atomicAdd(&mass_histogram[mass_bin], 1u);
+    }
+    let radius_bin = linear_bin(distance(body.position, plots.center), plots.max_radius);
+    if radius_bin == PLOT_BINS {
+        return;
+    }
+    atomicAdd(&shell_counts[radius_bin], 1u);
+    let speed_bin = linear_bin(distance(body.velocity, plots.bulk_velocity), plots.max_speed);
+    if speed_bin < PLOT_BINS {
+        atomicAdd(&phase_space[radius_bin * PLOT_BINS + speed_bin], 1u);
+    }
+}
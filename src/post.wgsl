@@ -0,0 +1,25 @@
+// Screen-space passes over the rendered frame, composed from `src/shaders` by
+// `rendering::preprocessor`. Each is one fullscreen triangle from
+// `vs_fullscreen` with its own fragment entry point and bindings.
+
+#include "background.wgsl"
+#include "debug_overlay.wgsl"
+#include "accumulation.wgsl"
+#include "lensing.wgsl"
+#include "tone_map.wgsl"
+
+struct FullscreenOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Top-left origin, like texture coordinates.
+    @location(0) uv: vec2<f32>,
+}
+
+// Three vertices, no vertex buffer: a triangle covering the whole viewport.
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    let corner = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: FullscreenOutput;
+    out.clip_position = vec4<f32>(corner * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    return out;
+}
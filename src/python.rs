@@ -0,0 +1,175 @@
+//! Python bindings, built as the `n_body_problem_webgpu` extension module
+//! (e.g. `maturin develop --features python`):
+//!
+//! ```python
+//! from n_body_problem_webgpu import Simulation
+//!
+//! sim = Simulation.scenario("Solar System")
+//! sim.step(10_000)
+//! positions = sim.positions()  # (N, 3) float32
+//! ```
+//!
+//! Steps run the same integrator as the app's simulation worker, including
+//! the scenario's potentials, unit normalization and per-body systems.
+
+use glam::{Vec3, Vec4};
+use numpy::ndarray::{Array1, Array2};
+use numpy::{IntoPyArray, PyArray1, PyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::simulation::worker::normalize_units;
+use crate::simulation::{
+    Body, BodyGroup, BodyWorld, ExternalPotential, SimulationState, builtin_simulations,
+};
+
+/// Bodies and clock of one headless run.
+#[pyclass(name = "Simulation", unsendable)]
+pub struct PySimulation {
+    world: BodyWorld,
+    state: SimulationState,
+    potentials: Vec<ExternalPotential>,
+}
+
+#[pymethods]
+impl PySimulation {
+    /// An empty system; add bodies with `add_body`.
+    #[new]
+    #[pyo3(signature = (gravitational_constant = 1.0, softening = 0.01, dt = 0.001))]
+    fn new(gravitational_constant: f32, softening: f32, dt: f32) -> Self {
+        Self {
+            world: BodyWorld::new(Vec::new(), Vec::new(), Vec::new()),
+            state: SimulationState {
+                gravitational_constant,
+                softening,
+                dt,
+                ..SimulationState::default()
+            },
+            potentials: Vec::new(),
+        }
+    }
+
+    /// Names accepted by `scenario`.
+    #[staticmethod]
+    fn scenarios() -> Vec<String> {
+        builtin_simulations()
+            .iter()
+            .map(|simulation| simulation.name().to_owned())
+            .collect()
+    }
+
+    /// A built-in scenario at its initial state, with default parameters.
+    #[staticmethod]
+    fn scenario(name: &str) -> PyResult<Self> {
+        let simulation = builtin_simulations()
+            .into_iter()
+            .find(|simulation| simulation.name() == name)
+            .ok_or_else(|| PyValueError::new_err(format!("no simulation named `{name}`")))?;
+        let mut world = BodyWorld::from_simulation(simulation.as_ref());
+        let mut state = simulation.initial_state();
+        let mut potentials = simulation.external_potentials();
+        normalize_units(simulation.as_ref(), &mut world, &mut state, &mut potentials);
+        Ok(Self {
+            world,
+            state,
+            potentials,
+        })
+    }
+
+    #[pyo3(signature = (position, velocity, mass, radius = 0.05))]
+    fn add_body(
+        &mut self,
+        position: [f32; 3],
+        velocity: [f32; 3],
+        mass: f32,
+        radius: f32,
+    ) -> PyResult<()> {
+        if !mass.is_finite() || mass < 0.0 {
+            return Err(PyValueError::new_err("mass must be a non-negative number"));
+        }
+        let body = Body::new(
+            Vec3::from_array(position),
+            Vec3::from_array(velocity),
+            mass,
+            radius,
+            Vec4::ONE,
+        );
+        self.world.queue_spawn(body, None, BodyGroup::Other);
+        // Spawns land in the packed array when the systems run.
+        self.world.run_systems(0.0, &self.state);
+        Ok(())
+    }
+
+    /// Advances `count` steps of `dt`, then runs the per-body systems once
+    /// over the elapsed time, as one tick of the app's worker does.
+    #[pyo3(signature = (count = 1))]
+    fn step(&mut self, count: u32) {
+        let mut elapsed = 0.0;
+        for _ in 0..count {
+            elapsed += self.state.effective_dt().abs();
+            self.world.step_physics(&mut self.state, &self.potentials);
+        }
+        self.world.run_systems(elapsed, &self.state);
+    }
+
+    #[getter]
+    fn time(&self) -> f64 {
+        self.state.time
+    }
+
+    #[getter]
+    fn steps(&self) -> u64 {
+        self.state.step
+    }
+
+    #[getter]
+    fn dt(&self) -> f32 {
+        self.state.dt
+    }
+
+    #[setter]
+    fn set_dt(&mut self, dt: f32) {
+        self.state.dt = dt;
+    }
+
+    fn __len__(&self) -> usize {
+        self.world.bodies().len()
+    }
+
+    /// `(N, 3)` float32 array.
+    fn positions<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f32>> {
+        self.vectors(py, |body| body.position)
+    }
+
+    /// `(N, 3)` float32 array.
+    fn velocities<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f32>> {
+        self.vectors(py, |body| body.velocity)
+    }
+
+    /// `(N,)` float32 array.
+    fn masses<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f32>> {
+        Array1::from_iter(self.world.bodies().iter().map(|body| body.mass)).into_pyarray(py)
+    }
+}
+
+impl PySimulation {
+    fn vectors<'py>(
+        &self,
+        py: Python<'py>,
+        field: impl Fn(&Body) -> Vec3,
+    ) -> Bound<'py, PyArray2<f32>> {
+        let bodies = self.world.bodies();
+        let values = bodies
+            .iter()
+            .flat_map(|body| field(body).to_array())
+            .collect();
+        Array2::from_shape_vec((bodies.len(), 3), values)
+            .expect("three values per body")
+            .into_pyarray(py)
+    }
+}
+
+#[pymodule]
+fn n_body_problem_webgpu(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySimulation>()
+}
@@ -0,0 +1,254 @@
+//! Video capture through an `ffmpeg` child process: raw frames go down its
+//! stdin and come out as H.264 (MP4) or VP9 (WebM), without audio.
+
+use std::env;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::rendering::texture::TextureFormat;
+use crate::simulation::StepCursor;
+
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("ffmpeg was not found on PATH")]
+    FfmpegNotFound,
+    #[error("cannot record {0} frames")]
+    UnsupportedFormat(TextureFormat),
+    #[error("rows of {0} bytes cannot hold the frame width")]
+    RowStride(u32),
+    #[error("frame has {actual} bytes, expected {expected}")]
+    FrameSize { expected: usize, actual: usize },
+    #[error("ffmpeg exited with {0}")]
+    Ffmpeg(ExitStatus),
+    #[error("ffmpeg I/O failed")]
+    Io(#[from] io::Error),
+}
+
+/// The `ffmpeg` executable on `PATH`, if there is one.
+pub fn find_ffmpeg() -> Option<PathBuf> {
+    let name = if cfg!(windows) {
+        "ffmpeg.exe"
+    } else {
+        "ffmpeg"
+    };
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|directory| directory.join(name))
+        .find(|path| path.is_file())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VideoCodec {
+    /// H.264 in MP4, for the widest player support.
+    H264,
+    /// VP9 in WebM, for browsers and smaller files.
+    Vp9,
+}
+
+impl VideoCodec {
+    /// VP9 for a `.webm` extension, H.264 otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("webm") => Self::Vp9,
+            _ => Self::H264,
+        }
+    }
+
+    fn arguments(self) -> &'static [&'static str] {
+        match self {
+            Self::H264 => &[
+                "-c:v",
+                "libx264",
+                "-preset",
+                "medium",
+                "-crf",
+                "18",
+                "-movflags",
+                "+faststart",
+            ],
+            Self::Vp9 => &[
+                "-c:v",
+                "libvpx-vp9",
+                "-b:v",
+                "0",
+                "-crf",
+                "30",
+                "-row-mt",
+                "1",
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VideoConfig {
+    pub width: u32,
+    pub height: u32,
+    /// Format of the read-back frames; 8-bit and 10-bit color targets are accepted.
+    pub format: TextureFormat,
+    /// Stride of the read-back rows, which GPU copies pad to 256 bytes.
+    pub bytes_per_row: u32,
+    pub codec: VideoCodec,
+    pub frame_rate: u32,
+    /// Simulation steps each video frame stands for, so playback follows the
+    /// fixed timestep rather than how fast frames were rendered.
+    pub steps_per_frame: u64,
+}
+
+impl VideoConfig {
+    /// Tightly packed rows at 60 fps, one frame per simulation step.
+    pub fn new(width: u32, height: u32, format: TextureFormat, codec: VideoCodec) -> Self {
+        Self {
+            width,
+            height,
+            format,
+            bytes_per_row: width * 4,
+            codec,
+            frame_rate: 60,
+            steps_per_frame: 1,
+        }
+    }
+}
+
+/// ffmpeg's name for the pixel layout of a readable color format.
+fn pixel_format(format: TextureFormat) -> Option<&'static str> {
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Some("rgba"),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => Some("bgra"),
+        TextureFormat::Rgb10a2Unorm => Some("x2bgr10le"),
+        // Float HDR needs tone mapping before it fits an SDR video.
+        TextureFormat::Rgba16Float | TextureFormat::R32Uint | TextureFormat::Depth32Float => None,
+    }
+}
+
+/// Writes one video frame per `steps_per_frame` simulation steps. Steps the
+/// app rendered more than once are skipped and steps it missed repeat the
+/// previous frame, so the video's timestamps match simulated time.
+pub struct VideoRecorder {
+    config: VideoConfig,
+    child: Child,
+    stdin: Option<BufWriter<ChildStdin>>,
+    cursor: StepCursor,
+    frames: u64,
+    /// Last written frame, for repeats.
+    previous: Vec<u8>,
+}
+
+impl VideoRecorder {
+    /// Spawns ffmpeg writing to `path`, which it overwrites.
+    pub fn start(path: &Path, config: VideoConfig) -> Result<Self, RecordingError> {
+        let ffmpeg = find_ffmpeg().ok_or(RecordingError::FfmpegNotFound)?;
+        let pixel_format =
+            pixel_format(config.format).ok_or(RecordingError::UnsupportedFormat(config.format))?;
+        if config.bytes_per_row < config.width * 4 {
+            return Err(RecordingError::RowStride(config.bytes_per_row));
+        }
+        let mut command = Command::new(ffmpeg);
+        command
+            .args(["-hide_banner", "-loglevel", "error", "-y"])
+            .args(["-f", "rawvideo", "-pix_fmt", pixel_format])
+            .args(["-s", &format!("{}x{}", config.width, config.height)])
+            .args(["-framerate", &config.frame_rate.to_string()])
+            // Frames arrive sRGB-encoded, whichever way the surface encodes them.
+            .args(["-color_primaries", "bt709", "-color_trc", "iec61966-2-1"])
+            .args(["-colorspace", "rgb", "-i", "-", "-an"])
+            .args([
+                "-vf",
+                "scale=out_color_matrix=bt709:out_range=tv,format=yuv420p",
+            ])
+            .args(["-color_primaries", "bt709", "-color_trc", "bt709"])
+            .args(["-colorspace", "bt709", "-color_range", "tv"])
+            .args(config.codec.arguments())
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null());
+        let mut child = command.spawn()?;
+        let stdin = child.stdin.take().map(BufWriter::new);
+        info!(path = %path.display(), codec = ?config.codec, "recording video");
+        Ok(Self {
+            config,
+            child,
+            stdin,
+            cursor: StepCursor::default(),
+            frames: 0,
+            previous: Vec::new(),
+        })
+    }
+
+    /// Frames written so far, repeats included.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Offers the frame rendered for simulation step `step`. `pixels` holds
+    /// `bytes_per_row * height` bytes as read back from the GPU.
+    pub fn push(&mut self, step: u64, pixels: &[u8]) -> Result<(), RecordingError> {
+        let expected = self.config.bytes_per_row as usize * self.config.height as usize;
+        if pixels.len() != expected {
+            return Err(RecordingError::FrameSize {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        let per_frame = self.config.steps_per_frame.max(1);
+        let Some(due) = self.cursor.due(step, per_frame) else {
+            return Ok(());
+        };
+        let missed = (step - due) / per_frame;
+        if !self.previous.is_empty() {
+            // At most a second's worth, e.g. across a stall.
+            for _ in 0..missed.min(u64::from(self.config.frame_rate)) {
+                self.write_previous()?;
+            }
+        }
+        self.cursor.schedule(due + (missed + 1) * per_frame);
+
+        let row = self.config.width as usize * 4;
+        self.previous.clear();
+        for line in pixels.chunks_exact(self.config.bytes_per_row as usize) {
+            self.previous.extend_from_slice(&line[..row]);
+        }
+        self.write_previous()
+    }
+
+    fn write_previous(&mut self) -> Result<(), RecordingError> {
+        if let Some(stdin) = &mut self.stdin {
+            stdin.write_all(&self.previous)?;
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Closes the stream and waits for ffmpeg to finish the file.
+    pub fn finish(mut self) -> Result<u64, RecordingError> {
+        self.close()?;
+        Ok(self.frames)
+    }
+
+    fn close(&mut self) -> Result<(), RecordingError> {
+        let Some(mut stdin) = self.stdin.take() else {
+            return Ok(());
+        };
+        let flushed = stdin.flush();
+        drop(stdin);
+        let status = self.child.wait()?;
+        flushed?;
+        if status.success() {
+            info!(frames = self.frames, "finished recording");
+            Ok(())
+        } else {
+            Err(RecordingError::Ffmpeg(status))
+        }
+    }
+}
+
+impl Drop for VideoRecorder {
+    fn drop(&mut self) {
+        if let Err(error) = self.close() {
+            warn!(%error, "video recording failed");
+        }
+    }
+}
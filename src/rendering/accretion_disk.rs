@@ -0,0 +1,77 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+use super::config::BlendMode;
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use crate::simulation::{AccretionDisk, Body};
+
+/// The disk glows on top of what is behind it, like the halos.
+pub const ACCRETION_DISK_BLEND: BlendMode = BlendMode::Additive;
+
+/// The disk pass: six vertices of `vs_accretion_disk`/`fs_accretion_disk`
+/// into `target`. Declare it after the body pass and before lensing, so the
+/// far side of the disk is lensed over the hole.
+pub fn add_accretion_disk_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("accretion_disk", &[], &[target])
+}
+
+/// Uniform block of the disk pass, bound next to `RenderUniforms`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct AccretionDiskUniforms {
+    pub center: Vec3,
+    pub inner_radius: f32,
+    pub axis_u: Vec3,
+    pub outer_radius: f32,
+    pub axis_v: Vec3,
+    pub inner_temperature: f32,
+    /// Keplerian angular speed at the inner edge, radians per unit time.
+    pub inner_angular_speed: f32,
+    pub beaming: f32,
+    /// Simulation time, wrapped so the pattern keeps f32 precision.
+    pub time: f32,
+    pub _padding: f32,
+}
+
+/// Turns of the outer edge after which the pattern's time wraps. The streaks
+/// jump once per wrap, rarely enough to go unnoticed.
+const WRAP_TURNS: f64 = 64.0;
+
+impl AccretionDiskUniforms {
+    /// Centers the disk on its body. `None` when the body is missing or the
+    /// disk has no area, in which case the pass should be skipped.
+    pub fn new(
+        disk: &AccretionDisk,
+        bodies: &[Body],
+        gravitational_constant: f32,
+        time: f64,
+    ) -> Option<Self> {
+        let central = bodies.get(disk.central)?;
+        let normal = disk.normal.try_normalize()?;
+        if !(disk.inner_radius > 0.0 && disk.outer_radius > disk.inner_radius) {
+            return None;
+        }
+        let axis_u = normal.any_orthonormal_vector();
+        let axis_v = normal.cross(axis_u);
+        let mu = gravitational_constant * central.mass;
+        let inner_angular_speed = (mu / disk.inner_radius.powi(3)).max(0.0).sqrt();
+        let outer_period = std::f64::consts::TAU
+            / f64::from(
+                (mu / disk.outer_radius.powi(3))
+                    .max(f32::MIN_POSITIVE)
+                    .sqrt(),
+            );
+        Some(Self {
+            center: central.position,
+            inner_radius: disk.inner_radius,
+            axis_u,
+            outer_radius: disk.outer_radius,
+            axis_v,
+            inner_temperature: disk.inner_temperature,
+            inner_angular_speed,
+            beaming: disk.beaming,
+            time: time.rem_euclid(WRAP_TURNS * outer_period) as f32,
+            _padding: 0.0,
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+
+/// Decay the accumulation toggle switches to: trails about a second long at 60 fps.
+pub const DEFAULT_DECAY: f32 = 0.93;
+
+/// Names of the two persistent HDR textures the host keeps for accumulation;
+/// each frame reads one and writes the other.
+pub const HISTORY_TEXTURES: [&str; 2] = ["accumulation_a", "accumulation_b"];
+
+/// The accumulation pass: `post.wgsl`'s `fs_accumulate` reading `frame` and
+/// last frame's history, writing this frame's, which it returns for the
+/// following passes to read instead of `frame`. History must outlive the
+/// frame, so both textures are imported and swapped by `frame_index` parity.
+pub fn add_accumulation_pass(
+    graph: &mut RenderGraph,
+    frame: ResourceHandle,
+    frame_index: u64,
+) -> (PassHandle, ResourceHandle) {
+    let parity = (frame_index % 2) as usize;
+    let previous = graph.import(HISTORY_TEXTURES[1 - parity]);
+    let next = graph.import(HISTORY_TEXTURES[parity]);
+    let pass = graph.add_pass("accumulation", &[frame, previous], &[next]);
+    (pass, next)
+}
+
+/// Uniform block of `fs_accumulate`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct AccumulationUniforms {
+    pub decay: f32,
+    pub reset: u32,
+    pub _padding: [u32; 2],
+}
+
+impl AccumulationUniforms {
+    /// `reset` discards the history; hosts set it on the first accumulated
+    /// frame, after a resize and when the simulation changes.
+    pub fn new(decay: f32, reset: bool) -> Self {
+        Self {
+            decay: if decay.is_finite() {
+                decay.clamp(0.0, 0.999)
+            } else {
+                0.0
+            },
+            reset: u32::from(reset),
+            _padding: [0; 2],
+        }
+    }
+}
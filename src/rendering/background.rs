@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use bytemuck::{Pod, Zeroable};
+use glam::Mat4;
+
+use super::config::{Backdrop, BackgroundConfig};
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use crate::camera::Camera;
+use crate::formats::ppm::{self, Image};
+
+/// Directory backdrop paths are relative to.
+pub const ASSETS_DIR: &str = "assets";
+
+/// Reads a scenario's backdrop from `assets`, normally `ASSETS_DIR`, for the
+/// host to upload as `background_image`. Hosts reload it when the scenario
+/// changes and fall back to no backdrop on error.
+pub fn load_backdrop(assets: &Path, backdrop: &Backdrop) -> crate::error::Result<Image> {
+    let bytes = std::fs::read(assets.join(backdrop.path))?;
+    Ok(ppm::decode(&bytes)?)
+}
+
+/// The background pass: `post.wgsl`'s `fs_background` filling `target` with
+/// the clear color, starfield and backdrop before anything else is drawn.
+/// The body pass then loads `target` instead of clearing it. Hosts skip the
+/// pass, and clear to `clear_color`, unless `BackgroundConfig::needs_pass`.
+pub fn add_background_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("background", &[], &[target])
+}
+
+/// Uniform block of `fs_background`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct BackgroundUniforms {
+    /// Maps clip space back to world directions.
+    pub inverse_view_projection: Mat4,
+    pub clear_color: [f32; 3],
+    pub starfield_density: f32,
+    /// Zero without a backdrop.
+    pub backdrop_brightness: f32,
+    pub _padding: [u32; 3],
+}
+
+impl BackgroundUniforms {
+    /// `backdrop_loaded` is false when the host has no image bound, in which
+    /// case the backdrop is left out rather than sampling a placeholder.
+    pub fn new(config: &BackgroundConfig, camera: &Camera, backdrop_loaded: bool) -> Self {
+        let backdrop_brightness = match config.backdrop {
+            Some(backdrop) if backdrop_loaded => backdrop.brightness,
+            _ => 0.0,
+        };
+        Self {
+            inverse_view_projection: camera.view_projection().inverse(),
+            clear_color: config.clear_color,
+            starfield_density: config.starfield_density,
+            backdrop_brightness,
+            _padding: [0; 3],
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/// What the adapter reports, reduced to the bits the pipelines care about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdapterCapabilities {
+    pub compute_shaders: bool,
+    pub vertex_writable_storage: bool,
+    pub max_storage_buffers_per_shader_stage: u32,
+    pub max_uniform_buffer_binding_size: u32,
+    pub max_texture_dimension_2d: u32,
+}
+
+impl AdapterCapabilities {
+    /// Roughly what a WebGL2 adapter offers.
+    pub fn downlevel_webgl2() -> Self {
+        Self {
+            compute_shaders: false,
+            vertex_writable_storage: false,
+            max_storage_buffers_per_shader_stage: 0,
+            max_uniform_buffer_binding_size: 16 << 10,
+            max_texture_dimension_2d: 2048,
+        }
+    }
+}
+
+/// How the vertex stage reads body data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyFetch {
+    /// Vertex pulling straight from the body storage buffer.
+    StorageBuffer,
+    /// Bodies packed into a uniform array, limited by the uniform binding size.
+    UniformBuffer { max_bodies: u32 },
+    /// Bodies packed into a square RGBA32F data texture, each in
+    /// `TEXELS_PER_BODY` consecutive texels, so one row holds as many bodies
+    /// as fit in its width.
+    DataTexture { max_bodies: u32 },
+}
+
+/// Pipeline variant chosen for the adapter, and the device features it needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PipelineVariant {
+    pub body_fetch: BodyFetch,
+    pub gpu_physics: bool,
+    pub requires_vertex_writable_storage: bool,
+}
+
+const BODY_SIZE: u32 = size_of::<crate::simulation::Body>() as u32;
+const TEXELS_PER_BODY: u32 = BODY_SIZE / 16;
+
+impl PipelineVariant {
+    /// Picks the most capable variant the adapter supports instead of letting
+    /// `request_device` fail on missing features.
+    pub fn select(capabilities: &AdapterCapabilities) -> Self {
+        let storage = capabilities.max_storage_buffers_per_shader_stage > 0;
+        let body_fetch = if storage && capabilities.vertex_writable_storage {
+            BodyFetch::StorageBuffer
+        } else if capabilities.max_texture_dimension_2d >= TEXELS_PER_BODY {
+            let dimension = capabilities.max_texture_dimension_2d;
+            BodyFetch::DataTexture {
+                max_bodies: (dimension / TEXELS_PER_BODY).saturating_mul(dimension),
+            }
+        } else {
+            BodyFetch::UniformBuffer {
+                max_bodies: capabilities.max_uniform_buffer_binding_size / BODY_SIZE,
+            }
+        };
+
+        Self {
+            body_fetch,
+            gpu_physics: capabilities.compute_shaders && storage,
+            requires_vertex_writable_storage: body_fetch == BodyFetch::StorageBuffer,
+        }
+    }
+
+    pub fn max_bodies(&self) -> Option<u32> {
+        match self.body_fetch {
+            BodyFetch::StorageBuffer => None,
+            BodyFetch::UniformBuffer { max_bodies } | BodyFetch::DataTexture { max_bodies } => {
+                Some(max_bodies)
+            }
+        }
+    }
+}
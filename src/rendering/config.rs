@@ -0,0 +1,344 @@
+use std::ops::RangeInclusive;
+
+use crate::localization::Localizer;
+use crate::simulation::{BodyGroup, GroupMask};
+
+/// How body billboards combine with what is already drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Over-compositing by alpha; solid planets hide what is behind them.
+    #[default]
+    Alpha,
+    /// Colors add up, so dense star fields glow instead of occluding.
+    Additive,
+}
+
+/// Blend factors, named after their wgpu counterparts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlendFactor {
+    One,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+}
+
+/// `src * src_factor + dst * dst_factor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlendComponent {
+    pub src_factor: BlendFactor,
+    pub dst_factor: BlendFactor,
+}
+
+impl BlendMode {
+    pub const ALL: [Self; 2] = [Self::Alpha, Self::Additive];
+
+    /// The other mode.
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Alpha => Self::Additive,
+            Self::Additive => Self::Alpha,
+        }
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::Alpha => "blend-mode-alpha",
+            Self::Additive => "blend-mode-additive",
+        })
+    }
+
+    /// Color blending of the body pipeline. Blend state is baked into render
+    /// pipelines, so the renderer builds one body pipeline per mode.
+    pub fn color(self) -> BlendComponent {
+        match self {
+            Self::Alpha => BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+            },
+            Self::Additive => BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::One,
+            },
+        }
+    }
+
+    pub fn alpha(self) -> BlendComponent {
+        match self {
+            Self::Alpha => BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+            },
+            Self::Additive => BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::One,
+            },
+        }
+    }
+}
+
+/// What each body is drawn as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BodyGeometry {
+    /// Camera-facing impostor quads; cheap enough for any body count.
+    #[default]
+    Billboards,
+    /// Instanced low-poly icospheres lit by the heaviest star, for a few
+    /// large bodies; see `rendering::icosphere`.
+    Icospheres,
+}
+
+/// Curve compressing the HDR frame into display range in the tone-mapping pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToneMapper {
+    /// Plain clipping; bright overlaps saturate to white.
+    #[default]
+    Clamp,
+    /// `x / (1 + x)`: gentle, never reaches white.
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve: punchier, with a soft shoulder.
+    Aces,
+}
+
+impl ToneMapper {
+    pub const ALL: [Self; 3] = [Self::Clamp, Self::Reinhard, Self::Aces];
+
+    /// The curve after this one, wrapping back to `Clamp`.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::Clamp => "tone-mapper-clamp",
+            Self::Reinhard => "tone-mapper-reinhard",
+            Self::Aces => "tone-mapper-aces",
+        })
+    }
+}
+
+/// Range the exposure multiplier is clamped to.
+pub const EXPOSURE_RANGE: RangeInclusive<f32> = 0.05..=16.0;
+/// Range the display gamma is clamped to.
+pub const GAMMA_RANGE: RangeInclusive<f32> = 1.0..=3.0;
+
+/// Glow around emissive bodies (`BodyKind::Star`), growing with mass so
+/// suns bloom while light stars and everything non-emissive stay crisp.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HaloConfig {
+    /// Emissive bodies lighter than this get no halo; each doubling of mass
+    /// above it adds one step of radius and brightness.
+    pub emissive_mass: f32,
+    /// Peak brightness added over the disk; zero turns halos off.
+    pub intensity: f32,
+    /// Halo radius added per step, in body radii.
+    pub radius: f32,
+}
+
+impl Default for HaloConfig {
+    fn default() -> Self {
+        Self {
+            emissive_mass: 0.5,
+            intensity: 1.0,
+            radius: 1.5,
+        }
+    }
+}
+
+/// Range trail lengths are clamped to, in samples.
+pub const TRAIL_LENGTH_RANGE: RangeInclusive<u32> = 2..=4096;
+/// Range trail widths are clamped to, in logical pixels.
+pub const TRAIL_WIDTH_RANGE: RangeInclusive<f32> = 0.5..=16.0;
+
+/// How a trail's opacity falls off from the body back to its oldest sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TrailFade {
+    /// Fully opaque along its whole length.
+    None,
+    /// Evenly to nothing.
+    #[default]
+    Linear,
+    /// Holding on near the body, then quickly fading: the square of `Linear`.
+    Quadratic,
+}
+
+impl TrailFade {
+    pub const ALL: [Self; 3] = [Self::None, Self::Linear, Self::Quadratic];
+
+    /// The curve after this one, wrapping back to `None`.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// Opacity at `age`, from 0 at the body to 1 at the oldest sample.
+    pub fn opacity(self, age: f32) -> f32 {
+        let remaining = (1.0 - age).clamp(0.0, 1.0);
+        match self {
+            Self::None => 1.0,
+            Self::Linear => remaining,
+            Self::Quadratic => remaining * remaining,
+        }
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::None => "trail-fade-none",
+            Self::Linear => "trail-fade-linear",
+            Self::Quadratic => "trail-fade-quadratic",
+        })
+    }
+}
+
+/// Trails behind the bodies, drawn from their recent positions by
+/// `rendering::trails`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrailConfig {
+    /// Groups whose bodies leave a trail; empty turns trails off.
+    pub groups: GroupMask,
+    /// Positions kept per trail, one per simulation frame.
+    pub length: u32,
+    /// Ribbon width in logical pixels.
+    pub width: f32,
+    pub fade: TrailFade,
+}
+
+impl TrailConfig {
+    /// Trails on planets only, for systems whose thousands of small bodies
+    /// would otherwise smear the screen.
+    pub fn planets_only() -> Self {
+        let mut groups = GroupMask(0);
+        groups.set(BodyGroup::Planet, true);
+        Self {
+            groups,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for TrailConfig {
+    fn default() -> Self {
+        Self {
+            groups: GroupMask::ALL,
+            length: 256,
+            width: 1.5,
+            fade: TrailFade::Linear,
+        }
+    }
+}
+
+/// Equirectangular image drawn behind the scene, from the assets directory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backdrop {
+    /// Binary PPM path relative to `rendering::background::ASSETS_DIR`.
+    pub path: &'static str,
+    /// Multiplier on the image, which is usually far brighter than the bodies
+    /// should let it appear.
+    pub brightness: f32,
+}
+
+/// What is drawn behind the bodies; see `rendering::background`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackgroundConfig {
+    /// Linear color the frame is cleared to.
+    pub clear_color: [f32; 3],
+    /// Fraction of sky cells holding a procedural star; zero turns the
+    /// starfield off.
+    pub starfield_density: f32,
+    pub backdrop: Option<Backdrop>,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            clear_color: [0.0; 3],
+            starfield_density: 0.0,
+            backdrop: None,
+        }
+    }
+}
+
+impl BackgroundConfig {
+    /// Whether anything beyond the clear color needs the background pass.
+    pub fn needs_pass(&self) -> bool {
+        self.starfield_density > 0.0 || self.backdrop.is_some()
+    }
+}
+
+/// Look of a scenario: its defaults come from `Simulation::render_config`,
+/// and the app keeps any changes the user makes per simulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderConfig {
+    pub geometry: BodyGeometry,
+    pub blend_mode: BlendMode,
+    /// Linear multiplier applied before tone mapping.
+    pub exposure: f32,
+    /// Display gamma the output is encoded for.
+    pub gamma: f32,
+    pub tone_mapper: ToneMapper,
+    pub halo: HaloConfig,
+    pub background: BackgroundConfig,
+    pub trails: TrailConfig,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            geometry: BodyGeometry::Billboards,
+            blend_mode: BlendMode::Alpha,
+            exposure: 1.0,
+            gamma: 2.2,
+            tone_mapper: ToneMapper::Clamp,
+            halo: HaloConfig::default(),
+            background: BackgroundConfig::default(),
+            trails: TrailConfig::default(),
+        }
+    }
+}
+
+impl RenderConfig {
+    /// With exposure, gamma and the trail length and width clamped to their
+    /// slider ranges and the halo and background settings made finite and
+    /// non-negative.
+    pub fn sanitized(self) -> Self {
+        let clamp = |value: f32, range: RangeInclusive<f32>, default: f32| {
+            if value.is_finite() {
+                value.clamp(*range.start(), *range.end())
+            } else {
+                default
+            }
+        };
+        let defaults = Self::default();
+        let halo = self.halo;
+        let background = self.background;
+        let trails = self.trails;
+        Self {
+            exposure: clamp(self.exposure, EXPOSURE_RANGE, defaults.exposure),
+            gamma: clamp(self.gamma, GAMMA_RANGE, defaults.gamma),
+            halo: HaloConfig {
+                emissive_mass: clamp(
+                    halo.emissive_mass,
+                    0.0..=f32::MAX,
+                    defaults.halo.emissive_mass,
+                ),
+                intensity: clamp(halo.intensity, 0.0..=f32::MAX, 0.0),
+                radius: clamp(halo.radius, 0.0..=f32::MAX, 0.0),
+            },
+            background: BackgroundConfig {
+                clear_color: background
+                    .clear_color
+                    .map(|channel| clamp(channel, 0.0..=f32::MAX, 0.0)),
+                starfield_density: clamp(background.starfield_density, 0.0..=1.0, 0.0),
+                backdrop: background.backdrop.map(|backdrop| Backdrop {
+                    brightness: clamp(backdrop.brightness, 0.0..=f32::MAX, 0.0),
+                    ..backdrop
+                }),
+            },
+            trails: TrailConfig {
+                length: trails
+                    .length
+                    .clamp(*TRAIL_LENGTH_RANGE.start(), *TRAIL_LENGTH_RANGE.end()),
+                width: clamp(trails.width, TRAIL_WIDTH_RANGE, defaults.trails.width),
+                ..trails
+            },
+            ..self
+        }
+    }
+}
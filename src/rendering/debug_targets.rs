@@ -0,0 +1,107 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use super::texture::{TextureDesc, TextureFormat};
+use crate::camera::Camera;
+use crate::localization::Localizer;
+use crate::simulation::Body;
+
+/// What the debug overlay shows instead of the shaded frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DebugChannel {
+    /// No overlay; the body pass uses `fs_main` and writes color only.
+    #[default]
+    Off,
+    /// A stable hue per body, for picking and overdraw problems.
+    BodyIndex,
+    /// View-space depth, near bright, for ordering problems.
+    Depth,
+    /// Speed on a heat ramp, scaled by coverage to show blending.
+    Speed,
+}
+
+impl DebugChannel {
+    pub const ALL: [Self; 4] = [Self::Off, Self::BodyIndex, Self::Depth, Self::Speed];
+
+    /// The channel after this one, wrapping back to `Off`.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::Off => "debug-channel-off",
+            Self::BodyIndex => "debug-channel-body-index",
+            Self::Depth => "debug-channel-depth",
+            Self::Speed => "debug-channel-speed",
+        })
+    }
+}
+
+/// Formats of the extra color attachments `fs_debug` writes at locations 1 and 2.
+/// Neither may be blended.
+pub const BODY_INDEX_FORMAT: TextureFormat = TextureFormat::R32Uint;
+pub const AUX_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The auxiliary render targets of the debug mode, as graph transients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DebugTargets {
+    pub body_index: ResourceHandle,
+    pub aux: ResourceHandle,
+}
+
+impl DebugTargets {
+    /// Declares both targets; the body pass must list them among its writes
+    /// and draw with `fs_debug`, clearing them to zero first.
+    pub fn create(graph: &mut RenderGraph, width: u32, height: u32) -> Self {
+        let desc = |format| TextureDesc {
+            width,
+            height,
+            format,
+        };
+        Self {
+            body_index: graph.create_texture("debug_body_index", desc(BODY_INDEX_FORMAT)),
+            aux: graph.create_texture("debug_aux", desc(AUX_FORMAT)),
+        }
+    }
+
+    /// The overlay pass: `post.wgsl`'s `fs_debug_overlay` drawn over `surface`.
+    pub fn add_overlay_pass(&self, graph: &mut RenderGraph, surface: ResourceHandle) -> PassHandle {
+        graph.add_pass("debug_overlay", &[self.body_index, self.aux], &[surface])
+    }
+}
+
+/// Uniform block of `fs_debug_overlay`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct DebugUniforms {
+    /// `DebugChannel` as `u32`.
+    pub channel: u32,
+    pub body_count: u32,
+    /// Depth and speed mapped to the top of their ramps.
+    pub max_depth: f32,
+    pub max_speed: f32,
+}
+
+impl DebugUniforms {
+    /// Scales the ramps to the farthest and fastest of `bodies`.
+    pub fn new(channel: DebugChannel, bodies: &[Body], camera: &Camera) -> Self {
+        let eye = camera.eye();
+        let forward = (camera.target - eye).normalize_or_zero();
+        let (max_depth, max_speed) = bodies
+            .iter()
+            .filter(|body| body.position.is_finite() && body.velocity.is_finite())
+            .fold((0.0f32, 0.0f32), |(depth, speed), body| {
+                (
+                    depth.max((body.position - eye).dot(forward)),
+                    speed.max(body.velocity.length()),
+                )
+            });
+        Self {
+            channel: channel as u32,
+            body_count: bodies.len() as u32,
+            max_depth: max_depth.max(camera.znear),
+            max_speed: max_speed.max(f32::EPSILON),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use thiserror::Error;
+
+use super::texture::TextureDesc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResourceHandle(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PassHandle(usize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ResourceKind {
+    /// Owned outside the graph: the surface texture, body buffers, the depth buffer.
+    Imported,
+    /// Allocated by the graph for the frame and shared between passes with disjoint lifetimes.
+    Transient(TextureDesc),
+}
+
+#[derive(Clone, Debug)]
+struct Resource {
+    name: &'static str,
+    kind: ResourceKind,
+}
+
+#[derive(Clone, Debug)]
+struct Pass {
+    name: &'static str,
+    reads: Vec<ResourceHandle>,
+    writes: Vec<ResourceHandle>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum GraphError {
+    #[error("render graph has a cycle through {}", .0.join(", "))]
+    Cycle(Vec<&'static str>),
+    #[error("transient `{0}` is read but never written")]
+    UnwrittenTransient(&'static str),
+}
+
+/// Frame graph: passes declare what they read and write, and `compile` orders them
+/// and packs transient targets into as few allocations as possible.
+#[derive(Clone, Debug, Default)]
+pub struct RenderGraph {
+    resources: Vec<Resource>,
+    passes: Vec<Pass>,
+}
+
+impl RenderGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn import(&mut self, name: &'static str) -> ResourceHandle {
+        self.add_resource(name, ResourceKind::Imported)
+    }
+
+    pub fn create_texture(&mut self, name: &'static str, desc: TextureDesc) -> ResourceHandle {
+        self.add_resource(name, ResourceKind::Transient(desc))
+    }
+
+    pub fn add_pass(
+        &mut self,
+        name: &'static str,
+        reads: &[ResourceHandle],
+        writes: &[ResourceHandle],
+    ) -> PassHandle {
+        self.passes.push(Pass {
+            name,
+            reads: reads.to_vec(),
+            writes: writes.to_vec(),
+        });
+        PassHandle(self.passes.len() - 1)
+    }
+
+    pub fn pass_name(&self, pass: PassHandle) -> &'static str {
+        self.passes[pass.0].name
+    }
+
+    pub fn resource_name(&self, resource: ResourceHandle) -> &'static str {
+        self.resources[resource.0].name
+    }
+
+    pub fn compile(&self) -> Result<CompiledGraph, GraphError> {
+        let order = self.topological_order()?;
+        let (transient_slots, slots) = self.allocate_transients(&order)?;
+        Ok(CompiledGraph {
+            order: order.into_iter().map(PassHandle).collect(),
+            transient_slots,
+            slots,
+        })
+    }
+
+    fn add_resource(&mut self, name: &'static str, kind: ResourceKind) -> ResourceHandle {
+        self.resources.push(Resource { name, kind });
+        ResourceHandle(self.resources.len() - 1)
+    }
+
+    /// Writers of a resource run before its readers, and writers of the same
+    /// resource keep their declaration order. Ties are broken by declaration order.
+    fn topological_order(&self) -> Result<Vec<usize>, GraphError> {
+        let pass_count = self.passes.len();
+        let mut edges = vec![Vec::new(); pass_count];
+        let mut in_degree = vec![0usize; pass_count];
+        let mut add_edge = |from: usize, to: usize| {
+            if from != to && !edges[from].contains(&to) {
+                edges[from].push(to);
+                in_degree[to] += 1;
+            }
+        };
+
+        for resource in 0..self.resources.len() {
+            let handle = ResourceHandle(resource);
+            let writers: Vec<usize> = (0..pass_count)
+                .filter(|&pass| self.passes[pass].writes.contains(&handle))
+                .collect();
+            for pair in writers.windows(2) {
+                add_edge(pair[0], pair[1]);
+            }
+            for reader in (0..pass_count).filter(|&pass| self.passes[pass].reads.contains(&handle))
+            {
+                for &writer in &writers {
+                    add_edge(writer, reader);
+                }
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..pass_count)
+            .filter(|&pass| in_degree[pass] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(pass_count);
+        while let Some(pass) = ready.pop_front() {
+            order.push(pass);
+            for &next in &edges[pass] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    let position = ready.partition_point(|&queued| queued < next);
+                    ready.insert(position, next);
+                }
+            }
+        }
+
+        if order.len() != pass_count {
+            let stuck = (0..pass_count)
+                .filter(|&pass| in_degree[pass] > 0)
+                .map(|pass| self.passes[pass].name)
+                .collect();
+            return Err(GraphError::Cycle(stuck));
+        }
+        Ok(order)
+    }
+
+    /// Greedy interval packing: a transient reuses the first slot with an identical
+    /// descriptor whose previous occupant is no longer used.
+    fn allocate_transients(
+        &self,
+        order: &[usize],
+    ) -> Result<(Vec<Option<usize>>, Vec<TextureDesc>), GraphError> {
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; self.resources.len()];
+        for (position, &pass) in order.iter().enumerate() {
+            let pass = &self.passes[pass];
+            for handle in pass.reads.iter().chain(&pass.writes) {
+                let lifetime = &mut lifetimes[handle.0];
+                *lifetime = Some(match *lifetime {
+                    Some((first, _)) => (first, position),
+                    None => (position, position),
+                });
+            }
+        }
+
+        let mut assignments = vec![None; self.resources.len()];
+        let mut slots: Vec<TextureDesc> = Vec::new();
+        let mut slot_free_after: Vec<usize> = Vec::new();
+
+        let mut transients: Vec<(usize, TextureDesc, (usize, usize))> = Vec::new();
+        for (index, resource) in self.resources.iter().enumerate() {
+            if let (ResourceKind::Transient(desc), Some(lifetime)) =
+                (&resource.kind, lifetimes[index])
+            {
+                let first_pass = &self.passes[order[lifetime.0]];
+                if !first_pass.writes.contains(&ResourceHandle(index)) {
+                    return Err(GraphError::UnwrittenTransient(resource.name));
+                }
+                transients.push((index, *desc, lifetime));
+            }
+        }
+        transients.sort_by_key(|&(_, _, (first, _))| first);
+
+        for (index, desc, (first, last)) in transients {
+            let reusable =
+                (0..slots.len()).find(|&slot| slots[slot] == desc && slot_free_after[slot] < first);
+            let slot = reusable.unwrap_or_else(|| {
+                slots.push(desc);
+                slot_free_after.push(0);
+                slots.len() - 1
+            });
+            slot_free_after[slot] = last;
+            assignments[index] = Some(slot);
+        }
+
+        Ok((assignments, slots))
+    }
+}
+
+/// Execution order plus the physical texture slot backing each transient.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledGraph {
+    pub order: Vec<PassHandle>,
+    transient_slots: Vec<Option<usize>>,
+    /// Descriptors of the textures to allocate, one per slot.
+    pub slots: Vec<TextureDesc>,
+}
+
+/// Debug-marker half of a command encoder, so passes show up by name in GPU captures.
+pub trait DebugMarkers {
+    fn push_debug_group(&mut self, label: &str);
+    fn pop_debug_group(&mut self);
+}
+
+impl CompiledGraph {
+    /// Runs `encode` for each pass in order, wrapped in a debug group and a profiling scope.
+    pub fn execute(
+        &self,
+        graph: &RenderGraph,
+        encoder: &mut impl DebugMarkers,
+        mut encode: impl FnMut(PassHandle, &mut dyn DebugMarkers),
+    ) {
+        for &pass in &self.order {
+            let name = graph.pass_name(pass);
+            profiling::scope!("encode_pass", name);
+            encoder.push_debug_group(name);
+            encode(pass, encoder);
+            encoder.pop_debug_group();
+        }
+    }
+
+    pub fn slot(&self, resource: ResourceHandle) -> Option<usize> {
+        self.transient_slots.get(resource.0).copied().flatten()
+    }
+}
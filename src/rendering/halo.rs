@@ -0,0 +1,39 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::config::{BlendMode, HaloConfig};
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+
+/// Halos stop growing after this many mass doublings, so a black hole a
+/// million times the threshold does not cover the screen.
+pub const MAX_HALO_STEPS: f32 = 8.0;
+
+/// Halos add light whatever the bodies' own blend mode.
+pub const HALO_BLEND: BlendMode = BlendMode::Additive;
+
+/// The halo pass: `vs_halo`/`fs_halo` instanced over the body buffer into
+/// `target`. Declare it after the body pass, which writes the same target,
+/// and before tone mapping.
+pub fn add_halo_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("halos", &[], &[target])
+}
+
+/// Uniform block of the halo pass, bound next to `RenderUniforms`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct HaloUniforms {
+    pub emissive_mass: f32,
+    pub intensity: f32,
+    pub radius: f32,
+    pub max_steps: f32,
+}
+
+impl HaloUniforms {
+    pub fn new(config: &HaloConfig) -> Self {
+        Self {
+            emissive_mass: config.emissive_mass,
+            intensity: config.intensity,
+            radius: config.radius,
+            max_steps: MAX_HALO_STEPS,
+        }
+    }
+}
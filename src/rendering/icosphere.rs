@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+use super::graph::{RenderGraph, ResourceHandle};
+use super::texture::{TextureDesc, TextureFormat};
+use crate::simulation::{Body, BodyKind};
+
+/// Subdivisions of the default mesh: 320 triangles, round enough for a
+/// planet filling a good part of the screen.
+pub const DEFAULT_SUBDIVISIONS: u32 = 2;
+
+/// Depth attachment of the mesh path; unlike billboards, spheres intersect.
+pub const MESH_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+/// Unit icosphere for `vs_mesh`: a subdivided icosahedron whose vertex
+/// positions double as normals, wound counter-clockwise seen from outside.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Icosphere {
+    pub positions: Vec<Vec3>,
+    pub indices: Vec<u32>,
+}
+
+impl Icosphere {
+    pub fn new(subdivisions: u32) -> Self {
+        let t = (1.0 + 5f32.sqrt()) / 2.0;
+        let mut positions: Vec<Vec3> = [
+            (-1.0, t, 0.0),
+            (1.0, t, 0.0),
+            (-1.0, -t, 0.0),
+            (1.0, -t, 0.0),
+            (0.0, -1.0, t),
+            (0.0, 1.0, t),
+            (0.0, -1.0, -t),
+            (0.0, 1.0, -t),
+            (t, 0.0, -1.0),
+            (t, 0.0, 1.0),
+            (-t, 0.0, -1.0),
+            (-t, 0.0, 1.0),
+        ]
+        .into_iter()
+        .map(|(x, y, z)| Vec3::new(x, y, z).normalize())
+        .collect();
+        let mut triangles: Vec<[u32; 3]> = vec![
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ];
+
+        for _ in 0..subdivisions {
+            // Edge midpoints are shared by two triangles; keep one vertex each.
+            let mut midpoints = HashMap::new();
+            let mut midpoint = |a: u32, b: u32, positions: &mut Vec<Vec3>| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    positions.push((positions[a as usize] + positions[b as usize]).normalize());
+                    positions.len() as u32 - 1
+                })
+            };
+            triangles = triangles
+                .into_iter()
+                .flat_map(|[a, b, c]| {
+                    let ab = midpoint(a, b, &mut positions);
+                    let bc = midpoint(b, c, &mut positions);
+                    let ca = midpoint(c, a, &mut positions);
+                    [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        Self {
+            positions,
+            indices: triangles.into_iter().flatten().collect(),
+        }
+    }
+}
+
+/// Declares the depth buffer the mesh pass tests and writes, cleared to 1.
+pub fn create_depth_target(graph: &mut RenderGraph, width: u32, height: u32) -> ResourceHandle {
+    graph.create_texture(
+        "mesh_depth",
+        TextureDesc {
+            width,
+            height,
+            format: MESH_DEPTH_FORMAT,
+        },
+    )
+}
+
+/// Uniform block of `vs_mesh`/`fs_mesh`, bound next to `RenderUniforms`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MeshUniforms {
+    pub light_position: Vec3,
+    /// Light reaching the night side, so it is dim rather than black.
+    pub ambient: f32,
+}
+
+impl MeshUniforms {
+    /// Lights the scene from the heaviest star, or from the origin if there
+    /// is none. `flags` is parallel to `bodies`.
+    pub fn new(bodies: &[Body], flags: &[u32]) -> Self {
+        let light_position = bodies
+            .iter()
+            .zip(flags)
+            .filter(|(body, flags)| {
+                BodyKind::from_flags(**flags) == BodyKind::Star && body.position.is_finite()
+            })
+            .max_by(|(a, _), (b, _)| a.mass.total_cmp(&b.mass))
+            .map_or(Vec3::ZERO, |(body, _)| body.position);
+        Self {
+            light_position,
+            ambient: 0.08,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec3};
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use super::texture::TextureDesc;
+use super::tone_mapping::HDR_FORMAT;
+use crate::camera::Camera;
+use crate::simulation::{Body, GravitationalLens};
+
+/// Apparent radius of the shadow in Schwarzschild radii: the photon sphere's
+/// critical impact parameter, 3√3/2.
+const SHADOW_RADIUS: f32 = 2.598_076;
+
+/// Photon-ring width in pixels; the ring stays a crisp line at any zoom.
+const RING_WIDTH: f32 = 1.5;
+
+/// The lensing pass: `post.wgsl`'s `fs_lens` sampling `frame` (with a linear,
+/// clamp-to-edge sampler) into a new HDR target, which it returns for tone
+/// mapping to read instead.
+pub fn add_lensing_pass(
+    graph: &mut RenderGraph,
+    frame: ResourceHandle,
+    width: u32,
+    height: u32,
+) -> (PassHandle, ResourceHandle) {
+    let lensed = graph.create_texture(
+        "lensed_frame",
+        TextureDesc {
+            width,
+            height,
+            format: HDR_FORMAT,
+        },
+    );
+    let pass = graph.add_pass("lensing", &[frame], &[lensed]);
+    (pass, lensed)
+}
+
+/// Uniform block of `fs_lens`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LensUniforms {
+    /// Projected hole position in pixels, top-left origin.
+    pub center: Vec2,
+    pub einstein_radius: f32,
+    pub shadow_radius: f32,
+    pub ring_color: Vec3,
+    pub ring_width: f32,
+}
+
+impl LensUniforms {
+    /// Projects the lensing body on the CPU. `None` when it is missing or
+    /// behind the camera, in which case the pass should be skipped.
+    pub fn new(
+        lens: &GravitationalLens,
+        bodies: &[Body],
+        camera: &Camera,
+        viewport_size: Vec2,
+    ) -> Option<Self> {
+        let position = bodies.get(lens.body)?.position;
+        let clip = camera.view_projection() * position.extend(1.0);
+        if !clip.is_finite() || clip.w <= camera.znear {
+            return None;
+        }
+        let ndc = clip.truncate().truncate() / clip.w;
+        let center = Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) * 0.5 * viewport_size;
+        // Pixels per radian near the view axis.
+        let pixels_per_radian = 0.5 * viewport_size.y / (0.5 * camera.fovy).tan();
+        let distance = (position - camera.eye()).length();
+        // θ_E = √(2 r_s / D) for a source at infinity.
+        let einstein_angle = (2.0 * lens.schwarzschild_radius / distance).sqrt();
+        let shadow_angle = SHADOW_RADIUS * lens.schwarzschild_radius / distance;
+        Some(Self {
+            center,
+            einstein_radius: einstein_angle * pixels_per_radian,
+            shadow_radius: shadow_angle * pixels_per_radian,
+            ring_color: lens.ring_color,
+            ring_width: RING_WIDTH,
+        })
+    }
+}
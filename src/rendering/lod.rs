@@ -0,0 +1,85 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::upload::{BufferId, UploadBelt};
+
+/// Default `LodUniforms::impostor_pixel_radius`: below this a shaded sphere
+/// is indistinguishable from a dot.
+pub const IMPOSTOR_PIXEL_RADIUS: f32 = 2.0;
+
+/// Arguments of one indirect draw, laid out as wgpu's `DrawIndirectArgs`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct DrawIndirectArgs {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+
+/// The two draws `cs_classify_lod` fills: a six-vertex quad per impostor
+/// (`vs_lod_impostor`/`fs_main`) and one point-list vertex per distant body
+/// (`vs_lod_point`/`fs_lod_point`). Instance counts start at zero each frame.
+pub const LOD_DRAWS_RESET: [DrawIndirectArgs; 2] = [
+    DrawIndirectArgs {
+        vertex_count: 6,
+        instance_count: 0,
+        first_vertex: 0,
+        first_instance: 0,
+    },
+    DrawIndirectArgs {
+        vertex_count: 1,
+        instance_count: 0,
+        first_vertex: 0,
+        first_instance: 0,
+    },
+];
+
+/// GPU buffers of the LOD path. `indices` holds two halves of `capacity`
+/// body indices each, the impostors' and the points'.
+#[derive(Clone, Copy, Debug)]
+pub struct LodBuffers {
+    pub uniforms: BufferId,
+    /// Both `DrawIndirectArgs`, used as `STORAGE | INDIRECT`.
+    pub draws: BufferId,
+    pub indices: BufferId,
+    pub capacity: u32,
+}
+
+impl LodBuffers {
+    /// Bytes the index buffer needs for `capacity` bodies.
+    pub fn indices_size(capacity: u32) -> u64 {
+        2 * u64::from(capacity) * std::mem::size_of::<u32>() as u64
+    }
+
+    /// Zeroes the instance counts and updates the uniforms before this
+    /// frame's classification dispatch (one thread per body).
+    pub fn prepare(&self, belt: &mut UploadBelt, uniforms: &LodUniforms) {
+        belt.write(self.draws, 0, &LOD_DRAWS_RESET);
+        belt.write(self.uniforms, 0, std::slice::from_ref(uniforms));
+    }
+
+    /// Byte offset of draw `index` in `draws`, for `draw_indirect`.
+    pub fn draw_offset(index: usize) -> u64 {
+        (index * std::mem::size_of::<DrawIndirectArgs>()) as u64
+    }
+}
+
+/// Uniform block of `cs_classify_lod`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LodUniforms {
+    pub impostor_pixel_radius: f32,
+    pub body_count: u32,
+    pub _padding: [u32; 2],
+}
+
+impl LodUniforms {
+    /// Bodies past the buffers' capacity are not classified and so not drawn.
+    pub fn new(impostor_pixel_radius: f32, body_count: usize, buffers: &LodBuffers) -> Self {
+        Self {
+            impostor_pixel_radius: impostor_pixel_radius.max(0.0),
+            body_count: (body_count as u32).min(buffers.capacity),
+            _padding: [0; 2],
+        }
+    }
+}
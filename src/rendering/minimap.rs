@@ -0,0 +1,185 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use super::picture_in_picture::{Corner, ScissorRect};
+use super::reference::LineVertex;
+use crate::camera::Camera;
+use crate::simulation::Body;
+
+const FOOTPRINT_COLOR: [f32; 4] = [1.0, 0.85, 0.3, 0.9];
+const SIGHT_LINE_COLOR: [f32; 4] = [1.0, 0.85, 0.3, 0.35];
+const BORDER_COLOR: [f32; 4] = [0.5, 0.6, 0.8, 0.6];
+
+/// Orthographic top-down inset of the whole system, with the main camera's
+/// footprint on the reference plane drawn over it so the user keeps their
+/// bearings while zoomed in on a single body.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Minimap {
+    pub enabled: bool,
+    pub corner: Corner,
+    /// Inset side as a fraction of the surface height.
+    pub size_fraction: f32,
+    pub margin: u32,
+    /// Empty border around the bodies, as a fraction of their extent.
+    pub padding: f32,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            corner: Corner::TopRight,
+            size_fraction: 0.25,
+            margin: 16,
+            padding: 0.1,
+        }
+    }
+}
+
+impl Minimap {
+    /// Square inset rectangle for the given surface, or `None` when it would
+    /// not fit.
+    pub fn viewport(&self, surface_width: u32, surface_height: u32) -> Option<ScissorRect> {
+        let side = (surface_height as f32 * self.size_fraction.clamp(0.05, 1.0)) as u32;
+        if side == 0 || side + 2 * self.margin > surface_width.min(surface_height) {
+            return None;
+        }
+        let near = self.margin;
+        let (far_x, far_y) = (
+            surface_width - side - self.margin,
+            surface_height - side - self.margin,
+        );
+        let (x, y) = match self.corner {
+            Corner::TopLeft => (near, near),
+            Corner::TopRight => (far_x, near),
+            Corner::BottomLeft => (near, far_y),
+            Corner::BottomRight => (far_x, far_y),
+        };
+        Some(ScissorRect {
+            x,
+            y,
+            width: side,
+            height: side,
+        })
+    }
+
+    /// Square region of the XZ plane the inset shows: every finite body plus
+    /// the main camera's target, padded.
+    pub fn view(&self, bodies: &[Body], main: &Camera) -> MinimapView {
+        let (min, max) = bodies
+            .iter()
+            .map(|body| body.position)
+            .filter(|position| position.is_finite())
+            .chain([main.target])
+            .map(Vec3::xz)
+            .fold((Vec2::INFINITY, Vec2::NEG_INFINITY), |(min, max), point| {
+                (min.min(point), max.max(point))
+            });
+        let half_extent = 0.5 * (max - min).max_element() * (1.0 + 2.0 * self.padding.max(0.0));
+        MinimapView {
+            center: 0.5 * (min + max),
+            half_extent: half_extent.max(main.znear),
+        }
+    }
+}
+
+/// The region of the reference plane shown in the minimap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinimapView {
+    /// Center, as (x, z).
+    pub center: Vec2,
+    /// Half the side of the square shown.
+    pub half_extent: f32,
+}
+
+impl MinimapView {
+    /// Looking down −Y with +X to the right and +Z towards the bottom, deep
+    /// enough in Y to keep everything within `depth` of the plane.
+    pub fn view_projection(&self, depth: f32) -> Mat4 {
+        let center = Vec3::new(self.center.x, 0.0, self.center.y);
+        let eye = center + Vec3::Y * depth;
+        let h = self.half_extent;
+        Mat4::orthographic_rh(-h, h, -h, h, 0.0, 2.0 * depth)
+            * Mat4::look_at_rh(eye, center, Vec3::NEG_Z)
+    }
+}
+
+/// Where the corners of the main camera's view meet the horizontal plane
+/// through its target, in bottom-left, bottom-right, top-right, top-left
+/// order. Corner rays that miss the plane (above the horizon) end at the far
+/// clip plane instead, dropped onto the plane.
+pub fn frustum_footprint(camera: &Camera) -> [Vec3; 4] {
+    let inverse = camera.view_projection().inverse();
+    let height = camera.target.y;
+    let unproject = |x: f32, y: f32, z: f32| {
+        let point = inverse * Vec4::new(x, y, z, 1.0);
+        point.xyz() / point.w
+    };
+    [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(x, y)| {
+        let near = unproject(x, y, 0.0);
+        let far = unproject(x, y, 1.0);
+        let t = (height - near.y) / (far.y - near.y);
+        let hit = if (0.0..=1.0).contains(&t) {
+            near.lerp(far, t)
+        } else {
+            far
+        };
+        Vec3::new(hit.x, height, hit.z)
+    })
+}
+
+/// Line list of the footprint outline, sight lines from the camera's
+/// position (dropped onto the plane) to its near corners, and the inset's
+/// border, for `vs_minimap_line`.
+pub fn minimap_lines(camera: &Camera, view: &MinimapView) -> Vec<LineVertex> {
+    let mut lines = Vec::new();
+    let mut line = |a: Vec3, b: Vec3, color: [f32; 4]| {
+        lines.push(LineVertex { position: a, color });
+        lines.push(LineVertex { position: b, color });
+    };
+    let footprint = frustum_footprint(camera);
+    for (index, &corner) in footprint.iter().enumerate() {
+        line(corner, footprint[(index + 1) % 4], FOOTPRINT_COLOR);
+    }
+    let eye = camera.eye() * Vec3::new(1.0, 0.0, 1.0) + Vec3::Y * camera.target.y;
+    line(eye, footprint[0], SIGHT_LINE_COLOR);
+    line(eye, footprint[1], SIGHT_LINE_COLOR);
+
+    // Slightly inside the edge so it survives rasterization.
+    let h = view.half_extent * 0.995;
+    let corners = [(-h, -h), (h, -h), (h, h), (-h, h)]
+        .map(|(x, z)| Vec3::new(view.center.x + x, 0.0, view.center.y + z));
+    for index in 0..4 {
+        line(corners[index], corners[(index + 1) % 4], BORDER_COLOR);
+    }
+    lines
+}
+
+/// The inset pass: bodies as points (`vs_minimap_body`) and the lines from
+/// `minimap_lines` (`vs_minimap_line`), drawn into `target` under the
+/// inset's viewport and scissor after the main view.
+pub fn add_minimap_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("minimap", &[], &[target])
+}
+
+/// Uniform block of the minimap shaders.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MinimapUniforms {
+    pub view_projection: Mat4,
+    pub body_count: u32,
+    pub _padding: [u32; 3],
+}
+
+impl MinimapUniforms {
+    /// `depth` bounds how far above or below the plane bodies may be and
+    /// still be drawn; the main camera's far plane is a safe choice.
+    pub fn new(view: &MinimapView, depth: f32, body_count: usize) -> Self {
+        Self {
+            view_projection: view.view_projection(depth),
+            body_count: body_count as u32,
+            _padding: [0; 3],
+        }
+    }
+}
@@ -0,0 +1,28 @@
+pub mod accretion_disk;
+pub mod accumulation;
+pub mod background;
+pub mod capabilities;
+pub mod config;
+pub mod debug_targets;
+pub mod graph;
+pub mod halo;
+pub mod icosphere;
+pub mod lensing;
+pub mod lod;
+pub mod minimap;
+pub mod orbit_outlines;
+pub mod palette;
+pub mod permutations;
+pub mod picture_in_picture;
+pub mod preprocessor;
+pub mod reference;
+pub mod reference_frame;
+pub mod render_scale;
+pub mod resize;
+pub mod shaders;
+pub mod surface;
+pub mod texture;
+pub mod tone_mapping;
+pub mod trails;
+pub mod uniforms;
+pub mod upload;
@@ -0,0 +1,136 @@
+use std::f32::consts::TAU;
+
+use glam::Vec3;
+
+use super::reference::LineVertex;
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::simulation::orbits::OrbitalElements;
+use crate::simulation::worker::SimulationFrame;
+use crate::simulation::{Body, BodyGroup};
+use crate::ui::orbit_hud::dominant_body;
+
+/// Simulation frames between refits; perturbations move an orbit far less
+/// than this in between.
+const REFIT_FRAMES: u32 = 30;
+/// Segments of each outline.
+const OUTLINE_SEGMENTS: usize = 128;
+/// Alpha of the outlines, times the body's own.
+const OUTLINE_ALPHA: f32 = 0.4;
+
+/// One planet's osculating ellipse, relative to the body it orbits.
+#[derive(Clone, Debug)]
+struct Outline {
+    central: usize,
+    /// Line list, closed.
+    points: Vec<Vec3>,
+    color: [f32; 4],
+}
+
+/// Orrery-style outlines of the named planets' full orbits, refitted every
+/// `REFIT_FRAMES` from the latest state vectors so they follow perturbed
+/// orbits. Fitted to the inertial bodies and drawn about where the central
+/// body is shown, in the reference pass.
+#[derive(Clone, Debug, Default)]
+pub struct OrbitOutlines {
+    outlines: Vec<Outline>,
+    time: Option<f64>,
+    frames_since_fit: u32,
+}
+
+impl OrbitOutlines {
+    pub fn clear(&mut self) {
+        self.outlines.clear();
+        self.time = None;
+        self.frames_since_fit = 0;
+    }
+
+    /// Counts a new frame, refitting when due or when nothing is fitted yet.
+    pub fn observe(&mut self, frame: &SimulationFrame) {
+        if self.time == Some(frame.state.time) {
+            return;
+        }
+        let first = self.time.is_none();
+        self.time = Some(frame.state.time);
+        self.frames_since_fit += 1;
+        if first || self.frames_since_fit >= REFIT_FRAMES {
+            self.fit(frame);
+        }
+    }
+
+    fn fit(&mut self, frame: &SimulationFrame) {
+        self.frames_since_fit = 0;
+        self.outlines.clear();
+        for (index, _) in &frame.names {
+            let Some(&flags) = frame.flags.get(*index) else {
+                continue;
+            };
+            if BodyGroup::from_flags(flags) != BodyGroup::Planet {
+                continue;
+            }
+            if let Some(outline) = fit_outline(frame, *index) {
+                self.outlines.push(outline);
+            }
+        }
+    }
+
+    /// Line list of every outline about its central body in `bodies`, the
+    /// bodies as shown.
+    pub fn vertices(&self, bodies: &[Body]) -> Vec<LineVertex> {
+        let mut lines = Vec::new();
+        for outline in &self.outlines {
+            let Some(center) = bodies.get(outline.central).map(|body| body.position) else {
+                continue;
+            };
+            lines.extend(outline.points.iter().map(|&point| LineVertex {
+                position: center + point,
+                color: outline.color,
+            }));
+        }
+        lines
+    }
+}
+
+/// The bound orbit of `index` about its dominant body; `None` for escaping
+/// or degenerate motion, which has no closed curve to draw.
+fn fit_outline(frame: &SimulationFrame, index: usize) -> Option<Outline> {
+    let central = dominant_body(&frame.bodies, index)?;
+    let (orbiter, primary) = (&frame.bodies[index], &frame.bodies[central]);
+    let mu = frame.state.gravitational_constant * (primary.mass + orbiter.mass);
+    let elements = OrbitalElements::from_state_vectors(
+        orbiter.position - primary.position,
+        orbiter.velocity - primary.velocity,
+        mu,
+    )?;
+    if !(elements.eccentricity < 1.0 && elements.semi_major_axis > 0.0) {
+        return None;
+    }
+    let point = |segment: usize| {
+        OrbitalElements {
+            true_anomaly: segment as f32 / OUTLINE_SEGMENTS as f32 * TAU,
+            ..elements
+        }
+        .state_vectors(mu)
+        .0
+    };
+    let points: Vec<Vec3> = (0..OUTLINE_SEGMENTS)
+        .flat_map(|segment| [point(segment), point(segment + 1)])
+        .collect();
+    if !points.iter().all(|point| point.is_finite()) {
+        return None;
+    }
+    let mut color = orbiter.color.to_array();
+    color[3] *= OUTLINE_ALPHA;
+    Some(Outline {
+        central,
+        points,
+        color,
+    })
+}
+
+impl EventHandler for OrbitOutlines {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if event.invalidates_bodies() {
+            self.clear();
+        }
+    }
+}
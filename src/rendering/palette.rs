@@ -0,0 +1,96 @@
+use glam::Vec4;
+
+use crate::localization::Localizer;
+
+/// Colors of the speed colormap and the per-kind tints, uploaded with
+/// `RenderUniforms`, and of the UI theme (`ui::theme::UiTheme`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorPalette {
+    /// Blue through red with speed; the original look.
+    #[default]
+    Standard,
+    /// Blue through yellow (cividis), with Okabe–Ito kind colors that stay
+    /// apart without green–red contrast.
+    Deuteranopia,
+    /// Blue through orange, never leaning on red, which protanopes see dark.
+    Protanopia,
+    /// Saturated, bright stops on a black UI with white text.
+    HighContrast,
+}
+
+impl ColorPalette {
+    pub const ALL: [Self; 4] = [
+        Self::Standard,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::HighContrast,
+    ];
+
+    /// The palette after this one, wrapping back to `Standard`.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::Standard => "palette-standard",
+            Self::Deuteranopia => "palette-deuteranopia",
+            Self::Protanopia => "palette-protanopia",
+            Self::HighContrast => "palette-high-contrast",
+        })
+    }
+
+    /// Slowest, middle and fastest colors of `ColorMode::Speed`, blended
+    /// linearly in between.
+    pub fn speed_colormap(self) -> [Vec4; 3] {
+        match self {
+            Self::Standard => [
+                Vec4::new(0.0, 0.0, 1.0, 1.0),
+                Vec4::new(0.5, 0.3, 0.5, 1.0),
+                Vec4::new(1.0, 0.0, 0.0, 1.0),
+            ],
+            Self::Deuteranopia => [
+                Vec4::new(0.0, 0.135, 0.304, 1.0),
+                Vec4::new(0.486, 0.482, 0.471, 1.0),
+                Vec4::new(0.995, 0.909, 0.217, 1.0),
+            ],
+            Self::Protanopia => [
+                Vec4::new(0.0, 0.447, 0.698, 1.0),
+                Vec4::new(0.85, 0.85, 0.85, 1.0),
+                Vec4::new(0.902, 0.624, 0.0, 1.0),
+            ],
+            Self::HighContrast => [
+                Vec4::new(0.0, 0.4, 1.0, 1.0),
+                Vec4::new(1.0, 1.0, 1.0, 1.0),
+                Vec4::new(1.0, 0.85, 0.0, 1.0),
+            ],
+        }
+    }
+
+    /// Tint of each `BodyKind` under `ColorMode::Kind`, by discriminant.
+    pub fn kind_colors(self) -> [Vec4; 5] {
+        match self {
+            Self::Standard => [
+                Vec4::new(0.8, 0.8, 0.8, 1.0),
+                Vec4::new(1.0, 0.85, 0.4, 1.0),
+                Vec4::new(0.4, 0.7, 1.0, 1.0),
+                Vec4::new(0.5, 0.3, 0.7, 1.0),
+                Vec4::new(0.5, 1.0, 0.6, 1.0),
+            ],
+            Self::Deuteranopia | Self::Protanopia => [
+                Vec4::new(0.8, 0.8, 0.8, 1.0),
+                Vec4::new(0.941, 0.894, 0.259, 1.0),
+                Vec4::new(0.337, 0.706, 0.914, 1.0),
+                Vec4::new(0.8, 0.475, 0.655, 1.0),
+                Vec4::new(0.902, 0.624, 0.0, 1.0),
+            ],
+            Self::HighContrast => [
+                Vec4::new(1.0, 1.0, 1.0, 1.0),
+                Vec4::new(1.0, 0.85, 0.0, 1.0),
+                Vec4::new(0.0, 0.9, 1.0, 1.0),
+                Vec4::new(1.0, 0.3, 1.0, 1.0),
+                Vec4::new(0.3, 1.0, 0.3, 1.0),
+            ],
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use crate::rendering::shaders::{PipelineConstants, ShaderId, ShaderSources};
+
+/// Creates compute pipelines; implemented on top of `create_shader_module` and
+/// `create_compute_pipeline` with the constants as compilation options.
+pub trait PipelineFactory {
+    type Pipeline;
+
+    fn create_compute_pipeline(
+        &mut self,
+        label: &str,
+        source: &str,
+        entry_point: &str,
+        constants: &[(&'static str, f64)],
+    ) -> Self::Pipeline;
+}
+
+/// One compute pipeline permutation: an entry point built with a set of
+/// override values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PipelineKey {
+    pub shader: ShaderId,
+    pub entry_point: &'static str,
+    pub constants: PipelineConstants,
+}
+
+#[derive(Debug)]
+struct CachedPipeline<P> {
+    pipeline: P,
+    /// `ShaderSources` revision the pipeline was built from.
+    revision: u64,
+    last_used: u64,
+}
+
+/// Compute pipelines keyed by permutation, created the first time a key is
+/// asked for. Toggling an option back and forth reuses the pipeline built the
+/// first time instead of recompiling it every switch. Pipelines whose shader
+/// was edited are rebuilt on next use, and the least recently used beyond
+/// `capacity` are dropped at the end of each frame, so permutations do not pile up.
+#[derive(Debug)]
+pub struct PipelineCache<P> {
+    pipelines: HashMap<PipelineKey, CachedPipeline<P>>,
+    capacity: usize,
+    frame: u64,
+}
+
+impl<P> PipelineCache<P> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pipelines: HashMap::new(),
+            capacity,
+            frame: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pipelines.is_empty()
+    }
+
+    /// The pipeline for `key`, built from the current source of its shader if
+    /// it is missing or was built from an older revision.
+    pub fn get<F>(&mut self, key: PipelineKey, sources: &ShaderSources, factory: &mut F) -> &P
+    where
+        F: PipelineFactory<Pipeline = P>,
+    {
+        let revision = sources.revision(key.shader);
+        let frame = self.frame;
+        let cached = self
+            .pipelines
+            .entry(key)
+            .and_modify(|cached| {
+                if cached.revision != revision {
+                    cached.pipeline = build(key, sources, factory);
+                    cached.revision = revision;
+                }
+            })
+            .or_insert_with(|| CachedPipeline {
+                pipeline: build(key, sources, factory),
+                revision,
+                last_used: frame,
+            });
+        cached.last_used = frame;
+        &cached.pipeline
+    }
+
+    /// Evicts the least recently used pipelines down to `capacity`.
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+        if self.pipelines.len() <= self.capacity {
+            return;
+        }
+        let mut ages: Vec<u64> = self
+            .pipelines
+            .values()
+            .map(|cached| cached.last_used)
+            .collect();
+        let excess = self.pipelines.len() - self.capacity;
+        let (_, &mut cutoff, _) = ages.select_nth_unstable(excess - 1);
+        let mut evicted = 0;
+        self.pipelines.retain(|_, cached| {
+            let evict = evicted < excess && cached.last_used <= cutoff;
+            evicted += usize::from(evict);
+            !evict
+        });
+    }
+}
+
+fn build<F: PipelineFactory>(
+    key: PipelineKey,
+    sources: &ShaderSources,
+    factory: &mut F,
+) -> F::Pipeline {
+    profiling::scope!("create_compute_pipeline", key.entry_point);
+    let label = format!("{}::{}", key.shader.label(), key.entry_point);
+    factory.create_compute_pipeline(
+        &label,
+        sources.source(key.shader),
+        key.entry_point,
+        &key.constants.entries(key.shader),
+    )
+}
@@ -0,0 +1,96 @@
+use glam::Vec3;
+
+use crate::camera::Camera;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Pixel rectangle used for both the inset viewport and its scissor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScissorRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Inset view zoomed onto the followed body, drawn in a second scissored pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PictureInPicture {
+    pub enabled: bool,
+    pub corner: Corner,
+    /// Inset height as a fraction of the surface height.
+    pub size_fraction: f32,
+    pub margin: u32,
+    /// Inset camera distance in multiples of the followed body's radius.
+    pub radii_in_view: f32,
+}
+
+impl Default for PictureInPicture {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            corner: Corner::BottomRight,
+            size_fraction: 0.3,
+            margin: 16,
+            radii_in_view: 12.0,
+        }
+    }
+}
+
+impl PictureInPicture {
+    /// Inset rectangle for the given surface, or `None` when it would not fit.
+    pub fn viewport(&self, surface_width: u32, surface_height: u32) -> Option<ScissorRect> {
+        let height = (surface_height as f32 * self.size_fraction.clamp(0.05, 1.0)) as u32;
+        let width = height * 4 / 3;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        if width + 2 * self.margin > surface_width || height + 2 * self.margin > surface_height {
+            return None;
+        }
+
+        let left = self.margin;
+        let right = surface_width - width - self.margin;
+        let top = self.margin;
+        let bottom = surface_height - height - self.margin;
+        let (x, y) = match self.corner {
+            Corner::TopLeft => (left, top),
+            Corner::TopRight => (right, top),
+            Corner::BottomLeft => (left, bottom),
+            Corner::BottomRight => (right, bottom),
+        };
+        Some(ScissorRect {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    /// Camera for the inset pass: same orientation as `main`, centered on the
+    /// followed body and pulled in close enough to fill the inset.
+    pub fn camera(
+        &self,
+        main: &Camera,
+        target: Vec3,
+        body_radius: f32,
+        rect: ScissorRect,
+    ) -> Camera {
+        let mut camera = *main;
+        camera.target = target;
+        camera.distance = (body_radius * self.radii_in_view).max(camera.znear * 10.0);
+        camera.aspect = rect.width as f32 / rect.height as f32;
+        camera
+    }
+
+    /// Whether the inset should be drawn this frame.
+    pub fn is_active(&self, main: &Camera) -> bool {
+        self.enabled && main.follow.is_some()
+    }
+}
@@ -0,0 +1,84 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PreprocessError {
+    #[error("{file}:{line}: unknown shader module `{module}`")]
+    UnknownModule {
+        file: String,
+        line: u32,
+        module: String,
+    },
+    #[error("{file}:{line}: expected `#include \"module\"`")]
+    MalformedDirective { file: String, line: u32 },
+}
+
+/// Preprocessed WGSL plus where each of its lines came from, so naga errors
+/// against the composed text can be reported against the file that was written.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComposedShader {
+    pub source: String,
+    /// File name and one-based line of every composed line.
+    origins: Vec<(String, u32)>,
+}
+
+impl ComposedShader {
+    /// File and line behind one-based `line` of the composed source.
+    pub fn origin(&self, line: u32) -> Option<(&str, u32)> {
+        let (file, line) = self.origins.get(line.checked_sub(1)? as usize)?;
+        Some((file, *line))
+    }
+}
+
+/// Resolves `#include "name"` lines against `modules`, pasting each module at
+/// most once so shared declarations are not duplicated and cycles end. Anything
+/// else is passed through untouched; per-pipeline tuning goes through WGSL
+/// `override` constants instead of textual defines.
+pub fn compose(
+    file: &str,
+    source: &str,
+    modules: &[(&str, &str)],
+) -> Result<ComposedShader, PreprocessError> {
+    let mut composed = ComposedShader::default();
+    let mut included = Vec::new();
+    append(file, source, modules, &mut included, &mut composed)?;
+    Ok(composed)
+}
+
+fn append<'a>(
+    file: &str,
+    source: &str,
+    modules: &[(&'a str, &'a str)],
+    included: &mut Vec<&'a str>,
+    composed: &mut ComposedShader,
+) -> Result<(), PreprocessError> {
+    for (index, text) in source.lines().enumerate() {
+        let line = index as u32 + 1;
+        let Some(directive) = text.trim().strip_prefix("#include") else {
+            composed.source.push_str(text);
+            composed.source.push('\n');
+            composed.origins.push((file.to_owned(), line));
+            continue;
+        };
+        let name = directive
+            .trim()
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .ok_or_else(|| PreprocessError::MalformedDirective {
+                file: file.to_owned(),
+                line,
+            })?;
+        let &(name, module) = modules
+            .iter()
+            .find(|(module, _)| *module == name)
+            .ok_or_else(|| PreprocessError::UnknownModule {
+                file: file.to_owned(),
+                line,
+                module: name.to_owned(),
+            })?;
+        if !included.contains(&name) {
+            included.push(name);
+            append(name, module, modules, included, composed)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use crate::camera::Camera;
+use crate::simulation::guides::ring_vertices;
+use crate::simulation::{Body, BodyGroup, GroupMask, UnitConversion};
+
+/// Grid lines drawn on each side of the center line, in both directions.
+pub const GRID_HALF_LINES: i32 = 20;
+/// Every this many grid lines, counted from the origin, is drawn brighter.
+pub const GRID_MAJOR_EVERY: i32 = 5;
+/// Length the scale bar aims for before rounding to a 1-2-5 step, in logical
+/// pixels.
+pub const SCALE_BAR_PIXELS: f32 = 160.0;
+
+const GRID_COLOR: [f32; 4] = [0.5, 0.6, 0.8, 0.12];
+const GRID_MAJOR_COLOR: [f32; 4] = [0.5, 0.6, 0.8, 0.3];
+const AXIS_COLORS: [[f32; 4]; 3] = [
+    [0.9, 0.3, 0.3, 0.8],
+    [0.3, 0.9, 0.3, 0.8],
+    [0.3, 0.5, 1.0, 0.8],
+];
+
+/// Vertex of the overlay's line list, for `vs_reference`. The color is an
+/// array because `Vec4` is 16-byte aligned and would pad the vertex.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LineVertex {
+    pub position: Vec3,
+    pub color: [f32; 4],
+}
+
+/// The unit lengths are displayed in: physical when the scenario runs in
+/// N-body units, else whatever it says it is authored in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LengthUnit {
+    pub label: &'static str,
+    /// Display units per simulation length unit.
+    pub per_unit: f64,
+}
+
+impl LengthUnit {
+    pub fn new(units: Option<&UnitConversion>, length_label: &'static str) -> Self {
+        match units {
+            // Metres are unreadable at planetary scales.
+            Some(units) if units.physical.length_label == "m" => Self {
+                label: "km",
+                per_unit: f64::from(units.length) * units.physical.length / 1000.0,
+            },
+            Some(units) => Self {
+                label: units.physical.length_label,
+                per_unit: f64::from(units.length),
+            },
+            None => Self {
+                label: length_label,
+                per_unit: 1.0,
+            },
+        }
+    }
+}
+
+/// The largest 1, 2 or 5 × 10^k not above `length`.
+fn round_down_1_2_5(length: f64) -> f64 {
+    let decade = 10f64.powf(length.log10().floor());
+    let mantissa = length / decade;
+    let step = if mantissa >= 5.0 {
+        5.0
+    } else if mantissa >= 2.0 {
+        2.0
+    } else {
+        1.0
+    };
+    step * decade
+}
+
+/// Screen-space scale bar for the UI to draw, measured at the camera target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleBar {
+    /// Physical pixels.
+    pub pixels: f32,
+    /// The same length in simulation units; the grid spacing.
+    pub world_length: f32,
+    /// E.g. "1 AU".
+    pub label: String,
+}
+
+impl ScaleBar {
+    /// `viewport_height` is in physical pixels and `ui_scale` is physical
+    /// per logical pixel. `None` for a degenerate camera or viewport.
+    pub fn new(
+        camera: &Camera,
+        viewport_height: f32,
+        ui_scale: f32,
+        unit: LengthUnit,
+    ) -> Option<Self> {
+        let world_per_pixel = 2.0 * camera.distance * (0.5 * camera.fovy).tan() / viewport_height;
+        let target_pixels = SCALE_BAR_PIXELS * ui_scale.max(f32::EPSILON);
+        let displayed = f64::from(target_pixels * world_per_pixel) * unit.per_unit;
+        if !(displayed.is_finite() && displayed > 0.0) {
+            return None;
+        }
+        let length = round_down_1_2_5(displayed);
+        let world_length = (length / unit.per_unit) as f32;
+        let number = if (1e-3..1e5).contains(&length) {
+            // Round-trip through f32 drops float noise such as 0.30000000000000004.
+            format!("{}", length as f32)
+        } else {
+            format!("{length:.0e}")
+        };
+        Some(Self {
+            pixels: world_length / world_per_pixel,
+            world_length,
+            label: format!("{number} {}", unit.label).trim_end().to_owned(),
+        })
+    }
+}
+
+/// Line list of the ground grid in the ecliptic (XZ) plane, centered on the
+/// camera target and snapped to `spacing`, plus X, Y and Z axes at the origin.
+pub fn reference_lines(target: Vec3, spacing: f32) -> Vec<LineVertex> {
+    let mut lines = Vec::new();
+    if !(spacing.is_finite() && spacing > 0.0) {
+        return lines;
+    }
+    let center_x = (target.x / spacing).round() as i32;
+    let center_z = (target.z / spacing).round() as i32;
+    let extent = GRID_HALF_LINES as f32 * spacing;
+    let mut line = |from: Vec3, to: Vec3, color: [f32; 4]| {
+        lines.push(LineVertex {
+            position: from,
+            color,
+        });
+        lines.push(LineVertex {
+            position: to,
+            color,
+        });
+    };
+    for offset in -GRID_HALF_LINES..=GRID_HALF_LINES {
+        let color = |index: i32| {
+            if index % GRID_MAJOR_EVERY == 0 {
+                GRID_MAJOR_COLOR
+            } else {
+                GRID_COLOR
+            }
+        };
+        let x = (center_x + offset) as f32 * spacing;
+        let z = center_z as f32 * spacing;
+        line(
+            Vec3::new(x, 0.0, z - extent),
+            Vec3::new(x, 0.0, z + extent),
+            color(center_x + offset),
+        );
+        let z = (center_z + offset) as f32 * spacing;
+        let x = center_x as f32 * spacing;
+        line(
+            Vec3::new(x - extent, 0.0, z),
+            Vec3::new(x + extent, 0.0, z),
+            color(center_z + offset),
+        );
+    }
+    let axis_length = GRID_MAJOR_EVERY as f32 * spacing;
+    for (axis, color) in [Vec3::X, Vec3::Y, Vec3::Z].into_iter().zip(AXIS_COLORS) {
+        line(Vec3::ZERO, axis * axis_length, color);
+    }
+    lines
+}
+
+/// Alpha of the drop-lines and their markers, times the body's own.
+const GUIDE_ALPHA: f32 = 0.35;
+/// Segments of the marker circle at each drop-line's foot.
+const MARKER_SEGMENTS: usize = 16;
+
+/// Groups that get ecliptic guides; moons and small bodies would bury the
+/// planets in lines.
+pub fn guided_groups() -> GroupMask {
+    let mut groups = GroupMask(0);
+    groups.set(BodyGroup::Star, true);
+    groups.set(BodyGroup::Planet, true);
+    groups
+}
+
+/// Orrery-style guides for reading inclined orbits in 3D: a vertical line
+/// from each body in `groups` down to the ecliptic (XZ) plane and a circle
+/// of twice its radius where it meets it, in the body's color. `flags` is
+/// parallel to `bodies`.
+pub fn ecliptic_guides(bodies: &[Body], flags: &[u32], groups: GroupMask) -> Vec<LineVertex> {
+    let mut lines = Vec::new();
+    for (body, &flags) in bodies.iter().zip(flags) {
+        if !groups.contains(BodyGroup::from_flags(flags)) || !body.position.is_finite() {
+            continue;
+        }
+        let mut color = body.color.to_array();
+        color[3] *= GUIDE_ALPHA;
+        let foot = Vec3::new(body.position.x, 0.0, body.position.z);
+        let mut line = |from: Vec3, to: Vec3| {
+            lines.push(LineVertex {
+                position: from,
+                color,
+            });
+            lines.push(LineVertex {
+                position: to,
+                color,
+            });
+        };
+        line(body.position, foot);
+        for pair in ring_vertices(foot, 2.0 * body.radius, MARKER_SEGMENTS).chunks_exact(2) {
+            line(pair[0], pair[1]);
+        }
+    }
+    lines
+}
+
+/// The overlay pass: `vs_reference`/`fs_reference` over the grid, axes and
+/// guide line lists into `target`, alpha-blended after the bodies.
+pub fn add_reference_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("reference", &[], &[target])
+}
@@ -0,0 +1,49 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use crate::simulation::ReferenceFrame;
+
+/// Name of the imported body buffer `cs_reference_frame` writes and the
+/// render passes bind as `bodies` while a non-inertial frame is shown.
+pub const FRAME_BODIES: &str = "frame_bodies";
+
+/// The reference-frame pass: `shader.wgsl`'s `cs_reference_frame`, dispatched
+/// as a single workgroup, reading the simulated `bodies` and returning the
+/// buffer for the render passes to read instead. Skip it for
+/// `ReferenceFrame::Inertial`.
+pub fn add_reference_frame_pass(
+    graph: &mut RenderGraph,
+    bodies: ResourceHandle,
+) -> (PassHandle, ResourceHandle) {
+    let frame_bodies = graph.import(FRAME_BODIES);
+    let pass = graph.add_pass("reference_frame", &[bodies], &[frame_bodies]);
+    (pass, frame_bodies)
+}
+
+/// Uniform block of `cs_reference_frame`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ReferenceFrameUniforms {
+    /// `FRAME_*` in the shader, in `ReferenceFrame` declaration order.
+    pub mode: u32,
+    pub body: u32,
+    pub body_count: u32,
+    pub _padding: u32,
+}
+
+impl ReferenceFrameUniforms {
+    pub fn new(frame: ReferenceFrame, body_count: usize) -> Self {
+        let (mode, body) = match frame {
+            ReferenceFrame::Inertial => (0, 0),
+            ReferenceFrame::Barycentric => (1, 0),
+            ReferenceFrame::Heliocentric => (2, 0),
+            ReferenceFrame::Body(index) => (3, index as u32),
+        };
+        Self {
+            mode,
+            body,
+            body_count: body_count as u32,
+            _padding: 0,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use super::resize::SurfaceSize;
+use crate::localization::Localizer;
+
+/// Internal resolution relative to the surface. Everything up to the
+/// tone-mapping pass renders at `internal_size`, and `fs_tone_map` filters
+/// the result onto the surface: above one supersamples (for screenshots),
+/// below one trades sharpness for frame time on weak GPUs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderScale(f32);
+
+impl RenderScale {
+    pub const RANGE: RangeInclusive<f32> = 0.5..=2.0;
+    /// Increment of `stepped`.
+    pub const STEP: f32 = 0.125;
+
+    /// Clamped to `RANGE`; non-finite values give the native scale.
+    pub fn new(scale: f32) -> Self {
+        if scale.is_finite() {
+            Self(scale.clamp(*Self::RANGE.start(), *Self::RANGE.end()))
+        } else {
+            Self::default()
+        }
+    }
+
+    pub fn get(self) -> f32 {
+        self.0
+    }
+
+    /// `steps` increments of `STEP` up (positive) or down, clamped.
+    pub fn stepped(self, steps: i32) -> Self {
+        Self::new(self.0 + steps as f32 * Self::STEP)
+    }
+
+    /// Size of the intermediate targets for a surface, at least 1×1.
+    pub fn internal_size(self, surface: SurfaceSize) -> SurfaceSize {
+        let scale = |pixels: u32| ((pixels as f32 * self.0).round() as u32).max(1);
+        SurfaceSize {
+            width: scale(surface.width),
+            height: scale(surface.height),
+        }
+    }
+
+    pub fn label(self) -> String {
+        format!("{}%", (self.0 * 100.0).round())
+    }
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Weight of each new GPU frame time in the running average.
+const SMOOTHING: f32 = 0.1;
+/// Consecutive over-budget frames before the scale drops; short, so a heavy
+/// scene recovers within a fraction of a second.
+const LOWER_AFTER: u32 = 10;
+/// Consecutive comfortable frames before the scale rises a step; long, so it
+/// does not oscillate around the budget.
+const RAISE_AFTER: u32 = 90;
+
+/// Lowers the render scale while GPU frame times (from timestamp queries)
+/// exceed a budget and raises it again, up to `ceiling`, once they are well
+/// under it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveResolution {
+    pub enabled: bool,
+    /// GPU time per frame to stay under; the default leaves headroom at 60 FPS.
+    pub budget: Duration,
+    /// The scale rises only while frames take less than this fraction of the
+    /// budget, estimated at the next step's pixel count.
+    pub raise_below: f32,
+    /// Highest scale to return to: the user's own setting.
+    pub ceiling: RenderScale,
+    /// Smoothed GPU frame time, in seconds.
+    average: Option<f32>,
+    over_budget: u32,
+    under_budget: u32,
+}
+
+impl Default for AdaptiveResolution {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            budget: Duration::from_micros(14_000),
+            raise_below: 0.85,
+            ceiling: RenderScale::default(),
+            average: None,
+            over_budget: 0,
+            under_budget: 0,
+        }
+    }
+}
+
+impl AdaptiveResolution {
+    /// Starts adapting below `ceiling`, forgetting earlier timings.
+    pub fn enable(&mut self, ceiling: RenderScale) {
+        *self = Self {
+            enabled: true,
+            ceiling,
+            ..*self
+        };
+        self.reset();
+    }
+
+    /// Forgets the timings, e.g. after a scene switch changed the workload.
+    pub fn reset(&mut self) {
+        self.average = None;
+        self.over_budget = 0;
+        self.under_budget = 0;
+    }
+
+    /// Smoothed GPU frame time.
+    pub fn average(&self) -> Option<Duration> {
+        self.average.map(Duration::from_secs_f32)
+    }
+
+    /// Feeds one frame's GPU time, measured at `scale`, and returns the scale
+    /// to use from now on if it should change. Drops straight to the scale
+    /// whose pixel count fits the budget; rises one `STEP` at a time.
+    pub fn observe(&mut self, gpu_time: Duration, scale: RenderScale) -> Option<RenderScale> {
+        if !self.enabled {
+            return None;
+        }
+        let seconds = gpu_time.as_secs_f32();
+        let average = match self.average {
+            Some(average) => average + SMOOTHING * (seconds - average),
+            None => seconds,
+        };
+        self.average = Some(average);
+        let budget = self.budget.as_secs_f32();
+        if average > budget {
+            self.over_budget += 1;
+            self.under_budget = 0;
+        } else {
+            self.over_budget = 0;
+            // GPU time grows with the pixel count, i.e. with the scale squared.
+            let raised = scale.stepped(1).get();
+            let estimate = average * (raised / scale.get()).powi(2);
+            if estimate < self.raise_below * budget {
+                self.under_budget += 1;
+            } else {
+                self.under_budget = 0;
+            }
+        }
+
+        let next = if self.over_budget >= LOWER_AFTER {
+            let fitting = scale.get() * (budget / average).sqrt();
+            let steps = ((fitting - scale.get()) / RenderScale::STEP).floor() as i32;
+            scale.stepped(steps.min(-1))
+        } else if self.under_budget >= RAISE_AFTER && scale.get() < self.ceiling.get() {
+            RenderScale::new(scale.stepped(1).get().min(self.ceiling.get()))
+        } else {
+            return None;
+        };
+        if next == scale {
+            return None;
+        }
+        // The average was measured at the old scale.
+        self.reset();
+        Some(next)
+    }
+
+    /// Status bar text while adapting, e.g. "Resolution 75% (auto)".
+    pub fn indicator(&self, scale: RenderScale, locale: &Localizer) -> Option<String> {
+        self.enabled
+            .then(|| locale.format("resolution-auto", &[("scale", scale.label().into())]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(ceiling: f32) -> AdaptiveResolution {
+        let mut adaptive = AdaptiveResolution::default();
+        adaptive.enable(RenderScale::new(ceiling));
+        adaptive
+    }
+
+    /// Feeds `frames` frames of `millis` at `scale`, returning the frame
+    /// (counted from one) that changed the scale and the new scale.
+    fn run(
+        adaptive: &mut AdaptiveResolution,
+        millis: u64,
+        scale: f32,
+        frames: u32,
+    ) -> Option<(u32, RenderScale)> {
+        (1..=frames).find_map(|frame| {
+            adaptive
+                .observe(Duration::from_millis(millis), RenderScale::new(scale))
+                .map(|next| (frame, next))
+        })
+    }
+
+    #[test]
+    fn disabled_never_changes_the_scale() {
+        let mut adaptive = AdaptiveResolution::default();
+        assert_eq!(run(&mut adaptive, 100, 1.0, 200), None);
+    }
+
+    #[test]
+    fn drops_to_the_fitting_scale_after_ten_slow_frames() {
+        let mut adaptive = enabled(1.0);
+        // sqrt(14 / 20) ≈ 0.84 of the pixels' scale, rounded down to a step.
+        assert_eq!(
+            run(&mut adaptive, 20, 1.0, 100),
+            Some((LOWER_AFTER, RenderScale::new(0.75)))
+        );
+    }
+
+    #[test]
+    fn rises_one_step_after_ninety_fast_frames() {
+        let mut adaptive = enabled(1.0);
+        assert_eq!(
+            run(&mut adaptive, 1, 0.5, 200),
+            Some((RAISE_AFTER, RenderScale::new(0.625)))
+        );
+    }
+
+    #[test]
+    fn a_slow_frame_restarts_the_raise_count() {
+        let mut adaptive = enabled(1.0);
+        assert_eq!(run(&mut adaptive, 1, 0.5, RAISE_AFTER - 1), None);
+        assert_eq!(run(&mut adaptive, 100, 0.5, 1), None);
+        // The average needs a while to come back under the budget.
+        let (frame, _) = run(&mut adaptive, 1, 0.5, 200).unwrap();
+        assert!(frame > RAISE_AFTER);
+    }
+
+    #[test]
+    fn never_rises_above_the_ceiling() {
+        let mut adaptive = enabled(0.75);
+        assert_eq!(run(&mut adaptive, 1, 0.75, 500), None);
+        let mut adaptive = enabled(0.7);
+        assert_eq!(
+            run(&mut adaptive, 1, 0.625, 200),
+            Some((RAISE_AFTER, RenderScale::new(0.7)))
+        );
+    }
+
+    #[test]
+    fn never_drops_below_the_minimum_scale() {
+        let mut adaptive = enabled(1.0);
+        assert_eq!(
+            run(&mut adaptive, 100, 1.0, 100),
+            Some((LOWER_AFTER, RenderScale::new(*RenderScale::RANGE.start())))
+        );
+        assert_eq!(run(&mut adaptive, 100, 0.5, 500), None);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::camera::Camera;
+
+/// Size in physical pixels, as the surface and render targets are sized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SurfaceSize {
+    pub fn is_zero(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+        LogicalSize {
+            width: f64::from(self.width) / scale_factor,
+            height: f64::from(self.height) / scale_factor,
+        }
+    }
+}
+
+/// Size in logical (DPI-independent) pixels, as windows are created and UI
+/// layouts are specified; one logical pixel is `scale_factor` physical ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogicalSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LogicalSize {
+    /// Rounded like winit, so both sides agree on the surface size.
+    pub fn to_physical(self, scale_factor: f64) -> SurfaceSize {
+        SurfaceSize {
+            width: (self.width * scale_factor).round() as u32,
+            height: (self.height * scale_factor).round() as u32,
+        }
+    }
+}
+
+/// Window size hosts create the window with, before any resize.
+pub const DEFAULT_WINDOW_SIZE: LogicalSize = LogicalSize {
+    width: 1280.0,
+    height: 720.0,
+};
+
+/// Anything whose resources depend on the surface size: the surface config,
+/// depth buffer, offscreen targets, the camera aspect.
+pub trait ResizeTarget {
+    fn resize(&mut self, size: SurfaceSize);
+}
+
+impl ResizeTarget for Camera {
+    fn resize(&mut self, size: SurfaceSize) {
+        self.set_viewport_size(size.width, size.height);
+    }
+}
+
+/// Collects resize events so the surface is reconfigured at most once per frame,
+/// and never with a zero size. Sizes are physical; the window's scale factor
+/// is kept alongside for converting to logical pixels.
+#[derive(Clone, Copy, Debug)]
+pub struct ResizeCoalescer {
+    current: SurfaceSize,
+    pending: Option<SurfaceSize>,
+    scale_factor: f64,
+}
+
+impl ResizeCoalescer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            current: SurfaceSize { width, height },
+            pending: None,
+            scale_factor: 1.0,
+        }
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// A `ScaleFactorChanged` event: the window moved to a monitor with a
+    /// different DPI, or the user changed it. `width` and `height` are the
+    /// new physical size the window system settled on. Non-finite or
+    /// non-positive factors are ignored.
+    pub fn request_scale_factor(&mut self, scale_factor: f64, width: u32, height: u32) {
+        if scale_factor.is_finite() && scale_factor > 0.0 {
+            self.scale_factor = scale_factor;
+        }
+        self.request(width, height);
+    }
+
+    pub fn request(&mut self, width: u32, height: u32) {
+        self.pending = Some(SurfaceSize { width, height });
+    }
+
+    pub fn current(&self) -> SurfaceSize {
+        self.current
+    }
+
+    /// True while the window is minimized; rendering should be skipped.
+    pub fn is_minimized(&self) -> bool {
+        self.pending.unwrap_or(self.current).is_zero()
+    }
+
+    /// Applies the latest requested size to every target, once. A zero-sized
+    /// request is held back until a real size arrives.
+    pub fn apply(&mut self, targets: &mut [&mut dyn ResizeTarget]) -> Option<SurfaceSize> {
+        let size = self.pending?;
+        if size.is_zero() {
+            return None;
+        }
+        self.pending = None;
+        if size == self.current {
+            return None;
+        }
+        self.current = size;
+        for target in targets.iter_mut() {
+            target.resize(size);
+        }
+        Some(size)
+    }
+}
@@ -0,0 +1,276 @@
+use std::sync::{Arc, LazyLock};
+
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::rendering::preprocessor::{self, ComposedShader, PreprocessError};
+
+/// Compute and body-render entry points, before preprocessing; see `src/shaders`.
+pub const SHADER_SOURCE: &str = include_str!("../shader.wgsl");
+
+/// Uniform-grid build passes for neighbour queries; see `simulation::grid`.
+pub const GRID_SHADER_SOURCE: &str = include_str!("../grid.wgsl");
+
+/// Binning passes behind the diagnostics panel; see `simulation::plots`.
+pub const PLOTS_SHADER_SOURCE: &str = include_str!("../plots.wgsl");
+
+/// Fullscreen passes over the rendered frame, such as the debug overlay.
+pub const POST_SHADER_SOURCE: &str = include_str!("../post.wgsl");
+
+/// Modules the shaders pull in with `#include "name"`: every file in
+/// `src/shaders`, listed by `build.rs`.
+pub const SHADER_MODULES: &[(&str, &str)] =
+    include!(concat!(env!("OUT_DIR"), "/shader_modules.rs"));
+
+/// The built-in shaders with their includes resolved, composed on first use.
+static BUILTIN: LazyLock<[String; ShaderId::ALL.len()]> = LazyLock::new(|| {
+    ShaderId::ALL.map(|shader| {
+        preprocessor::compose(shader.label(), shader.root_source(), SHADER_MODULES)
+            .unwrap_or_else(|error| panic!("{error}"))
+            .source
+    })
+});
+
+/// Values for the shaders' `override` constants, passed as the pipeline
+/// compilation constants when a pipeline is created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PipelineConstants {
+    pub workgroup_size: u32,
+    pub spin_markers: bool,
+    pub contacts: bool,
+    pub post_newtonian: bool,
+}
+
+impl Default for PipelineConstants {
+    fn default() -> Self {
+        Self {
+            workgroup_size: 64,
+            spin_markers: true,
+            contacts: true,
+            post_newtonian: true,
+        }
+    }
+}
+
+impl PipelineConstants {
+    /// Name and value of every override `shader` declares.
+    pub fn entries(&self, shader: ShaderId) -> Vec<(&'static str, f64)> {
+        if shader == ShaderId::Post {
+            return Vec::new();
+        }
+        let mut entries = vec![("WORKGROUP_SIZE", f64::from(self.workgroup_size))];
+        if shader == ShaderId::Bodies {
+            entries.extend([
+                ("SPIN_MARKERS", f64::from(u8::from(self.spin_markers))),
+                ("CONTACTS", f64::from(u8::from(self.contacts))),
+                ("POST_NEWTONIAN", f64::from(u8::from(self.post_newtonian))),
+            ]);
+        }
+        entries
+    }
+
+    /// Workgroups covering `items` one-thread-per-item invocations.
+    pub fn workgroups(&self, items: u32) -> u32 {
+        items.div_ceil(self.workgroup_size)
+    }
+}
+
+/// Shader modules the pipelines are built from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderId {
+    Bodies,
+    Grid,
+    Plots,
+    Post,
+}
+
+impl ShaderId {
+    pub const ALL: [Self; 4] = [Self::Bodies, Self::Grid, Self::Plots, Self::Post];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bodies => "shader.wgsl",
+            Self::Grid => "grid.wgsl",
+            Self::Plots => "plots.wgsl",
+            Self::Post => "post.wgsl",
+        }
+    }
+
+    /// Entry file, before its includes are resolved.
+    pub fn root_source(self) -> &'static str {
+        match self {
+            Self::Bodies => SHADER_SOURCE,
+            Self::Grid => GRID_SHADER_SOURCE,
+            Self::Plots => PLOTS_SHADER_SOURCE,
+            Self::Post => POST_SHADER_SOURCE,
+        }
+    }
+
+    /// Source compiled into the binary, includes resolved.
+    pub fn builtin_source(self) -> &'static str {
+        &BUILTIN[self.slot()]
+    }
+
+    fn slot(self) -> usize {
+        self as usize
+    }
+}
+
+/// Where and why a shader source was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    pub message: String,
+    /// One-based line and column of the first labelled span, if any.
+    pub location: Option<(u32, u32)>,
+    /// Full report with the offending source lines underlined, as naga prints it.
+    pub report: String,
+}
+
+impl ShaderDiagnostic {
+    fn without_location(message: String) -> Self {
+        Self {
+            report: message.clone(),
+            message,
+            location: None,
+        }
+    }
+}
+
+/// Resolves the includes of `text`, an edited root file of `shader`.
+fn compose(shader: ShaderId, text: &str) -> Result<ComposedShader, ShaderDiagnostic> {
+    preprocessor::compose(shader.label(), text, SHADER_MODULES).map_err(|error| {
+        let location = match &error {
+            PreprocessError::UnknownModule { line, .. }
+            | PreprocessError::MalformedDirective { line, .. } => Some((*line, 1)),
+        };
+        ShaderDiagnostic {
+            message: error.to_string(),
+            location,
+            report: error.to_string(),
+        }
+    })
+}
+
+/// Preprocesses, parses and validates `text` as a replacement root file for
+/// `shader`. Besides naga's own checks, every entry point of the built-in
+/// shader must still exist, since the pipelines look them up by name.
+pub fn validate(shader: ShaderId, text: &str) -> Result<(), ShaderDiagnostic> {
+    let composed = compose(shader, text)?;
+    let source = composed.source.as_str();
+    // Errors inside an included module are named after it instead of
+    // pointing into the edited text.
+    let diagnostic =
+        |message: String, location: Option<naga::SourceLocation>, report| match location.and_then(
+            |location| {
+                let (file, line) = composed.origin(location.line_number)?;
+                Some((file, line, location.line_position))
+            },
+        ) {
+            Some((file, line, column)) if file == shader.label() => ShaderDiagnostic {
+                message,
+                location: Some((line, column)),
+                report,
+            },
+            Some((file, line, _)) => ShaderDiagnostic {
+                message: format!("{message} (in {file}:{line})"),
+                location: None,
+                report,
+            },
+            None => ShaderDiagnostic {
+                message,
+                location: None,
+                report,
+            },
+        };
+    let module = naga::front::wgsl::parse_str(source).map_err(|error| {
+        diagnostic(
+            error.message().to_owned(),
+            error.location(source),
+            error.emit_to_string(source),
+        )
+    })?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|error| {
+        diagnostic(
+            error.as_inner().to_string(),
+            error.location(source),
+            error.emit_to_string(source),
+        )
+    })?;
+
+    let builtin = naga::front::wgsl::parse_str(shader.builtin_source())
+        .expect("build.rs validates the built-in shaders");
+    for expected in &builtin.entry_points {
+        let found = module
+            .entry_points
+            .iter()
+            .any(|entry| entry.name == expected.name && entry.stage == expected.stage);
+        if !found {
+            return Err(ShaderDiagnostic::without_location(format!(
+                "missing {:?} entry point `{}`",
+                expected.stage, expected.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// An edited root file and its composed source.
+#[derive(Clone, Debug)]
+struct EditedShader {
+    text: Arc<str>,
+    source: String,
+}
+
+/// Current source of every shader module. Replacing a source bumps its
+/// revision, which is the renderer's cue to rebuild the pipelines built from
+/// that module.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderSources {
+    edited: [Option<EditedShader>; ShaderId::ALL.len()],
+    revisions: [u64; ShaderId::ALL.len()],
+}
+
+impl ShaderSources {
+    /// Root file of `shader` as written, includes unresolved; what the editor shows.
+    pub fn text(&self, shader: ShaderId) -> &str {
+        self.edited[shader.slot()]
+            .as_ref()
+            .map_or(shader.root_source(), |edited| &edited.text)
+    }
+
+    /// Composed source to build the pipelines from.
+    pub fn source(&self, shader: ShaderId) -> &str {
+        self.edited[shader.slot()]
+            .as_ref()
+            .map_or(shader.builtin_source(), |edited| &edited.source)
+    }
+
+    pub fn revision(&self, shader: ShaderId) -> u64 {
+        self.revisions[shader.slot()]
+    }
+
+    pub fn is_modified(&self, shader: ShaderId) -> bool {
+        self.edited[shader.slot()].is_some()
+    }
+
+    /// Swaps in an edited root file, which must have passed `validate`; `None`
+    /// goes back to the built-in source.
+    pub fn replace(&mut self, shader: ShaderId, text: Option<Arc<str>>) {
+        self.edited[shader.slot()] = text.and_then(|text| {
+            let source = compose(shader, &text).ok()?.source;
+            Some(EditedShader { text, source })
+        });
+        self.revisions[shader.slot()] += 1;
+    }
+}
+
+impl EventHandler for ShaderSources {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if let AppEvent::ShaderChanged { shader, text } = event {
+            self.replace(*shader, text.clone());
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use super::texture::TextureFormat;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// 8-bit output with hardware sRGB encoding.
+    Srgb,
+    /// Extended-range float output.
+    Hdr,
+    /// No sRGB encoding available; shaders must encode gamma themselves.
+    Linear,
+}
+
+/// Surface configuration picked from the formats the surface reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceFormatChoice {
+    /// Format the surface is configured with.
+    pub format: TextureFormat,
+    /// Format render passes view the surface texture through, when it differs.
+    pub view_format: Option<TextureFormat>,
+    pub color_space: ColorSpace,
+}
+
+impl SurfaceFormatChoice {
+    /// Format pipelines must be built against.
+    pub fn render_format(&self) -> TextureFormat {
+        self.view_format.unwrap_or(self.format)
+    }
+}
+
+impl fmt::Display for SurfaceFormatChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:?})", self.format, self.color_space)?;
+        if let Some(view) = self.view_format {
+            write!(f, " viewed as {view}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Prefers HDR when asked and available, then a native sRGB format, then a linear
+/// format viewed through its sRGB twin, and only then plain linear output.
+///
+/// `view_formats_supported` says whether the device allows surface view formats;
+/// without it the sRGB-suffix view trick is not valid.
+pub fn select_surface_format(
+    available: &[TextureFormat],
+    prefer_hdr: bool,
+    view_formats_supported: bool,
+) -> Option<SurfaceFormatChoice> {
+    if prefer_hdr && let Some(&format) = available.iter().find(|format| format.is_hdr()) {
+        return Some(SurfaceFormatChoice {
+            format,
+            view_format: None,
+            color_space: ColorSpace::Hdr,
+        });
+    }
+
+    if let Some(&format) = available.iter().find(|format| format.is_srgb()) {
+        return Some(SurfaceFormatChoice {
+            format,
+            view_format: None,
+            color_space: ColorSpace::Srgb,
+        });
+    }
+
+    if view_formats_supported
+        && let Some(&format) = available
+            .iter()
+            .find(|format| format.add_srgb_suffix().is_srgb())
+    {
+        return Some(SurfaceFormatChoice {
+            format,
+            view_format: Some(format.add_srgb_suffix()),
+            color_space: ColorSpace::Srgb,
+        });
+    }
+
+    available
+        .iter()
+        .find(|format| !format.is_hdr())
+        .or(available.first())
+        .map(|&format| SurfaceFormatChoice {
+            format,
+            view_format: None,
+            color_space: if format.is_hdr() {
+                ColorSpace::Hdr
+            } else {
+                ColorSpace::Linear
+            },
+        })
+}
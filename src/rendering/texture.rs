@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Texture formats used by the render targets, named after their wgpu counterparts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextureFormat {
+    Rgba8Unorm,
+    Rgba8UnormSrgb,
+    Bgra8Unorm,
+    Bgra8UnormSrgb,
+    Rgb10a2Unorm,
+    Rgba16Float,
+    R32Uint,
+    Depth32Float,
+}
+
+impl TextureFormat {
+    pub fn is_srgb(self) -> bool {
+        matches!(self, Self::Rgba8UnormSrgb | Self::Bgra8UnormSrgb)
+    }
+
+    pub fn is_hdr(self) -> bool {
+        matches!(self, Self::Rgba16Float | Self::Rgb10a2Unorm)
+    }
+
+    /// The sRGB twin of a linear 8-bit format, if it has one.
+    pub fn add_srgb_suffix(self) -> Self {
+        match self {
+            Self::Rgba8Unorm => Self::Rgba8UnormSrgb,
+            Self::Bgra8Unorm => Self::Bgra8UnormSrgb,
+            other => other,
+        }
+    }
+
+    pub fn remove_srgb_suffix(self) -> Self {
+        match self {
+            Self::Rgba8UnormSrgb => Self::Rgba8Unorm,
+            Self::Bgra8UnormSrgb => Self::Bgra8Unorm,
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for TextureFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureDesc {
+    pub width: u32,
+    pub height: u32,
+    pub format: TextureFormat,
+}
@@ -0,0 +1,67 @@
+use bytemuck::{Pod, Zeroable};
+
+use super::config::RenderConfig;
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use super::surface::ColorSpace;
+use super::texture::{TextureDesc, TextureFormat};
+
+/// Format of the frame the body pass renders into, so additive overlaps can
+/// exceed 1.0 until the tone-mapping pass compresses them.
+pub const HDR_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Gamma the hardware applies when the surface encodes sRGB itself.
+const SRGB_GAMMA: f32 = 2.2;
+
+/// Declares the HDR frame; the body pass writes it instead of the surface.
+/// Pass the internal size from `RenderScale::internal_size`, which every
+/// target up to the tone-mapping pass shares.
+pub fn create_hdr_target(graph: &mut RenderGraph, width: u32, height: u32) -> ResourceHandle {
+    graph.create_texture(
+        "hdr_frame",
+        TextureDesc {
+            width,
+            height,
+            format: HDR_FORMAT,
+        },
+    )
+}
+
+/// The resolve pass: `post.wgsl`'s `fs_tone_map` reading `hdr` and writing
+/// `surface`, bilinearly resampling when the two sizes differ. Overlays that
+/// should not be tone mapped are added after it.
+pub fn add_tone_map_pass(
+    graph: &mut RenderGraph,
+    hdr: ResourceHandle,
+    surface: ResourceHandle,
+) -> PassHandle {
+    graph.add_pass("tone_map", &[hdr], &[surface])
+}
+
+/// Uniform block of `fs_tone_map`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ToneMapUniforms {
+    pub exposure: f32,
+    pub gamma_power: f32,
+    /// `ToneMapper` as `u32`.
+    pub tone_mapper: u32,
+    pub _padding: u32,
+}
+
+impl ToneMapUniforms {
+    /// Folds the surface's own encoding into the gamma exponent, so the
+    /// displayed result is `mapped^(1 / gamma)` on every surface.
+    pub fn new(config: &RenderConfig, color_space: ColorSpace) -> Self {
+        let config = config.sanitized();
+        let gamma_power = match color_space {
+            ColorSpace::Linear => 1.0 / config.gamma,
+            ColorSpace::Srgb | ColorSpace::Hdr => SRGB_GAMMA / config.gamma,
+        };
+        Self {
+            exposure: config.exposure,
+            gamma_power,
+            tone_mapper: config.tone_mapper as u32,
+            _padding: 0,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use glam::Vec3;
+
+use super::config::TrailConfig;
+use super::graph::{PassHandle, RenderGraph, ResourceHandle};
+use super::reference::LineVertex;
+use crate::camera::Camera;
+use crate::simulation::{Body, BodyGroup};
+
+/// Triangle list of camera-facing ribbons along each trail in `trails`
+/// (oldest position first, parallel to `bodies` and `flags`), for bodies in
+/// `config.groups`. Ribbons are `config.width` logical pixels wide at any
+/// depth and fade with `config.fade` from the body back, in the body's color.
+/// `viewport_height` is in physical pixels and `ui_scale` is physical per
+/// logical pixel.
+pub fn trail_ribbons(
+    trails: &[VecDeque<Vec3>],
+    bodies: &[Body],
+    flags: &[u32],
+    config: &TrailConfig,
+    camera: &Camera,
+    viewport_height: f32,
+    ui_scale: f32,
+) -> Vec<LineVertex> {
+    let mut vertices = Vec::new();
+    if viewport_height <= 0.0 {
+        return vertices;
+    }
+    let eye = camera.eye();
+    let forward = (camera.target - eye).normalize_or_zero();
+    // World length of one logical pixel at unit depth.
+    let pixel_at_unit_depth = 2.0 * (0.5 * camera.fovy).tan() * ui_scale / viewport_height;
+    for ((trail, body), &flags) in trails.iter().zip(bodies).zip(flags) {
+        if trail.len() < 2 || !config.groups.contains(BodyGroup::from_flags(flags)) {
+            continue;
+        }
+        let last = (trail.len() - 1) as f32;
+        // Each sample's two ribbon edges; `None` behind the near plane.
+        let edges: Vec<Option<[LineVertex; 2]>> = trail
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| {
+                let depth = (position - eye).dot(forward);
+                if !position.is_finite() || depth <= camera.znear {
+                    return None;
+                }
+                let along = match (index.checked_sub(1), trail.get(index + 1)) {
+                    (_, Some(&next)) => next - position,
+                    (Some(previous), None) => position - trail[previous],
+                    (None, None) => return None,
+                };
+                let side = along.cross(position - eye).normalize_or_zero();
+                let half_width = 0.5 * config.width * pixel_at_unit_depth * depth;
+                let mut color = body.color.to_array();
+                color[3] *= config.fade.opacity(1.0 - index as f32 / last);
+                Some([
+                    LineVertex {
+                        position: position - side * half_width,
+                        color,
+                    },
+                    LineVertex {
+                        position: position + side * half_width,
+                        color,
+                    },
+                ])
+            })
+            .collect();
+        for pair in edges.windows(2) {
+            if let [Some([a0, a1]), Some([b0, b1])] = *pair {
+                vertices.extend_from_slice(&[a0, a1, b1, a0, b1, b0]);
+            }
+        }
+    }
+    vertices
+}
+
+/// The trail pass: `vs_reference`/`fs_reference` over the ribbon triangle
+/// list into `target`, alpha-blended after the bodies.
+pub fn add_trail_pass(graph: &mut RenderGraph, target: ResourceHandle) -> PassHandle {
+    graph.add_pass("trails", &[], &[target])
+}
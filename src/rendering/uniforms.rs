@@ -0,0 +1,90 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec2, Vec3, Vec4};
+
+use super::palette::ColorPalette;
+use crate::camera::Camera;
+use crate::simulation::GroupMask;
+
+/// Per-frame uniform block for the body render pipeline.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct RenderUniforms {
+    pub view_projection: Mat4,
+    pub camera_right: Vec3,
+    /// Bodies whose flags share no bit with this mask are culled in the vertex shader.
+    pub visibility_mask: u32,
+    pub camera_up: Vec3,
+    /// `ColorMode` as `u32`.
+    pub color_mode: u32,
+    /// Surface size in pixels.
+    pub viewport_size: Vec2,
+    /// Billboards are never drawn smaller than this radius in pixels; ones
+    /// that would be fade instead, so distant bodies stay single steady pixels.
+    pub min_pixel_radius: f32,
+    pub _padding: f32,
+    /// `ColorPalette::speed_colormap`.
+    pub speed_colormap: [Vec4; 3],
+    /// `ColorPalette::kind_colors`.
+    pub kind_colors: [Vec4; 5],
+}
+
+/// Default `RenderUniforms::min_pixel_radius`: enough to always cover one pixel center.
+pub const MIN_PIXEL_RADIUS: f32 = 0.75;
+
+/// How the body billboards are tinted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// The colors the scenario gave its bodies.
+    #[default]
+    Body,
+    /// Along the palette's speed colormap, slow to fast.
+    Speed,
+    /// One hue per `BodyKind`, from the palette.
+    Kind,
+}
+
+impl ColorMode {
+    pub const ALL: [Self; 3] = [Self::Body, Self::Speed, Self::Kind];
+}
+
+impl RenderUniforms {
+    pub fn new(
+        camera: &Camera,
+        visible_groups: GroupMask,
+        color_mode: ColorMode,
+        palette: ColorPalette,
+        viewport_size: Vec2,
+    ) -> Self {
+        Self::from_matrices(
+            camera.view_matrix(),
+            camera.projection_matrix(),
+            visible_groups,
+            color_mode,
+            palette,
+            viewport_size,
+        )
+    }
+
+    /// For views that are not an orbit `Camera`, e.g. one eye of a headset.
+    pub fn from_matrices(
+        view: Mat4,
+        projection: Mat4,
+        visible_groups: GroupMask,
+        color_mode: ColorMode,
+        palette: ColorPalette,
+        viewport_size: Vec2,
+    ) -> Self {
+        Self {
+            view_projection: projection * view,
+            camera_right: view.row(0).truncate(),
+            visibility_mask: visible_groups.0,
+            camera_up: view.row(1).truncate(),
+            color_mode: color_mode as u32,
+            viewport_size,
+            min_pixel_radius: MIN_PIXEL_RADIUS,
+            _padding: 0.0,
+            speed_colormap: palette.speed_colormap(),
+            kind_colors: palette.kind_colors(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::ops::Range;
+
+/// Opaque id of a GPU buffer owned by the renderer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferId(pub u32);
+
+/// Destination of flushed uploads; implemented on top of `queue.write_buffer`.
+pub trait UploadSink {
+    fn write_buffer(&mut self, buffer: BufferId, offset: u64, data: &[u8]);
+}
+
+#[derive(Clone, Debug)]
+struct PendingWrite {
+    buffer: BufferId,
+    offset: u64,
+    staging: Range<usize>,
+}
+
+/// Ring of staging memory for buffer writes. Writes are recorded during the frame
+/// and flushed once; the staging allocation is kept between frames so swapping
+/// large scenarios does not allocate a fresh vector per write.
+#[derive(Debug, Default)]
+pub struct UploadBelt {
+    staging: Vec<u8>,
+    pending: Vec<PendingWrite>,
+}
+
+impl UploadBelt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            staging: Vec::with_capacity(bytes),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn write<T: bytemuck::Pod>(&mut self, buffer: BufferId, offset: u64, data: &[T]) {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        if bytes.is_empty() {
+            return;
+        }
+        let start = self.staging.len();
+        self.staging.extend_from_slice(bytes);
+        let end = self.staging.len();
+
+        // Contiguous writes to the same buffer collapse into one upload.
+        if let Some(last) = self.pending.last_mut() {
+            let last_end = last.offset + (last.staging.end - last.staging.start) as u64;
+            if last.buffer == buffer && last_end == offset && last.staging.end == start {
+                last.staging.end = end;
+                return;
+            }
+        }
+        self.pending.push(PendingWrite {
+            buffer,
+            offset,
+            staging: start..end,
+        });
+    }
+
+    pub fn pending_bytes(&self) -> usize {
+        self.staging.len()
+    }
+
+    /// Hands every recorded write to `sink` and recycles the staging memory.
+    pub fn flush(&mut self, sink: &mut impl UploadSink) {
+        profiling::scope!("submit_uploads");
+        for write in self.pending.drain(..) {
+            sink.write_buffer(write.buffer, write.offset, &self.staging[write.staging]);
+        }
+        self.staging.clear();
+    }
+}
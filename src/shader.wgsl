@@ -0,0 +1,14 @@
+// Body pipelines: compute passes, billboard vertex stage and fragment shading,
+// composed from `src/shaders` by `rendering::preprocessor`. Tune them through
+// the `override` constants (`PipelineConstants`) rather than by editing here.
+
+#include "integrator.wgsl"
+#include "evolution.wgsl"
+#include "shading.wgsl"
+#include "halo.wgsl"
+#include "accretion_disk.wgsl"
+#include "lod.wgsl"
+#include "mesh.wgsl"
+#include "reference.wgsl"
+#include "reference_frame.wgsl"
+#include "minimap.wgsl"
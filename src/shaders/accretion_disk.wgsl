@@ -0,0 +1,104 @@
+// Parametric accretion disk: a camera-independent quad in the disk plane,
+// cut to an annulus and shaded procedurally; mirrors `rendering::accretion_disk`.
+
+#include "bindings.wgsl"
+
+struct AccretionDiskUniforms {
+    center: vec3<f32>,
+    inner_radius: f32,
+    // In-plane unit axes; the disk orbits from `axis_u` towards `axis_v`.
+    axis_u: vec3<f32>,
+    outer_radius: f32,
+    axis_v: vec3<f32>,
+    inner_temperature: f32,
+    inner_angular_speed: f32,
+    beaming: f32,
+    time: f32,
+    _padding: f32,
+}
+
+@group(0) @binding(13) var<uniform> disk: AccretionDiskUniforms;
+
+// Angular cells of the streak pattern; the noise wraps around the disk.
+const DISK_CELLS: u32 = 48u;
+
+struct DiskOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // In simulation units from the center, along `axis_u` and `axis_v`.
+    @location(0) local: vec2<f32>,
+}
+
+@vertex
+fn vs_accretion_disk(@builtin(vertex_index) vertex_index: u32) -> DiskOutput {
+    var quad = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    var out: DiskOutput;
+    out.local = quad[vertex_index] * disk.outer_radius;
+    let position = disk.center + disk.axis_u * out.local.x + disk.axis_v * out.local.y;
+    out.clip_position = render.view_projection * vec4<f32>(position, 1.0);
+    return out;
+}
+
+// Relative RGB of a black body at `kelvin`: Planck's law at three wavelengths,
+// normalized to the brightest.
+fn blackbody(kelvin: f32) -> vec3<f32> {
+    let wavelengths = vec3<f32>(610e-9, 550e-9, 465e-9);
+    // hc / k in metre-kelvin.
+    let x = 1.4388e-2 / (wavelengths * max(kelvin, 800.0));
+    let radiance = 1.0 / (pow(wavelengths * 1e6, vec3<f32>(5.0)) * (exp(x) - 1.0));
+    return radiance / max(max(radiance.r, radiance.g), radiance.b);
+}
+
+fn hash2(cell: vec2<u32>) -> f32 {
+    var h = cell.x * 1597334677u ^ cell.y * 3812015801u;
+    h = (h ^ (h >> 16u)) * 2246822519u;
+    return f32(h >> 8u) / 16777216.0;
+}
+
+// Value noise in (radius, angle) cells, wrapping in angle.
+fn disk_noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = p - cell;
+    let t = f * f * (3.0 - 2.0 * f);
+    let r = u32(max(cell.x, 0.0));
+    let a0 = u32(cell.y) % DISK_CELLS;
+    let a1 = (a0 + 1u) % DISK_CELLS;
+    let low = mix(hash2(vec2<u32>(r, a0)), hash2(vec2<u32>(r, a1)), t.y);
+    let high = mix(hash2(vec2<u32>(r + 1u, a0)), hash2(vec2<u32>(r + 1u, a1)), t.y);
+    return mix(low, high, t.x);
+}
+
+@fragment
+fn fs_accretion_disk(in: DiskOutput) -> @location(0) vec4<f32> {
+    let r = length(in.local);
+    if r < disk.inner_radius || r > disk.outer_radius {
+        discard;
+    }
+    let x = r / disk.inner_radius;
+    // Keplerian shear: each ring turns at its own rate, winding the pattern up.
+    let angular_speed = disk.inner_angular_speed * pow(x, -1.5);
+    let angle = atan2(in.local.y, in.local.x) - angular_speed * disk.time;
+    let cells = fract(angle / 6.2831853) * f32(DISK_CELLS);
+    let streaks = 0.55 + 0.45 * disk_noise(vec2<f32>(x * 6.0, cells))
+        * (0.6 + 0.4 * disk_noise(vec2<f32>(x * 20.0, cells * 2.0)));
+
+    // Fake Doppler beaming: the orbital velocity's component towards the
+    // viewer shifts the temperature and boosts brightness as D³.
+    let radial = (disk.axis_u * in.local.x + disk.axis_v * in.local.y) / r;
+    let normal = cross(disk.axis_u, disk.axis_v);
+    let toward_viewer = cross(render.camera_right, render.camera_up);
+    let approach = dot(cross(normal, radial), toward_viewer) * inverseSqrt(x);
+    let doppler = max(1.0 + disk.beaming * approach, 0.05);
+
+    let temperature = disk.inner_temperature * pow(x, -0.75) * doppler;
+    // Falls off as T² rather than T⁴, so the outer disk stays visible.
+    let brightness = pow(x, -1.5) * doppler * doppler * doppler;
+    let edges = smoothstep(1.0, 1.15, x) * (1.0 - smoothstep(0.8 * disk.outer_radius, disk.outer_radius, r));
+    return vec4<f32>(blackbody(temperature) * brightness * streaks, edges);
+}
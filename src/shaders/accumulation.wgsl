@@ -0,0 +1,27 @@
+// Temporal accumulation: the previous accumulated frame fades by `decay` and
+// the current one is laid over it, leaving streaks behind moving bodies;
+// mirrors `rendering::accumulation`.
+
+struct AccumulationUniforms {
+    decay: f32,
+    // Non-zero on the first frame after a switch or resize: history is stale.
+    reset: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(8) var<uniform> accumulation: AccumulationUniforms;
+@group(0) @binding(9) var accumulation_current: texture_2d<f32>;
+@group(0) @binding(10) var accumulation_previous: texture_2d<f32>;
+
+@fragment
+fn fs_accumulate(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = vec2<i32>(position.xy);
+    let current = textureLoad(accumulation_current, texel, 0);
+    if accumulation.reset != 0u {
+        return current;
+    }
+    // The maximum rather than a sum, so bodies standing still keep their
+    // brightness and only what moved leaves a fading trail.
+    let previous = textureLoad(accumulation_previous, texel, 0) * accumulation.decay;
+    return max(current, previous);
+}
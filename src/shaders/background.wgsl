@@ -0,0 +1,66 @@
+// Scene background: clear color, a procedural starfield fixed to the sky and
+// an optional equirectangular backdrop; mirrors `rendering::background`.
+
+struct BackgroundUniforms {
+    inverse_view_projection: mat4x4<f32>,
+    clear_color: vec3<f32>,
+    starfield_density: f32,
+    backdrop_brightness: f32,
+    _padding: vec3<u32>,
+}
+
+@group(0) @binding(11) var<uniform> background: BackgroundUniforms;
+@group(0) @binding(12) var background_image: texture_2d<f32>;
+@group(0) @binding(13) var background_sampler: sampler;
+
+// Sky cells per radian of longitude; latitude cells are as tall.
+const STAR_CELLS: f32 = 160.0;
+const PI: f32 = 3.14159265;
+
+fn star_hash(cell: vec2<f32>) -> vec3<f32> {
+    var p = fract(vec3<f32>(cell.xyx) * vec3<f32>(0.1031, 0.1030, 0.0973));
+    p += dot(p, p.yxz + 33.33);
+    return fract((p.xxy + p.yzz) * p.zyx);
+}
+
+// Longitude and latitude of a world direction.
+fn sky_coordinates(direction: vec3<f32>) -> vec2<f32> {
+    return vec2<f32>(atan2(direction.z, direction.x), asin(clamp(direction.y, -1.0, 1.0)));
+}
+
+fn starfield(sky: vec2<f32>) -> vec3<f32> {
+    let scaled = sky * STAR_CELLS;
+    let cell = floor(scaled);
+    let random = star_hash(cell);
+    if random.x >= background.starfield_density {
+        return vec3<f32>(0.0);
+    }
+    // A small disc at a random spot in the cell. Cells near the poles cover
+    // less sky, so stars bunch up slightly there.
+    let center = cell + 0.2 + 0.6 * random.yz;
+    let falloff = exp(-8.0 * dot(scaled - center, scaled - center));
+    let brightness = 0.3 + 0.7 * random.y * random.y;
+    // Cool and warm stars around white.
+    let tint = mix(vec3<f32>(1.0, 0.85, 0.7), vec3<f32>(0.75, 0.85, 1.0), random.z);
+    return tint * brightness * falloff;
+}
+
+@fragment
+fn fs_background(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    let ndc = vec2<f32>(in.uv.x * 2.0 - 1.0, 1.0 - in.uv.y * 2.0);
+    let near = background.inverse_view_projection * vec4<f32>(ndc, 0.0, 1.0);
+    let far = background.inverse_view_projection * vec4<f32>(ndc, 1.0, 1.0);
+    let direction = normalize(far.xyz / far.w - near.xyz / near.w);
+    let sky = sky_coordinates(direction);
+
+    var color = background.clear_color;
+    if background.backdrop_brightness > 0.0 {
+        let uv = vec2<f32>(sky.x / (2.0 * PI) + 0.5, 0.5 - sky.y / PI);
+        color += textureSampleLevel(background_image, background_sampler, uv, 0.0).rgb
+            * background.backdrop_brightness;
+    }
+    if background.starfield_density > 0.0 {
+        color += starfield(sky);
+    }
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,113 @@
+// Vertex stages: body billboards and the bounds wireframe.
+
+#include "bindings.wgsl"
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) kind: u32,
+    @location(3) @interpolate(flat) spin: vec4<f32>,
+    // For `fs_debug`: the body's index plus one (zero is background), its
+    // view-space depth and speed.
+    @location(4) @interpolate(flat) index: u32,
+    @location(5) view_depth: f32,
+    @location(6) @interpolate(flat) speed: f32,
+}
+
+// Tint for `render.color_mode`, keeping the body's alpha.
+fn body_color(body: Body, kind: u32) -> vec4<f32> {
+    switch render.color_mode {
+        case COLOR_SPEED: {
+            // Saturates smoothly; N-body units keep typical speeds near one.
+            let speed = length(body.velocity);
+            let t = speed / (speed + 1.0);
+            let low = mix(render.speed_colormap[0].rgb, render.speed_colormap[1].rgb, saturate(2.0 * t));
+            let color = mix(low, render.speed_colormap[2].rgb, saturate(2.0 * t - 1.0));
+            return vec4<f32>(color, body.color.a);
+        }
+        case COLOR_KIND: {
+            return vec4<f32>(render.kind_colors[min(kind, 4u)].rgb, body.color.a);
+        }
+        default: {
+            return body.color;
+        }
+    }
+}
+
+// Radius in pixels `body` would cover on screen, or zero behind the camera.
+fn projected_pixel_radius(body: Body) -> f32 {
+    let center = render.view_projection * vec4<f32>(body.position, 1.0);
+    if center.w <= 0.0 {
+        return 0.0;
+    }
+    let rim = render.view_projection * vec4<f32>(body.position + render.camera_up * body.radius, 1.0);
+    return length((rim.xy / rim.w - center.xy / center.w) * render.viewport_size * 0.5);
+}
+
+// Whether the body is culled: hidden group or never drawn.
+fn body_hidden(index: u32) -> bool {
+    return (body_flags[index] & render.visibility_mask) == 0u || body_kind(index) == KIND_DARK_MATTER;
+}
+
+// Camera-facing quad corner `vertex_index` of body `index`.
+fn billboard(vertex_index: u32, index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let kind = body_kind(index);
+    if body_hidden(index) {
+        // Outside the clip volume, so the whole quad is dropped.
+        out.clip_position = vec4<f32>(0.0, 0.0, 2.0, 1.0);
+        return out;
+    }
+
+    var quad = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let corner = quad[vertex_index];
+    let body = bodies[index];
+
+    // World-space offsets give perspective size attenuation. Below
+    // `min_pixel_radius` the quad is grown back to it and faded by the area
+    // it gained, so distant bodies stay steady pixels instead of vanishing
+    // between pixel centers.
+    var radius = body.radius;
+    var coverage = 1.0;
+    let pixels = projected_pixel_radius(body);
+    if pixels > 0.0 && pixels < render.min_pixel_radius {
+        let grow = render.min_pixel_radius / max(pixels, 1e-6);
+        radius *= grow;
+        coverage = 1.0 / (grow * grow);
+    }
+    let offset = (render.camera_right * corner.x + render.camera_up * corner.y) * radius;
+
+    out.clip_position = render.view_projection * vec4<f32>(body.position + offset, 1.0);
+    out.color = body_color(body, kind);
+    out.color.a *= coverage;
+    out.uv = corner;
+    out.kind = kind;
+    out.spin = body_spins[index];
+    out.index = index + 1u;
+    out.view_depth = out.clip_position.w;
+    out.speed = length(body.velocity);
+    return out;
+}
+
+// One camera-facing quad per body instance, pulled from the body buffer.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    return billboard(vertex_index, instance_index);
+}
+
+// Wireframe pass for the world bounds (line list, positions in a vertex buffer).
+@vertex
+fn vs_line(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return render.view_projection * vec4<f32>(position, 1.0);
+}
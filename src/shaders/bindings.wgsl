@@ -0,0 +1,126 @@
+// Uniforms, constants and bindings of the body pipelines.
+
+#include "body.wgsl"
+
+struct SimulationUniforms {
+    gravitational_constant: f32,
+    softening: f32,
+    dt: f32,
+    body_count: u32,
+    escape_radius: f32,
+    boundary_mode: u32,
+    potential_count: u32,
+    time: f32,
+    bounds_half_extent: vec3<f32>,
+    // Interactive gravity well; zero mass when inactive.
+    well_position: vec3<f32>,
+    well_mass: f32,
+    well_softening: f32,
+    // Soft-sphere contacts; zero stiffness disables them.
+    contact_stiffness: f32,
+    contact_damping: f32,
+    // First post-Newtonian correction for one body; zero 1/c² disables it.
+    post_newtonian_inv_c2: f32,
+    post_newtonian_central: u32,
+    post_newtonian_target: u32,
+    // `ForcePrecision`: plain or Neumaier-compensated pairwise sum.
+    force_precision: u32,
+    // Gyr of stellar age per unit of simulated time; zero disables `cs_evolve`.
+    stellar_gyr_per_time: f32,
+}
+
+struct Potential {
+    vector: vec3<f32>,
+    kind: u32,
+    strength: f32,
+    length: f32,
+    speed: f32,
+}
+
+// Mirrors `simulation::evolution::StarState`; zero mass for bodies that do not evolve.
+struct StarState {
+    mass: f32,
+    initial_radius: f32,
+    age: f32,
+    luminosity: f32,
+}
+
+struct ImpulseUniforms {
+    center: vec3<f32>,
+    speed: f32,
+    radius: f32,
+    source: u32,
+}
+
+const POTENTIAL_UNIFORM: u32 = 0u;
+const POTENTIAL_HARMONIC: u32 = 1u;
+const POTENTIAL_NFW: u32 = 2u;
+const POTENTIAL_BAR: u32 = 3u;
+const POTENTIAL_POINT: u32 = 4u;
+
+// Body kinds, stored in the high half of the per-body flags (`BodyKind`).
+const KIND_SHIFT: u32 = 16u;
+const KIND_STAR: u32 = 1u;
+const KIND_GAS: u32 = 2u;
+const KIND_DARK_MATTER: u32 = 3u;
+const KIND_TEST_PARTICLE: u32 = 4u;
+
+const PRECISION_COMPENSATED: u32 = 1u;
+
+const FLAG_ESCAPED: u32 = 1u;
+const FLAG_NON_FINITE: u32 = 2u;
+
+const BOUNDARY_BOUNCE: u32 = 1u;
+const BOUNDARY_WRAP: u32 = 2u;
+
+struct RenderUniforms {
+    view_projection: mat4x4<f32>,
+    camera_right: vec3<f32>,
+    visibility_mask: u32,
+    camera_up: vec3<f32>,
+    color_mode: u32,
+    viewport_size: vec2<f32>,
+    min_pixel_radius: f32,
+    // `ColorPalette`: slow, middle and fast speed colors, then one tint per
+    // body kind.
+    speed_colormap: array<vec4<f32>, 3>,
+    kind_colors: array<vec4<f32>, 5>,
+}
+
+// Mirrors `rendering::halo::HaloUniforms`.
+struct HaloUniforms {
+    emissive_mass: f32,
+    intensity: f32,
+    radius: f32,
+    max_steps: f32,
+}
+
+// `ColorMode`.
+const COLOR_BODY: u32 = 0u;
+const COLOR_SPEED: u32 = 1u;
+const COLOR_KIND: u32 = 2u;
+
+// Shared bindings.
+@group(0) @binding(5) var<storage, read> body_flags: array<u32>;
+
+// Compute bindings.
+@group(0) @binding(0) var<uniform> simulation: SimulationUniforms;
+@group(0) @binding(1) var<storage, read> bodies_in: array<Body>;
+@group(0) @binding(2) var<storage, read_write> bodies_out: array<Body>;
+@group(0) @binding(6) var<storage, read_write> escaped: array<u32>;
+@group(0) @binding(7) var<storage, read> potentials: array<Potential>;
+// Per-body input accelerations (xyz), e.g. spacecraft thrust.
+@group(0) @binding(8) var<storage, read> input_accelerations: array<vec4<f32>>;
+@group(0) @binding(9) var<uniform> impulse: ImpulseUniforms;
+@group(0) @binding(11) var<storage, read_write> stars: array<StarState>;
+
+// Render bindings.
+@group(0) @binding(3) var<uniform> render: RenderUniforms;
+@group(0) @binding(4) var<storage, read> bodies: array<Body>;
+// Per-body angular velocity (xyz) and rotation phase in radians (w).
+@group(0) @binding(10) var<storage, read> body_spins: array<vec4<f32>>;
+@group(0) @binding(12) var<uniform> halo: HaloUniforms;
+
+fn body_kind(index: u32) -> u32 {
+    return body_flags[index] >> KIND_SHIFT;
+}
@@ -0,0 +1,8 @@
+// Mirrors `simulation::Body`; shared by every pass that reads the body buffer.
+struct Body {
+    position: vec3<f32>,
+    mass: f32,
+    velocity: vec3<f32>,
+    radius: f32,
+    color: vec4<f32>,
+}
@@ -0,0 +1,68 @@
+// Channel picker over the debug attachments `fs_debug` writes; mirrors
+// `rendering::debug_targets`.
+
+struct DebugUniforms {
+    channel: u32,
+    body_count: u32,
+    max_depth: f32,
+    max_speed: f32,
+}
+
+// `DebugChannel`.
+const DEBUG_BODY_INDEX: u32 = 1u;
+const DEBUG_DEPTH: u32 = 2u;
+const DEBUG_SPEED: u32 = 3u;
+
+@group(0) @binding(0) var<uniform> debug: DebugUniforms;
+// Body index plus one; zero where no body was drawn.
+@group(0) @binding(1) var debug_body_index: texture_2d<u32>;
+// View depth, speed and coverage alpha.
+@group(0) @binding(2) var debug_aux: texture_2d<f32>;
+
+// Distinct, stable hue per body, so neighbours and overdraw stand out.
+fn index_color(index: u32) -> vec3<f32> {
+    var h = index * 747796405u + 2891336453u;
+    h = ((h >> ((h >> 28u) + 4u)) ^ h) * 277803737u;
+    h = (h >> 22u) ^ h;
+    return vec3<f32>(
+        f32(h & 255u),
+        f32((h >> 8u) & 255u),
+        f32((h >> 16u) & 255u),
+    ) / 255.0 * 0.8 + 0.2;
+}
+
+// Black through blue and green to yellow.
+fn ramp(t: f32) -> vec3<f32> {
+    let x = clamp(t, 0.0, 1.0);
+    return vec3<f32>(smoothstep(0.5, 1.0, x), smoothstep(0.2, 0.8, x), x * (1.0 - x) * 4.0);
+}
+
+@fragment
+fn fs_debug_overlay(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    // The targets are at the internal resolution, which may differ from the
+    // surface's.
+    let texel = vec2<i32>(in.uv * vec2<f32>(textureDimensions(debug_aux)));
+    let aux = textureLoad(debug_aux, texel, 0);
+    switch debug.channel {
+        case DEBUG_BODY_INDEX: {
+            let index = textureLoad(debug_body_index, texel, 0).r;
+            if index == 0u || index > debug.body_count {
+                return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+            }
+            return vec4<f32>(index_color(index - 1u), 1.0);
+        }
+        case DEBUG_DEPTH: {
+            if aux.w == 0.0 {
+                return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+            }
+            // Near is bright.
+            return vec4<f32>(vec3<f32>(1.0 - aux.x / debug.max_depth), 1.0);
+        }
+        case DEBUG_SPEED: {
+            return vec4<f32>(ramp(aux.y / debug.max_speed) * aux.w, 1.0);
+        }
+        default: {
+            return vec4<f32>(0.0);
+        }
+    }
+}
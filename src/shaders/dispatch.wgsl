@@ -0,0 +1,4 @@
+// Threads per workgroup of the one-thread-per-item compute passes. Overridden
+// at pipeline creation (`PipelineConstants`) to suit the adapter; the CPU
+// side divides the dispatch size by the same value.
+override WORKGROUP_SIZE: u32 = 64u;
@@ -0,0 +1,40 @@
+// Stellar ageing pass.
+
+#include "bindings.wgsl"
+#include "dispatch.wgsl"
+
+// Toy stellar ageing, mirroring `StellarEvolution::advance`: brightening on the
+// main sequence, then a geometric climb up the giant branch.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_evolve(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= simulation.body_count || simulation.stellar_gyr_per_time == 0.0 {
+        return;
+    }
+    var star = stars[i];
+    if star.mass <= 0.0 {
+        return;
+    }
+    star.age += abs(simulation.dt) * simulation.stellar_gyr_per_time;
+    let progress = star.age / (10.0 * pow(star.mass, -2.5));
+    var luminosity = 1.0 + 0.8 * progress;
+    var radius = 1.0 + 0.5 * progress;
+    if progress >= 1.0 {
+        let climb = min((progress - 1.0) / 0.15, 1.0);
+        luminosity = 1.8 * pow(100.0 / 1.8, climb);
+        radius = 1.5 * pow(30.0 / 1.5, climb);
+    }
+    star.luminosity = pow(star.mass, 3.5) * luminosity;
+    stars[i] = star;
+    let temperature = 5778.0 * pow(star.mass, 0.475) * pow(luminosity, 0.25) / sqrt(radius);
+    bodies_out[i].radius = star.initial_radius * radius;
+    bodies_out[i].color = vec4<f32>(blackbody_color(temperature), bodies_out[i].color.a);
+}
+
+// Planck spectrum at 610, 550 and 465 nm, normalised to the brightest channel.
+fn blackbody_color(temperature: f32) -> vec3<f32> {
+    let wavelength = vec3<f32>(0.61, 0.55, 0.465);
+    let t = max(temperature, 1000.0);
+    let radiance = pow(wavelength, vec3<f32>(-5.0)) / (exp(14388.0 / (wavelength * t)) - 1.0);
+    return radiance / max(radiance.r, max(radiance.g, radiance.b));
+}
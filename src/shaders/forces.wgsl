@@ -0,0 +1,76 @@
+// Force terms summed by `cs_main`.
+
+#include "bindings.wgsl"
+
+// Mirrors `ExternalPotential::acceleration`.
+fn external_acceleration(position: vec3<f32>, time: f32) -> vec3<f32> {
+    let g = simulation.gravitational_constant;
+    var acceleration = vec3<f32>(0.0);
+    for (var k = 0u; k < simulation.potential_count; k++) {
+        let potential = potentials[k];
+        switch potential.kind {
+            case POTENTIAL_UNIFORM: {
+                acceleration += potential.vector;
+            }
+            case POTENTIAL_HARMONIC: {
+                acceleration -= potential.strength * (position - potential.vector);
+            }
+            case POTENTIAL_NFW: {
+                let r = max(length(position), 1e-6 * potential.length);
+                let x = r / potential.length;
+                let enclosed = potential.strength * (log(1.0 + x) - x / (1.0 + x));
+                acceleration -= g * enclosed / (r * r * r) * position;
+            }
+            case POTENTIAL_BAR: {
+                let angle = potential.speed * time;
+                let tip = vec3<f32>(cos(angle), 0.0, -sin(angle)) * potential.length;
+                let softening_sq = 0.25 * potential.length * potential.length;
+                for (var side = 0; side < 2; side++) {
+                    let offset = select(-tip, tip, side == 0) - position;
+                    let distance_sq = dot(offset, offset) + softening_sq;
+                    acceleration += g * offset * (0.5 * potential.strength / (distance_sq * sqrt(distance_sq)));
+                }
+            }
+            case POTENTIAL_POINT: {
+                acceleration += point_mass(position, potential.vector, potential.strength, potential.length);
+            }
+            default: {}
+        }
+    }
+    acceleration += point_mass(position, simulation.well_position, simulation.well_mass, simulation.well_softening);
+    return acceleration;
+}
+
+// Mirrors `SoftSphere::acceleration`; `offset` runs from `body` to `other`.
+fn soft_sphere(body: Body, other: Body, offset: vec3<f32>) -> vec3<f32> {
+    let distance = length(offset);
+    let overlap = body.radius + other.radius - distance;
+    let total_mass = body.mass + other.mass;
+    if overlap <= 0.0 || distance == 0.0 || total_mass <= 0.0 {
+        return vec3<f32>(0.0);
+    }
+    let normal = offset / distance;
+    let approach = dot(other.velocity - body.velocity, normal);
+    let push = max(simulation.contact_stiffness * overlap - simulation.contact_damping * approach, 0.0);
+    return -normal * (push * other.mass / total_mass);
+}
+
+// Mirrors `PostNewtonian::acceleration`, with positions drifted to the half step.
+fn post_newtonian(body: Body, drifted: vec3<f32>, central: Body, half_dt: f32) -> vec3<f32> {
+    let r = drifted - (central.position + central.velocity * half_dt);
+    let v = body.velocity - central.velocity;
+    let distance_sq = dot(r, r);
+    if distance_sq == 0.0 {
+        return vec3<f32>(0.0);
+    }
+    let distance = sqrt(distance_sq);
+    let mu = simulation.gravitational_constant * central.mass;
+    return mu * simulation.post_newtonian_inv_c2 / (distance_sq * distance)
+        * ((4.0 * mu / distance - dot(v, v)) * r + 4.0 * dot(r, v) * v);
+}
+
+fn point_mass(position: vec3<f32>, center: vec3<f32>, mass: f32, softening: f32) -> vec3<f32> {
+    let offset = center - position;
+    let distance_sq = dot(offset, offset) + softening * softening;
+    return simulation.gravitational_constant * mass / (distance_sq * sqrt(distance_sq)) * offset;
+}
@@ -0,0 +1,71 @@
+// Additive glow around emissive bodies, drawn as a second instanced pass over
+// the same body buffer after the bodies themselves.
+
+#include "bindings.wgsl"
+
+struct HaloOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    // In units of the halo radius.
+    @location(1) uv: vec2<f32>,
+    // Body radius over halo radius: where the disk ends.
+    @location(2) @interpolate(flat) core: f32,
+    @location(3) @interpolate(flat) intensity: f32,
+}
+
+// Doublings of mass above `halo.emissive_mass`, plus one; zero below it.
+fn halo_steps(mass: f32) -> f32 {
+    if mass < halo.emissive_mass || halo.intensity <= 0.0 {
+        return 0.0;
+    }
+    return min(log2(mass / halo.emissive_mass) + 1.0, halo.max_steps);
+}
+
+@vertex
+fn vs_halo(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> HaloOutput {
+    var out: HaloOutput;
+    let body = bodies[instance_index];
+    let steps = halo_steps(body.mass);
+    if (body_flags[instance_index] & render.visibility_mask) == 0u
+        || body_kind(instance_index) != KIND_STAR
+        || steps == 0.0 {
+        out.clip_position = vec4<f32>(0.0, 0.0, 2.0, 1.0);
+        return out;
+    }
+
+    var quad = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let corner = quad[vertex_index];
+    let extent = body.radius * (1.0 + halo.radius * steps);
+    let offset = (render.camera_right * corner.x + render.camera_up * corner.y) * extent;
+
+    out.clip_position = render.view_projection * vec4<f32>(body.position + offset, 1.0);
+    out.color = body.color.rgb;
+    out.uv = corner;
+    out.core = body.radius / extent;
+    out.intensity = halo.intensity * steps / (steps + 1.0);
+    return out;
+}
+
+// Full strength over the disk, then falling off to nothing at the quad's
+// edge. Blended as `BlendMode::Additive`, so alpha scales the added color.
+@fragment
+fn fs_halo(in: HaloOutput) -> @location(0) vec4<f32> {
+    let r = length(in.uv);
+    if r >= 1.0 {
+        discard;
+    }
+    let t = clamp((r - in.core) / max(1.0 - in.core, 1e-4), 0.0, 1.0);
+    let falloff = (1.0 - t) * (1.0 - t) * exp(-3.0 * t);
+    let glow = in.intensity * select(falloff, 1.0, r < in.core);
+    return vec4<f32>(in.color, glow);
+}
@@ -0,0 +1,135 @@
+// Per-step compute passes: the integrator, escape flags and impulses.
+
+#include "bindings.wgsl"
+#include "dispatch.wgsl"
+#include "forces.wgsl"
+
+// Compile out the optional force terms; the uniforms still switch them at run time.
+override CONTACTS: bool = true;
+override POST_NEWTONIAN: bool = true;
+
+// Drift-kick-drift leapfrog in one pass: every body's half-step position is
+// recomputed on the fly, matching `simulation::physics::step` on the CPU.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= simulation.body_count {
+        return;
+    }
+
+    let half_dt = 0.5 * simulation.dt;
+    let softening_sq = simulation.softening * simulation.softening;
+    let body = bodies_in[i];
+    let drifted = body.position + body.velocity * half_dt;
+
+    var acceleration = vec3<f32>(0.0);
+    var compensation = vec3<f32>(0.0);
+    let compensated = simulation.force_precision == PRECISION_COMPENSATED;
+    var contact = vec3<f32>(0.0);
+    for (var j = 0u; j < simulation.body_count; j++) {
+        let other = bodies_in[j];
+        if j == i || other.mass == 0.0 || body_kind(j) == KIND_TEST_PARTICLE {
+            continue;
+        }
+        let offset = other.position + other.velocity * half_dt - drifted;
+        let inv_distance = inverseSqrt(dot(offset, offset) + softening_sq);
+        let term = offset * (other.mass * inv_distance * inv_distance * inv_distance);
+        if compensated {
+            // Neumaier summation, as in `physics::compensated_add`.
+            let sum = acceleration + term;
+            compensation += select(
+                (term - sum) + acceleration,
+                (acceleration - sum) + term,
+                abs(acceleration) >= abs(term),
+            );
+            acceleration = sum;
+        } else {
+            acceleration += term;
+        }
+        if CONTACTS && simulation.contact_stiffness > 0.0 {
+            contact += soft_sphere(body, other, offset);
+        }
+    }
+
+    var correction = vec3<f32>(0.0);
+    if POST_NEWTONIAN && simulation.post_newtonian_inv_c2 > 0.0 && i == simulation.post_newtonian_target {
+        correction = post_newtonian(body, drifted, bodies_in[simulation.post_newtonian_central], half_dt);
+    }
+
+    let time = simulation.time + half_dt;
+    let total = (acceleration + compensation) * simulation.gravitational_constant
+        + contact
+        + correction
+        + external_acceleration(drifted, time)
+        + input_accelerations[i].xyz;
+
+    var result = body;
+    result.velocity = body.velocity + total * simulation.dt;
+    result.position = drifted + result.velocity * half_dt;
+    bodies_out[i] = apply_bounds(result);
+}
+
+fn apply_bounds(body: Body) -> Body {
+    var result = body;
+    let h = simulation.bounds_half_extent;
+    if simulation.boundary_mode == BOUNDARY_BOUNCE {
+        let above = result.position > h;
+        let below = result.position < -h;
+        result.position = select(result.position, 2.0 * h - result.position, above);
+        result.position = select(result.position, -2.0 * h - result.position, below);
+        result.velocity = select(result.velocity, -abs(result.velocity), above);
+        result.velocity = select(result.velocity, abs(result.velocity), below);
+        result.position = clamp(result.position, -h, h);
+    } else if simulation.boundary_mode == BOUNDARY_WRAP {
+        let size = 2.0 * h;
+        let shifted = result.position + h;
+        result.position = shifted - size * floor(shifted / size) - h;
+    }
+    return result;
+}
+
+// Flags bodies past the escape radius (1) and bodies whose position or
+// velocity is no longer finite (2); the flags are read back and the CPU
+// compacts or respawns escaped bodies and reports diverged ones.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_flag_escaped(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= simulation.body_count {
+        return;
+    }
+    let body = bodies_in[i];
+    if !(all_finite(body.position) && all_finite(body.velocity)) {
+        escaped[i] = FLAG_NON_FINITE;
+        return;
+    }
+    let radius = simulation.escape_radius;
+    let outside = radius > 0.0 && dot(body.position, body.position) > radius * radius;
+    escaped[i] = select(0u, FLAG_ESCAPED, outside);
+}
+
+// NaN and ±Inf are the values with an all-ones exponent.
+fn all_finite(v: vec3<f32>) -> bool {
+    let exponent = bitcast<vec3<u32>>(v) & vec3<u32>(0x7f800000u);
+    return all(exponent != vec3<u32>(0x7f800000u));
+}
+
+// One-shot supernova kick, mirroring `Supernova::apply`: the progenitor loses
+// its mass and everything else is pushed radially away from it.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_impulse(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= simulation.body_count {
+        return;
+    }
+    if i == impulse.source {
+        bodies_out[i].mass = 0.0;
+        return;
+    }
+    let offset = bodies_out[i].position - impulse.center;
+    let distance_sq = dot(offset, offset);
+    if distance_sq == 0.0 {
+        return;
+    }
+    let falloff = 1.0 / (1.0 + distance_sq / (impulse.radius * impulse.radius));
+    bodies_out[i].velocity += offset * inverseSqrt(distance_sq) * impulse.speed * falloff;
+}
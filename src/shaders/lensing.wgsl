@@ -0,0 +1,36 @@
+// Point-mass lensing of the HDR frame around a black hole's projected
+// position, with its shadow and photon ring; mirrors `rendering::lensing`.
+
+struct LensUniforms {
+    // In pixels, top-left origin like `@builtin(position)`.
+    center: vec2<f32>,
+    einstein_radius: f32,
+    shadow_radius: f32,
+    ring_color: vec3<f32>,
+    ring_width: f32,
+}
+
+@group(0) @binding(5) var<uniform> lens: LensUniforms;
+@group(0) @binding(6) var lens_source: texture_2d<f32>;
+@group(0) @binding(7) var lens_sampler: sampler;
+
+@fragment
+fn fs_lens(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let offset = position.xy - lens.center;
+    let r = length(offset);
+    // Light inside the shadow fell in; a rim of one pixel keeps it smooth.
+    let outside = smoothstep(lens.shadow_radius - 0.5, lens.shadow_radius + 0.5, r);
+    if outside == 0.0 {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    // Lens equation with the background far behind the hole: an image at `r`
+    // shows the source at `r - θ_E² / r`, which is on the opposite side
+    // (the secondary image) inside the Einstein ring.
+    let source_radius = r - lens.einstein_radius * lens.einstein_radius / r;
+    let source = lens.center + offset / r * source_radius;
+    let size = vec2<f32>(textureDimensions(lens_source));
+    let lensed = textureSampleLevel(lens_source, lens_sampler, source / size, 0.0);
+    let from_ring = (r - lens.shadow_radius) / lens.ring_width;
+    let ring = lens.ring_color * exp(-from_ring * from_ring);
+    return vec4<f32>((lensed.rgb + ring) * outside, lensed.a);
+}
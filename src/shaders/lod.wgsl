@@ -0,0 +1,85 @@
+// Level of detail: a compute pass sorts visible bodies into impostors (large
+// on screen) and points (the rest), appending their indices and instance
+// counts for two indirect draws; mirrors `rendering::lod`.
+
+#include "billboard.wgsl"
+#include "dispatch.wgsl"
+
+struct LodUniforms {
+    // Bodies at least this many pixels in radius are drawn as impostors.
+    impostor_pixel_radius: f32,
+    body_count: u32,
+    _padding: vec2<u32>,
+}
+
+// `DrawIndirectArgs`, with the instance count appended to atomically.
+struct LodDraw {
+    vertex_count: u32,
+    instance_count: atomic<u32>,
+    first_vertex: u32,
+    first_instance: u32,
+}
+
+@group(0) @binding(14) var<uniform> lod: LodUniforms;
+@group(0) @binding(15) var<storage, read_write> lod_draws: array<LodDraw, 2>;
+// One buffer in two halves: impostors from the start, points from the middle.
+// Written by the compute pass, read by the two draws.
+@group(0) @binding(16) var<storage, read_write> lod_indices_out: array<u32>;
+@group(0) @binding(17) var<storage, read> lod_indices: array<u32>;
+
+const LOD_IMPOSTORS: u32 = 0u;
+const LOD_POINTS: u32 = 1u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn cs_classify_lod(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= lod.body_count || body_hidden(i) {
+        return;
+    }
+    let pixels = projected_pixel_radius(bodies[i]);
+    if pixels <= 0.0 {
+        return;
+    }
+    if pixels >= lod.impostor_pixel_radius {
+        let slot = atomicAdd(&lod_draws[LOD_IMPOSTORS].instance_count, 1u);
+        lod_indices_out[slot] = i;
+    } else {
+        let slot = atomicAdd(&lod_draws[LOD_POINTS].instance_count, 1u);
+        lod_indices_out[arrayLength(&lod_indices_out) / 2u + slot] = i;
+    }
+}
+
+// Impostor draw: `vs_main` over the first half of the index list, shaded by
+// `fs_main` as usual.
+@vertex
+fn vs_lod_impostor(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    return billboard(vertex_index, lod_indices[instance_index]);
+}
+
+struct PointOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+// Point draw: one vertex per body with point-list topology, over the second
+// half of the index list. A point covers one pixel, so alpha carries the
+// fraction of it the body would have covered.
+@vertex
+fn vs_lod_point(@builtin(instance_index) instance_index: u32) -> PointOutput {
+    let index = lod_indices[arrayLength(&lod_indices) / 2u + instance_index];
+    let body = bodies[index];
+    var out: PointOutput;
+    out.clip_position = render.view_projection * vec4<f32>(body.position, 1.0);
+    out.color = body_color(body, body_kind(index));
+    let pixels = max(projected_pixel_radius(body), render.min_pixel_radius);
+    out.color.a *= min(3.14159265 * pixels * pixels, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_lod_point(in: PointOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
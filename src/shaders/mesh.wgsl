@@ -0,0 +1,54 @@
+// Instanced icosphere path: one mesh instance per body, scaled by its radius
+// and lit from a single point light; mirrors `rendering::icosphere`.
+
+#include "billboard.wgsl"
+
+struct MeshUniforms {
+    light_position: vec3<f32>,
+    ambient: f32,
+}
+
+@group(0) @binding(18) var<uniform> mesh: MeshUniforms;
+
+struct MeshOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) world_position: vec3<f32>,
+    @location(2) normal: vec3<f32>,
+    @location(3) @interpolate(flat) kind: u32,
+}
+
+// `position` is the unit-sphere vertex from the vertex buffer, also its normal.
+@vertex
+fn vs_mesh(
+    @location(0) position: vec3<f32>,
+    @builtin(instance_index) instance_index: u32,
+) -> MeshOutput {
+    var out: MeshOutput;
+    if body_hidden(instance_index) {
+        out.clip_position = vec4<f32>(0.0, 0.0, 2.0, 1.0);
+        return out;
+    }
+    let body = bodies[instance_index];
+    out.kind = body_kind(instance_index);
+    out.world_position = body.position + position * body.radius;
+    out.clip_position = render.view_projection * vec4<f32>(out.world_position, 1.0);
+    out.color = body_color(body, out.kind);
+    out.normal = position;
+    return out;
+}
+
+// Stars are their own light; everything else is Lambert-shaded towards it.
+@fragment
+fn fs_mesh(in: MeshOutput) -> @location(0) vec4<f32> {
+    if in.kind == KIND_STAR {
+        return in.color;
+    }
+    let to_light = mesh.light_position - in.world_position;
+    var diffuse = 1.0;
+    if dot(to_light, to_light) > 0.0 {
+        diffuse = max(dot(normalize(in.normal), normalize(to_light)), 0.0);
+    }
+    let light = mesh.ambient + (1.0 - mesh.ambient) * diffuse;
+    return vec4<f32>(in.color.rgb * light, in.color.a);
+}
@@ -0,0 +1,39 @@
+// Minimap inset: an orthographic top-down view of every body as a point, plus
+// the main camera's footprint as lines; mirrors `rendering::minimap`. Points
+// are shaded by `fs_lod_point` and lines by `fs_reference`.
+
+#include "lod.wgsl"
+#include "reference.wgsl"
+
+struct MinimapUniforms {
+    view_projection: mat4x4<f32>,
+    body_count: u32,
+    _padding: vec3<u32>,
+}
+
+@group(0) @binding(21) var<uniform> minimap: MinimapUniforms;
+
+// One point-list vertex per body, drawn at full opacity whatever its size.
+@vertex
+fn vs_minimap_body(@builtin(vertex_index) index: u32) -> PointOutput {
+    var out: PointOutput;
+    if index >= minimap.body_count || body_hidden(index) {
+        out.clip_position = vec4<f32>(0.0, 0.0, 2.0, 1.0);
+        return out;
+    }
+    let body = bodies[index];
+    out.clip_position = minimap.view_projection * vec4<f32>(body.position, 1.0);
+    out.color = vec4<f32>(body_color(body, body_kind(index)).rgb, 1.0);
+    return out;
+}
+
+@vertex
+fn vs_minimap_line(
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+) -> ReferenceOutput {
+    var out: ReferenceOutput;
+    out.clip_position = minimap.view_projection * vec4<f32>(position, 1.0);
+    out.color = color;
+    return out;
+}
@@ -0,0 +1,25 @@
+// Reference overlay: ground grid and axes as a colored line list; mirrors
+// `rendering::reference`.
+
+#include "bindings.wgsl"
+
+struct ReferenceOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@vertex
+fn vs_reference(
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+) -> ReferenceOutput {
+    var out: ReferenceOutput;
+    out.clip_position = render.view_projection * vec4<f32>(position, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_reference(in: ReferenceOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,96 @@
+// Reference frame: a single workgroup finds the frame's origin (center of
+// mass, heaviest body or a chosen body) and writes the bodies relative to it
+// into `frame_bodies`, which the render passes then bind as `bodies`. The
+// simulated state is left untouched; mirrors `rendering::reference_frame`.
+
+struct ReferenceFrameUniforms {
+    // `FRAME_*`.
+    mode: u32,
+    // Origin body of `FRAME_BODY`.
+    body: u32,
+    body_count: u32,
+    _padding: u32,
+}
+
+@group(0) @binding(19) var<uniform> reference_frame: ReferenceFrameUniforms;
+@group(0) @binding(20) var<storage, read_write> frame_bodies: array<Body>;
+
+const FRAME_INERTIAL: u32 = 0u;
+const FRAME_BARYCENTRIC: u32 = 1u;
+const FRAME_HELIOCENTRIC: u32 = 2u;
+const FRAME_BODY: u32 = 3u;
+
+const FRAME_WORKGROUP_SIZE: u32 = 256u;
+
+// Per thread: mass-weighted position (xyz) and mass (w), mass-weighted velocity.
+var<workgroup> frame_moments: array<vec4<f32>, FRAME_WORKGROUP_SIZE>;
+var<workgroup> frame_momenta: array<vec3<f32>, FRAME_WORKGROUP_SIZE>;
+// Heaviest body seen per thread, as (mass, index).
+var<workgroup> frame_heaviest: array<vec2<f32>, FRAME_WORKGROUP_SIZE>;
+
+@compute @workgroup_size(256)
+fn cs_reference_frame(@builtin(local_invocation_index) thread: u32) {
+    let count = reference_frame.body_count;
+    var moment = vec4<f32>(0.0);
+    var momentum = vec3<f32>(0.0);
+    var heaviest = vec2<f32>(-1.0, 0.0);
+    for (var i = thread; i < count; i += FRAME_WORKGROUP_SIZE) {
+        let body = bodies_in[i];
+        if body.mass > 0.0 {
+            moment += vec4<f32>(body.position * body.mass, body.mass);
+            momentum += body.velocity * body.mass;
+        }
+        if body.mass > heaviest.x {
+            heaviest = vec2<f32>(body.mass, f32(i));
+        }
+    }
+    frame_moments[thread] = moment;
+    frame_momenta[thread] = momentum;
+    frame_heaviest[thread] = heaviest;
+    workgroupBarrier();
+
+    for (var stride = FRAME_WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        if thread < stride {
+            frame_moments[thread] += frame_moments[thread + stride];
+            frame_momenta[thread] += frame_momenta[thread + stride];
+            if frame_heaviest[thread + stride].x > frame_heaviest[thread].x {
+                frame_heaviest[thread] = frame_heaviest[thread + stride];
+            }
+        }
+        workgroupBarrier();
+    }
+
+    var origin_position = vec3<f32>(0.0);
+    var origin_velocity = vec3<f32>(0.0);
+    let total = frame_moments[0];
+    switch reference_frame.mode {
+        case FRAME_BARYCENTRIC: {
+            if total.w > 0.0 {
+                origin_position = total.xyz / total.w;
+                origin_velocity = frame_momenta[0] / total.w;
+            }
+        }
+        case FRAME_HELIOCENTRIC: {
+            if count > 0u {
+                let origin = bodies_in[u32(frame_heaviest[0].y)];
+                origin_position = origin.position;
+                origin_velocity = origin.velocity;
+            }
+        }
+        case FRAME_BODY: {
+            if reference_frame.body < count {
+                let origin = bodies_in[reference_frame.body];
+                origin_position = origin.position;
+                origin_velocity = origin.velocity;
+            }
+        }
+        default: {}
+    }
+
+    for (var i = thread; i < count; i += FRAME_WORKGROUP_SIZE) {
+        var body = bodies_in[i];
+        body.position -= origin_position;
+        body.velocity -= origin_velocity;
+        frame_bodies[i] = body;
+    }
+}
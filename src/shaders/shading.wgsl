@@ -0,0 +1,105 @@
+// Fragment stages.
+
+#include "billboard.wgsl"
+
+// Draw the rotating meridian on plain bodies.
+override SPIN_MARKERS: bool = true;
+
+@fragment
+fn fs_line() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.5, 0.6, 0.8, 0.6);
+}
+
+// Rodrigues rotation of `v` by `angle` about the unit `axis`.
+fn rotate_about(v: vec3<f32>, axis: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return v * c + cross(axis, v) * s + axis * dot(axis, v) * (1.0 - c);
+}
+
+// Darkens a prime meridian on the impostor sphere, turned by the body's
+// rotation phase, so spin and tidal locking are visible.
+fn spin_marker(uv: vec2<f32>, r2: f32, spin: vec4<f32>) -> f32 {
+    let rate = length(spin.xyz);
+    if rate == 0.0 && spin.w == 0.0 {
+        return 1.0;
+    }
+    var axis = vec3<f32>(0.0, -1.0, 0.0);
+    if rate > 0.0 {
+        axis = spin.xyz / rate;
+    }
+    let toward_viewer = cross(render.camera_right, render.camera_up);
+    let normal = render.camera_right * uv.x + render.camera_up * uv.y + toward_viewer * sqrt(1.0 - r2);
+    let local = rotate_about(normal, axis, -spin.w);
+    var pick = vec3<f32>(1.0, 0.0, 0.0);
+    if abs(axis.x) > 0.9 {
+        pick = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let reference = normalize(cross(axis, pick));
+    let longitude = atan2(dot(local, cross(axis, reference)), dot(local, reference));
+    return select(1.0, 0.35, abs(longitude) < 0.12);
+}
+
+// Per-kind styling: stars get a white-hot core, gas a soft Gaussian blob,
+// everything else a flat disk with its spin marker. `r2` is inside the disk.
+fn shade(in: VertexOutput, r2: f32) -> vec4<f32> {
+    switch in.kind {
+        case KIND_STAR: {
+            let core = exp(-8.0 * r2);
+            return vec4<f32>(mix(in.color.rgb, vec3<f32>(1.0), core), in.color.a * (1.0 - r2 * r2));
+        }
+        case KIND_GAS: {
+            return vec4<f32>(in.color.rgb, in.color.a * exp(-4.0 * r2));
+        }
+        default: {
+            if !SPIN_MARKERS {
+                return in.color;
+            }
+            return vec4<f32>(in.color.rgb * spin_marker(in.uv, r2, in.spin), in.color.a);
+        }
+    }
+}
+
+// Alpha of the disk's rim, fading over about one pixel as measured by the
+// screen-space derivative of the radius.
+fn edge_coverage(r2: f32) -> f32 {
+    let r = sqrt(r2);
+    let width = max(fwidth(r), 1e-4);
+    return 1.0 - smoothstep(1.0 - width, 1.0, r);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let r2 = dot(in.uv, in.uv);
+    // Derivatives before the discard, while every fragment of the quad runs.
+    let edge = edge_coverage(r2);
+    if r2 > 1.0 {
+        discard;
+    }
+    let color = shade(in, r2);
+    return vec4<f32>(color.rgb, color.a * edge);
+}
+
+// Color plus the `rendering::debug_targets` attachments. Those are not
+// blended, so each texel keeps whichever fragment was drawn last.
+struct DebugOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) body_index: u32,
+    // View depth, speed and coverage alpha.
+    @location(2) aux: vec4<f32>,
+}
+
+@fragment
+fn fs_debug(in: VertexOutput) -> DebugOutput {
+    let r2 = dot(in.uv, in.uv);
+    let edge = edge_coverage(r2);
+    if r2 > 1.0 {
+        discard;
+    }
+    var out: DebugOutput;
+    out.color = shade(in, r2);
+    out.color.a *= edge;
+    out.body_index = in.index;
+    out.aux = vec4<f32>(in.view_depth, in.speed, out.color.a, 1.0);
+    return out;
+}
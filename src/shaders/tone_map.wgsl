@@ -0,0 +1,50 @@
+// Resolves the HDR frame the body pass renders into onto the surface; mirrors
+// `rendering::tone_mapping`.
+
+struct ToneMapUniforms {
+    exposure: f32,
+    // Applied after the curve: `1 / gamma` when the surface stores values
+    // as-is, `2.2 / gamma` when it sRGB-encodes them itself.
+    gamma_power: f32,
+    tone_mapper: u32,
+    _padding: u32,
+}
+
+// `ToneMapper`.
+const TONE_MAP_REINHARD: u32 = 1u;
+const TONE_MAP_ACES: u32 = 2u;
+
+@group(0) @binding(3) var<uniform> tone_map: ToneMapUniforms;
+@group(0) @binding(4) var hdr_frame: texture_2d<f32>;
+// Linear filtering; the frame may be rendered at a different resolution
+// (`RenderScale`) than the surface.
+@group(0) @binding(14) var hdr_sampler: sampler;
+
+fn reinhard(x: vec3<f32>) -> vec3<f32> {
+    return x / (1.0 + x);
+}
+
+// Narkowicz's fit of the ACES reference rendering transform.
+fn aces(x: vec3<f32>) -> vec3<f32> {
+    return clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_tone_map(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    // At 2x a bilinear tap at each surface pixel center averages a 2×2 block.
+    let hdr = textureSampleLevel(hdr_frame, hdr_sampler, in.uv, 0.0);
+    let exposed = max(hdr.rgb * tone_map.exposure, vec3<f32>(0.0));
+    var mapped: vec3<f32>;
+    switch tone_map.tone_mapper {
+        case TONE_MAP_REINHARD: {
+            mapped = reinhard(exposed);
+        }
+        case TONE_MAP_ACES: {
+            mapped = aces(exposed);
+        }
+        default: {
+            mapped = min(exposed, vec3<f32>(1.0));
+        }
+    }
+    return vec4<f32>(pow(mapped, vec3<f32>(tone_map.gamma_power)), 1.0);
+}
@@ -0,0 +1,273 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use glam::Vec3;
+use thiserror::Error;
+
+use crate::camera::Camera;
+
+/// Bumped whenever the byte layout of a code changes.
+const FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShareError {
+    #[error("not a scenario code")]
+    Encoding,
+    #[error("scenario code version {0} is not supported")]
+    Version(u8),
+    #[error("scenario code is truncated")]
+    Truncated,
+    #[error("no simulation named `{0}`")]
+    UnknownSimulation(String),
+    #[error("scenario code has an invalid {0}")]
+    InvalidValue(&'static str),
+}
+
+/// Where the orbit camera sits, without the viewport-dependent fields.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraPose {
+    pub target: Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl CameraPose {
+    pub fn of(camera: &Camera) -> Self {
+        Self {
+            target: camera.target,
+            distance: camera.distance,
+            yaw: camera.yaw,
+            pitch: camera.pitch,
+        }
+    }
+
+    pub fn apply(&self, camera: &mut Camera) {
+        camera.target = self.target;
+        camera.distance = self.distance;
+        camera.yaw = self.yaw;
+        camera.pitch = self.pitch;
+        camera.follow = None;
+    }
+}
+
+/// A scenario as someone was looking at it, small enough to paste into chat:
+/// the scenario, its seed and parameter overrides, the camera and the time
+/// scale. Encoded as a versioned little-endian byte string in URL-safe base64.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedScenario {
+    pub simulation: String,
+    pub seed: Option<u64>,
+    /// Parameters that differ from the scenario's defaults.
+    pub parameters: Vec<(String, f32)>,
+    pub camera: CameraPose,
+    pub time_scale: f32,
+}
+
+impl SharedScenario {
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![FORMAT_VERSION];
+        write_str(&mut bytes, &self.simulation);
+        match self.seed {
+            Some(seed) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&seed.to_le_bytes());
+            }
+            None => bytes.push(0),
+        }
+        let camera = &self.camera;
+        for value in camera.target.to_array().into_iter().chain([
+            camera.distance,
+            camera.yaw,
+            camera.pitch,
+            self.time_scale,
+        ]) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.push(self.parameters.len().min(u8::MAX.into()) as u8);
+        for (name, value) in self.parameters.iter().take(u8::MAX.into()) {
+            write_str(&mut bytes, name);
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Parses a code from `encode`, ignoring surrounding whitespace.
+    pub fn decode(code: &str) -> Result<Self, ShareError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(|_| ShareError::Encoding)?;
+        let mut reader = Reader(&bytes);
+        let version = reader.u8()?;
+        if version != FORMAT_VERSION {
+            return Err(ShareError::Version(version));
+        }
+        let simulation = reader.string()?;
+        let seed = match reader.u8()? {
+            0 => None,
+            _ => Some(u64::from_le_bytes(reader.array()?)),
+        };
+        let target = Vec3::new(reader.f32()?, reader.f32()?, reader.f32()?);
+        let camera = CameraPose {
+            target,
+            distance: reader.f32()?,
+            yaw: reader.f32()?,
+            pitch: reader.f32()?,
+        };
+        let time_scale = reader.f32()?;
+        let parameters: Vec<(String, f32)> = (0..reader.u8()?)
+            .map(|_| Ok((reader.string()?, reader.f32()?)))
+            .collect::<Result<_, ShareError>>()?;
+        // The values go straight into the camera and the worker.
+        if !camera.target.is_finite() {
+            return Err(ShareError::InvalidValue("camera target"));
+        }
+        if !(camera.distance.is_finite() && camera.distance > 0.0) {
+            return Err(ShareError::InvalidValue("camera distance"));
+        }
+        if !(camera.yaw.is_finite() && camera.pitch.is_finite()) {
+            return Err(ShareError::InvalidValue("camera angle"));
+        }
+        if !(time_scale.is_finite() && time_scale > 0.0) {
+            return Err(ShareError::InvalidValue("time scale"));
+        }
+        if parameters.iter().any(|(_, value)| !value.is_finite()) {
+            return Err(ShareError::InvalidValue("parameter"));
+        }
+        Ok(Self {
+            simulation,
+            seed,
+            parameters,
+            camera,
+            time_scale,
+        })
+    }
+}
+
+/// Length-prefixed UTF-8; names are short, so longer ones are cut.
+fn write_str(bytes: &mut Vec<u8>, text: &str) {
+    let mut end = text.len().min(u8::MAX.into());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    bytes.push(end as u8);
+    bytes.extend_from_slice(&text.as_bytes()[..end]);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], ShareError> {
+        if self.0.len() < count {
+            return Err(ShareError::Truncated);
+        }
+        let (head, tail) = self.0.split_at(count);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ShareError> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, ShareError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn f32(&mut self) -> Result<f32, ShareError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn string(&mut self) -> Result<String, ShareError> {
+        let length = self.u8()?.into();
+        String::from_utf8(self.take(length)?.to_vec()).map_err(|_| ShareError::Encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> SharedScenario {
+        SharedScenario {
+            simulation: "Solar System".to_owned(),
+            seed: Some(42),
+            parameters: vec![("moon_mass".to_owned(), 10.0)],
+            camera: CameraPose {
+                target: Vec3::new(1.0, -2.0, 0.5),
+                distance: 12.0,
+                yaw: 0.3,
+                pitch: -0.2,
+            },
+            time_scale: 0.5,
+        }
+    }
+
+    #[test]
+    fn decode_inverts_encode() {
+        let shared = scenario();
+        assert_eq!(SharedScenario::decode(&shared.encode()), Ok(shared));
+        let unseeded = SharedScenario {
+            seed: None,
+            parameters: Vec::new(),
+            ..scenario()
+        };
+        assert_eq!(
+            SharedScenario::decode(&format!("  {}\n", unseeded.encode())),
+            Ok(unseeded)
+        );
+    }
+
+    #[test]
+    fn corrupted_codes_are_rejected() {
+        let code = scenario().encode();
+        assert_eq!(
+            SharedScenario::decode(&code[..code.len() - 4]),
+            Err(ShareError::Truncated)
+        );
+        assert_eq!(
+            SharedScenario::decode("not a code!"),
+            Err(ShareError::Encoding)
+        );
+        let mut bytes = URL_SAFE_NO_PAD.decode(&code).unwrap();
+        bytes[0] = FORMAT_VERSION + 1;
+        assert_eq!(
+            SharedScenario::decode(&URL_SAFE_NO_PAD.encode(bytes)),
+            Err(ShareError::Version(FORMAT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn non_finite_and_non_positive_values_are_rejected() {
+        let decode = |shared: SharedScenario| SharedScenario::decode(&shared.encode());
+        let mut shared = scenario();
+        shared.camera.distance = f32::NAN;
+        assert_eq!(
+            decode(shared),
+            Err(ShareError::InvalidValue("camera distance"))
+        );
+        let mut shared = scenario();
+        shared.camera.distance = -1.0;
+        assert_eq!(
+            decode(shared),
+            Err(ShareError::InvalidValue("camera distance"))
+        );
+        let mut shared = scenario();
+        shared.camera.target.y = f32::INFINITY;
+        assert_eq!(
+            decode(shared),
+            Err(ShareError::InvalidValue("camera target"))
+        );
+        let mut shared = scenario();
+        shared.camera.pitch = f32::NAN;
+        assert_eq!(
+            decode(shared),
+            Err(ShareError::InvalidValue("camera angle"))
+        );
+        let mut shared = scenario();
+        shared.time_scale = 0.0;
+        assert_eq!(decode(shared), Err(ShareError::InvalidValue("time scale")));
+        let mut shared = scenario();
+        shared.parameters[0].1 = f32::NEG_INFINITY;
+        assert_eq!(decode(shared), Err(ShareError::InvalidValue("parameter")));
+    }
+}
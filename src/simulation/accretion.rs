@@ -0,0 +1,25 @@
+use glam::Vec3;
+
+/// Parametric accretion disk drawn around a central body: a flat annulus with
+/// a Shakura–Sunyaev temperature profile, T ∝ r^(-3/4), and a faked Doppler
+/// beaming that brightens the side orbiting towards the viewer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccretionDisk {
+    pub central: usize,
+    /// In simulation units; the disk follows the central body as it moves.
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Temperature at the inner edge in kelvin; sets the color gradient.
+    pub inner_temperature: f32,
+    /// The disk orbits anticlockwise about this axis.
+    pub normal: Vec3,
+    /// Brightness boost of the approaching side, 0 for none.
+    pub beaming: f32,
+}
+
+impl AccretionDisk {
+    /// Temperature at `radius`, matching the shader.
+    pub fn temperature(&self, radius: f32) -> f32 {
+        self.inner_temperature * (radius / self.inner_radius).powf(-0.75)
+    }
+}
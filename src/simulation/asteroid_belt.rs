@@ -0,0 +1,188 @@
+use glam::{Vec3, Vec4};
+
+use super::diagnostics::HistogramSpec;
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Body, BodyGroup, ParamDescriptor, Rng, Simulation, SimulationError, SimulationState,
+    sanitize_parameter,
+};
+use crate::rendering::config::{RenderConfig, TrailConfig};
+
+const JUPITER_SEMI_MAJOR_AXIS: f32 = 5.203;
+const JUPITER_MASS: f32 = 9.55e-4;
+const BELT_INNER: f32 = 2.0;
+const BELT_OUTER: f32 = 3.5;
+
+/// Mean-motion resonances with Jupiter (asteroid : Jupiter orbits) that carve the gaps.
+const RESONANCES: [(&str, f32); 4] = [("3:1", 3.0), ("5:2", 2.5), ("7:3", 7.0 / 3.0), ("2:1", 2.0)];
+
+/// Sun, Jupiter and massless test asteroids; over long runs the belt empties at
+/// the Kirkwood gaps (AU, years, solar masses).
+pub struct AsteroidBeltSimulation {
+    pub asteroid_count: usize,
+    /// Jupiter's mass in units of its real one; larger values open the gaps sooner.
+    pub jupiter_mass_scale: f32,
+    pub seed: u64,
+}
+
+impl Default for AsteroidBeltSimulation {
+    fn default() -> Self {
+        Self {
+            asteroid_count: 4000,
+            jupiter_mass_scale: 10.0,
+            seed: 3,
+        }
+    }
+}
+
+fn orbit(semi_major_axis: f32, eccentricity: f32, anomaly: f32, mass: f32, color: Vec4) -> Body {
+    let semi_latus_rectum = semi_major_axis * (1.0 - eccentricity * eccentricity);
+    let distance = semi_latus_rectum / (1.0 + eccentricity * anomaly.cos());
+    let (sin, cos) = anomaly.sin_cos();
+    let speed = (G_ASTRONOMICAL / semi_latus_rectum).sqrt();
+    Body::new(
+        Vec3::new(cos, 0.0, sin) * distance,
+        Vec3::new(-sin, 0.0, eccentricity + cos) * speed,
+        mass,
+        0.01,
+        color,
+    )
+}
+
+impl Simulation for AsteroidBeltSimulation {
+    fn name(&self) -> &str {
+        "Kirkwood Gaps"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    /// Trails from thousands of asteroids would hide the gaps.
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            trails: TrailConfig::planets_only(),
+            ..RenderConfig::default()
+        }
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut rng = Rng::new(self.seed);
+        let mut bodies = Vec::with_capacity(self.asteroid_count + 2);
+        bodies.push(Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            1.0,
+            0.1,
+            Vec4::new(1.0, 0.9, 0.4, 1.0),
+        ));
+        let mut jupiter = orbit(
+            JUPITER_SEMI_MAJOR_AXIS,
+            0.0,
+            0.0,
+            JUPITER_MASS * self.jupiter_mass_scale,
+            Vec4::new(0.9, 0.7, 0.5, 1.0),
+        );
+        jupiter.radius = 0.08;
+        bodies.push(jupiter);
+
+        for _ in 0..self.asteroid_count {
+            let mut asteroid = orbit(
+                rng.range(BELT_INNER, BELT_OUTER),
+                rng.range(0.0, 0.1),
+                rng.range(0.0, std::f32::consts::TAU),
+                0.0,
+                Vec4::new(0.7, 0.65, 0.6, 1.0),
+            );
+            // Random orientation of the ellipse in the plane.
+            let turn = glam::Quat::from_rotation_y(rng.range(0.0, std::f32::consts::TAU));
+            asteroid.position = turn * asteroid.position;
+            asteroid.velocity = turn * asteroid.velocity;
+            bodies.push(asteroid);
+        }
+        bodies
+    }
+
+    fn histogram(&self) -> Option<HistogramSpec> {
+        Some(HistogramSpec {
+            central: 0,
+            first_body: 2,
+            min: BELT_INNER,
+            max: BELT_OUTER,
+            bins: 150,
+            markers: RESONANCES
+                .iter()
+                .map(|&(label, ratio)| (label, JUPITER_SEMI_MAJOR_AXIS * ratio.powf(-2.0 / 3.0)))
+                .collect(),
+        })
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        match index {
+            0 => BodyGroup::Star,
+            1 => BodyGroup::Planet,
+            _ => BodyGroup::Asteroid,
+        }
+    }
+
+    fn body_name(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("Sun"),
+            1 => Some("Jupiter"),
+            _ => None,
+        }
+    }
+
+    /// Gaps take thousands of Jupiter orbits to open, so the worker takes many
+    /// steps per tick; test particles keep each step O(N).
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            softening: 0.0,
+            dt: 5.0e-3,
+            substeps: 32,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::integer(
+                "asteroid_count",
+                100,
+                50_000,
+                defaults.asteroid_count as u32,
+                self.asteroid_count as u32,
+            ),
+            ParamDescriptor::new(
+                "jupiter_mass_scale",
+                1.0,
+                30.0,
+                defaults.jupiter_mass_scale,
+                self.jupiter_mass_scale,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "asteroid_count" => self.asteroid_count = value as usize,
+            _ => self.jupiter_mass_scale = value,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,70 @@
+use glam::Vec4;
+
+use super::Body;
+use super::orbits::{OrbitalElements, about_barycenter};
+
+/// Orbit of a bound pair, started at apoapsis on the +X axis and running from
+/// +X towards +Z.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BinaryOrbit {
+    pub primary_mass: f32,
+    pub secondary_mass: f32,
+    pub semi_major_axis: f32,
+    pub eccentricity: f32,
+}
+
+impl BinaryOrbit {
+    pub fn total_mass(&self) -> f32 {
+        self.primary_mass + self.secondary_mass
+    }
+
+    /// Secondary's share of the total mass.
+    pub fn mass_ratio(&self) -> f32 {
+        self.secondary_mass / self.total_mass()
+    }
+
+    /// Both members about their barycenter at the origin, primary first.
+    pub fn bodies(
+        &self,
+        gravitational_constant: f32,
+        radii: [f32; 2],
+        colors: [Vec4; 2],
+    ) -> [Body; 2] {
+        let relative = OrbitalElements {
+            semi_major_axis: self.semi_major_axis,
+            eccentricity: self.eccentricity,
+            argument_of_periapsis: std::f32::consts::PI,
+            true_anomaly: std::f32::consts::PI,
+            ..OrbitalElements::default()
+        }
+        .state_vectors(gravitational_constant * self.total_mass());
+        let [(primary, primary_velocity), (secondary, secondary_velocity)] =
+            about_barycenter(self.primary_mass, self.secondary_mass, relative);
+        [
+            Body::new(
+                primary,
+                primary_velocity,
+                self.primary_mass,
+                radii[0],
+                colors[0],
+            ),
+            Body::new(
+                secondary,
+                secondary_velocity,
+                self.secondary_mass,
+                radii[1],
+                colors[1],
+            ),
+        ]
+    }
+
+    /// Innermost stable circumbinary (P-type) orbit radius, from the fit of
+    /// Holman & Wiegert (1999).
+    pub fn critical_radius(&self) -> f32 {
+        let mu = self.mass_ratio();
+        let e = self.eccentricity;
+        let ratio = 1.60 + 5.10 * e - 2.22 * e * e + 4.12 * mu - 4.27 * e * mu - 5.09 * mu * mu
+            + 4.61 * e * e * mu * mu;
+        ratio * self.semi_major_axis
+    }
+}
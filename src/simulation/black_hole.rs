@@ -0,0 +1,245 @@
+use std::f32::consts::TAU;
+
+use glam::{Quat, Vec3, Vec4};
+
+use super::{
+    AccretionDisk, Body, BodyGroup, GravitationalLens, ParamDescriptor, PostNewtonian, Rng,
+    Simulation, SimulationError, SimulationState, sanitize_parameter,
+};
+use crate::rendering::config::{BackgroundConfig, BlendMode, HaloConfig, RenderConfig, ToneMapper};
+
+const HOLE_MASS: f32 = 1.0;
+const STAR_MASS: f32 = 1.0e-5;
+const STAR_COUNT: usize = 12;
+/// The first star follows S2's orbit shape; the rest are drawn at random.
+const S2_SEMI_MAJOR_AXIS: f32 = 1.0;
+const S2_ECCENTRICITY: f32 = 0.88;
+/// Particle disk extent in Schwarzschild radii, from just outside the
+/// innermost stable circular orbit (3 r_s) outwards.
+const DISK_INNER: f32 = 4.0;
+const DISK_OUTER: f32 = 16.0;
+/// Innermost stable circular orbit, where the drawn accretion disk begins.
+const ISCO: f32 = 3.0;
+
+/// Bound orbit about the hole at the origin, in the XZ plane before `turn`.
+fn orbit(semi_major_axis: f32, eccentricity: f32, anomaly: f32, turn: Quat) -> (Vec3, Vec3) {
+    let semi_latus_rectum = semi_major_axis * (1.0 - eccentricity * eccentricity);
+    let distance = semi_latus_rectum / (1.0 + eccentricity * anomaly.cos());
+    let (sin, cos) = anomaly.sin_cos();
+    let speed = (HOLE_MASS / semi_latus_rectum).sqrt();
+    (
+        turn * (Vec3::new(cos, 0.0, sin) * distance),
+        turn * (Vec3::new(-sin, 0.0, eccentricity + cos) * speed),
+    )
+}
+
+/// A black hole with S-stars on eccentric orbits, an accretion disk and a
+/// thin ring of test particles (arbitrary units with G = 1). The renderer
+/// lenses the background around it and draws its shadow and photon ring; the
+/// first star feels the post-Newtonian correction, so its orbit precesses.
+pub struct BlackHoleSimulation {
+    /// In simulation units; lower values grow the hole and its lensing.
+    pub speed_of_light: f32,
+    pub disk_count: usize,
+    /// Accretion disk temperature at its inner edge, in kelvin.
+    pub disk_temperature: f32,
+    /// How much brighter the disk's approaching side is drawn.
+    pub disk_beaming: f32,
+    pub seed: u64,
+}
+
+impl Default for BlackHoleSimulation {
+    fn default() -> Self {
+        Self {
+            speed_of_light: 10.0,
+            disk_count: 3000,
+            disk_temperature: 12_000.0,
+            disk_beaming: 0.6,
+            seed: 11,
+        }
+    }
+}
+
+/// Slightly tilted so the lensed far side of the disk shows above the hole.
+fn disk_tilt() -> Quat {
+    Quat::from_rotation_x(0.1)
+}
+
+impl BlackHoleSimulation {
+    fn schwarzschild_radius(&self) -> f32 {
+        GravitationalLens::schwarzschild_radius(HOLE_MASS, 1.0, self.speed_of_light)
+    }
+}
+
+impl Simulation for BlackHoleSimulation {
+    fn name(&self) -> &str {
+        "Black Hole"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut rng = Rng::new(self.seed);
+        let schwarzschild_radius = self.schwarzschild_radius();
+        let mut bodies = Vec::with_capacity(1 + STAR_COUNT + self.disk_count);
+        bodies.push(Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            HOLE_MASS,
+            schwarzschild_radius,
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        ));
+
+        for index in 0..STAR_COUNT {
+            let (semi_major_axis, eccentricity) = if index == 0 {
+                (S2_SEMI_MAJOR_AXIS, S2_ECCENTRICITY)
+            } else {
+                (rng.range(0.4, 2.0), rng.range(0.2, 0.9))
+            };
+            let axis = Vec3::new(rng.gaussian(), rng.gaussian(), rng.gaussian())
+                .try_normalize()
+                .unwrap_or(Vec3::X);
+            let turn = Quat::from_axis_angle(axis, rng.range(0.0, TAU));
+            let (position, velocity) =
+                orbit(semi_major_axis, eccentricity, rng.range(0.0, TAU), turn);
+            let warmth = rng.next_f32();
+            bodies.push(Body::new(
+                position,
+                velocity,
+                STAR_MASS,
+                0.02,
+                Vec4::new(0.7 + 0.3 * warmth, 0.8, 1.0 - 0.3 * warmth, 1.0),
+            ));
+        }
+
+        let tilt = disk_tilt();
+        for _ in 0..self.disk_count {
+            let radius = schwarzschild_radius * rng.range(DISK_INNER, DISK_OUTER);
+            let (position, velocity) = orbit(radius, 0.0, rng.range(0.0, TAU), tilt);
+            // Hotter, whiter gas further in.
+            let heat = (DISK_INNER * schwarzschild_radius / radius).sqrt();
+            bodies.push(Body::new(
+                position,
+                velocity,
+                0.0,
+                0.2 * schwarzschild_radius,
+                Vec4::new(1.0, 0.4 + 0.5 * heat, 0.1 + 0.6 * heat, 0.8),
+            ));
+        }
+        bodies
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        match index {
+            0 => BodyGroup::Other,
+            index if index <= STAR_COUNT => BodyGroup::Star,
+            _ => BodyGroup::Gas,
+        }
+    }
+
+    fn gravitational_lens(&self) -> Option<GravitationalLens> {
+        Some(GravitationalLens {
+            body: 0,
+            schwarzschild_radius: self.schwarzschild_radius(),
+            ring_color: Vec3::new(1.0, 0.75, 0.4),
+        })
+    }
+
+    fn accretion_disk(&self) -> Option<AccretionDisk> {
+        let schwarzschild_radius = self.schwarzschild_radius();
+        Some(AccretionDisk {
+            central: 0,
+            inner_radius: ISCO * schwarzschild_radius,
+            outer_radius: DISK_OUTER * schwarzschild_radius,
+            inner_temperature: self.disk_temperature,
+            // `orbit` runs from +X towards +Z, i.e. anticlockwise about -Y.
+            normal: disk_tilt() * Vec3::NEG_Y,
+            beaming: self.disk_beaming,
+        })
+    }
+
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            blend_mode: BlendMode::Additive,
+            tone_mapper: ToneMapper::Aces,
+            halo: HaloConfig {
+                emissive_mass: 0.5 * STAR_MASS,
+                intensity: 0.6,
+                ..HaloConfig::default()
+            },
+            // Background stars show the lens distorting the sky.
+            background: BackgroundConfig {
+                starfield_density: 0.04,
+                ..BackgroundConfig::default()
+            },
+            ..RenderConfig::default()
+        }
+    }
+
+    /// The inner disk orbits in a fraction of a time unit, and S2 speeds up
+    /// tenfold at periapsis.
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: 0.0,
+            dt: 5.0e-4,
+            substeps: 16,
+            post_newtonian: Some(PostNewtonian {
+                speed_of_light: self.speed_of_light,
+                central: 0,
+                target: 1,
+            }),
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "speed_of_light",
+                4.0,
+                50.0,
+                defaults.speed_of_light,
+                self.speed_of_light,
+            ),
+            ParamDescriptor::integer(
+                "disk_count",
+                0,
+                20_000,
+                defaults.disk_count as u32,
+                self.disk_count as u32,
+            ),
+            ParamDescriptor::new(
+                "disk_temperature",
+                2_000.0,
+                40_000.0,
+                defaults.disk_temperature,
+                self.disk_temperature,
+            ),
+            ParamDescriptor::new(
+                "disk_beaming",
+                0.0,
+                1.0,
+                defaults.disk_beaming,
+                self.disk_beaming,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "speed_of_light" => self.speed_of_light = value,
+            "disk_count" => self.disk_count = value as usize,
+            "disk_temperature" => self.disk_temperature = value,
+            _ => self.disk_beaming = value,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,25 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec3, Vec4};
+
+/// Per-body data, laid out to match the storage buffer read by the shaders.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct Body {
+    pub position: Vec3,
+    pub mass: f32,
+    pub velocity: Vec3,
+    pub radius: f32,
+    pub color: Vec4,
+}
+
+impl Body {
+    pub fn new(position: Vec3, velocity: Vec3, mass: f32, radius: f32, color: Vec4) -> Self {
+        Self {
+            position,
+            mass,
+            velocity,
+            radius,
+            color,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use glam::Vec3;
+
+use super::Body;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BoundaryMode {
+    #[default]
+    Off,
+    /// Reflect the velocity component normal to the wall.
+    Bounce,
+    /// Re-enter from the opposite face (periodic box).
+    Wrap,
+}
+
+/// Axis-aligned box centered on the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldBounds {
+    pub mode: BoundaryMode,
+    pub half_extent: Vec3,
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self {
+            mode: BoundaryMode::Off,
+            half_extent: Vec3::splat(1.0),
+        }
+    }
+}
+
+impl WorldBounds {
+    pub fn apply(&self, body: &mut Body) {
+        match self.mode {
+            BoundaryMode::Off => {}
+            BoundaryMode::Bounce => {
+                for axis in 0..3 {
+                    let limit = self.half_extent[axis];
+                    if body.position[axis] > limit {
+                        body.position[axis] = 2.0 * limit - body.position[axis];
+                        body.velocity[axis] = -body.velocity[axis].abs();
+                    } else if body.position[axis] < -limit {
+                        body.position[axis] = -2.0 * limit - body.position[axis];
+                        body.velocity[axis] = body.velocity[axis].abs();
+                    }
+                }
+                // A body faster than the box is wide can still overshoot; pin it inside.
+                body.position = body.position.clamp(-self.half_extent, self.half_extent);
+            }
+            BoundaryMode::Wrap => {
+                let size = 2.0 * self.half_extent;
+                body.position =
+                    (body.position + self.half_extent).rem_euclid(size) - self.half_extent;
+            }
+        }
+    }
+
+    /// The 12 box edges as line-list vertex pairs, for the wireframe pass.
+    pub fn wireframe_vertices(&self) -> [Vec3; 24] {
+        let h = self.half_extent;
+        let corner = |x: f32, y: f32, z: f32| Vec3::new(x * h.x, y * h.y, z * h.z);
+        let c = [
+            corner(-1.0, -1.0, -1.0),
+            corner(1.0, -1.0, -1.0),
+            corner(1.0, 1.0, -1.0),
+            corner(-1.0, 1.0, -1.0),
+            corner(-1.0, -1.0, 1.0),
+            corner(1.0, -1.0, 1.0),
+            corner(1.0, 1.0, 1.0),
+            corner(-1.0, 1.0, 1.0),
+        ];
+        [
+            c[0], c[1], c[1], c[2], c[2], c[3], c[3], c[0], // back face
+            c[4], c[5], c[5], c[6], c[6], c[7], c[7], c[4], // front face
+            c[0], c[4], c[1], c[5], c[2], c[6], c[3], c[7], // connecting edges
+        ]
+    }
+}
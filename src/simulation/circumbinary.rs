@@ -0,0 +1,171 @@
+use glam::Vec4;
+
+use super::binary::BinaryOrbit;
+use super::guides::GuideCircle;
+use super::orbits::OrbitalElements;
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Body, BodyGroup, EscapePolicy, ParamDescriptor, Rng, Simulation, SimulationError,
+    SimulationState, sanitize_parameter,
+};
+
+/// Kepler-16 AB (Doyle et al. 2011).
+const KEPLER_16: BinaryOrbit = BinaryOrbit {
+    primary_mass: 0.6897,
+    secondary_mass: 0.20255,
+    semi_major_axis: 0.22431,
+    eccentricity: 0.15944,
+};
+const PLANET_MASS: f32 = 3.18e-4;
+const PLANET_SEMI_MAJOR_AXIS: f32 = 0.7048;
+const DISK_INNER: f32 = 0.35;
+const DISK_OUTER: f32 = 1.5;
+
+/// Kepler-16-like pair of stars with a circumbinary planet and a massless debris
+/// disk; disk particles inside the critical radius are cleared out
+/// (AU, years, solar masses).
+pub struct CircumbinarySimulation {
+    pub eccentricity: f32,
+    pub disk_count: usize,
+    pub seed: u64,
+}
+
+impl Default for CircumbinarySimulation {
+    fn default() -> Self {
+        Self {
+            eccentricity: KEPLER_16.eccentricity,
+            disk_count: 1500,
+            seed: 16,
+        }
+    }
+}
+
+impl CircumbinarySimulation {
+    fn binary(&self) -> BinaryOrbit {
+        BinaryOrbit {
+            eccentricity: self.eccentricity,
+            ..KEPLER_16
+        }
+    }
+}
+
+impl Simulation for CircumbinarySimulation {
+    fn name(&self) -> &str {
+        "Circumbinary Planet"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let binary = self.binary();
+        let mut bodies = Vec::from(binary.bodies(
+            G_ASTRONOMICAL,
+            [0.03, 0.02],
+            [Vec4::new(1.0, 0.8, 0.5, 1.0), Vec4::new(1.0, 0.5, 0.3, 1.0)],
+        ));
+
+        let mu = G_ASTRONOMICAL * binary.total_mass();
+        let (position, velocity) =
+            OrbitalElements::circular(PLANET_SEMI_MAJOR_AXIS, 1.0).state_vectors(mu);
+        bodies.push(Body::new(
+            position,
+            velocity,
+            PLANET_MASS,
+            0.015,
+            Vec4::new(0.6, 0.7, 0.9, 1.0),
+        ));
+
+        let mut rng = Rng::new(self.seed);
+        for _ in 0..self.disk_count {
+            let radius = rng.range(DISK_INNER, DISK_OUTER);
+            let angle = rng.range(0.0, std::f32::consts::TAU);
+            let (position, velocity) = OrbitalElements::circular(radius, angle).state_vectors(mu);
+            bodies.push(Body::new(
+                position,
+                velocity,
+                0.0,
+                0.005,
+                Vec4::new(0.7, 0.6, 0.5, 0.8),
+            ));
+        }
+        bodies
+    }
+
+    fn guide_circles(&self) -> Vec<GuideCircle> {
+        vec![GuideCircle {
+            label: "stability limit",
+            center: glam::Vec3::ZERO,
+            radius: self.binary().critical_radius(),
+        }]
+    }
+
+    fn escape_policy(&self) -> EscapePolicy {
+        EscapePolicy {
+            radius: Some(10.0),
+            respawn_radius: None,
+        }
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        match index {
+            0 | 1 => BodyGroup::Star,
+            2 => BodyGroup::Planet,
+            _ => BodyGroup::Asteroid,
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        let binary = self.binary();
+        let period = (binary.semi_major_axis.powi(3) / binary.total_mass()).sqrt();
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            softening: 0.0,
+            dt: period / 1000.0,
+            substeps: 8,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "eccentricity",
+                0.0,
+                0.8,
+                defaults.eccentricity,
+                self.eccentricity,
+            ),
+            ParamDescriptor::integer(
+                "disk_count",
+                0,
+                20_000,
+                defaults.disk_count as u32,
+                self.disk_count as u32,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "eccentricity" => self.eccentricity = value,
+            _ => self.disk_count = value as usize,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
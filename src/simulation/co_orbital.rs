@@ -0,0 +1,136 @@
+use glam::{Vec3, Vec4};
+
+use super::{
+    Body, BodyGroup, ParamDescriptor, RotatingFrame, Simulation, SimulationError, SimulationState,
+    sanitize_parameter,
+};
+
+const PLANET_MASS: f32 = 1.0;
+const MEAN_RADIUS: f32 = 1.0;
+/// Moon masses, exaggerated about 10⁴× over Janus and Epimetheus so the
+/// horseshoe is wide and exchanges come every few dozen orbits.
+const INNER_MASS: f32 = 1.0e-4;
+const OUTER_MASS: f32 = 3.0e-5;
+
+/// Two moons sharing nearly the same orbit around a planet, Janus–Epimetheus
+/// style: each close approach swaps which one is on the inner, faster track
+/// (arbitrary units with G = 1). Best seen in the rotating frame.
+pub struct CoOrbitalSimulation {
+    /// Difference of the two orbital radii.
+    pub radial_separation: f32,
+    /// Initial lead of the outer moon, in degrees.
+    pub angular_separation: f32,
+}
+
+impl Default for CoOrbitalSimulation {
+    fn default() -> Self {
+        Self {
+            radial_separation: 0.008,
+            angular_separation: 180.0,
+        }
+    }
+}
+
+fn circular(radius: f32, angle: f32, color: Vec4, mass: f32) -> Body {
+    let (sin, cos) = angle.sin_cos();
+    let speed = (PLANET_MASS / radius).sqrt();
+    Body::new(
+        Vec3::new(cos, 0.0, sin) * radius,
+        Vec3::new(-sin, 0.0, cos) * speed,
+        mass,
+        0.01,
+        color,
+    )
+}
+
+impl CoOrbitalSimulation {
+    /// Mass-weighted orbital radius; the pair's guiding center moves at its mean motion.
+    fn guiding_radius(&self) -> f32 {
+        let half = 0.5 * self.radial_separation;
+        MEAN_RADIUS + half * (OUTER_MASS - INNER_MASS) / (OUTER_MASS + INNER_MASS)
+    }
+}
+
+impl Simulation for CoOrbitalSimulation {
+    fn name(&self) -> &str {
+        "Co-orbital Moons"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let half = 0.5 * self.radial_separation;
+        vec![
+            Body::new(
+                Vec3::ZERO,
+                Vec3::ZERO,
+                PLANET_MASS,
+                0.3,
+                Vec4::new(0.9, 0.8, 0.6, 1.0),
+            ),
+            circular(
+                MEAN_RADIUS - half,
+                0.0,
+                Vec4::new(0.8, 0.8, 1.0, 1.0),
+                INNER_MASS,
+            ),
+            circular(
+                MEAN_RADIUS + half,
+                self.angular_separation.to_radians(),
+                Vec4::new(1.0, 0.7, 0.7, 1.0),
+                OUTER_MASS,
+            ),
+        ]
+    }
+
+    fn rotating_frame(&self) -> Option<RotatingFrame> {
+        Some(RotatingFrame {
+            origin: 0,
+            angular_speed: (PLANET_MASS / self.guiding_radius().powi(3)).sqrt(),
+        })
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Planet
+        } else {
+            BodyGroup::Moon
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: 0.0,
+            dt: 0.01,
+            substeps: 16,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "radial_separation",
+                0.001,
+                0.05,
+                defaults.radial_separation,
+                self.radial_separation,
+            ),
+            ParamDescriptor::new(
+                "angular_separation",
+                5.0,
+                180.0,
+                defaults.angular_separation,
+                self.angular_separation,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "radial_separation" => self.radial_separation = value,
+            _ => self.angular_separation = value,
+        }
+        Ok(())
+    }
+}
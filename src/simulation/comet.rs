@@ -0,0 +1,143 @@
+use glam::{Vec3, Vec4};
+
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Body, BodyGroup, EmitterSpec, EscapePolicy, ParamDescriptor, Simulation, SimulationError,
+    SimulationState, sanitize_parameter,
+};
+
+/// Heliocentric distance the comet starts at, on its inbound leg.
+const START_DISTANCE: f32 = 2.0;
+
+/// A comet on an eccentric orbit around the Sun, shedding tail particles that
+/// the solar wind blows outward near perihelion (AU, years, solar masses).
+pub struct CometSimulation {
+    pub perihelion: f32,
+    pub eccentricity: f32,
+    /// Tail particles per year at 1 AU from the Sun.
+    pub emission_rate: f32,
+}
+
+impl Default for CometSimulation {
+    fn default() -> Self {
+        Self {
+            perihelion: 0.5,
+            eccentricity: 0.9,
+            emission_rate: 500.0,
+        }
+    }
+}
+
+impl Simulation for CometSimulation {
+    fn name(&self) -> &str {
+        "Comet"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let e = self.eccentricity;
+        let semi_latus_rectum = self.perihelion * (1.0 + e);
+        let apohelion = semi_latus_rectum / (1.0 - e);
+        let distance = START_DISTANCE.clamp(self.perihelion, apohelion);
+        // Inbound: negative true anomaly.
+        let anomaly = -((semi_latus_rectum / distance - 1.0) / e)
+            .clamp(-1.0, 1.0)
+            .acos();
+        let (sin, cos) = anomaly.sin_cos();
+        let speed = (G_ASTRONOMICAL / semi_latus_rectum).sqrt();
+
+        vec![
+            Body::new(
+                Vec3::ZERO,
+                Vec3::ZERO,
+                1.0,
+                0.05,
+                Vec4::new(1.0, 0.9, 0.4, 1.0),
+            ),
+            Body::new(
+                Vec3::new(cos, 0.0, sin) * distance,
+                Vec3::new(-sin, 0.0, e + cos) * speed,
+                1.0e-12,
+                0.01,
+                Vec4::new(0.8, 0.9, 1.0, 1.0),
+            ),
+        ]
+    }
+
+    fn emitters(&self) -> Vec<EmitterSpec> {
+        vec![EmitterSpec {
+            index: 1,
+            source: 0,
+            rate: self.emission_rate,
+            particle_mass: 1.0e-15,
+            particle_radius: 0.004,
+            color: Vec4::new(0.5, 0.7, 1.0, 0.6),
+            spread: 0.05,
+            lifetime: 0.5,
+            // About 1.5 times solar gravity, so the tail points away from the Sun.
+            wind_strength: 1.5 * G_ASTRONOMICAL,
+        }]
+    }
+
+    fn escape_policy(&self) -> EscapePolicy {
+        EscapePolicy {
+            radius: Some(50.0),
+            respawn_radius: None,
+        }
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Star
+        } else {
+            BodyGroup::Other
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            softening: 1.0e-4,
+            dt: 2.0e-4,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new("perihelion", 0.1, 2.0, defaults.perihelion, self.perihelion),
+            ParamDescriptor::new(
+                "eccentricity",
+                0.5,
+                0.98,
+                defaults.eccentricity,
+                self.eccentricity,
+            ),
+            ParamDescriptor::new(
+                "emission_rate",
+                0.0,
+                5000.0,
+                defaults.emission_rate,
+                self.emission_rate,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "perihelion" => self.perihelion = value,
+            "eccentricity" => self.eccentricity = value,
+            _ => self.emission_rate = value,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use super::Body;
+
+/// Damped spring contact between overlapping spheres, so loose aggregates such
+/// as rubble piles rest on each other instead of collapsing under gravity.
+///
+/// Both coefficients are per unit reduced mass, so a pair's contact oscillates
+/// at `sqrt(stiffness)` whatever the masses; keep `dt * sqrt(stiffness)` well below one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoftSphere {
+    /// Spring constant over reduced mass (1/time²).
+    pub stiffness: f32,
+    /// Damping coefficient over reduced mass (1/time).
+    pub damping: f32,
+}
+
+impl SoftSphere {
+    /// Contact acceleration of `body` from `other`; zero unless they overlap.
+    pub fn acceleration(&self, body: &Body, other: &Body) -> glam::Vec3 {
+        let offset = other.position - body.position;
+        let distance = offset.length();
+        let overlap = body.radius + other.radius - distance;
+        let total_mass = body.mass + other.mass;
+        if overlap <= 0.0 || distance == 0.0 || total_mass <= 0.0 {
+            return glam::Vec3::ZERO;
+        }
+        let normal = offset / distance;
+        let approach = (other.velocity - body.velocity).dot(normal);
+        let push = (self.stiffness * overlap - self.damping * approach).max(0.0);
+        -normal * (push * other.mass / total_mass)
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::VecDeque;
+
+use glam::Vec3;
+
+use super::{Body, SimulationState};
+
+/// Samples kept per history; older ones are dropped.
+const HISTORY_CAPACITY: usize = 2048;
+
+/// Inner orbit of a hierarchical system whose osculating elements are logged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InnerOrbit {
+    pub central: usize,
+    pub target: usize,
+    /// Body whose orbit around the inner pair defines the reference plane;
+    /// `None` uses the XZ plane.
+    pub perturber: Option<usize>,
+    /// Simulated time between samples.
+    pub sample_interval: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrbitSample {
+    pub time: f64,
+    pub eccentricity: f32,
+    /// Against the reference plane, in radians.
+    pub inclination: f32,
+}
+
+impl InnerOrbit {
+    pub fn sample(
+        &self,
+        bodies: &[Body],
+        gravitational_constant: f32,
+        time: f64,
+    ) -> Option<OrbitSample> {
+        let central = bodies.get(self.central)?;
+        let target = bodies.get(self.target)?;
+        let mu = gravitational_constant * (central.mass + target.mass);
+        let r = target.position - central.position;
+        let v = target.velocity - central.velocity;
+        let h = r.cross(v);
+        let eccentricity = v.cross(h) / mu - r.normalize_or_zero();
+
+        // Orbits in the XZ plane run from +X towards +Z, so their angular momentum is -Y.
+        let mut normal = Vec3::NEG_Y;
+        if let Some(perturber) = self.perturber.and_then(|index| bodies.get(index)) {
+            let pair_mass = central.mass + target.mass;
+            let center =
+                (central.position * central.mass + target.position * target.mass) / pair_mass;
+            let velocity =
+                (central.velocity * central.mass + target.velocity * target.mass) / pair_mass;
+            normal = (perturber.position - center).cross(perturber.velocity - velocity);
+        }
+
+        Some(OrbitSample {
+            time,
+            eccentricity: eccentricity.length(),
+            inclination: h.angle_between(normal),
+        })
+    }
+}
+
+/// Bounded time series of inner-orbit samples for the diagnostics plot.
+#[derive(Clone, Debug)]
+pub struct OrbitHistory {
+    pub orbit: InnerOrbit,
+    samples: VecDeque<OrbitSample>,
+    next_sample: f64,
+}
+
+impl OrbitHistory {
+    pub fn new(orbit: InnerOrbit) -> Self {
+        Self {
+            orbit,
+            samples: VecDeque::with_capacity(HISTORY_CAPACITY),
+            next_sample: 0.0,
+        }
+    }
+
+    /// Appends a sample once `sample_interval` has elapsed since the last one.
+    pub fn record(&mut self, bodies: &[Body], gravitational_constant: f32, time: f64) {
+        if time < self.next_sample {
+            return;
+        }
+        self.next_sample = time + f64::from(self.orbit.sample_interval);
+        if let Some(sample) = self.orbit.sample(bodies, gravitational_constant, time) {
+            if self.samples.len() == HISTORY_CAPACITY {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(sample);
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &OrbitSample> {
+        self.samples.iter()
+    }
+}
+
+/// Cluster-wide dynamical state sampled over time, for watching relaxation
+/// and core collapse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClusterDiagnostics {
+    /// Simulated time between samples; each costs an O(N²) potential sum.
+    pub sample_interval: f32,
+}
+
+/// Mass fractions whose enclosing radii are tracked.
+pub const LAGRANGIAN_FRACTIONS: [f32; 3] = [0.1, 0.5, 0.9];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ClusterSample {
+    pub time: f64,
+    /// 2T / |W|; one in virial equilibrium.
+    pub virial_ratio: f32,
+    /// Radii about the centre of mass enclosing `LAGRANGIAN_FRACTIONS` of the mass.
+    pub lagrangian_radii: [f32; 3],
+}
+
+impl ClusterDiagnostics {
+    pub fn sample(&self, bodies: &[Body], state: &SimulationState) -> Option<ClusterSample> {
+        let total_mass: f32 = bodies.iter().map(|body| body.mass).sum();
+        if total_mass <= 0.0 {
+            return None;
+        }
+        let center = bodies
+            .iter()
+            .map(|body| body.position * body.mass)
+            .sum::<Vec3>()
+            / total_mass;
+        let bulk_velocity = bodies
+            .iter()
+            .map(|body| body.velocity * body.mass)
+            .sum::<Vec3>()
+            / total_mass;
+
+        let kinetic: f32 = bodies
+            .iter()
+            .map(|body| 0.5 * body.mass * (body.velocity - bulk_velocity).length_squared())
+            .sum();
+        let softening_sq = state.softening * state.softening;
+        let mut potential = 0.0;
+        for (i, body) in bodies.iter().enumerate() {
+            for other in &bodies[i + 1..] {
+                let distance_sq = body.position.distance_squared(other.position) + softening_sq;
+                potential -= body.mass * other.mass / distance_sq.sqrt();
+            }
+        }
+        potential *= state.gravitational_constant;
+
+        let mut shells: Vec<(f32, f32)> = bodies
+            .iter()
+            .map(|body| (body.position.distance(center), body.mass))
+            .collect();
+        shells.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut lagrangian_radii = [0.0; 3];
+        let mut enclosed = 0.0;
+        let mut next = 0;
+        for (radius, mass) in shells {
+            enclosed += mass;
+            while next < LAGRANGIAN_FRACTIONS.len()
+                && enclosed >= LAGRANGIAN_FRACTIONS[next] * total_mass
+            {
+                lagrangian_radii[next] = radius;
+                next += 1;
+            }
+        }
+
+        Some(ClusterSample {
+            time: state.time,
+            virial_ratio: if potential < 0.0 {
+                2.0 * kinetic / -potential
+            } else {
+                0.0
+            },
+            lagrangian_radii,
+        })
+    }
+}
+
+/// Above this many bodies the O(N²) energy sum is not tracked.
+const ENERGY_MAX_BODIES: usize = 4096;
+
+/// Kinetic plus softened pairwise potential energy, summed in f64.
+pub fn total_energy(bodies: &[Body], state: &SimulationState) -> f64 {
+    let kinetic: f64 = bodies
+        .iter()
+        .map(|body| 0.5 * f64::from(body.mass) * f64::from(body.velocity.length_squared()))
+        .sum();
+    let softening_sq = f64::from(state.softening).powi(2);
+    let mut potential = 0.0;
+    for (i, body) in bodies.iter().enumerate() {
+        for other in &bodies[i + 1..] {
+            let distance_sq =
+                f64::from(body.position.distance_squared(other.position)) + softening_sq;
+            potential -= f64::from(body.mass) * f64::from(other.mass) / distance_sq.sqrt();
+        }
+    }
+    kinetic + f64::from(state.gravitational_constant) * potential
+}
+
+/// Relative change of `total_energy` since the scenario was loaded, the usual
+/// check of integrator accuracy. External potentials, contacts and bodies
+/// added or removed are not accounted for, so they show up as drift too.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnergyMonitor {
+    initial: f64,
+    drift: f32,
+}
+
+impl EnergyMonitor {
+    /// `None` for systems too large to sum or without any energy to compare to.
+    pub fn new(bodies: &[Body], state: &SimulationState) -> Option<Self> {
+        if bodies.len() > ENERGY_MAX_BODIES {
+            return None;
+        }
+        let initial = total_energy(bodies, state);
+        (initial != 0.0 && initial.is_finite()).then_some(Self {
+            initial,
+            drift: 0.0,
+        })
+    }
+
+    pub fn observe(&mut self, bodies: &[Body], state: &SimulationState) {
+        if bodies.len() <= ENERGY_MAX_BODIES {
+            let energy = total_energy(bodies, state);
+            self.drift = ((energy - self.initial) / self.initial.abs()) as f32;
+        }
+    }
+
+    /// (E − E₀) / |E₀| at the last observation.
+    pub fn drift(&self) -> f32 {
+        self.drift
+    }
+}
+
+/// Bounded time series of cluster samples for the diagnostics plot.
+#[derive(Clone, Debug)]
+pub struct ClusterHistory {
+    pub diagnostics: ClusterDiagnostics,
+    samples: VecDeque<ClusterSample>,
+    next_sample: f64,
+}
+
+impl ClusterHistory {
+    pub fn new(diagnostics: ClusterDiagnostics) -> Self {
+        Self {
+            diagnostics,
+            samples: VecDeque::with_capacity(HISTORY_CAPACITY),
+            next_sample: 0.0,
+        }
+    }
+
+    /// Appends a sample once `sample_interval` has elapsed since the last one.
+    pub fn record(&mut self, bodies: &[Body], state: &SimulationState) {
+        if state.time < self.next_sample {
+            return;
+        }
+        self.next_sample = state.time + f64::from(self.diagnostics.sample_interval);
+        if let Some(sample) = self.diagnostics.sample(bodies, state) {
+            if self.samples.len() == HISTORY_CAPACITY {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(sample);
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &ClusterSample> {
+        self.samples.iter()
+    }
+}
+
+/// Osculating semi-major axis of `body` around `central`; negative when unbound.
+pub fn semi_major_axis(central: &Body, body: &Body, gravitational_constant: f32) -> f32 {
+    let mu = gravitational_constant * (central.mass + body.mass);
+    let distance = body.position.distance(central.position);
+    let speed_sq = (body.velocity - central.velocity).length_squared();
+    1.0 / (2.0 / distance - speed_sq / mu)
+}
+
+/// Binning of the bodies' semi-major axes around a central body, for the
+/// resonance-gap overlay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramSpec {
+    pub central: usize,
+    /// Bodies before this index (the central body, planets) are left out.
+    pub first_body: usize,
+    pub min: f32,
+    pub max: f32,
+    pub bins: usize,
+    /// Labelled reference positions drawn over the bars, e.g. resonances.
+    pub markers: Vec<(&'static str, f32)>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+    pub min: f32,
+    pub max: f32,
+    pub counts: Vec<u32>,
+    pub markers: Vec<(&'static str, f32)>,
+}
+
+impl HistogramSpec {
+    /// Recomputes `histogram` in place so its allocation is reused across frames.
+    pub fn fill(&self, bodies: &[Body], gravitational_constant: f32, histogram: &mut Histogram) {
+        histogram.min = self.min;
+        histogram.max = self.max;
+        histogram.counts.clear();
+        histogram.counts.resize(self.bins, 0);
+        histogram.markers.clone_from(&self.markers);
+        let Some(central) = bodies.get(self.central) else {
+            return;
+        };
+        let scale = self.bins as f32 / (self.max - self.min);
+        for body in bodies.iter().skip(self.first_body) {
+            let a = semi_major_axis(central, body, gravitational_constant);
+            if a >= self.min && a < self.max {
+                let bin = (((a - self.min) * scale) as usize).min(self.bins - 1);
+                histogram.counts[bin] += 1;
+            }
+        }
+    }
+}
+
+/// Mean longitude of `body` around `central` for an orbit in the XZ plane,
+/// measured from +X towards +Z, in radians.
+pub fn mean_longitude(central: &Body, body: &Body, gravitational_constant: f32) -> f32 {
+    let mu = gravitational_constant * (central.mass + body.mass);
+    let r = body.position - central.position;
+    let v = body.velocity - central.velocity;
+    let eccentricity_vector = v.cross(r.cross(v)) / mu - r.normalize_or_zero();
+    let eccentricity = eccentricity_vector.length().min(0.999);
+    let longitude = r.z.atan2(r.x);
+    let periapsis = eccentricity_vector.z.atan2(eccentricity_vector.x);
+    let true_anomaly = longitude - periapsis;
+    let eccentric_anomaly = 2.0
+        * (((1.0 - eccentricity) / (1.0 + eccentricity)).sqrt() * (0.5 * true_anomaly).tan())
+            .atan();
+    periapsis + eccentric_anomaly - eccentricity * eccentric_anomaly.sin()
+}
+
+/// Resonant argument Σ kᵢ λᵢ over the mean longitudes of a few bodies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResonanceAngle {
+    pub label: &'static str,
+    pub central: usize,
+    /// Body index and integer coefficient pairs.
+    pub terms: &'static [(usize, i32)],
+}
+
+impl ResonanceAngle {
+    /// Current value in radians, wrapped to (-π, π].
+    pub fn evaluate(&self, bodies: &[Body], gravitational_constant: f32) -> Option<f32> {
+        let central = bodies.get(self.central)?;
+        let mut angle = 0.0;
+        for &(index, coefficient) in self.terms {
+            let body = bodies.get(index)?;
+            angle += coefficient as f32 * mean_longitude(central, body, gravitational_constant);
+        }
+        let wrapped = angle.rem_euclid(std::f32::consts::TAU);
+        Some(if wrapped > std::f32::consts::PI {
+            wrapped - std::f32::consts::TAU
+        } else {
+            wrapped
+        })
+    }
+}
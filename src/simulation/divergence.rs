@@ -0,0 +1,29 @@
+use super::Body;
+
+/// First body whose position or velocity is no longer finite.
+pub fn first_non_finite(bodies: &[Body]) -> Option<usize> {
+    bodies
+        .iter()
+        .position(|body| !(body.position.is_finite() && body.velocity.is_finite()))
+}
+
+/// Where a run first produced NaN or infinite values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Divergence {
+    pub body: usize,
+    /// Step after which the body was first non-finite.
+    pub step: u64,
+    pub time: f64,
+}
+
+/// What the worker does once a step diverges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecoveryPolicy {
+    /// Log it and keep stepping.
+    Report,
+    /// Log it and pause with the broken bodies in place for inspection.
+    Pause,
+    /// Log it, restore the last good snapshot and pause there.
+    #[default]
+    Rollback,
+}
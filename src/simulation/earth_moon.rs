@@ -0,0 +1,132 @@
+use glam::{Vec3, Vec4};
+
+use super::orbits::{OrbitalElements, about_barycenter};
+use super::{
+    Body, BodyGroup, ParamDescriptor, PhysicalUnits, Simulation, SimulationError, SimulationState,
+    TidalSpin, sanitize_parameter,
+};
+
+const G: f32 = 6.674e-11;
+const EARTH_MASS: f32 = 5.972e24;
+const MOON_MASS: f32 = 7.342e22;
+const EARTH_RADIUS: f32 = 6.371e6;
+const MOON_RADIUS: f32 = 1.737e6;
+const EARTH_MOON_DISTANCE: f32 = 3.844e8;
+const DAY: f32 = 86_400.0;
+/// Sidereal day in seconds.
+const EARTH_ROTATION: f32 = 86_164.0;
+/// Tidal lag exaggerated some ten-million-fold so the Moon locks within a few orbits.
+const TIDAL_TIME_LAG: f32 = 5.0e9;
+
+/// Earth and Moon around their barycenter, authored in SI units and run in
+/// N-body units. The Moon starts spinning faster than it orbits and tides
+/// brake it into synchronous rotation.
+pub struct EarthMoonSimulation {
+    /// Moon mass as a fraction of Earth's.
+    pub mass_ratio: f32,
+    /// Initial rotation period of the Moon in days.
+    pub moon_rotation_days: f32,
+}
+
+impl Default for EarthMoonSimulation {
+    fn default() -> Self {
+        Self {
+            mass_ratio: MOON_MASS / EARTH_MASS,
+            moon_rotation_days: 5.0,
+        }
+    }
+}
+
+impl Simulation for EarthMoonSimulation {
+    fn name(&self) -> &str {
+        "Earth-Moon"
+    }
+
+    fn units(&self) -> Option<PhysicalUnits> {
+        Some(PhysicalUnits::SI)
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let moon_mass = EARTH_MASS * self.mass_ratio;
+        let relative = OrbitalElements::circular(EARTH_MOON_DISTANCE, 0.0)
+            .state_vectors(G * (EARTH_MASS + moon_mass));
+        let [(earth, earth_velocity), (moon, moon_velocity)] =
+            about_barycenter(EARTH_MASS, moon_mass, relative);
+
+        vec![
+            Body::new(
+                earth,
+                earth_velocity,
+                EARTH_MASS,
+                EARTH_RADIUS,
+                Vec4::new(0.2, 0.4, 1.0, 1.0),
+            ),
+            Body::new(
+                moon,
+                moon_velocity,
+                moon_mass,
+                MOON_RADIUS,
+                Vec4::new(0.8, 0.8, 0.8, 1.0),
+            ),
+        ]
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Planet
+        } else {
+            BodyGroup::Moon
+        }
+    }
+
+    /// Both spin prograde, about −Y like the orbit.
+    fn body_spin(&self, index: usize) -> Vec3 {
+        let period = if index == 0 {
+            EARTH_ROTATION
+        } else {
+            self.moon_rotation_days * DAY
+        };
+        Vec3::NEG_Y * (std::f32::consts::TAU / period)
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G,
+            softening: 1.0e3,
+            dt: 60.0,
+            tides: Some(TidalSpin {
+                time_lag: TIDAL_TIME_LAG,
+            }),
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "mass_ratio",
+                0.001,
+                1.0,
+                defaults.mass_ratio,
+                self.mass_ratio,
+            ),
+            ParamDescriptor::new(
+                "moon_rotation_days",
+                0.5,
+                60.0,
+                defaults.moon_rotation_days,
+                self.moon_rotation_days,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "mass_ratio" => self.mass_ratio = value,
+            _ => self.moon_rotation_days = value,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,741 @@
+use bevy_ecs::prelude::*;
+use glam::{Vec3, Vec4};
+
+use super::emitter::{Emitter, Lifetime, SolarWind};
+use super::escape::{EscapePolicy, respawn_on_ring};
+use super::evolution::StarState;
+use super::groups::body_flags;
+use super::physics::{self, Forces};
+use super::potentials::ExternalPotential;
+use super::snapshot::Snapshot;
+use super::spacecraft::{Spacecraft, SpacecraftStatus, ThrustInput};
+use super::spin::advance_phase;
+use super::supernova::Supernova;
+use super::{Body, BodyGroup, BodyKind, BodyProperties, Rng, Simulation, SimulationState};
+
+/// Position of an entity's body in the packed array the physics and GPU buffers use.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BodyIndex(pub usize);
+
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct Name(pub String);
+
+/// Marks a body added by an undoable edit, so the edit can find it again
+/// after removals have reshuffled the packed indices.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EditTag(pub u64);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Group(pub BodyGroup);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Kind(pub BodyKind);
+
+/// Constant acceleration applied on top of gravity.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ScriptedForce {
+    pub acceleration: Vec3,
+}
+
+/// Packed body data, ordered by `BodyIndex`.
+#[derive(Resource, Debug, Default)]
+pub struct PackedBodies {
+    pub bodies: Vec<Body>,
+    /// Per-body flag bits (group membership) uploaded next to the bodies.
+    pub flags: Vec<u32>,
+    /// Per-body input accelerations read by the integrator (xyz; w unused).
+    pub accelerations: Vec<Vec4>,
+    /// Per-body angular velocity (xyz) and rotation phase in radians (w).
+    pub spins: Vec<Vec4>,
+    /// Per-body stellar age and luminosity; zero mass for bodies that do not evolve.
+    pub stars: Vec<StarState>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpawnRequest {
+    pub body: Body,
+    pub name: Option<String>,
+    pub group: BodyGroup,
+    pub tag: Option<EditTag>,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct SpawnQueue(pub Vec<SpawnRequest>);
+
+/// Body that joins the simulation once the simulated time reaches `time`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arrival {
+    pub time: f64,
+    pub body: Body,
+    pub group: BodyGroup,
+}
+
+/// Scheduled arrivals that have not entered yet.
+#[derive(Resource, Debug, Default)]
+pub struct Arrivals(pub Vec<Arrival>);
+
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Escape(pub EscapePolicy);
+
+/// Randomness shared by the systems (respawns, particle emission).
+#[derive(Resource, Clone, Debug)]
+pub struct WorldRng(pub Rng);
+
+/// Packed indices to drop at the end of this run, besides escaped bodies.
+#[derive(Resource, Debug, Default)]
+pub struct PendingRemovals(pub Vec<usize>);
+
+/// Simulated time covered by the steps since the systems last ran.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct StepDuration(pub f32);
+
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Physics(pub SimulationState);
+
+/// ECS-backed body storage: per-body behaviour lives in components and systems
+/// while the physics still iterates one packed array.
+pub struct BodyWorld {
+    world: World,
+    schedule: Schedule,
+}
+
+impl BodyWorld {
+    pub fn new(bodies: Vec<Body>, groups: Vec<BodyGroup>, kinds: Vec<BodyKind>) -> Self {
+        debug_assert_eq!(bodies.len(), groups.len());
+        debug_assert_eq!(bodies.len(), kinds.len());
+        let mut world = World::new();
+        for (index, (&group, &kind)) in groups.iter().zip(&kinds).enumerate() {
+            world.spawn((BodyIndex(index), Group(group), Kind(kind)));
+        }
+        world.insert_resource(PackedBodies {
+            accelerations: vec![Vec4::ZERO; bodies.len()],
+            spins: vec![Vec4::ZERO; bodies.len()],
+            stars: vec![StarState::default(); bodies.len()],
+            bodies,
+            flags: groups
+                .iter()
+                .zip(&kinds)
+                .map(|(&group, &kind)| body_flags(group, kind))
+                .collect(),
+        });
+        world.init_resource::<SpawnQueue>();
+        world.init_resource::<Arrivals>();
+        world.init_resource::<Escape>();
+        world.insert_resource(WorldRng(Rng::new(0x5EED)));
+        world.init_resource::<PendingRemovals>();
+        world.init_resource::<StepDuration>();
+        world.init_resource::<Physics>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                release_arrivals,
+                spawn_bodies,
+                sync_group_flags,
+                integrate_spins,
+                evolve_stars,
+                emit_particles,
+                expire_bodies,
+                update_spacecraft,
+                apply_solar_wind,
+                gather_input_accelerations,
+                remove_bodies,
+            )
+                .chain(),
+        );
+
+        Self { world, schedule }
+    }
+
+    /// Builds the world from a scenario's initial bodies and their groups.
+    pub fn from_simulation(simulation: &dyn Simulation) -> Self {
+        let bodies = simulation.initialize_bodies();
+        let groups = (0..bodies.len())
+            .map(|index| simulation.body_group(index))
+            .collect();
+        let kinds = (0..bodies.len())
+            .map(|index| simulation.body_kind(index))
+            .collect();
+        let mut world = Self::new(bodies, groups, kinds);
+        let packed = world.world.resource_mut::<PackedBodies>().into_inner();
+        for (index, (spin, star)) in packed.spins.iter_mut().zip(&mut packed.stars).enumerate() {
+            *spin = simulation.body_spin(index).extend(0.0);
+            let mass = simulation.stellar_mass(index);
+            if mass > 0.0 {
+                *star = StarState::new(mass, &packed.bodies[index]);
+            }
+        }
+        world.set_escape_policy(simulation.escape_policy());
+        world.world.resource_mut::<Arrivals>().0 = simulation.arrivals();
+        if let Some(spec) = simulation.spacecraft() {
+            world.attach(
+                spec.index,
+                (
+                    Spacecraft::new(&spec),
+                    ScriptedForce {
+                        acceleration: Vec3::ZERO,
+                    },
+                ),
+            );
+        }
+        for spec in simulation.emitters() {
+            if let Some(source) = world.entity(spec.source) {
+                world.attach(spec.index, Emitter::new(spec, source));
+            }
+        }
+        for index in 0..world.bodies().len() {
+            if let Some(name) = simulation.body_name(index) {
+                world.attach(index, Name(name.to_owned()));
+            }
+        }
+        world
+    }
+
+    pub fn flags(&self) -> &[u32] {
+        &self.world.resource::<PackedBodies>().flags
+    }
+
+    pub fn bodies(&self) -> &[Body] {
+        &self.world.resource::<PackedBodies>().bodies
+    }
+
+    pub fn accelerations(&self) -> &[Vec4] {
+        &self.world.resource::<PackedBodies>().accelerations
+    }
+
+    pub fn spins(&self) -> &[Vec4] {
+        &self.world.resource::<PackedBodies>().spins
+    }
+
+    pub fn stars(&self) -> &[StarState] {
+        &self.world.resource::<PackedBodies>().stars
+    }
+
+    pub fn spins_mut(&mut self) -> &mut [Vec4] {
+        &mut self.world.resource_mut::<PackedBodies>().into_inner().spins
+    }
+
+    /// The entity owning the `index`-th body.
+    fn entity(&mut self, index: usize) -> Option<Entity> {
+        let mut query = self.world.query::<(Entity, &BodyIndex)>();
+        query
+            .iter(&self.world)
+            .find(|(_, body_index)| body_index.0 == index)
+            .map(|(entity, _)| entity)
+    }
+
+    /// Inserts components on the entity owning the `index`-th body.
+    pub fn attach(&mut self, index: usize, bundle: impl Bundle) {
+        if let Some(entity) = self.entity(index) {
+            self.world.entity_mut(entity).insert(bundle);
+        }
+    }
+
+    /// Advances the packed bodies one step with the current input accelerations.
+    pub fn step_physics(&mut self, state: &mut SimulationState, potentials: &[ExternalPotential]) {
+        let packed = self.world.resource_mut::<PackedBodies>().into_inner();
+        let forces = Forces {
+            potentials,
+            per_body: &packed.accelerations,
+            flags: &packed.flags,
+        };
+        physics::step(&mut packed.bodies, state, &forces);
+    }
+
+    /// Explodes the `index`-th body (the most massive if `None`): neighbours are
+    /// kicked now and the progenitor is removed on the next `run_systems`.
+    pub fn detonate(
+        &mut self,
+        index: Option<usize>,
+        gravitational_constant: f32,
+    ) -> Option<Supernova> {
+        let packed = self.world.resource_mut::<PackedBodies>().into_inner();
+        let index = index.or_else(|| Supernova::most_massive(&packed.bodies))?;
+        let supernova = Supernova::new(&packed.bodies, index, gravitational_constant)?;
+        supernova.apply(&mut packed.bodies);
+        self.world.resource_mut::<PendingRemovals>().0.push(index);
+        Some(supernova)
+    }
+
+    pub fn set_thrust(&mut self, input: ThrustInput) {
+        let mut query = self.world.query::<&mut Spacecraft>();
+        for mut spacecraft in query.iter_mut(&mut self.world) {
+            spacecraft.throttle = input;
+        }
+    }
+
+    pub fn spacecraft_status(&mut self) -> Option<SpacecraftStatus> {
+        let mut query = self.world.query::<(&BodyIndex, &Spacecraft)>();
+        query
+            .iter(&self.world)
+            .next()
+            .map(|(index, spacecraft)| SpacecraftStatus {
+                index: index.0,
+                fuel: spacecraft.fuel,
+                capacity: spacecraft.capacity,
+                thrusting: spacecraft.fuel > 0.0 && !spacecraft.throttle.is_idle(),
+            })
+    }
+
+    pub fn bodies_mut(&mut self) -> &mut [Body] {
+        &mut self
+            .world
+            .resource_mut::<PackedBodies>()
+            .into_inner()
+            .bodies
+    }
+
+    pub fn snapshot(&self, state: &SimulationState) -> Snapshot {
+        let packed = self.world.resource::<PackedBodies>();
+        Snapshot {
+            bodies: packed.bodies.clone(),
+            flags: packed.flags.clone(),
+            spins: packed.spins.clone(),
+            stars: packed.stars.clone(),
+            state: *state,
+        }
+    }
+
+    /// Puts the bodies back as captured; the caller restores the state. Entities
+    /// are kept when the body count still matches and rebuilt from the flags
+    /// otherwise, which drops per-body components such as spacecraft.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        let mut query = self.world.query_filtered::<Entity, With<BodyIndex>>();
+        let entities: Vec<Entity> = query.iter(&self.world).collect();
+        if entities.len() != snapshot.bodies.len() {
+            for entity in entities {
+                self.world.despawn(entity);
+            }
+            for (index, &flags) in snapshot.flags.iter().enumerate() {
+                self.world.spawn((
+                    BodyIndex(index),
+                    Group(BodyGroup::from_flags(flags)),
+                    Kind(BodyKind::from_flags(flags)),
+                ));
+            }
+        }
+        self.world.resource_mut::<SpawnQueue>().0.clear();
+        self.world.resource_mut::<PendingRemovals>().0.clear();
+        let mut packed = self.world.resource_mut::<PackedBodies>();
+        packed.bodies.clone_from(&snapshot.bodies);
+        packed.flags.clone_from(&snapshot.flags);
+        packed.spins.clone_from(&snapshot.spins);
+        packed.stars.clone_from(&snapshot.stars);
+        packed.accelerations.clear();
+        packed
+            .accelerations
+            .resize(snapshot.bodies.len(), Vec4::ZERO);
+    }
+
+    /// Queues a body; it joins the packed array on the next `run_systems`.
+    pub fn queue_spawn(&mut self, body: Body, name: Option<String>, group: BodyGroup) {
+        self.world
+            .resource_mut::<SpawnQueue>()
+            .0
+            .push(SpawnRequest {
+                body,
+                name,
+                group,
+                tag: None,
+            });
+    }
+
+    /// Queues a body for an undoable edit; see `find_tagged`.
+    pub fn queue_tagged_spawn(
+        &mut self,
+        body: Body,
+        name: Option<String>,
+        group: BodyGroup,
+        tag: EditTag,
+    ) {
+        self.world
+            .resource_mut::<SpawnQueue>()
+            .0
+            .push(SpawnRequest {
+                body,
+                name,
+                group,
+                tag: Some(tag),
+            });
+    }
+
+    /// Names of the bodies that have one, by index.
+    pub fn names(&mut self) -> Vec<(usize, String)> {
+        let mut query = self.world.query::<(&BodyIndex, &Name)>();
+        query
+            .iter(&self.world)
+            .map(|(index, name)| (index.0, name.0.clone()))
+            .collect()
+    }
+
+    /// Current index of the body spawned with `tag`, if it still exists.
+    pub fn find_tagged(&mut self, tag: EditTag) -> Option<usize> {
+        let mut query = self.world.query::<(&BodyIndex, &EditTag)>();
+        query
+            .iter(&self.world)
+            .find(|(_, body_tag)| **body_tag == tag)
+            .map(|(index, _)| index.0)
+    }
+
+    /// Tag of the `index`-th body, giving it `tag` if it has none yet.
+    pub fn ensure_tag(&mut self, index: usize, tag: EditTag) -> Option<EditTag> {
+        let mut query = self.world.query::<(Entity, &BodyIndex, Option<&EditTag>)>();
+        let (entity, existing) = query
+            .iter(&self.world)
+            .find(|(_, body_index, _)| body_index.0 == index)
+            .map(|(entity, _, existing)| (entity, existing.copied()))?;
+        if existing.is_none() {
+            self.world.entity_mut(entity).insert(tag);
+        }
+        Some(existing.unwrap_or(tag))
+    }
+
+    /// Overwrites the editable fields of the `index`-th body, returning the
+    /// previous values. The caller writes the change to the GPU buffer.
+    pub fn edit_body(
+        &mut self,
+        index: usize,
+        properties: BodyProperties,
+    ) -> Option<BodyProperties> {
+        let body = self.bodies_mut().get_mut(index)?;
+        let before = BodyProperties::of(body);
+        properties.apply(body);
+        Some(before)
+    }
+
+    /// Puts the `index`-th body at `position`, returning where it was. The
+    /// caller writes the change to the GPU buffer.
+    pub fn move_body(&mut self, index: usize, position: Vec3) -> Option<Vec3> {
+        let body = self.bodies_mut().get_mut(index)?;
+        Some(std::mem::replace(&mut body.position, position))
+    }
+
+    /// Removes the `index`-th body on the next `run_systems`, returning it,
+    /// its name and its group as they are now.
+    pub fn queue_removal(&mut self, index: usize) -> Option<(Body, Option<String>, BodyGroup)> {
+        let packed = self.world.resource::<PackedBodies>();
+        let body = *packed.bodies.get(index)?;
+        let group = BodyGroup::from_flags(packed.flags[index]);
+        let name = self
+            .names()
+            .into_iter()
+            .find(|&(named, _)| named == index)
+            .map(|(_, name)| name);
+        self.world.resource_mut::<PendingRemovals>().0.push(index);
+        Some((body, name, group))
+    }
+
+    pub fn set_escape_policy(&mut self, policy: EscapePolicy) {
+        self.world.resource_mut::<Escape>().0 = policy;
+    }
+
+    pub fn run_systems(&mut self, dt: f32, state: &SimulationState) {
+        self.world.resource_mut::<StepDuration>().0 = dt;
+        self.world.resource_mut::<Physics>().0 = *state;
+        self.schedule.run(&mut self.world);
+    }
+}
+
+/// Queues the arrivals that are due, drifted by however far the tick overshot
+/// their entry time.
+fn release_arrivals(
+    physics: Res<Physics>,
+    mut arrivals: ResMut<Arrivals>,
+    mut queue: ResMut<SpawnQueue>,
+) {
+    let time = physics.0.time;
+    for arrival in arrivals.0.extract_if(.., |arrival| arrival.time <= time) {
+        let mut body = arrival.body;
+        body.position += body.velocity * (time - arrival.time) as f32;
+        queue.0.push(SpawnRequest {
+            body,
+            name: None,
+            group: arrival.group,
+            tag: None,
+        });
+    }
+}
+
+fn spawn_bodies(
+    mut commands: Commands,
+    mut queue: ResMut<SpawnQueue>,
+    mut packed: ResMut<PackedBodies>,
+) {
+    if queue.0.is_empty() {
+        return;
+    }
+    for request in queue.0.drain(..) {
+        let mut entity = spawn_packed(&mut commands, &mut packed, request.body, request.group);
+        if let Some(name) = request.name {
+            entity.insert(Name(name));
+        }
+        if let Some(tag) = request.tag {
+            entity.insert(tag);
+        }
+    }
+}
+
+/// Appends a body to the packed arrays and spawns the entity that owns it.
+fn spawn_packed<'a>(
+    commands: &'a mut Commands,
+    packed: &mut PackedBodies,
+    body: Body,
+    group: BodyGroup,
+) -> EntityCommands<'a> {
+    let kind = group.default_kind();
+    let entity = commands.spawn((BodyIndex(packed.bodies.len()), Group(group), Kind(kind)));
+    packed.bodies.push(body);
+    packed.flags.push(body_flags(group, kind));
+    packed.accelerations.push(Vec4::ZERO);
+    packed.spins.push(Vec4::ZERO);
+    packed.stars.push(StarState::default());
+    entity
+}
+
+type FlagsChanged = Or<(Changed<Group>, Changed<Kind>)>;
+
+fn sync_group_flags(
+    query: Query<(&BodyIndex, &Group, &Kind), FlagsChanged>,
+    mut packed: ResMut<PackedBodies>,
+) {
+    for (index, group, kind) in &query {
+        if let Some(flags) = packed.flags.get_mut(index.0) {
+            *flags = body_flags(group.0, kind.0);
+        }
+    }
+}
+
+/// Applies tidal torques to the spins and turns every body by its spin.
+fn integrate_spins(dt: Res<StepDuration>, physics: Res<Physics>, mut packed: ResMut<PackedBodies>) {
+    let packed = packed.as_mut();
+    let state = &physics.0;
+    if let Some(tides) = &state.tides {
+        tides.relax(
+            &mut packed.spins,
+            &packed.bodies,
+            state.gravitational_constant,
+            dt.0,
+        );
+    }
+    for spin in &mut packed.spins {
+        advance_phase(spin, dt.0);
+    }
+}
+
+/// Ages the stars and redraws their bodies.
+fn evolve_stars(dt: Res<StepDuration>, physics: Res<Physics>, mut packed: ResMut<PackedBodies>) {
+    let Some(evolution) = &physics.0.stellar_evolution else {
+        return;
+    };
+    let packed = packed.as_mut();
+    for (star, body) in packed.stars.iter_mut().zip(&mut packed.bodies) {
+        evolution.advance(star, body, dt.0);
+    }
+}
+
+/// Releases particles from emitters at a rate rising as they near their source.
+fn emit_particles(
+    mut commands: Commands,
+    mut query: Query<(&BodyIndex, &mut Emitter)>,
+    indices: Query<&BodyIndex>,
+    dt: Res<StepDuration>,
+    mut rng: ResMut<WorldRng>,
+    mut packed: ResMut<PackedBodies>,
+) {
+    for (index, mut emitter) in &mut query {
+        let source = indices.get(emitter.source).ok();
+        let source = source.and_then(|source| packed.bodies.get(source.0));
+        let (Some(&parent), Some(source)) = (packed.bodies.get(index.0), source) else {
+            continue;
+        };
+        let distance = parent.position.distance(source.position);
+        let spec = emitter.spec;
+        for _ in 0..emitter.advance(distance, dt.0) {
+            let particle = emitter.particle(&parent, &mut rng.0);
+            spawn_packed(&mut commands, &mut packed, particle, BodyGroup::Gas).insert((
+                Lifetime(spec.lifetime),
+                SolarWind {
+                    source: emitter.source,
+                    strength: spec.wind_strength,
+                },
+                ScriptedForce {
+                    acceleration: Vec3::ZERO,
+                },
+            ));
+        }
+    }
+}
+
+fn expire_bodies(
+    mut query: Query<(&BodyIndex, &mut Lifetime)>,
+    dt: Res<StepDuration>,
+    mut removals: ResMut<PendingRemovals>,
+) {
+    for (index, mut lifetime) in &mut query {
+        lifetime.0 -= dt.0;
+        if lifetime.0 <= 0.0 {
+            removals.0.push(index.0);
+        }
+    }
+}
+
+fn apply_solar_wind(
+    mut query: Query<(&BodyIndex, &SolarWind, &mut ScriptedForce)>,
+    indices: Query<&BodyIndex>,
+    packed: Res<PackedBodies>,
+) {
+    for (index, wind, mut force) in &mut query {
+        let source = indices.get(wind.source).ok();
+        let source = source.and_then(|source| packed.bodies.get(source.0));
+        force.acceleration = match (packed.bodies.get(index.0), source) {
+            (Some(body), Some(source)) => wind.acceleration(body.position, source.position),
+            _ => Vec3::ZERO,
+        };
+    }
+}
+
+/// Burns fuel for the thrust applied over the last steps, then aims the next burn.
+fn update_spacecraft(
+    mut query: Query<(&BodyIndex, &mut Spacecraft, &mut ScriptedForce)>,
+    dt: Res<StepDuration>,
+    packed: Res<PackedBodies>,
+) {
+    for (index, mut spacecraft, mut force) in &mut query {
+        spacecraft.fuel = (spacecraft.fuel - force.acceleration.length() * dt.0).max(0.0);
+        force.acceleration = packed
+            .bodies
+            .get(index.0)
+            .map(|body| spacecraft.acceleration(body.position, body.velocity))
+            .unwrap_or_default();
+    }
+}
+
+/// Rebuilds the per-body input acceleration buffer from scripted forces.
+fn gather_input_accelerations(
+    query: Query<(&BodyIndex, &ScriptedForce)>,
+    mut packed: ResMut<PackedBodies>,
+) {
+    let packed = packed.as_mut();
+    packed.accelerations.fill(Vec4::ZERO);
+    for (index, force) in &query {
+        if let Some(acceleration) = packed.accelerations.get_mut(index.0) {
+            *acceleration = force.acceleration.extend(0.0);
+        }
+    }
+}
+
+/// Respawns or removes bodies past the escape radius and drops expired ones.
+/// Removal compacts the packed array with swap-removes and re-points the entity
+/// whose body moved.
+fn remove_bodies(
+    mut commands: Commands,
+    escape: Res<Escape>,
+    physics: Res<Physics>,
+    mut rng: ResMut<WorldRng>,
+    mut removals: ResMut<PendingRemovals>,
+    mut packed: ResMut<PackedBodies>,
+    mut query: Query<(Entity, &mut BodyIndex)>,
+) {
+    let policy = escape.0;
+    let escaped: Vec<usize> = (0..packed.bodies.len())
+        .filter(|&index| policy.is_escaped(&packed.bodies[index]))
+        .collect();
+
+    if let Some(respawn_radius) = policy.respawn_radius {
+        let total_mass: f32 = packed.bodies.iter().map(|body| body.mass).sum();
+        for &index in &escaped {
+            respawn_on_ring(
+                &mut packed.bodies[index],
+                respawn_radius,
+                total_mass,
+                physics.0.gravitational_constant,
+                &mut rng.0,
+            );
+        }
+    } else {
+        removals.0.extend(escaped);
+    }
+
+    let mut doomed = std::mem::take(&mut removals.0);
+    if doomed.is_empty() {
+        return;
+    }
+    doomed.sort_unstable();
+    doomed.dedup();
+
+    let mut owners: Vec<Option<Entity>> = vec![None; packed.bodies.len()];
+    for (entity, index) in &query {
+        if let Some(owner) = owners.get_mut(index.0) {
+            *owner = Some(entity);
+        }
+    }
+
+    for &index in doomed.iter().rev() {
+        packed.bodies.swap_remove(index);
+        packed.flags.swap_remove(index);
+        packed.accelerations.swap_remove(index);
+        packed.spins.swap_remove(index);
+        packed.stars.swap_remove(index);
+        if let Some(entity) = owners.swap_remove(index) {
+            commands.entity(entity).despawn();
+        }
+        if let Some(Some(moved)) = owners.get(index)
+            && let Ok((_, mut body_index)) = query.get_mut(*moved)
+        {
+            body_index.0 = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::EmitterSpec;
+
+    fn body(x: f32, mass: f32) -> Body {
+        Body::new(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, mass, 0.01, Vec4::ONE)
+    }
+
+    /// A sun, a comet one unit away emitting a tail, and a planet.
+    fn comet_world() -> BodyWorld {
+        let mut world = BodyWorld::new(
+            vec![body(0.0, 1.0), body(1.0, 0.0), body(5.0, 1.0e-3)],
+            vec![BodyGroup::Star, BodyGroup::Other, BodyGroup::Planet],
+            vec![BodyKind::Star, BodyKind::Massive, BodyKind::Massive],
+        );
+        let spec = EmitterSpec {
+            index: 1,
+            source: 0,
+            rate: 1000.0,
+            particle_mass: 0.0,
+            particle_radius: 0.001,
+            color: Vec4::ONE,
+            spread: 0.0,
+            lifetime: 100.0,
+            wind_strength: 1.0,
+        };
+        let source = world.entity(spec.source).unwrap();
+        world.attach(spec.index, Emitter::new(spec, source));
+        world
+    }
+
+    #[test]
+    fn removing_the_source_stops_the_tail_and_its_wind() {
+        let mut world = comet_world();
+        let state = SimulationState::default();
+        world.run_systems(0.01, &state);
+        // Whichever body is swapped into the sun's slot used to become the
+        // source.
+        world.queue_removal(0);
+        world.run_systems(0.01, &state);
+        let count = world.bodies().len();
+        world.run_systems(0.01, &state);
+        assert_eq!(world.bodies().len(), count);
+        assert!(
+            world
+                .accelerations()
+                .iter()
+                .all(|&acceleration| acceleration == Vec4::ZERO)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+
+use glam::{Vec3, Vec4};
+
+use super::ecs::EditTag;
+use super::{Body, BodyGroup};
+
+/// The body fields the inspector edits; position is left to the simulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BodyProperties {
+    pub mass: f32,
+    pub velocity: Vec3,
+    pub color: Vec4,
+}
+
+impl BodyProperties {
+    pub fn of(body: &Body) -> Self {
+        Self {
+            mass: body.mass,
+            velocity: body.velocity,
+            color: body.color,
+        }
+    }
+
+    pub fn apply(self, body: &mut Body) {
+        body.mass = self.mass;
+        body.velocity = self.velocity;
+        body.color = self.color;
+    }
+
+    /// Whether the values can be given to the physics: finite, with a
+    /// non-negative mass.
+    pub fn is_valid(&self) -> bool {
+        self.mass.is_finite()
+            && self.mass >= 0.0
+            && self.velocity.is_finite()
+            && self.color.is_finite()
+    }
+}
+
+/// One interactive change, stored as the delta needed to replay it. Body
+/// edits keep the body as it was when last added or removed, so undoing a
+/// deletion puts it back where it was deleted rather than where it started.
+/// Spin and stellar age are not kept; restored bodies start from defaults.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Edit {
+    Spawn {
+        tag: EditTag,
+        body: Body,
+        name: Option<String>,
+        group: BodyGroup,
+    },
+    Delete {
+        tag: EditTag,
+        body: Body,
+        name: Option<String>,
+        group: BodyGroup,
+    },
+    Properties {
+        tag: EditTag,
+        before: BodyProperties,
+        after: BodyProperties,
+    },
+    /// A body dragged from `before` to `after`.
+    Move {
+        tag: EditTag,
+        before: Vec3,
+        after: Vec3,
+    },
+    Parameter {
+        name: &'static str,
+        before: f32,
+        after: f32,
+    },
+}
+
+impl Edit {
+    /// The edit that undoes this one.
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Spawn {
+                tag,
+                body,
+                name,
+                group,
+            } => Self::Delete {
+                tag,
+                body,
+                name,
+                group,
+            },
+            Self::Delete {
+                tag,
+                body,
+                name,
+                group,
+            } => Self::Spawn {
+                tag,
+                body,
+                name,
+                group,
+            },
+            Self::Properties { tag, before, after } => Self::Properties {
+                tag,
+                before: after,
+                after: before,
+            },
+            Self::Move { tag, before, after } => Self::Move {
+                tag,
+                before: after,
+                after: before,
+            },
+            Self::Parameter {
+                name,
+                before,
+                after,
+            } => Self::Parameter {
+                name,
+                before: after,
+                after: before,
+            },
+        }
+    }
+
+    fn is_body_edit(&self) -> bool {
+        !matches!(self, Self::Parameter { .. })
+    }
+}
+
+/// Undo and redo stacks of interactive edits, newest last, dropping the
+/// oldest past `capacity`.
+#[derive(Clone, Debug)]
+pub struct EditHistory {
+    undo: VecDeque<Edit>,
+    redo: Vec<Edit>,
+    capacity: usize,
+    next_tag: u64,
+}
+
+impl EditHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+            capacity: capacity.max(1),
+            next_tag: 0,
+        }
+    }
+
+    /// A tag no body has carried before.
+    pub fn next_tag(&mut self) -> EditTag {
+        self.next_tag += 1;
+        EditTag(self.next_tag)
+    }
+
+    /// Records a new edit, which discards whatever could be redone.
+    pub fn record(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.push_undo(edit);
+    }
+
+    pub fn take_undo(&mut self) -> Option<Edit> {
+        self.undo.pop_back()
+    }
+
+    pub fn take_redo(&mut self) -> Option<Edit> {
+        self.redo.pop()
+    }
+
+    /// Records a redone edit without touching the redo stack.
+    pub fn push_undo(&mut self, edit: Edit) {
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(edit);
+    }
+
+    pub fn push_redo(&mut self, edit: Edit) {
+        self.redo.push(edit);
+    }
+
+    /// Drops the body edits, whose bodies are gone once the scenario is
+    /// regenerated; parameter edits stay undoable.
+    pub fn forget_bodies(&mut self) {
+        self.undo.retain(|edit| !edit.is_body_edit());
+        self.redo.retain(|edit| !edit.is_body_edit());
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
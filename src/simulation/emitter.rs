@@ -0,0 +1,84 @@
+use bevy_ecs::prelude::{Component, Entity};
+use glam::{Vec3, Vec4};
+
+use super::{Body, Rng};
+
+/// Continuous particle source attached to a scenario body, e.g. a comet's tail.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmitterSpec {
+    /// Body the particles are released from.
+    pub index: usize,
+    /// Body whose distance drives the emission rate and whose wind pushes the particles.
+    pub source: usize,
+    /// Particles per unit time at unit distance from the source; scales with 1/r².
+    pub rate: f32,
+    pub particle_mass: f32,
+    pub particle_radius: f32,
+    pub color: Vec4,
+    /// Random ejection speed relative to the emitting body.
+    pub spread: f32,
+    /// Simulated time a particle lives before it is removed.
+    pub lifetime: f32,
+    /// Outward acceleration on the particles at unit distance from the source.
+    pub wind_strength: f32,
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Emitter {
+    pub spec: EmitterSpec,
+    /// Entity of the body at `spec.source` when the emitter was attached, so
+    /// removals that reshuffle the packed indices cannot re-point it.
+    pub source: Entity,
+    /// Fractional particles carried over between ticks.
+    pub pending: f32,
+}
+
+impl Emitter {
+    pub fn new(spec: EmitterSpec, source: Entity) -> Self {
+        Self {
+            spec,
+            source,
+            pending: 0.0,
+        }
+    }
+
+    /// Number of particles due after `dt` for an emitter at `distance` from its source.
+    pub fn advance(&mut self, distance: f32, dt: f32) -> usize {
+        let rate = self.spec.rate / distance.max(1.0e-3).powi(2);
+        self.pending += rate * dt;
+        let count = self.pending.floor();
+        self.pending -= count;
+        count as usize
+    }
+
+    pub fn particle(&self, parent: &Body, rng: &mut Rng) -> Body {
+        let kick = Vec3::new(rng.gaussian(), rng.gaussian(), rng.gaussian()) * self.spec.spread;
+        Body::new(
+            parent.position,
+            parent.velocity + kick,
+            self.spec.particle_mass,
+            self.spec.particle_radius,
+            self.spec.color,
+        )
+    }
+}
+
+/// Radial push away from the `source` body falling off as 1/r², applied only to
+/// the bodies carrying it. Stops once the source is removed.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SolarWind {
+    pub source: Entity,
+    pub strength: f32,
+}
+
+impl SolarWind {
+    pub fn acceleration(&self, position: Vec3, source: Vec3) -> Vec3 {
+        let offset = position - source;
+        let distance_sq = offset.length_squared().max(1.0e-6);
+        offset * (self.strength / (distance_sq * distance_sq.sqrt()))
+    }
+}
+
+/// Remaining simulated time before the body is removed.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Lifetime(pub f32);
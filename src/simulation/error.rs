@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SimulationError {
+    #[error("no simulations are registered")]
+    NoSimulations,
+    #[error("simulation index {index} is out of range (have {count})")]
+    UnknownSimulation { index: usize, count: usize },
+    #[error("unknown parameter `{0}`")]
+    UnknownParameter(String),
+    #[error("parameter `{0}` must be a finite number")]
+    NonFiniteParameter(String),
+    #[error("failed to spawn the simulation thread")]
+    SpawnThread(#[source] std::io::Error),
+}
@@ -0,0 +1,34 @@
+use glam::Vec3;
+
+use super::{Body, Rng};
+
+/// What to do with bodies that wander past `radius` from the origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EscapePolicy {
+    /// Escape radius; `None` disables the check.
+    pub radius: Option<f32>,
+    /// Respawn escaped bodies on a circular orbit of this radius instead of removing them.
+    pub respawn_radius: Option<f32>,
+}
+
+impl EscapePolicy {
+    pub fn is_escaped(&self, body: &Body) -> bool {
+        self.radius
+            .is_some_and(|radius| body.position.length_squared() > radius * radius)
+    }
+}
+
+/// Puts `body` back on a circular orbit in the XZ plane around `enclosed_mass`.
+pub fn respawn_on_ring(
+    body: &mut Body,
+    radius: f32,
+    enclosed_mass: f32,
+    gravitational_constant: f32,
+    rng: &mut Rng,
+) {
+    let angle = rng.range(0.0, std::f32::consts::TAU);
+    let (sin, cos) = angle.sin_cos();
+    let speed = (gravitational_constant * enclosed_mass / radius).sqrt();
+    body.position = Vec3::new(cos, 0.0, sin) * radius;
+    body.velocity = Vec3::new(-sin, 0.0, cos) * speed;
+}
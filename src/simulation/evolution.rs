@@ -0,0 +1,153 @@
+//! Toy single-star evolution for recolouring long runs: stars sit on the main
+//! sequence for `10 Gyr · M^-2.5`, brightening slightly, then swell into red
+//! giants and stay there (no remnants). Luminosity and radius follow simple
+//! power laws and the temperature follows from Stefan–Boltzmann, which is
+//! enough for stars to redden and grow and for the Hertzsprung–Russell plot
+//! to show a main sequence peeling off into a giant branch.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec3};
+
+use super::Body;
+
+/// Effective temperature of the Sun in kelvin.
+pub const SOLAR_TEMPERATURE: f32 = 5778.0;
+
+/// Main-sequence lifetime of a one-solar-mass star in Gyr.
+const SOLAR_LIFETIME: f32 = 10.0;
+/// Main-sequence brightening and swelling by the end of core hydrogen burning.
+const TURNOFF_LUMINOSITY: f32 = 1.8;
+const TURNOFF_RADIUS: f32 = 1.5;
+/// Giant-branch duration as a fraction of the main-sequence lifetime.
+const GIANT_BRANCH: f32 = 0.15;
+/// Growth at the tip of the giant branch, relative to the zero-age star.
+const GIANT_LUMINOSITY: f32 = 100.0;
+const GIANT_RADIUS: f32 = 30.0;
+
+/// Age-driven colour and size of the star-kind bodies, mirrored by `cs_evolve`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StellarEvolution {
+    /// Gyr of stellar age per unit of simulated time; far above any real
+    /// value so evolution is visible within a session.
+    pub gyr_per_time: f32,
+}
+
+/// Per-body stellar state, parallel to the bodies and laid out to match
+/// `StarState` in the shader. A zero mass marks a body that does not evolve.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct StarState {
+    /// Zero-age main-sequence mass in solar masses.
+    pub mass: f32,
+    /// Body radius at zero age, in simulation units.
+    pub initial_radius: f32,
+    /// Age in Gyr.
+    pub age: f32,
+    /// Luminosity in solar luminosities.
+    pub luminosity: f32,
+}
+
+impl StarState {
+    /// Zero-age star of `mass` solar masses drawn at the body's current radius.
+    pub fn new(mass: f32, body: &Body) -> Self {
+        Self {
+            mass,
+            initial_radius: body.radius,
+            age: 0.0,
+            luminosity: mass.powf(3.5),
+        }
+    }
+
+    pub fn is_star(&self) -> bool {
+        self.mass > 0.0
+    }
+
+    pub fn lifetime(&self) -> f32 {
+        SOLAR_LIFETIME * self.mass.powf(-2.5)
+    }
+
+    /// Luminosity and radius relative to the zero-age star.
+    fn growth(&self) -> (f32, f32) {
+        let progress = self.age / self.lifetime();
+        if progress < 1.0 {
+            return (
+                1.0 + (TURNOFF_LUMINOSITY - 1.0) * progress,
+                1.0 + (TURNOFF_RADIUS - 1.0) * progress,
+            );
+        }
+        // Geometric climb from the turnoff to the tip of the giant branch.
+        let climb = ((progress - 1.0) / GIANT_BRANCH).min(1.0);
+        (
+            TURNOFF_LUMINOSITY * (GIANT_LUMINOSITY / TURNOFF_LUMINOSITY).powf(climb),
+            TURNOFF_RADIUS * (GIANT_RADIUS / TURNOFF_RADIUS).powf(climb),
+        )
+    }
+
+    /// Effective temperature in kelvin.
+    pub fn temperature(&self) -> f32 {
+        let (luminosity, radius) = self.growth();
+        // Zero-age main sequence: L ∝ M^3.5 and R ∝ M^0.8, so T ∝ M^0.475.
+        SOLAR_TEMPERATURE * self.mass.powf(0.475) * luminosity.powf(0.25) / radius.sqrt()
+    }
+}
+
+impl StellarEvolution {
+    /// Ages the star by `dt` of simulated time and redraws its body.
+    pub fn advance(&self, star: &mut StarState, body: &mut Body, dt: f32) {
+        if !star.is_star() {
+            return;
+        }
+        star.age += dt * self.gyr_per_time;
+        let (luminosity, radius) = star.growth();
+        star.luminosity = star.mass.powf(3.5) * luminosity;
+        body.radius = star.initial_radius * radius;
+        body.color = blackbody_color(star.temperature()).extend(body.color.w);
+    }
+}
+
+/// Planck spectrum sampled at red, green and blue wavelengths, scaled so the
+/// brightest channel is one; mirrored by `blackbody_color` in the shader.
+pub fn blackbody_color(temperature: f32) -> Vec3 {
+    // Wavelengths in µm and the second radiation constant hc/k in µm·K.
+    const WAVELENGTHS: Vec3 = Vec3::new(0.61, 0.55, 0.465);
+    const HC_OVER_K: f32 = 14_388.0;
+    let temperature = temperature.max(1000.0);
+    let radiance = Vec3::from_array(WAVELENGTHS.to_array().map(|wavelength| {
+        wavelength.powi(-5) / ((HC_OVER_K / (wavelength * temperature)).exp() - 1.0)
+    }));
+    radiance / radiance.max_element()
+}
+
+/// Salpeter initial mass function (dN/dM ∝ M^-2.35) between `min` and `max`
+/// solar masses, from a uniform sample `u` in `[0, 1)`.
+pub fn salpeter_mass(u: f32, min: f32, max: f32) -> f32 {
+    const EXPONENT: f32 = 1.0 - 2.35;
+    let low = min.powf(EXPONENT);
+    let high = max.powf(EXPONENT);
+    (low + u * (high - low)).powf(EXPONENT.recip())
+}
+
+/// One star on the Hertzsprung–Russell plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HrPoint {
+    pub body: usize,
+    pub temperature: f32,
+    pub luminosity: f32,
+}
+
+impl HrPoint {
+    /// Hottest and coolest temperatures on the plot, in kelvin.
+    pub const TEMPERATURE_RANGE: (f32, f32) = (40_000.0, 2_500.0);
+    /// Luminosity range on the plot, in solar luminosities.
+    pub const LUMINOSITY_RANGE: (f32, f32) = (1.0e-3, 1.0e6);
+
+    /// Position in the unit square: log temperature falling to the right, log
+    /// luminosity rising upwards, as the diagram is conventionally drawn.
+    pub fn plot_position(&self) -> Vec2 {
+        let (hot, cool) = Self::TEMPERATURE_RANGE;
+        let (dim, bright) = Self::LUMINOSITY_RANGE;
+        let x = (hot / self.temperature).log10() / (hot / cool).log10();
+        let y = (self.luminosity / dim).log10() / (bright / dim).log10();
+        Vec2::new(x, y).clamp(Vec2::ZERO, Vec2::ONE)
+    }
+}
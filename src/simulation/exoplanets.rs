@@ -0,0 +1,210 @@
+use glam::{Vec3, Vec4};
+
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Body, BodyGroup, ParamDescriptor, Simulation, SimulationError, SimulationState,
+    sanitize_parameter,
+};
+
+const EARTH_MASS: f32 = 3.003e-6;
+const DAYS_PER_YEAR: f32 = 365.25;
+/// Integration steps per orbit of the innermost planet.
+const STEPS_PER_INNER_ORBIT: f32 = 400.0;
+
+struct Exoplanet {
+    period_days: f32,
+    /// Earth masses.
+    mass: f32,
+    /// Earth radii; only used to size the sprites.
+    radius: f32,
+}
+
+struct PlanetarySystem {
+    name: &'static str,
+    /// Solar masses.
+    star_mass: f32,
+    star_color: [f32; 3],
+    planets: &'static [Exoplanet],
+}
+
+const fn planet(period_days: f32, mass: f32, radius: f32) -> Exoplanet {
+    Exoplanet {
+        period_days,
+        mass,
+        radius,
+    }
+}
+
+const SYSTEMS: [PlanetarySystem; 3] = [
+    // Agol et al. (2021), transit-timing masses.
+    PlanetarySystem {
+        name: "TRAPPIST-1",
+        star_mass: 0.0898,
+        star_color: [1.0, 0.45, 0.3],
+        planets: &[
+            planet(1.510826, 1.374, 1.116),
+            planet(2.421937, 1.308, 1.097),
+            planet(4.049219, 0.388, 0.788),
+            planet(6.101013, 0.692, 0.920),
+            planet(9.20754, 1.039, 1.045),
+            planet(12.352446, 1.321, 1.129),
+            planet(18.772866, 0.326, 0.755),
+        ],
+    },
+    // Only g and h have measured masses (Liang et al. 2021); the inner six use a
+    // mass–radius relation.
+    PlanetarySystem {
+        name: "Kepler-90",
+        star_mass: 1.2,
+        star_color: [1.0, 0.95, 0.8],
+        planets: &[
+            planet(7.008151, 2.7, 1.31),
+            planet(8.719375, 1.8, 1.18),
+            planet(14.44912, 2.8, 1.32),
+            planet(59.73667, 8.8, 2.88),
+            planet(91.93913, 7.6, 2.67),
+            planet(124.9144, 8.9, 2.89),
+            planet(210.60697, 15.0, 8.13),
+            planet(331.6006, 203.0, 11.32),
+        ],
+    },
+    // Lissauer et al. (2013); g only has an upper limit, taken as 8 Earth masses.
+    PlanetarySystem {
+        name: "Kepler-11",
+        star_mass: 0.961,
+        star_color: [1.0, 0.95, 0.75],
+        planets: &[
+            planet(10.3039, 1.9, 1.80),
+            planet(13.0241, 2.9, 2.87),
+            planet(22.6845, 7.3, 3.12),
+            planet(31.9996, 8.0, 4.19),
+            planet(46.6888, 2.0, 2.49),
+            planet(118.3807, 8.0, 3.33),
+        ],
+    },
+];
+
+/// Known compact multi-planet systems on circular, coplanar orbits built from
+/// their published periods and masses (AU, years, solar masses).
+#[derive(Default)]
+pub struct ExoplanetSimulation {
+    /// Index into the built-in systems.
+    pub system: usize,
+}
+
+impl ExoplanetSimulation {
+    fn data(&self) -> &'static PlanetarySystem {
+        &SYSTEMS[self.system.min(SYSTEMS.len() - 1)]
+    }
+
+    pub fn system_names() -> impl Iterator<Item = &'static str> {
+        SYSTEMS.iter().map(|system| system.name)
+    }
+}
+
+/// Kepler's third law, a³ = G (M + m) P² / 4π², which is (M + m) P² since
+/// G = 4π² in these units.
+fn semi_major_axis(period_years: f32, total_mass: f32) -> f32 {
+    (total_mass * period_years * period_years).cbrt()
+}
+
+impl Simulation for ExoplanetSimulation {
+    fn name(&self) -> &str {
+        self.data().name
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let system = self.data();
+        let inner_period = system.planets[0].period_days / DAYS_PER_YEAR;
+        let inner_axis = semi_major_axis(inner_period, system.star_mass);
+
+        let mut star = Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            system.star_mass,
+            0.3 * inner_axis,
+            Vec3::from(system.star_color).extend(1.0),
+        );
+        let mut bodies = vec![star];
+        let count = system.planets.len() as f32;
+        for (index, exoplanet) in system.planets.iter().enumerate() {
+            let mass = exoplanet.mass * EARTH_MASS;
+            let axis = semi_major_axis(
+                exoplanet.period_days / DAYS_PER_YEAR,
+                system.star_mass + mass,
+            );
+            // Golden-angle phases keep neighbours from starting in conjunction.
+            let angle = index as f32 * 2.399_963;
+            let (sin, cos) = angle.sin_cos();
+            let speed = (G_ASTRONOMICAL * (system.star_mass + mass) / axis).sqrt();
+            let warmth = 1.0 - index as f32 / count;
+            bodies.push(Body::new(
+                Vec3::new(cos, 0.0, sin) * axis,
+                Vec3::new(-sin, 0.0, cos) * speed,
+                mass,
+                0.08 * inner_axis * exoplanet.radius.sqrt(),
+                Vec4::new(0.4 + 0.5 * warmth, 0.6, 1.0 - 0.5 * warmth, 1.0),
+            ));
+        }
+
+        // Move the star so the system's barycenter is at rest at the origin.
+        let momentum: Vec3 = bodies[1..]
+            .iter()
+            .map(|body| body.velocity * body.mass)
+            .sum();
+        let moment: Vec3 = bodies[1..]
+            .iter()
+            .map(|body| body.position * body.mass)
+            .sum();
+        star.velocity = -momentum / system.star_mass;
+        star.position = -moment / system.star_mass;
+        bodies[0] = star;
+        bodies
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Star
+        } else {
+            BodyGroup::Planet
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        let system = self.data();
+        let inner_period = system.planets[0].period_days / DAYS_PER_YEAR;
+        let outer_period = system.planets[system.planets.len() - 1].period_days / DAYS_PER_YEAR;
+        let dt = inner_period / STEPS_PER_INNER_ORBIT;
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            softening: 0.0,
+            dt,
+            // Roughly one outer orbit every ten seconds at 120 ticks per second.
+            substeps: ((outer_period / dt) / 1200.0).ceil().max(1.0) as u32,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        vec![ParamDescriptor::integer(
+            "system",
+            0,
+            SYSTEMS.len() as u32 - 1,
+            0,
+            self.system as u32,
+        )]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        self.system = sanitize_parameter(&self.parameters(), name, value)? as usize;
+        Ok(())
+    }
+}
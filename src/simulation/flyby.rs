@@ -0,0 +1,207 @@
+use glam::{Vec3, Vec4};
+
+use super::orbits::OrbitalElements;
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Arrival, Body, BodyGroup, EscapePolicy, ParamDescriptor, Rng, Simulation, SimulationError,
+    SimulationState, sanitize_parameter,
+};
+
+/// Giant planets as (semi-major axis in AU, mass in M☉, radius, color).
+const PLANETS: [(f32, f32, f32, [f32; 3]); 4] = [
+    (5.2, 9.55e-4, 0.8, [0.9, 0.7, 0.5]),
+    (9.58, 2.86e-4, 0.7, [0.9, 0.8, 0.6]),
+    (19.2, 4.37e-5, 0.5, [0.6, 0.8, 0.9]),
+    (30.1, 5.15e-5, 0.5, [0.4, 0.5, 0.9]),
+];
+const DISK_INNER: f32 = 35.0;
+const DISK_OUTER: f32 = 100.0;
+/// Distance from the Sun at which the intruder enters.
+const ENTRY_DISTANCE: f32 = 300.0;
+
+/// The outer Solar System with a Kuiper-belt-like disk of test particles; a
+/// second star enters on a hyperbolic orbit after `arrival_time` and stirs up or
+/// strips the disk (AU, years, solar masses).
+pub struct FlybySimulation {
+    pub intruder_mass: f32,
+    /// Miss distance the intruder would have without gravitational focusing.
+    pub impact_parameter: f32,
+    /// Relative speed far from the Sun, in AU/yr (1 km/s ≈ 0.21 AU/yr).
+    pub speed: f32,
+    pub arrival_time: f32,
+    pub disk_count: usize,
+    pub seed: u64,
+}
+
+impl Default for FlybySimulation {
+    fn default() -> Self {
+        Self {
+            intruder_mass: 0.5,
+            impact_parameter: 150.0,
+            speed: 0.5,
+            arrival_time: 50.0,
+            disk_count: 2000,
+            seed: 11,
+        }
+    }
+}
+
+impl FlybySimulation {
+    /// Places the intruder on the two-body hyperbola through `ENTRY_DISTANCE`
+    /// with the requested impact parameter and speed at infinity.
+    fn intruder(&self) -> Body {
+        let mu = G_ASTRONOMICAL * (1.0 + self.intruder_mass);
+        let speed = (self.speed * self.speed + 2.0 * mu / ENTRY_DISTANCE).sqrt();
+        let sin = (self.impact_parameter * self.speed / (ENTRY_DISTANCE * speed)).min(1.0);
+        let cos = (1.0 - sin * sin).sqrt();
+        Body::new(
+            Vec3::new(-ENTRY_DISTANCE, 0.0, 0.0),
+            Vec3::new(cos, 0.0, sin) * speed,
+            self.intruder_mass,
+            3.0,
+            Vec4::new(1.0, 0.4, 0.3, 1.0),
+        )
+    }
+}
+
+impl Simulation for FlybySimulation {
+    fn name(&self) -> &str {
+        "Stellar Flyby"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut bodies = Vec::with_capacity(PLANETS.len() + self.disk_count + 1);
+        bodies.push(Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            1.0,
+            2.0,
+            Vec4::new(1.0, 0.9, 0.4, 1.0),
+        ));
+        for (index, &(semi_major_axis, mass, radius, [r, g, b])) in PLANETS.iter().enumerate() {
+            let (position, velocity) =
+                OrbitalElements::circular(semi_major_axis, index as f32 * 2.0)
+                    .state_vectors(G_ASTRONOMICAL);
+            bodies.push(Body::new(
+                position,
+                velocity,
+                mass,
+                radius,
+                Vec4::new(r, g, b, 1.0),
+            ));
+        }
+
+        let mut rng = Rng::new(self.seed);
+        for _ in 0..self.disk_count {
+            let radius = rng.range(DISK_INNER, DISK_OUTER);
+            let angle = rng.range(0.0, std::f32::consts::TAU);
+            let (position, velocity) =
+                OrbitalElements::circular(radius, angle).state_vectors(G_ASTRONOMICAL);
+            bodies.push(Body::new(
+                position,
+                velocity,
+                0.0,
+                0.3,
+                Vec4::new(0.6, 0.7, 0.8, 0.8),
+            ));
+        }
+        bodies
+    }
+
+    fn arrivals(&self) -> Vec<Arrival> {
+        vec![Arrival {
+            time: f64::from(self.arrival_time),
+            body: self.intruder(),
+            group: BodyGroup::Star,
+        }]
+    }
+
+    /// The intruder and stripped particles are dropped once well clear.
+    fn escape_policy(&self) -> EscapePolicy {
+        EscapePolicy {
+            radius: Some(2.0 * ENTRY_DISTANCE),
+            respawn_radius: None,
+        }
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        match index {
+            0 => BodyGroup::Star,
+            1..=4 => BodyGroup::Planet,
+            _ => BodyGroup::KuiperBelt,
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            softening: 0.0,
+            dt: 0.05,
+            substeps: 16,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "intruder_mass",
+                0.05,
+                5.0,
+                defaults.intruder_mass,
+                self.intruder_mass,
+            ),
+            ParamDescriptor::new(
+                "impact_parameter",
+                0.0,
+                400.0,
+                defaults.impact_parameter,
+                self.impact_parameter,
+            ),
+            ParamDescriptor::new("speed", 0.05, 10.0, defaults.speed, self.speed),
+            ParamDescriptor::new(
+                "arrival_time",
+                0.0,
+                1000.0,
+                defaults.arrival_time,
+                self.arrival_time,
+            ),
+            ParamDescriptor::integer(
+                "disk_count",
+                0,
+                20_000,
+                defaults.disk_count as u32,
+                self.disk_count as u32,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "intruder_mass" => self.intruder_mass = value,
+            "impact_parameter" => self.impact_parameter = value,
+            "speed" => self.speed = value,
+            "arrival_time" => self.arrival_time = value,
+            _ => self.disk_count = value as usize,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,188 @@
+use glam::{Quat, Vec3};
+
+use super::Body;
+use crate::localization::Localizer;
+
+/// Origin the scene is shown relative to. Display only: the simulation keeps
+/// integrating in whatever frame the scenario set up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReferenceFrame {
+    /// Positions as simulated.
+    #[default]
+    Inertial,
+    /// Centered on the center of mass, moving with it.
+    Barycentric,
+    /// Centered on the heaviest body.
+    Heliocentric,
+    /// Centered on one body, e.g. the one the camera follows.
+    Body(usize),
+}
+
+impl ReferenceFrame {
+    /// The frame after this one. `Body` follows `Heliocentric` only when
+    /// `selected` names a body to center on.
+    pub fn next(self, selected: Option<usize>) -> Self {
+        match (self, selected) {
+            (Self::Inertial, _) => Self::Barycentric,
+            (Self::Barycentric, _) => Self::Heliocentric,
+            (Self::Heliocentric, Some(index)) => Self::Body(index),
+            (Self::Heliocentric, None) | (Self::Body(_), _) => Self::Inertial,
+        }
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        match self {
+            Self::Inertial => locale.text("reference-frame-inertial"),
+            Self::Barycentric => locale.text("reference-frame-barycentric"),
+            Self::Heliocentric => locale.text("reference-frame-heliocentric"),
+            Self::Body(index) => locale.format("reference-frame-body", &[("index", index.into())]),
+        }
+    }
+
+    /// Position and velocity of the frame's origin, or `None` when there is
+    /// nothing to subtract (inertial, or no bodies or mass to center on).
+    pub fn origin(self, bodies: &[Body]) -> Option<(Vec3, Vec3)> {
+        let body = |body: &Body| (body.position, body.velocity);
+        match self {
+            Self::Inertial => None,
+            Self::Barycentric => {
+                let (mass, position, velocity) = bodies
+                    .iter()
+                    .filter(|body| body.mass > 0.0 && body.position.is_finite())
+                    .fold((0.0, Vec3::ZERO, Vec3::ZERO), |(m, p, v), body| {
+                        (
+                            m + body.mass,
+                            p + body.position * body.mass,
+                            v + body.velocity * body.mass,
+                        )
+                    });
+                (mass > 0.0).then(|| (position / mass, velocity / mass))
+            }
+            Self::Heliocentric => bodies
+                .iter()
+                .max_by(|a, b| a.mass.total_cmp(&b.mass))
+                .map(body),
+            Self::Body(index) => bodies.get(index).map(body),
+        }
+    }
+
+    /// Rewrites `bodies` relative to the frame's origin; the CPU counterpart
+    /// of `cs_reference_frame`.
+    pub fn transform(self, bodies: &mut [Body]) {
+        let Some((position, velocity)) = self.origin(bodies) else {
+            return;
+        };
+        for body in bodies {
+            body.position -= position;
+            body.velocity -= velocity;
+        }
+    }
+}
+
+/// Frame co-rotating about +Y with a scenario's mean orbit, centred on one body.
+/// Orbits in the XZ plane run from +X towards +Z, i.e. with angular velocity
+/// `-angular_speed` along Y; viewing in this frame freezes that mean motion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RotatingFrame {
+    pub origin: usize,
+    pub angular_speed: f32,
+}
+
+impl RotatingFrame {
+    /// Rewrites `bodies` (simulated at `time`) into the rotating frame.
+    pub fn transform(&self, bodies: &mut [Body], time: f64) {
+        let Some(origin) = bodies.get(self.origin).copied() else {
+            return;
+        };
+        let angle = (f64::from(self.angular_speed) * time).rem_euclid(std::f64::consts::TAU);
+        rotate_about(
+            bodies,
+            (origin.position, origin.velocity),
+            angle as f32,
+            self.angular_speed,
+        );
+    }
+}
+
+/// Frame co-rotating with a binary: centred on the pair's barycenter, with
+/// the line from `primary` to `secondary` held along +X. Follows the pair's
+/// actual motion, so eccentric or perturbed binaries stay fixed too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BinaryFrame {
+    pub primary: usize,
+    pub secondary: usize,
+}
+
+impl BinaryFrame {
+    pub fn transform(&self, bodies: &mut [Body]) {
+        let (Some(&a), Some(&b)) = (bodies.get(self.primary), bodies.get(self.secondary)) else {
+            return;
+        };
+        let mass = a.mass + b.mass;
+        // Within the XZ plane the frame rotates in.
+        let separation = (b.position - a.position) * Vec3::new(1.0, 0.0, 1.0);
+        let distance_sq = separation.length_squared();
+        if self.primary == self.secondary || mass <= 0.0 || distance_sq == 0.0 {
+            return;
+        }
+        let barycenter = (
+            (a.position * a.mass + b.position * b.mass) / mass,
+            (a.velocity * a.mass + b.velocity * b.mass) / mass,
+        );
+        let angle = separation.z.atan2(separation.x);
+        let relative_velocity = b.velocity - a.velocity;
+        // Rate about −Y, the sense of orbits running from +X towards +Z.
+        let angular_speed = -separation.cross(relative_velocity).y / distance_sq;
+        rotate_about(bodies, barycenter, angle, angular_speed);
+    }
+}
+
+/// Which co-rotating frame the rotating view uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RotatingView {
+    /// The scenario's own `Simulation::rotating_frame`, if it has one.
+    #[default]
+    Scenario,
+    /// A fixed rate about the display origin, e.g. the frame chosen by
+    /// `ReferenceFrame`; positive rates follow orbits from +X towards +Z.
+    Rate(f32),
+    /// Locked to a binary.
+    Binary(BinaryFrame),
+}
+
+impl RotatingView {
+    /// Rewrites `bodies` (simulated at `time`) into the view's frame;
+    /// `scenario` is the scenario's frame for `Scenario`.
+    pub fn transform(self, bodies: &mut [Body], time: f64, scenario: Option<RotatingFrame>) {
+        match self {
+            Self::Scenario => {
+                if let Some(frame) = scenario {
+                    frame.transform(bodies, time);
+                }
+            }
+            Self::Rate(angular_speed) => {
+                let angle = (f64::from(angular_speed) * time).rem_euclid(std::f64::consts::TAU);
+                rotate_about(
+                    bodies,
+                    (Vec3::ZERO, Vec3::ZERO),
+                    angle as f32,
+                    angular_speed,
+                );
+            }
+            Self::Binary(binary) => binary.transform(bodies),
+        }
+    }
+}
+
+/// Moves `bodies` into a frame centred on `origin` (position, velocity) and
+/// turned by `angle` about +Y, rotating at `angular_speed` about −Y.
+fn rotate_about(bodies: &mut [Body], origin: (Vec3, Vec3), angle: f32, angular_speed: f32) {
+    let rotation = Quat::from_rotation_y(angle);
+    let omega = Vec3::NEG_Y * angular_speed;
+    for body in bodies {
+        let offset = body.position - origin.0;
+        let velocity = body.velocity - origin.1 - omega.cross(offset);
+        body.position = rotation * offset;
+        body.velocity = rotation * velocity;
+    }
+}
@@ -0,0 +1,216 @@
+use glam::{Vec3, Vec4};
+
+use super::evolution::salpeter_mass;
+use super::{
+    Body, BodyGroup, EscapePolicy, ExternalPotential, ParamDescriptor, PhysicalUnits, Rng,
+    Simulation, SimulationError, SimulationState, StellarEvolution, sanitize_parameter,
+};
+use crate::rendering::config::{BackgroundConfig, BlendMode, HaloConfig, RenderConfig, ToneMapper};
+
+/// Rotating spiral disk around a central massive body, in arbitrary units with G = 1.
+pub struct GalaxySimulation {
+    pub body_count: usize,
+    pub arm_count: u32,
+    pub disk_radius: f32,
+    pub central_mass: f32,
+    pub disk_mass: f32,
+    /// Mass of an NFW dark-matter halo; zero disables it.
+    pub halo_mass: f32,
+    pub seed: u64,
+}
+
+impl Default for GalaxySimulation {
+    fn default() -> Self {
+        Self {
+            body_count: 4096,
+            arm_count: 2,
+            disk_radius: 50.0,
+            central_mass: 1.0e4,
+            disk_mass: 2.0e3,
+            halo_mass: 0.0,
+            seed: 1,
+        }
+    }
+}
+
+impl GalaxySimulation {
+    fn halo_scale_radius(&self) -> f32 {
+        0.5 * self.disk_radius
+    }
+}
+
+impl Simulation for GalaxySimulation {
+    fn name(&self) -> &str {
+        "Galaxy"
+    }
+
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            blend_mode: BlendMode::Additive,
+            tone_mapper: ToneMapper::Aces,
+            // Only the central mass; the disk stars are drawn by the thousand.
+            halo: HaloConfig {
+                emissive_mass: 0.5 * self.central_mass,
+                ..HaloConfig::default()
+            },
+            // Intergalactic space, faintly blue rather than black.
+            background: BackgroundConfig {
+                clear_color: [0.004, 0.005, 0.012],
+                ..BackgroundConfig::default()
+            },
+            ..RenderConfig::default()
+        }
+    }
+
+    fn units(&self) -> Option<PhysicalUnits> {
+        Some(PhysicalUnits::DIMENSIONLESS)
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut rng = Rng::new(self.seed);
+        let star_count = self.body_count.saturating_sub(1);
+        let star_mass = self.disk_mass / star_count.max(1) as f32;
+
+        let mut radii: Vec<f32> = (0..star_count)
+            .map(|_| {
+                // Exponential disk profile with scale length a quarter of the disk radius.
+                let r = -0.25 * self.disk_radius * (1.0 - rng.next_f32()).ln();
+                r.clamp(1.0, self.disk_radius)
+            })
+            .collect();
+        radii.sort_by(f32::total_cmp);
+
+        let mut bodies = Vec::with_capacity(self.body_count);
+        bodies.push(Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            self.central_mass,
+            1.0,
+            Vec4::new(1.0, 0.9, 0.7, 1.0),
+        ));
+
+        for (index, radius) in radii.into_iter().enumerate() {
+            let arm = rng.next_u64() % u64::from(self.arm_count.max(1));
+            let arm_offset = arm as f32 * std::f32::consts::TAU / self.arm_count.max(1) as f32;
+            let winding = radius / self.disk_radius * std::f32::consts::TAU;
+            let angle = arm_offset + winding + rng.gaussian() * 0.3;
+            let (sin, cos) = angle.sin_cos();
+            let height = rng.gaussian() * 0.02 * self.disk_radius;
+
+            let x = radius / self.halo_scale_radius();
+            let halo_mass = self.halo_mass * ((1.0 + x).ln() - x / (1.0 + x));
+            let enclosed_mass = self.central_mass + star_mass * index as f32 + halo_mass;
+            let speed = (enclosed_mass / radius).sqrt();
+            let warmth = 1.0 - radius / self.disk_radius;
+
+            bodies.push(Body::new(
+                Vec3::new(cos * radius, height, sin * radius),
+                Vec3::new(-sin, 0.0, cos) * speed,
+                star_mass,
+                0.1,
+                Vec4::new(
+                    0.6 + 0.4 * warmth,
+                    0.6 + 0.2 * warmth,
+                    1.0 - 0.3 * warmth,
+                    1.0,
+                ),
+            ));
+        }
+
+        bodies
+    }
+
+    fn external_potentials(&self) -> Vec<ExternalPotential> {
+        if self.halo_mass <= 0.0 {
+            return Vec::new();
+        }
+        vec![ExternalPotential::NfwHalo {
+            mass: self.halo_mass,
+            scale_radius: self.halo_scale_radius(),
+        }]
+    }
+
+    /// Stars flung far out are recycled at the disk edge to keep long runs dense.
+    fn escape_policy(&self) -> EscapePolicy {
+        EscapePolicy {
+            radius: Some(4.0 * self.disk_radius),
+            respawn_radius: Some(self.disk_radius),
+        }
+    }
+
+    fn body_group(&self, _index: usize) -> BodyGroup {
+        BodyGroup::Star
+    }
+
+    /// Each disk particle stands in for one star drawn from a Salpeter IMF; the
+    /// central mass does not evolve.
+    fn stellar_mass(&self, index: usize) -> f32 {
+        if index == 0 {
+            return 0.0;
+        }
+        let mut rng = Rng::new(self.seed.wrapping_add(index as u64));
+        salpeter_mass(rng.next_f32(), 0.5, 20.0)
+    }
+
+    /// Stars of about two solar masses leave the main sequence within a minute
+    /// at the default time scale.
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: 0.5,
+            dt: 0.01,
+            stellar_evolution: Some(StellarEvolution { gyr_per_time: 0.03 }),
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::integer(
+                "body_count",
+                2,
+                65_536,
+                defaults.body_count as u32,
+                self.body_count as u32,
+            ),
+            ParamDescriptor::integer("arm_count", 1, 8, defaults.arm_count, self.arm_count),
+            ParamDescriptor::new(
+                "disk_radius",
+                5.0,
+                500.0,
+                defaults.disk_radius,
+                self.disk_radius,
+            ),
+            ParamDescriptor::new(
+                "central_mass",
+                0.0,
+                1.0e6,
+                defaults.central_mass,
+                self.central_mass,
+            ),
+            ParamDescriptor::new("disk_mass", 0.0, 1.0e5, defaults.disk_mass, self.disk_mass),
+            ParamDescriptor::new("halo_mass", 0.0, 1.0e5, defaults.halo_mass, self.halo_mass),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "body_count" => self.body_count = value as usize,
+            "arm_count" => self.arm_count = value as u32,
+            "disk_radius" => self.disk_radius = value,
+            "central_mass" => self.central_mass = value,
+            "halo_mass" => self.halo_mass = value,
+            _ => self.disk_mass = value,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,125 @@
+use glam::{Vec3, Vec4};
+
+use super::diagnostics::ResonanceAngle;
+use super::{Body, BodyGroup, Simulation, SimulationState};
+
+struct Moon {
+    period_days: f32,
+    /// In Jupiter masses.
+    mass: f32,
+    radius: f32,
+    color: [f32; 3],
+    /// Initial mean longitude in degrees.
+    longitude: f32,
+}
+
+/// Io, Europa, Ganymede, Callisto. Starting longitudes put the Laplace angle at
+/// its libration center of 180°.
+const MOONS: [Moon; 4] = [
+    Moon {
+        period_days: 1.769_138,
+        mass: 4.704e-5,
+        radius: 0.05,
+        color: [1.0, 0.85, 0.3],
+        longitude: 0.0,
+    },
+    Moon {
+        period_days: 3.551_181,
+        mass: 2.528e-5,
+        radius: 0.045,
+        color: [0.9, 0.85, 0.75],
+        longitude: 180.0,
+    },
+    Moon {
+        period_days: 7.154_553,
+        mass: 7.805e-5,
+        radius: 0.07,
+        color: [0.7, 0.65, 0.6],
+        longitude: 0.0,
+    },
+    Moon {
+        period_days: 16.689_02,
+        mass: 5.667e-5,
+        radius: 0.065,
+        color: [0.45, 0.42, 0.4],
+        longitude: 90.0,
+    },
+];
+
+/// Laplace resonance λ_Io − 3 λ_Europa + 2 λ_Ganymede.
+const LAPLACE: ResonanceAngle = ResonanceAngle {
+    label: "Laplace (Io-Europa-Ganymede)",
+    central: 0,
+    terms: &[(1, 1), (2, -3), (3, 2)],
+};
+
+/// Io's semi-major axis, the length unit.
+const IO_SEMI_MAJOR_AXIS: f32 = 1.0;
+
+/// Jupiter and the four Galilean moons; lengths in units of Io's orbit, time in
+/// days, masses in Jupiter masses, with G fixed by Io's period.
+pub struct GalileanMoonsSimulation;
+
+fn gravitational_constant() -> f32 {
+    let io = &MOONS[0];
+    let mean_motion = std::f32::consts::TAU / io.period_days;
+    mean_motion * mean_motion * IO_SEMI_MAJOR_AXIS.powi(3) / (1.0 + io.mass)
+}
+
+impl Simulation for GalileanMoonsSimulation {
+    fn name(&self) -> &str {
+        "Galilean Moons"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let g = gravitational_constant();
+        let mut bodies = vec![Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            1.0,
+            0.17,
+            Vec4::new(0.9, 0.75, 0.6, 1.0),
+        )];
+        for moon in &MOONS {
+            let mu = g * (1.0 + moon.mass);
+            let mean_motion = std::f32::consts::TAU / moon.period_days;
+            let axis = (mu / (mean_motion * mean_motion)).cbrt();
+            let (sin, cos) = moon.longitude.to_radians().sin_cos();
+            bodies.push(Body::new(
+                Vec3::new(cos, 0.0, sin) * axis,
+                Vec3::new(-sin, 0.0, cos) * axis * mean_motion,
+                moon.mass,
+                moon.radius,
+                Vec3::from(moon.color).extend(1.0),
+            ));
+        }
+        let momentum: Vec3 = bodies[1..]
+            .iter()
+            .map(|body| body.velocity * body.mass)
+            .sum();
+        bodies[0].velocity = -momentum;
+        bodies
+    }
+
+    fn resonance_angles(&self) -> Vec<ResonanceAngle> {
+        vec![LAPLACE]
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Planet
+        } else {
+            BodyGroup::Moon
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: gravitational_constant(),
+            softening: 0.0,
+            dt: MOONS[0].period_days / 500.0,
+            substeps: 4,
+            ..SimulationState::default()
+        }
+    }
+}
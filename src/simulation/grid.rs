@@ -0,0 +1,230 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{UVec3, Vec3};
+
+use super::Body;
+
+/// Cells per axis at most, so one far-flung body cannot blow up the grid.
+const MAX_CELLS_PER_AXIS: u32 = 64;
+
+/// Uniform grid layout shared by the CPU build and the `grid.wgsl` passes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct GridUniforms {
+    pub origin: Vec3,
+    pub cell_size: f32,
+    pub dimensions: UVec3,
+    pub body_count: u32,
+}
+
+impl GridUniforms {
+    /// Covers the bodies' bounding box with cells of at least `cell_size`,
+    /// growing them if the box would need more than `MAX_CELLS_PER_AXIS`.
+    pub fn fit(bodies: &[Body], cell_size: f32) -> Self {
+        let (min, max) = bodies.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), body| (min.min(body.position), max.max(body.position)),
+        );
+        if bodies.is_empty() {
+            return Self {
+                cell_size: cell_size.max(f32::EPSILON),
+                dimensions: UVec3::ONE,
+                ..Self::default()
+            };
+        }
+        let extent = (max - min).max_element();
+        let cell_size = cell_size
+            .max(extent / MAX_CELLS_PER_AXIS as f32)
+            .max(f32::EPSILON);
+        Self {
+            origin: min,
+            cell_size,
+            dimensions: ((max - min) / cell_size).as_uvec3() + UVec3::ONE,
+            body_count: bodies.len() as u32,
+        }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        (self.dimensions.x * self.dimensions.y * self.dimensions.z) as usize
+    }
+
+    /// Integer cell coordinates of `position`, clamped into the grid.
+    pub fn cell_coords(&self, position: Vec3) -> UVec3 {
+        ((position - self.origin) / self.cell_size)
+            .max(Vec3::ZERO)
+            .as_uvec3()
+            .min(self.dimensions - UVec3::ONE)
+    }
+
+    pub fn cell_index(&self, coords: UVec3) -> usize {
+        (coords.x + self.dimensions.x * (coords.y + self.dimensions.y * coords.z)) as usize
+    }
+}
+
+/// Bodies bucketed by cell, built in the same four passes as the GPU version:
+/// assign each body a cell, count per cell, exclusive prefix sum, reorder.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialGrid {
+    pub uniforms: GridUniforms,
+    /// Cell of each body.
+    pub body_cells: Vec<u32>,
+    /// Offset of each cell's run in `sorted`; one extra entry holds the total.
+    pub cell_start: Vec<u32>,
+    /// Body indices ordered by cell.
+    pub sorted: Vec<u32>,
+}
+
+impl SpatialGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuilds the grid for `bodies`; neighbour queries are exact within
+    /// `cell_size`, which callers set to their interaction range.
+    pub fn build(&mut self, bodies: &[Body], cell_size: f32) {
+        self.uniforms = GridUniforms::fit(bodies, cell_size);
+
+        self.body_cells.clear();
+        self.body_cells.extend(bodies.iter().map(|body| {
+            let coords = self.uniforms.cell_coords(body.position);
+            self.uniforms.cell_index(coords) as u32
+        }));
+
+        self.cell_start.clear();
+        self.cell_start.resize(self.uniforms.cell_count() + 1, 0);
+        for &cell in &self.body_cells {
+            self.cell_start[cell as usize + 1] += 1;
+        }
+        for cell in 1..self.cell_start.len() {
+            self.cell_start[cell] += self.cell_start[cell - 1];
+        }
+
+        // Stable on the CPU; the GPU scatter leaves each cell's order unspecified.
+        let mut next = self.cell_start.clone();
+        self.sorted.clear();
+        self.sorted.resize(bodies.len(), 0);
+        for (index, &cell) in self.body_cells.iter().enumerate() {
+            let slot = &mut next[cell as usize];
+            self.sorted[*slot as usize] = index as u32;
+            *slot += 1;
+        }
+    }
+
+    /// Bodies in the cells overlapping a cube of half-size `radius` around
+    /// `position`, a superset of those within `radius`.
+    pub fn neighbors(&self, position: Vec3, radius: f32) -> impl Iterator<Item = usize> + '_ {
+        let uniforms = self.uniforms;
+        let low = uniforms.cell_coords(position - Vec3::splat(radius));
+        let high = uniforms.cell_coords(position + Vec3::splat(radius));
+        (low.z..=high.z)
+            .flat_map(move |z| (low.y..=high.y).map(move |y| (y, z)))
+            .flat_map(move |(y, z)| (low.x..=high.x).map(move |x| UVec3::new(x, y, z)))
+            .flat_map(move |coords| {
+                let cell = uniforms.cell_index(coords);
+                let run = self.cell_start[cell] as usize..self.cell_start[cell + 1] as usize;
+                self.sorted[run].iter().map(|&index| index as usize)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec4;
+
+    use super::*;
+    use crate::simulation::Rng;
+
+    fn body_at(position: Vec3) -> Body {
+        Body::new(position, Vec3::ZERO, 1.0, 0.01, Vec4::ONE)
+    }
+
+    fn cloud(seed: u64, count: usize, extent: f32) -> Vec<Body> {
+        let mut rng = Rng::new(seed);
+        (0..count)
+            .map(|_| {
+                body_at(Vec3::new(
+                    rng.range(-extent, extent),
+                    rng.range(-extent, extent),
+                    rng.range(-extent, extent),
+                ))
+            })
+            .collect()
+    }
+
+    fn brute_force(bodies: &[Body], position: Vec3, radius: f32) -> Vec<usize> {
+        (0..bodies.len())
+            .filter(|&index| bodies[index].position.distance(position) <= radius)
+            .collect()
+    }
+
+    #[test]
+    fn every_body_is_sorted_into_its_own_cell() {
+        let bodies = cloud(7, 200, 5.0);
+        let mut grid = SpatialGrid::new();
+        grid.build(&bodies, 0.8);
+        assert_eq!(grid.cell_start.last().copied(), Some(bodies.len() as u32));
+        let mut sorted = grid.sorted.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..bodies.len() as u32).collect::<Vec<_>>());
+        for cell in 0..grid.uniforms.cell_count() {
+            let run = grid.cell_start[cell] as usize..grid.cell_start[cell + 1] as usize;
+            for &index in &grid.sorted[run] {
+                assert_eq!(grid.body_cells[index as usize], cell as u32);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_cover_every_body_within_the_radius() {
+        let bodies = cloud(42, 300, 4.0);
+        let radius = 0.7;
+        let mut grid = SpatialGrid::new();
+        grid.build(&bodies, radius);
+        for body in &bodies {
+            let found: Vec<usize> = grid.neighbors(body.position, radius).collect();
+            for index in brute_force(&bodies, body.position, radius) {
+                assert!(found.contains(&index), "missed body {index}");
+            }
+            let mut unique = found.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(unique.len(), found.len(), "body listed twice");
+        }
+    }
+
+    #[test]
+    fn bodies_on_cell_boundaries_are_found_from_both_sides() {
+        let bodies: Vec<Body> = (0..=4)
+            .map(|step| body_at(Vec3::new(step as f32, 0.0, 0.0)))
+            .collect();
+        let mut grid = SpatialGrid::new();
+        grid.build(&bodies, 1.0);
+        assert_eq!(grid.uniforms.dimensions, UVec3::new(5, 1, 1));
+        for (index, body) in bodies.iter().enumerate() {
+            assert_eq!(grid.body_cells[index], index as u32);
+            let found: Vec<usize> = grid.neighbors(body.position, 1.0).collect();
+            for neighbor in brute_force(&bodies, body.position, 1.0) {
+                assert!(found.contains(&neighbor), "body {index} missed {neighbor}");
+            }
+        }
+    }
+
+    #[test]
+    fn positions_outside_the_grid_clamp_to_its_edge_cells() {
+        let bodies = cloud(3, 50, 2.0);
+        let mut grid = SpatialGrid::new();
+        grid.build(&bodies, 0.5);
+        let last = grid.uniforms.dimensions - UVec3::ONE;
+        assert_eq!(grid.uniforms.cell_coords(Vec3::splat(-100.0)), UVec3::ZERO);
+        assert_eq!(grid.uniforms.cell_coords(Vec3::splat(100.0)), last);
+        let all: Vec<usize> = grid.neighbors(Vec3::ZERO, 100.0).collect();
+        assert_eq!(all.len(), bodies.len());
+    }
+
+    #[test]
+    fn far_flung_bodies_grow_the_cells_instead_of_the_grid() {
+        let bodies = [body_at(Vec3::ZERO), body_at(Vec3::splat(1.0e6))];
+        let uniforms = GridUniforms::fit(&bodies, 0.1);
+        assert!(uniforms.dimensions.max_element() <= MAX_CELLS_PER_AXIS + 1);
+        assert_eq!(GridUniforms::fit(&[], 0.1).cell_count(), 1);
+    }
+}
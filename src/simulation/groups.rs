@@ -0,0 +1,137 @@
+use crate::localization::Localizer;
+
+/// Category an initializer tags each body with; stored as a bit in the per-body flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum BodyGroup {
+    Star,
+    Planet,
+    Moon,
+    Asteroid,
+    KuiperBelt,
+    Gas,
+    Other,
+}
+
+impl BodyGroup {
+    pub const ALL: [Self; 7] = [
+        Self::Star,
+        Self::Planet,
+        Self::Moon,
+        Self::Asteroid,
+        Self::KuiperBelt,
+        Self::Gas,
+        Self::Other,
+    ];
+
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    /// Group whose bit is set in `flags`; `Other` if none is.
+    pub fn from_flags(flags: u32) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|group| flags & group.bit() != 0)
+            .unwrap_or(Self::Other)
+    }
+
+    /// Kind used when a scenario or spawner does not pick one.
+    pub fn default_kind(self) -> BodyKind {
+        match self {
+            Self::Star => BodyKind::Star,
+            Self::Gas => BodyKind::Gas,
+            _ => BodyKind::Massive,
+        }
+    }
+
+    pub fn label(self, locale: &Localizer) -> String {
+        locale.text(match self {
+            Self::Star => "body-group-star",
+            Self::Planet => "body-group-planet",
+            Self::Moon => "body-group-moon",
+            Self::Asteroid => "body-group-asteroid",
+            Self::KuiperBelt => "body-group-kuiper-belt",
+            Self::Gas => "body-group-gas",
+            Self::Other => "body-group-other",
+        })
+    }
+}
+
+/// Group bits occupy the low half of the per-body flags, the body kind the high half.
+const GROUP_BITS: u32 = 0xffff;
+const KIND_SHIFT: u32 = 16;
+
+/// How the physics and renderer treat a body, independently of its group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum BodyKind {
+    /// Ordinary gravitating body drawn as a solid disk.
+    #[default]
+    Massive,
+    /// Gravitating and drawn with a bright, glowing core.
+    Star,
+    /// Drawn as a soft blob; reserved for bodies coupled to a gas solver.
+    Gas,
+    /// Gravitates but is never drawn.
+    DarkMatter,
+    /// Feels gravity but exerts none, whatever its mass.
+    TestParticle,
+}
+
+impl BodyKind {
+    pub fn bits(self) -> u32 {
+        (self as u32) << KIND_SHIFT
+    }
+
+    pub fn from_flags(flags: u32) -> Self {
+        match flags >> KIND_SHIFT {
+            1 => Self::Star,
+            2 => Self::Gas,
+            3 => Self::DarkMatter,
+            4 => Self::TestParticle,
+            _ => Self::Massive,
+        }
+    }
+
+    /// Whether the body's mass pulls on the others.
+    pub fn is_source(self) -> bool {
+        self != Self::TestParticle
+    }
+}
+
+/// Packs a body's group and kind into its flag word.
+pub fn body_flags(group: BodyGroup, kind: BodyKind) -> u32 {
+    group.bit() | kind.bits()
+}
+
+/// Set of visible groups, uploaded as the vertex shader's visibility mask.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupMask(pub u32);
+
+impl GroupMask {
+    /// Every group and none of the kind bits, so kinds never affect visibility.
+    pub const ALL: Self = Self(GROUP_BITS);
+
+    pub fn contains(self, group: BodyGroup) -> bool {
+        self.0 & group.bit() != 0
+    }
+
+    pub fn set(&mut self, group: BodyGroup, visible: bool) {
+        if visible {
+            self.0 |= group.bit();
+        } else {
+            self.0 &= !group.bit();
+        }
+    }
+
+    pub fn toggle(&mut self, group: BodyGroup) {
+        self.0 ^= group.bit();
+    }
+}
+
+impl Default for GroupMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
@@ -0,0 +1,27 @@
+use glam::Vec3;
+
+/// Line-list circle in the XZ plane, for the wireframe pass.
+pub fn ring_vertices(center: Vec3, radius: f32, segments: usize) -> Vec<Vec3> {
+    let point = |segment: usize| {
+        let angle = segment as f32 / segments as f32 * std::f32::consts::TAU;
+        let (sin, cos) = angle.sin_cos();
+        center + Vec3::new(cos, 0.0, sin) * radius
+    };
+    (0..segments)
+        .flat_map(|segment| [point(segment), point(segment + 1)])
+        .collect()
+}
+
+/// Static reference circle a scenario draws over its bodies, e.g. a stability limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GuideCircle {
+    pub label: &'static str,
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl GuideCircle {
+    pub fn vertices(&self, segments: usize) -> Vec<Vec3> {
+        ring_vertices(self.center, self.radius, segments)
+    }
+}
@@ -0,0 +1,140 @@
+use glam::{Quat, Vec3, Vec4};
+
+use super::{
+    Body, BodyGroup, InnerOrbit, ParamDescriptor, Simulation, SimulationError, SimulationState,
+    sanitize_parameter,
+};
+
+const STAR_MASS: f32 = 1.0;
+const PLANET_MASS: f32 = 1.0e-3;
+const COMPANION_MASS: f32 = 1.0;
+const INNER_SEMI_MAJOR_AXIS: f32 = 1.0;
+const INNER_ECCENTRICITY: f32 = 0.01;
+
+/// Hierarchical triple: a planet around a star, with a distant companion star
+/// on an inclined circular orbit (arbitrary units with G = 1). Above roughly
+/// 39° of mutual inclination the planet's eccentricity and inclination trade
+/// off in Kozai–Lidov cycles lasting hundreds of inner orbits.
+pub struct KozaiSimulation {
+    /// Mutual inclination in degrees.
+    pub inclination: f32,
+    /// Companion distance in units of the inner semi-major axis.
+    pub outer_separation: f32,
+}
+
+impl Default for KozaiSimulation {
+    fn default() -> Self {
+        Self {
+            inclination: 65.0,
+            outer_separation: 5.0,
+        }
+    }
+}
+
+impl Simulation for KozaiSimulation {
+    fn name(&self) -> &str {
+        "Kozai-Lidov"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let inner_mass = STAR_MASS + PLANET_MASS;
+        let periapsis = INNER_SEMI_MAJOR_AXIS * (1.0 - INNER_ECCENTRICITY);
+        let inner_speed = (inner_mass * (2.0 / periapsis - 1.0 / INNER_SEMI_MAJOR_AXIS)).sqrt();
+        let planet_offset = Vec3::new(periapsis, 0.0, 0.0);
+        let planet_velocity = Vec3::new(0.0, 0.0, inner_speed);
+
+        // Outer orbit of the companion around the inner pair, tilted about +X.
+        let separation = self.outer_separation * INNER_SEMI_MAJOR_AXIS;
+        let total_mass = inner_mass + COMPANION_MASS;
+        let tilt = Quat::from_rotation_x(self.inclination.to_radians());
+        let outer_offset = tilt * Vec3::new(0.0, 0.0, -separation);
+        let outer_velocity = tilt * Vec3::new((total_mass / separation).sqrt(), 0.0, 0.0);
+
+        // Place everything about the common barycenter.
+        let planet_share = PLANET_MASS / inner_mass;
+        let companion_share = COMPANION_MASS / total_mass;
+        let pair_offset = -outer_offset * companion_share;
+        let pair_velocity = -outer_velocity * companion_share;
+
+        vec![
+            Body::new(
+                pair_offset - planet_offset * planet_share,
+                pair_velocity - planet_velocity * planet_share,
+                STAR_MASS,
+                0.08,
+                Vec4::new(1.0, 0.9, 0.5, 1.0),
+            ),
+            Body::new(
+                pair_offset + planet_offset * (1.0 - planet_share),
+                pair_velocity + planet_velocity * (1.0 - planet_share),
+                PLANET_MASS,
+                0.03,
+                Vec4::new(0.4, 0.7, 1.0, 1.0),
+            ),
+            Body::new(
+                pair_offset + outer_offset,
+                pair_velocity + outer_velocity,
+                COMPANION_MASS,
+                0.08,
+                Vec4::new(1.0, 0.5, 0.3, 1.0),
+            ),
+        ]
+    }
+
+    fn inner_orbit(&self) -> Option<InnerOrbit> {
+        Some(InnerOrbit {
+            central: 0,
+            target: 1,
+            perturber: Some(2),
+            sample_interval: 1.0,
+        })
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 1 {
+            BodyGroup::Planet
+        } else {
+            BodyGroup::Star
+        }
+    }
+
+    /// At peak eccentricity the planet skims the star, so the step stays small
+    /// and the worker takes many of them per tick.
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: 0.0,
+            dt: 2.0e-3,
+            substeps: 64,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "inclination",
+                0.0,
+                90.0,
+                defaults.inclination,
+                self.inclination,
+            ),
+            ParamDescriptor::new(
+                "outer_separation",
+                4.0,
+                20.0,
+                defaults.outer_separation,
+                self.outer_separation,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "inclination" => self.inclination = value,
+            _ => self.outer_separation = value,
+        }
+        Ok(())
+    }
+}
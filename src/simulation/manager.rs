@@ -0,0 +1,94 @@
+use super::{EarthMoonSimulation, Simulation, SimulationError};
+
+/// Registry of the available scenarios and the index of the active one.
+pub struct SimulationManager {
+    simulations: Vec<Box<dyn Simulation>>,
+    current: usize,
+}
+
+impl Default for SimulationManager {
+    fn default() -> Self {
+        Self {
+            simulations: builtin_simulations(),
+            current: 0,
+        }
+    }
+}
+
+/// Scenarios compiled into this build; optional packs are behind cargo features.
+#[allow(unused_mut)]
+pub fn builtin_simulations() -> Vec<Box<dyn Simulation>> {
+    let mut simulations: Vec<Box<dyn Simulation>> = vec![Box::new(EarthMoonSimulation::default())];
+    #[cfg(feature = "sims-particles")]
+    simulations.push(Box::new(super::GalaxySimulation::default()));
+    #[cfg(feature = "sims-particles")]
+    simulations.push(Box::new(super::RubblePileSimulation::default()));
+    #[cfg(feature = "sims-particles")]
+    simulations.push(Box::new(super::StarClusterSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::SolarSystemSimulation));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::CometSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::MercurySimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::KozaiSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::CoOrbitalSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::AsteroidBeltSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::ExoplanetSimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::GalileanMoonsSimulation));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::CircumbinarySimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::FlybySimulation::default()));
+    #[cfg(feature = "sims-astro")]
+    simulations.push(Box::new(super::BlackHoleSimulation::default()));
+    simulations
+}
+
+impl SimulationManager {
+    pub fn new(simulations: Vec<Box<dyn Simulation>>) -> Result<Self, SimulationError> {
+        if simulations.is_empty() {
+            return Err(SimulationError::NoSimulations);
+        }
+        Ok(Self {
+            simulations,
+            current: 0,
+        })
+    }
+
+    pub fn get_simulation_count(&self) -> usize {
+        self.simulations.len()
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> &dyn Simulation {
+        self.simulations[self.current].as_ref()
+    }
+
+    pub fn current_mut(&mut self) -> &mut dyn Simulation {
+        self.simulations[self.current].as_mut()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.simulations.iter().map(|simulation| simulation.name())
+    }
+
+    pub fn switch_simulation(&mut self, index: usize) -> Result<&dyn Simulation, SimulationError> {
+        if index >= self.simulations.len() {
+            return Err(SimulationError::UnknownSimulation {
+                index,
+                count: self.simulations.len(),
+            });
+        }
+        self.current = index;
+        Ok(self.current())
+    }
+}
@@ -0,0 +1,126 @@
+use glam::{Vec3, Vec4};
+
+use super::units::G_ASTRONOMICAL;
+use super::{
+    Body, BodyGroup, ForcePrecision, ParamDescriptor, PostNewtonian, Simulation, SimulationError,
+    SimulationState, TrackedOrbit, sanitize_parameter,
+};
+
+/// Speed of light in AU/yr.
+const SPEED_OF_LIGHT: f32 = 63_241.0;
+
+const MERCURY_SEMI_MAJOR_AXIS: f32 = 0.387;
+const MERCURY_ECCENTRICITY: f32 = 0.2056;
+const MERCURY_MASS: f32 = 1.66e-7;
+
+/// Sun and Mercury alone, with an optional first post-Newtonian correction on
+/// Mercury. The real effect is 43″ per century, so the speed of light is
+/// adjustable to make the precession visible within a few orbits.
+pub struct MercurySimulation {
+    pub post_newtonian: bool,
+    /// In AU/yr.
+    pub speed_of_light: f32,
+}
+
+impl Default for MercurySimulation {
+    fn default() -> Self {
+        Self {
+            post_newtonian: true,
+            speed_of_light: 100.0,
+        }
+    }
+}
+
+impl Simulation for MercurySimulation {
+    fn name(&self) -> &str {
+        "Mercury Precession"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let perihelion = MERCURY_SEMI_MAJOR_AXIS * (1.0 - MERCURY_ECCENTRICITY);
+        let speed = (G_ASTRONOMICAL * (2.0 / perihelion - 1.0 / MERCURY_SEMI_MAJOR_AXIS)).sqrt();
+        vec![
+            Body::new(
+                Vec3::ZERO,
+                Vec3::ZERO,
+                1.0,
+                0.02,
+                Vec4::new(1.0, 0.9, 0.4, 1.0),
+            ),
+            Body::new(
+                Vec3::new(perihelion, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, speed),
+                MERCURY_MASS,
+                0.005,
+                Vec4::new(0.6, 0.6, 0.6, 1.0),
+            ),
+        ]
+    }
+
+    fn tracked_orbit(&self) -> Option<TrackedOrbit> {
+        Some(TrackedOrbit {
+            central: 0,
+            target: 1,
+        })
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Star
+        } else {
+            BodyGroup::Planet
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            force_precision: ForcePrecision::Compensated,
+            softening: 0.0,
+            dt: 2.0e-4,
+            post_newtonian: self.post_newtonian.then_some(PostNewtonian {
+                speed_of_light: self.speed_of_light,
+                central: 0,
+                target: 1,
+            }),
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::integer(
+                "post_newtonian",
+                0,
+                1,
+                u32::from(defaults.post_newtonian),
+                u32::from(self.post_newtonian),
+            ),
+            ParamDescriptor::new(
+                "speed_of_light",
+                20.0,
+                SPEED_OF_LIGHT,
+                defaults.speed_of_light,
+                self.speed_of_light,
+            ),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "post_newtonian" => self.post_newtonian = value != 0.0,
+            _ => self.speed_of_light = value,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+pub mod accretion;
+#[cfg(feature = "sims-astro")]
+mod asteroid_belt;
+pub mod binary;
+#[cfg(feature = "sims-astro")]
+mod black_hole;
+mod body;
+mod bounds;
+#[cfg(feature = "sims-astro")]
+mod circumbinary;
+#[cfg(feature = "sims-astro")]
+mod co_orbital;
+#[cfg(feature = "sims-astro")]
+mod comet;
+pub mod contact;
+pub mod diagnostics;
+pub mod divergence;
+mod earth_moon;
+pub mod ecs;
+mod edits;
+pub mod emitter;
+mod error;
+mod escape;
+pub mod evolution;
+#[cfg(feature = "sims-astro")]
+mod exoplanets;
+#[cfg(feature = "sims-astro")]
+mod flyby;
+pub mod frames;
+#[cfg(feature = "sims-particles")]
+mod galaxy;
+#[cfg(feature = "sims-astro")]
+mod galilean_moons;
+pub mod grid;
+mod groups;
+pub mod guides;
+#[cfg(feature = "sims-astro")]
+mod kozai;
+mod manager;
+#[cfg(feature = "sims-astro")]
+mod mercury;
+pub mod orbits;
+mod parameters;
+pub mod physics;
+pub mod plots;
+pub mod potentials;
+pub mod precession;
+pub mod prediction;
+mod random;
+pub mod relativity;
+mod resources;
+#[cfg(feature = "sims-particles")]
+mod rubble_pile;
+mod snapshot;
+#[cfg(feature = "sims-astro")]
+mod solar_system;
+pub mod spacecraft;
+pub mod spin;
+#[cfg(feature = "sims-particles")]
+mod star_cluster;
+mod state;
+mod step_cursor;
+pub mod supernova;
+pub mod timesteps;
+pub mod units;
+pub mod worker;
+
+pub use accretion::AccretionDisk;
+#[cfg(feature = "sims-astro")]
+pub use asteroid_belt::AsteroidBeltSimulation;
+#[cfg(feature = "sims-astro")]
+pub use black_hole::BlackHoleSimulation;
+pub use body::Body;
+pub use bounds::{BoundaryMode, WorldBounds};
+#[cfg(feature = "sims-astro")]
+pub use circumbinary::CircumbinarySimulation;
+#[cfg(feature = "sims-astro")]
+pub use co_orbital::CoOrbitalSimulation;
+#[cfg(feature = "sims-astro")]
+pub use comet::CometSimulation;
+pub use contact::SoftSphere;
+pub use diagnostics::{
+    ClusterDiagnostics, ClusterSample, Histogram, HistogramSpec, InnerOrbit, OrbitSample,
+    ResonanceAngle,
+};
+pub use divergence::{Divergence, RecoveryPolicy};
+pub use earth_moon::EarthMoonSimulation;
+pub use ecs::{Arrival, BodyWorld};
+pub use edits::{BodyProperties, Edit, EditHistory};
+pub use emitter::EmitterSpec;
+pub use error::SimulationError;
+pub use escape::EscapePolicy;
+pub use evolution::{HrPoint, StarState, StellarEvolution};
+#[cfg(feature = "sims-astro")]
+pub use exoplanets::ExoplanetSimulation;
+#[cfg(feature = "sims-astro")]
+pub use flyby::FlybySimulation;
+pub use frames::{BinaryFrame, ReferenceFrame, RotatingFrame, RotatingView};
+#[cfg(feature = "sims-particles")]
+pub use galaxy::GalaxySimulation;
+#[cfg(feature = "sims-astro")]
+pub use galilean_moons::GalileanMoonsSimulation;
+pub use groups::{BodyGroup, BodyKind, GroupMask};
+pub use guides::GuideCircle;
+#[cfg(feature = "sims-astro")]
+pub use kozai::KozaiSimulation;
+pub use manager::{SimulationManager, builtin_simulations};
+#[cfg(feature = "sims-astro")]
+pub use mercury::MercurySimulation;
+pub use orbits::OrbitalElements;
+pub use parameters::ParamDescriptor;
+pub use plots::DiagnosticPlots;
+pub use potentials::ExternalPotential;
+pub use precession::{PrecessionReadout, TrackedOrbit};
+pub use random::Rng;
+pub use relativity::{GravitationalLens, PostNewtonian};
+pub use resources::{SimulationResources, SimulationUniforms};
+#[cfg(feature = "sims-particles")]
+pub use rubble_pile::RubblePileSimulation;
+pub use snapshot::{Snapshot, SnapshotRing};
+#[cfg(feature = "sims-astro")]
+pub use solar_system::SolarSystemSimulation;
+pub use spacecraft::{SpacecraftSpec, SpacecraftStatus, ThrustInput};
+pub use spin::TidalSpin;
+#[cfg(feature = "sims-particles")]
+pub use star_cluster::StarClusterSimulation;
+pub use state::{ForcePrecision, SimulationState};
+pub use step_cursor::StepCursor;
+pub use supernova::{BlastWave, Supernova};
+pub use timesteps::BlockTimesteps;
+pub use units::{PhysicalUnits, UnitConversion};
+
+use crate::rendering::config::RenderConfig;
+
+/// A scenario: produces the initial bodies and the physics constants they are tuned for.
+pub trait Simulation: Send {
+    fn name(&self) -> &str;
+    fn initialize_bodies(&self) -> Vec<Body>;
+    fn initial_state(&self) -> SimulationState;
+
+    /// Authoring units of a scenario that should run in N-body units; `None`
+    /// keeps its values as they are. See `units`.
+    fn units(&self) -> Option<PhysicalUnits> {
+        None
+    }
+
+    /// Unit the scenario's lengths are authored in, for scale bars, when it
+    /// does not run in N-body units; empty for arbitrary units.
+    fn length_label(&self) -> &'static str {
+        ""
+    }
+
+    /// Likewise for times, e.g. orbital periods.
+    fn time_label(&self) -> &'static str {
+        ""
+    }
+
+    /// Background potentials evaluated on top of pairwise gravity.
+    fn external_potentials(&self) -> Vec<ExternalPotential> {
+        Vec::new()
+    }
+
+    /// Body the user can steer with thrust, if the scenario has one.
+    fn spacecraft(&self) -> Option<SpacecraftSpec> {
+        None
+    }
+
+    /// Particle sources attached to scenario bodies.
+    fn emitters(&self) -> Vec<EmitterSpec> {
+        Vec::new()
+    }
+
+    /// Bodies that enter partway through the run rather than at start-up.
+    fn arrivals(&self) -> Vec<Arrival> {
+        Vec::new()
+    }
+
+    /// Orbit whose perihelion precession is measured and reported.
+    fn tracked_orbit(&self) -> Option<TrackedOrbit> {
+        None
+    }
+
+    /// Inner orbit whose eccentricity and inclination are logged over time.
+    fn inner_orbit(&self) -> Option<InnerOrbit> {
+        None
+    }
+
+    /// Virial ratio and Lagrangian radii logged over time, for star clusters.
+    fn cluster_diagnostics(&self) -> Option<ClusterDiagnostics> {
+        None
+    }
+
+    /// Blending and other looks the scenario is best viewed with.
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig::default()
+    }
+
+    /// Black hole whose lensing the renderer draws, if any.
+    fn gravitational_lens(&self) -> Option<GravitationalLens> {
+        None
+    }
+
+    /// Accretion disk drawn around one of the bodies, if any.
+    fn accretion_disk(&self) -> Option<AccretionDisk> {
+        None
+    }
+
+    /// Co-rotating frame the scenario is best viewed in, if any.
+    fn rotating_frame(&self) -> Option<RotatingFrame> {
+        None
+    }
+
+    /// Semi-major-axis histogram computed each tick for an overlay.
+    fn histogram(&self) -> Option<HistogramSpec> {
+        None
+    }
+
+    /// Resonant arguments shown in the overlay.
+    fn resonance_angles(&self) -> Vec<ResonanceAngle> {
+        Vec::new()
+    }
+
+    /// Reference circles drawn over the scene.
+    fn guide_circles(&self) -> Vec<GuideCircle> {
+        Vec::new()
+    }
+
+    fn escape_policy(&self) -> EscapePolicy {
+        EscapePolicy::default()
+    }
+
+    /// Group of the `index`-th body returned by `initialize_bodies`.
+    fn body_group(&self, _index: usize) -> BodyGroup {
+        BodyGroup::Other
+    }
+
+    /// Name of the `index`-th body, shown in its tooltip; planets with one
+    /// get an orbit outline.
+    fn body_name(&self, _index: usize) -> Option<&str> {
+        None
+    }
+
+    /// Initial angular velocity of the `index`-th body (radians per unit time).
+    fn body_spin(&self, _index: usize) -> glam::Vec3 {
+        glam::Vec3::ZERO
+    }
+
+    /// Zero-age mass in solar masses of the `index`-th body if it should age
+    /// as a star under `SimulationState::stellar_evolution`; zero leaves it be.
+    fn stellar_mass(&self, _index: usize) -> f32 {
+        0.0
+    }
+
+    /// Kind of the `index`-th body; follows its group unless overridden.
+    fn body_kind(&self, index: usize) -> BodyKind {
+        self.body_group(index).default_kind()
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        Vec::new()
+    }
+
+    /// Updates a parameter; callers reinitialize the bodies afterwards.
+    fn set_parameter(&mut self, name: &str, _value: f32) -> Result<(), SimulationError> {
+        Err(SimulationError::UnknownParameter(name.to_owned()))
+    }
+
+    /// Random seed of a procedurally generated scenario.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// Replaces the seed; ignored by scenarios without one. Callers
+    /// reinitialize the bodies afterwards.
+    fn set_seed(&mut self, _seed: u64) {}
+}
+
+/// Looks up `name` in `parameters` and returns the sanitized value.
+pub(crate) fn sanitize_parameter(
+    parameters: &[ParamDescriptor],
+    name: &str,
+    value: f32,
+) -> Result<f32, SimulationError> {
+    parameters
+        .iter()
+        .find(|parameter| parameter.name == name)
+        .ok_or_else(|| SimulationError::UnknownParameter(name.to_owned()))?
+        .sanitize(value)
+}
@@ -0,0 +1,136 @@
+use std::f32::consts::TAU;
+
+use glam::{Quat, Vec3};
+
+/// Classical Keplerian elements; angles in radians.
+///
+/// The reference plane is XZ and zero inclination means the usual prograde
+/// sense from +X towards +Z (angular momentum along −Y). The node and periapsis
+/// angles are measured from +X in that sense.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrbitalElements {
+    pub semi_major_axis: f32,
+    pub eccentricity: f32,
+    pub inclination: f32,
+    pub longitude_of_ascending_node: f32,
+    pub argument_of_periapsis: f32,
+    pub true_anomaly: f32,
+}
+
+impl OrbitalElements {
+    /// Circular orbit in the reference plane, `angle` from +X.
+    pub fn circular(radius: f32, angle: f32) -> Self {
+        Self {
+            semi_major_axis: radius,
+            true_anomaly: angle,
+            ..Self::default()
+        }
+    }
+
+    /// Position and velocity relative to the central body, with
+    /// `mu = G * (M + m)`.
+    pub fn state_vectors(&self, mu: f32) -> (Vec3, Vec3) {
+        let e = self.eccentricity;
+        let semi_latus_rectum = self.semi_major_axis * (1.0 - e * e);
+        let (sin, cos) = self.true_anomaly.sin_cos();
+        let distance = semi_latus_rectum / (1.0 + e * cos);
+        let speed = (mu / semi_latus_rectum).sqrt();
+
+        // Perifocal frame, then the textbook z-x-z rotation with the orbit
+        // normal along +z.
+        let position = Vec3::new(cos, sin, 0.0) * distance;
+        let velocity = Vec3::new(-sin, e + cos, 0.0) * speed;
+        let rotation = Quat::from_rotation_z(self.longitude_of_ascending_node)
+            * Quat::from_rotation_x(self.inclination)
+            * Quat::from_rotation_z(self.argument_of_periapsis);
+        (to_world(rotation * position), to_world(rotation * velocity))
+    }
+
+    /// Osculating elements of a relative state, the inverse of
+    /// `state_vectors`. Hyperbolic orbits get a negative semi-major axis.
+    /// Undefined angles (the node of an orbit in the reference plane, the
+    /// periapsis of a circular one) are taken as zero. `None` for radial or
+    /// parabolic motion and non-finite input.
+    pub fn from_state_vectors(position: Vec3, velocity: Vec3, mu: f32) -> Option<Self> {
+        const EPSILON: f32 = 1e-6;
+        let r = from_world(position);
+        let v = from_world(velocity);
+        let distance = r.length();
+        let h = r.cross(v);
+        let energy = 0.5 * v.length_squared() - mu / distance;
+        if !(distance > 0.0 && mu > 0.0 && energy.is_finite() && h.length() > 0.0) {
+            return None;
+        }
+        let semi_major_axis = -mu / (2.0 * energy);
+        if !semi_major_axis.is_finite() {
+            return None;
+        }
+        let normal = h.normalize();
+        let e = ((v.length_squared() - mu / distance) * r - r.dot(v) * v) / mu;
+        let eccentricity = e.length();
+        let node = Vec3::Z.cross(h);
+        let node = (node.length() > EPSILON * h.length()).then(|| node.normalize());
+        // Signed angle from `from` to `to` about the orbit normal.
+        let angle = |from: Vec3, to: Vec3| -> f32 {
+            from.cross(to)
+                .dot(normal)
+                .atan2(from.dot(to))
+                .rem_euclid(TAU)
+        };
+        let reference = node.unwrap_or(Vec3::X);
+        let (argument_of_periapsis, true_anomaly) = if eccentricity > EPSILON {
+            (angle(reference, e), angle(e, r))
+        } else {
+            (0.0, angle(reference, r))
+        };
+        Some(Self {
+            semi_major_axis,
+            eccentricity,
+            inclination: normal.z.clamp(-1.0, 1.0).acos(),
+            longitude_of_ascending_node: node
+                .map_or(0.0, |node| node.y.atan2(node.x).rem_euclid(TAU)),
+            argument_of_periapsis,
+            true_anomaly,
+        })
+    }
+
+    /// Orbital period, for bound orbits.
+    pub fn period(&self, mu: f32) -> Option<f32> {
+        (self.eccentricity < 1.0 && self.semi_major_axis > 0.0)
+            .then(|| TAU * (self.semi_major_axis.powi(3) / mu).sqrt())
+    }
+
+    /// Closest approach to the central body.
+    pub fn periapsis(&self) -> f32 {
+        self.semi_major_axis * (1.0 - self.eccentricity)
+    }
+
+    /// Farthest distance from the central body, for bound orbits.
+    pub fn apoapsis(&self) -> Option<f32> {
+        (self.eccentricity < 1.0).then_some(self.semi_major_axis * (1.0 + self.eccentricity))
+    }
+}
+
+/// Maps the textbook frame (reference plane xy, normal +z) onto XZ with the
+/// normal along −Y.
+fn to_world(v: Vec3) -> Vec3 {
+    Vec3::new(v.x, -v.z, v.y)
+}
+
+fn from_world(v: Vec3) -> Vec3 {
+    Vec3::new(v.x, v.z, -v.y)
+}
+
+/// Splits a relative orbit between two masses about their common barycenter,
+/// primary first.
+pub fn about_barycenter(
+    primary_mass: f32,
+    secondary_mass: f32,
+    (position, velocity): (Vec3, Vec3),
+) -> [(Vec3, Vec3); 2] {
+    let share = secondary_mass / (primary_mass + secondary_mass);
+    [
+        (-position * share, -velocity * share),
+        (position * (1.0 - share), velocity * (1.0 - share)),
+    ]
+}
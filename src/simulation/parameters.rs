@@ -0,0 +1,76 @@
+use super::SimulationError;
+
+/// A tunable scenario value the UI can turn into a slider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamDescriptor {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+    pub value: f32,
+    /// Whether the slider should snap to whole numbers.
+    pub integer: bool,
+}
+
+impl ParamDescriptor {
+    pub fn new(name: &'static str, min: f32, max: f32, default: f32, value: f32) -> Self {
+        Self {
+            name,
+            min,
+            max,
+            default,
+            value,
+            integer: false,
+        }
+    }
+
+    pub fn integer(name: &'static str, min: u32, max: u32, default: u32, value: u32) -> Self {
+        Self {
+            integer: true,
+            ..Self::new(name, min as f32, max as f32, default as f32, value as f32)
+        }
+    }
+
+    /// Clamps (and rounds, for integer parameters) a value into range; NaN
+    /// and infinities are rejected rather than clamped.
+    pub fn sanitize(&self, value: f32) -> Result<f32, SimulationError> {
+        if !value.is_finite() {
+            return Err(SimulationError::NonFiniteParameter(self.name.to_owned()));
+        }
+        let value = value.clamp(self.min, self.max);
+        Ok(if self.integer { value.round() } else { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_clamped_into_range() {
+        let parameter = ParamDescriptor::new("mass", 0.5, 2.0, 1.0, 1.0);
+        assert_eq!(parameter.sanitize(0.1).unwrap(), 0.5);
+        assert_eq!(parameter.sanitize(1.25).unwrap(), 1.25);
+        assert_eq!(parameter.sanitize(9.0).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn integer_values_are_rounded() {
+        let parameter = ParamDescriptor::integer("body_count", 2, 100, 10, 10);
+        assert_eq!(parameter.sanitize(7.4).unwrap(), 7.0);
+        assert_eq!(parameter.sanitize(7.6).unwrap(), 8.0);
+        assert_eq!(parameter.sanitize(1.0).unwrap(), 2.0);
+        assert_eq!(parameter.sanitize(250.7).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn non_finite_values_are_rejected() {
+        let parameter = ParamDescriptor::integer("body_count", 2, 100, 10, 10);
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                parameter.sanitize(value),
+                Err(SimulationError::NonFiniteParameter(name)) if name == "body_count"
+            ));
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use glam::{Vec3, Vec4};
+
+use super::grid::SpatialGrid;
+use super::potentials::{ExternalPotential, external_acceleration};
+use super::timesteps::BlockTimesteps;
+use super::{Body, BodyKind, ForcePrecision, SimulationState};
+
+/// Softened pairwise gravitational acceleration on every body (direct O(N²) sum),
+/// plus soft-sphere contact forces when the state enables them.
+#[profiling::function]
+pub fn compute_accelerations(bodies: &[Body], flags: &[u32], state: &SimulationState) -> Vec<Vec3> {
+    compute_accelerations_for(bodies, flags, state, 0..bodies.len())
+}
+
+/// Like [`compute_accelerations`], but only on the `targets`, in their order.
+pub fn compute_accelerations_for(
+    bodies: &[Body],
+    flags: &[u32],
+    state: &SimulationState,
+    targets: impl IntoIterator<Item = usize>,
+) -> Vec<Vec3> {
+    let softening_sq = state.softening * state.softening;
+    // Test particles feel gravity but exert none, so skip them as sources.
+    let sources: Vec<(usize, &Body)> = bodies
+        .iter()
+        .enumerate()
+        .filter(|&(index, body)| {
+            body.mass != 0.0
+                && flags
+                    .get(index)
+                    .is_none_or(|&flags| BodyKind::from_flags(flags).is_source())
+        })
+        .collect();
+    // Contacts only reach as far as the largest pair of radii.
+    let contacts = state.contact.map(|contact| {
+        let reach = 2.0 * bodies.iter().map(|body| body.radius).fold(0.0, f32::max);
+        let mut grid = SpatialGrid::new();
+        grid.build(bodies, reach);
+        (contact, grid, reach)
+    });
+    targets
+        .into_iter()
+        .map(|i| {
+            let body = &bodies[i];
+            let mut acceleration = Vec3::ZERO;
+            let mut compensation = Vec3::ZERO;
+            for &(j, other) in &sources {
+                if i == j {
+                    continue;
+                }
+                let offset = other.position - body.position;
+                let distance_sq = offset.length_squared() + softening_sq;
+                let inv_distance = distance_sq.sqrt().recip();
+                let term = offset * (other.mass * inv_distance * inv_distance * inv_distance);
+                match state.force_precision {
+                    ForcePrecision::Single => acceleration += term,
+                    ForcePrecision::Compensated => {
+                        compensated_add(&mut acceleration, &mut compensation, term)
+                    }
+                }
+            }
+            acceleration = (acceleration + compensation) * state.gravitational_constant;
+            if let Some((contact, grid, reach)) = &contacts {
+                for j in grid.neighbors(body.position, *reach) {
+                    if i != j {
+                        acceleration += contact.acceleration(body, &bodies[j]);
+                    }
+                }
+            }
+            acceleration
+        })
+        .collect()
+}
+
+/// Neumaier's variant of Kahan summation, per component; mirrored by the
+/// compensated path of `cs_main`.
+fn compensated_add(sum: &mut Vec3, compensation: &mut Vec3, term: Vec3) {
+    let total = *sum + term;
+    let larger_sum = sum.abs().cmpge(term.abs());
+    *compensation += Vec3::select(larger_sum, (*sum - total) + term, (term - total) + *sum);
+    *sum = total;
+}
+
+/// Accelerations applied on top of pairwise gravity.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Forces<'a> {
+    pub potentials: &'a [ExternalPotential],
+    /// Per-body input accelerations (xyz), e.g. spacecraft thrust; may be shorter than the bodies.
+    pub per_body: &'a [Vec4],
+    /// Per-body flag bits; bodies whose kind is not a source exert no gravity.
+    pub flags: &'a [u32],
+}
+
+/// Advances the bodies by one drift-kick-drift leapfrog step and updates the clock,
+/// or by one block step when the state enables block timesteps.
+pub fn step(bodies: &mut [Body], state: &mut SimulationState, forces: &Forces) {
+    if let Some(blocks) = state.block_timesteps {
+        step_blocks(bodies, state, forces, &blocks);
+        return;
+    }
+    let dt = state.effective_dt();
+    let half_dt = 0.5 * dt;
+
+    for body in bodies.iter_mut() {
+        body.position += body.velocity * half_dt;
+    }
+    let mut accelerations = compute_accelerations(bodies, forces.flags, state);
+    if let Some(pn) = &state.post_newtonian
+        && let (Some(central), Some(target)) = (bodies.get(pn.central), bodies.get(pn.target))
+    {
+        accelerations[pn.target] += pn.acceleration(central, target, state.gravitational_constant);
+    }
+    let midpoint_time = state.time as f32 + half_dt;
+    for (index, (body, acceleration)) in bodies.iter_mut().zip(accelerations).enumerate() {
+        let external = external_forces(forces, index, body.position, midpoint_time, state);
+        body.velocity += (acceleration + external) * dt;
+        body.position += body.velocity * half_dt;
+        state.bounds.apply(body);
+    }
+
+    state.time += f64::from(dt);
+    state.step += 1;
+}
+
+/// Potentials and per-body input accelerations on the `index`-th body.
+fn external_forces(
+    forces: &Forces,
+    index: usize,
+    position: Vec3,
+    time: f32,
+    state: &SimulationState,
+) -> Vec3 {
+    let mut external = external_acceleration(
+        forces.potentials,
+        position,
+        time,
+        state.gravitational_constant,
+    );
+    if let Some(input) = forces.per_body.get(index) {
+        external += input.truncate();
+    }
+    external
+}
+
+/// Total acceleration on each of the `targets` at the current positions.
+fn accelerations_at(
+    bodies: &[Body],
+    targets: &[usize],
+    state: &SimulationState,
+    forces: &Forces,
+    time: f32,
+) -> Vec<Vec3> {
+    let mut accelerations =
+        compute_accelerations_for(bodies, forces.flags, state, targets.iter().copied());
+    for (&index, acceleration) in targets.iter().zip(&mut accelerations) {
+        if let Some(pn) = &state.post_newtonian
+            && pn.target == index
+            && let Some(central) = bodies.get(pn.central)
+        {
+            *acceleration += pn.acceleration(central, &bodies[index], state.gravitational_constant);
+        }
+        *acceleration += external_forces(forces, index, bodies[index].position, time, state);
+    }
+    accelerations
+}
+
+/// One step of `dt` as `2^max_level` kick-drift-kick substeps, kicking each
+/// body only at the boundaries of its own bin.
+fn step_blocks(
+    bodies: &mut [Body],
+    state: &mut SimulationState,
+    forces: &Forces,
+    blocks: &BlockTimesteps,
+) {
+    let dt = state.effective_dt();
+    let levels = blocks.levels(
+        bodies,
+        forces.flags,
+        state.gravitational_constant,
+        state.softening,
+        dt,
+    );
+    let substeps = blocks.substeps();
+    let substep_dt = dt / substeps as f32;
+    let start_time = state.time;
+    let all: Vec<usize> = (0..bodies.len()).collect();
+    let mut accelerations = accelerations_at(bodies, &all, state, forces, start_time as f32);
+    let mut active = Vec::with_capacity(bodies.len());
+
+    for substep in 0..substeps {
+        // Opening half-kick for the bins starting a step here.
+        for (index, body) in bodies.iter_mut().enumerate() {
+            if blocks.is_boundary(levels[index], substep) {
+                let level_dt = substep_dt * blocks.stride(levels[index]) as f32;
+                body.velocity += accelerations[index] * (0.5 * level_dt);
+            }
+        }
+        for body in bodies.iter_mut() {
+            body.position += body.velocity * substep_dt;
+            state.bounds.apply(body);
+        }
+        state.time = start_time + f64::from(substep_dt) * f64::from(substep + 1);
+
+        // Closing half-kick with fresh forces for the bins ending a step here.
+        active.clear();
+        active.extend(
+            (0..bodies.len()).filter(|&index| blocks.is_boundary(levels[index], substep + 1)),
+        );
+        let fresh = accelerations_at(bodies, &active, state, forces, state.time as f32);
+        for (&index, acceleration) in active.iter().zip(fresh) {
+            let level_dt = substep_dt * blocks.stride(levels[index]) as f32;
+            accelerations[index] = acceleration;
+            bodies[index].velocity += acceleration * (0.5 * level_dt);
+        }
+    }
+    state.time = start_time + f64::from(dt);
+    state.step += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An equal-mass circular binary of separation 1 with G = 1, period 2π/√2.
+    fn binary() -> Vec<Body> {
+        let speed = 0.5_f32.sqrt();
+        [1.0_f32, -1.0]
+            .map(|side| {
+                Body::new(
+                    Vec3::new(0.5 * side, 0.0, 0.0),
+                    Vec3::new(0.0, 0.5 * speed * side, 0.0),
+                    0.5,
+                    0.01,
+                    Vec4::ONE,
+                )
+            })
+            .to_vec()
+    }
+
+    fn state(block_timesteps: Option<BlockTimesteps>) -> SimulationState {
+        SimulationState {
+            softening: 0.0,
+            dt: 1.0e-3,
+            block_timesteps,
+            ..SimulationState::default()
+        }
+    }
+
+    #[test]
+    fn block_steps_in_bin_zero_are_kick_drift_kick_leapfrog() {
+        // Loose enough that both bodies stay in the coarsest bin.
+        let blocks = BlockTimesteps::new(3, 1.0e3);
+        let mut state = state(Some(blocks));
+        let mut bodies = binary();
+        assert_eq!(blocks.levels(&bodies, &[], 1.0, 0.0, state.dt), [0, 0]);
+        let mut expected = binary();
+        let reference = self::state(None);
+        let dt = reference.dt;
+        let before = compute_accelerations(&expected, &[], &reference);
+        for (body, acceleration) in expected.iter_mut().zip(before) {
+            body.velocity += acceleration * (0.5 * dt);
+            body.position += body.velocity * dt;
+        }
+        let after = compute_accelerations(&expected, &[], &reference);
+        for (body, acceleration) in expected.iter_mut().zip(after) {
+            body.velocity += acceleration * (0.5 * dt);
+        }
+
+        step(&mut bodies, &mut state, &Forces::default());
+        for (body, expected) in bodies.iter().zip(&expected) {
+            assert!(body.position.abs_diff_eq(expected.position, 1.0e-6));
+            assert!(body.velocity.abs_diff_eq(expected.velocity, 1.0e-6));
+        }
+        assert_eq!(state.time, f64::from(dt));
+        assert_eq!(state.step, 1);
+    }
+
+    #[test]
+    fn block_steps_in_bin_zero_follow_uniform_leapfrog() {
+        let mut uniform = (binary(), state(None));
+        let mut blocks = (binary(), state(Some(BlockTimesteps::new(3, 1.0e3))));
+        // A quarter of an orbit.
+        for _ in 0..1100 {
+            step(&mut uniform.0, &mut uniform.1, &Forces::default());
+            step(&mut blocks.0, &mut blocks.1, &Forces::default());
+        }
+        for (uniform, block) in uniform.0.iter().zip(&blocks.0) {
+            assert!(uniform.position.abs_diff_eq(block.position, 1.0e-4));
+            assert!(uniform.velocity.abs_diff_eq(block.velocity, 1.0e-4));
+        }
+        assert!((uniform.1.time - blocks.1.time).abs() < 1.0e-9);
+    }
+}
@@ -0,0 +1,177 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+use super::Body;
+
+/// Bins per axis of every diagnostics plot; matches `PLOT_BINS` in `plots.wgsl`.
+pub const PLOT_BINS: usize = 32;
+
+/// Fraction of the bodies the radius and speed axes are fitted to, so a few
+/// escapers do not squash everything into the first bins.
+const FIT_QUANTILE: f32 = 0.95;
+
+/// Axis ranges of the diagnostics plots, mirrored by `PlotUniforms` in `plots.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PlotUniforms {
+    /// Centre of mass; radii are measured from it.
+    pub center: Vec3,
+    pub body_count: u32,
+    /// Centre-of-mass velocity; speeds are measured against it.
+    pub bulk_velocity: Vec3,
+    pub max_radius: f32,
+    pub max_speed: f32,
+    pub min_log_mass: f32,
+    pub max_log_mass: f32,
+    pub _padding: f32,
+}
+
+impl PlotUniforms {
+    /// Centres the axes on the bodies and fits them to `FIT_QUANTILE` of them.
+    pub fn fit(bodies: &[Body]) -> Self {
+        let total_mass: f32 = bodies.iter().map(|body| body.mass).sum();
+        let (center, bulk_velocity) = if total_mass > 0.0 {
+            let weighted = |field: fn(&Body) -> Vec3| {
+                bodies
+                    .iter()
+                    .map(|body| field(body) * body.mass)
+                    .sum::<Vec3>()
+                    / total_mass
+            };
+            (
+                weighted(|body| body.position),
+                weighted(|body| body.velocity),
+            )
+        } else {
+            let count = bodies.len().max(1) as f32;
+            (
+                bodies.iter().map(|body| body.position).sum::<Vec3>() / count,
+                bodies.iter().map(|body| body.velocity).sum::<Vec3>() / count,
+            )
+        };
+
+        let mut radii: Vec<f32> = bodies
+            .iter()
+            .map(|body| body.position.distance(center))
+            .collect();
+        let mut speeds: Vec<f32> = bodies
+            .iter()
+            .map(|body| body.velocity.distance(bulk_velocity))
+            .collect();
+        let (min_log_mass, max_log_mass) = bodies
+            .iter()
+            .filter(|body| body.mass > 0.0)
+            .map(|body| body.mass.log10())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), value| {
+                (low.min(value), high.max(value))
+            });
+        // A single mass still gets a bin-wide range around it.
+        let (min_log_mass, max_log_mass) = if min_log_mass < max_log_mass {
+            (min_log_mass, max_log_mass)
+        } else if min_log_mass.is_finite() {
+            (min_log_mass - 0.5, min_log_mass + 0.5)
+        } else {
+            (0.0, 1.0)
+        };
+
+        Self {
+            center,
+            body_count: bodies.len() as u32,
+            bulk_velocity,
+            max_radius: quantile(&mut radii, FIT_QUANTILE).max(f32::MIN_POSITIVE),
+            max_speed: quantile(&mut speeds, FIT_QUANTILE).max(f32::MIN_POSITIVE),
+            min_log_mass,
+            max_log_mass,
+            _padding: 0.0,
+        }
+    }
+
+    fn bin(value: f32, min: f32, max: f32) -> Option<usize> {
+        let scaled = (value - min) / (max - min) * PLOT_BINS as f32;
+        (scaled >= 0.0 && scaled < PLOT_BINS as f32).then_some(scaled as usize)
+    }
+
+    pub fn radius_bin(&self, radius: f32) -> Option<usize> {
+        Self::bin(radius, 0.0, self.max_radius)
+    }
+
+    pub fn speed_bin(&self, speed: f32) -> Option<usize> {
+        Self::bin(speed, 0.0, self.max_speed)
+    }
+
+    /// Masses outside the range land in the end bins; massless bodies in none.
+    pub fn mass_bin(&self, mass: f32) -> Option<usize> {
+        if mass <= 0.0 {
+            return None;
+        }
+        let scaled = (mass.log10() - self.min_log_mass) / (self.max_log_mass - self.min_log_mass);
+        Some(((scaled * PLOT_BINS as f32) as usize).min(PLOT_BINS - 1))
+    }
+}
+
+/// `fraction`-quantile of `values`, reordering them; zero when empty.
+fn quantile(values: &mut [f32], fraction: f32) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let index = ((values.len() - 1) as f32 * fraction) as usize;
+    *values.select_nth_unstable_by(index, f32::total_cmp).1
+}
+
+/// Live diagnostics plots: a radius–speed phase-space density, a log-mass
+/// histogram and a radial number-density profile. Binned on the GPU by
+/// `cs_plots_bin` into small buffers that are read back asynchronously; `fill`
+/// is the CPU mirror the worker uses.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiagnosticPlots {
+    pub uniforms: PlotUniforms,
+    /// Counts per (radius, speed) cell, radius-major, `PLOT_BINS²` entries.
+    pub phase_space: Vec<u32>,
+    /// Counts per log10(mass) bin.
+    pub mass_histogram: Vec<u32>,
+    /// Counts per spherical shell of width `max_radius / PLOT_BINS`.
+    pub shell_counts: Vec<u32>,
+}
+
+impl DiagnosticPlots {
+    /// Re-fits the axes and rebins in place so the allocations are reused.
+    pub fn fill(&mut self, bodies: &[Body]) {
+        self.uniforms = PlotUniforms::fit(bodies);
+        self.phase_space.clear();
+        self.phase_space.resize(PLOT_BINS * PLOT_BINS, 0);
+        self.mass_histogram.clear();
+        self.mass_histogram.resize(PLOT_BINS, 0);
+        self.shell_counts.clear();
+        self.shell_counts.resize(PLOT_BINS, 0);
+
+        let uniforms = self.uniforms;
+        for body in bodies {
+            let radius = body.position.distance(uniforms.center);
+            let speed = body.velocity.distance(uniforms.bulk_velocity);
+            if let Some(mass_bin) = uniforms.mass_bin(body.mass) {
+                self.mass_histogram[mass_bin] += 1;
+            }
+            let Some(radius_bin) = uniforms.radius_bin(radius) else {
+                continue;
+            };
+            self.shell_counts[radius_bin] += 1;
+            if let Some(speed_bin) = uniforms.speed_bin(speed) {
+                self.phase_space[radius_bin * PLOT_BINS + speed_bin] += 1;
+            }
+        }
+    }
+
+    /// Bodies per unit volume in each shell, innermost first.
+    pub fn density_profile(&self) -> impl Iterator<Item = f32> + '_ {
+        let width = self.uniforms.max_radius / PLOT_BINS as f32;
+        self.shell_counts
+            .iter()
+            .enumerate()
+            .map(move |(shell, &count)| {
+                let inner = shell as f32 * width;
+                let outer = inner + width;
+                let volume = 4.0 / 3.0 * std::f32::consts::PI * (outer.powi(3) - inner.powi(3));
+                count as f32 / volume
+            })
+    }
+}
@@ -0,0 +1,178 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Quat, Vec3};
+
+/// Analytic background potential evaluated alongside pairwise gravity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExternalPotential {
+    /// Constant acceleration everywhere, e.g. "down" in a box experiment.
+    UniformField { acceleration: Vec3 },
+    /// Spring toward `center`: a = -stiffness · (x - center).
+    HarmonicTrap { center: Vec3, stiffness: f32 },
+    /// Navarro-Frenk-White dark-matter halo centered on the origin. `mass` is the
+    /// characteristic mass 4π·ρ₀·r_s³.
+    NfwHalo { mass: f32, scale_radius: f32 },
+    /// Softened point mass at a fixed position, like the interactive gravity well.
+    /// Negative mass repels.
+    PointMass {
+        position: Vec3,
+        mass: f32,
+        softening: f32,
+    },
+    /// Bar in the XZ plane spinning about +Y, modelled as two softened point
+    /// masses at its tips.
+    RotatingBar {
+        mass: f32,
+        half_length: f32,
+        pattern_speed: f32,
+    },
+}
+
+impl ExternalPotential {
+    /// The same potential in units where one new length, mass and time unit is
+    /// `length`, `mass` and `time` of the current ones.
+    pub fn rescaled(self, length: f32, mass: f32, time: f32) -> Self {
+        match self {
+            Self::UniformField { acceleration } => Self::UniformField {
+                acceleration: acceleration * (time * time / length),
+            },
+            Self::HarmonicTrap { center, stiffness } => Self::HarmonicTrap {
+                center: center / length,
+                stiffness: stiffness * time * time,
+            },
+            Self::NfwHalo {
+                mass: halo,
+                scale_radius,
+            } => Self::NfwHalo {
+                mass: halo / mass,
+                scale_radius: scale_radius / length,
+            },
+            Self::PointMass {
+                position,
+                mass: point,
+                softening,
+            } => Self::PointMass {
+                position: position / length,
+                mass: point / mass,
+                softening: softening / length,
+            },
+            Self::RotatingBar {
+                mass: bar,
+                half_length,
+                pattern_speed,
+            } => Self::RotatingBar {
+                mass: bar / mass,
+                half_length: half_length / length,
+                pattern_speed: pattern_speed * time,
+            },
+        }
+    }
+
+    pub fn acceleration(&self, position: Vec3, time: f32, gravitational_constant: f32) -> Vec3 {
+        match *self {
+            Self::UniformField { acceleration } => acceleration,
+            Self::HarmonicTrap { center, stiffness } => -stiffness * (position - center),
+            Self::NfwHalo { mass, scale_radius } => {
+                let r = position.length().max(1e-6 * scale_radius);
+                let x = r / scale_radius;
+                let enclosed = mass * ((1.0 + x).ln() - x / (1.0 + x));
+                -gravitational_constant * enclosed / (r * r * r) * position
+            }
+            Self::PointMass {
+                position: center,
+                mass,
+                softening,
+            } => {
+                let offset = center - position;
+                let distance_sq = offset.length_squared() + softening * softening;
+                gravitational_constant * mass / (distance_sq * distance_sq.sqrt()) * offset
+            }
+            Self::RotatingBar {
+                mass,
+                half_length,
+                pattern_speed,
+            } => {
+                let axis = Quat::from_rotation_y(pattern_speed * time) * Vec3::X;
+                let softening_sq = 0.25 * half_length * half_length;
+                [axis, -axis]
+                    .into_iter()
+                    .map(|tip| {
+                        let offset = tip * half_length - position;
+                        let distance_sq = offset.length_squared() + softening_sq;
+                        offset * (0.5 * mass / (distance_sq * distance_sq.sqrt()))
+                    })
+                    .sum::<Vec3>()
+                    * gravitational_constant
+            }
+        }
+    }
+}
+
+pub fn external_acceleration(
+    potentials: &[ExternalPotential],
+    position: Vec3,
+    time: f32,
+    gravitational_constant: f32,
+) -> Vec3 {
+    potentials
+        .iter()
+        .map(|potential| potential.acceleration(position, time, gravitational_constant))
+        .sum()
+}
+
+/// Storage-buffer form of a potential; `kind` selects how the fields are read.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct GpuPotential {
+    pub vector: Vec3,
+    pub kind: u32,
+    pub strength: f32,
+    pub length: f32,
+    pub speed: f32,
+    pub _padding: f32,
+}
+
+impl From<ExternalPotential> for GpuPotential {
+    fn from(potential: ExternalPotential) -> Self {
+        match potential {
+            ExternalPotential::UniformField { acceleration } => Self {
+                vector: acceleration,
+                kind: 0,
+                ..Self::default()
+            },
+            ExternalPotential::HarmonicTrap { center, stiffness } => Self {
+                vector: center,
+                kind: 1,
+                strength: stiffness,
+                ..Self::default()
+            },
+            ExternalPotential::NfwHalo { mass, scale_radius } => Self {
+                kind: 2,
+                strength: mass,
+                length: scale_radius,
+                ..Self::default()
+            },
+            ExternalPotential::PointMass {
+                position,
+                mass,
+                softening,
+            } => Self {
+                vector: position,
+                kind: 4,
+                strength: mass,
+                length: softening,
+                ..Self::default()
+            },
+            ExternalPotential::RotatingBar {
+                mass,
+                half_length,
+                pattern_speed,
+            } => Self {
+                kind: 3,
+                strength: mass,
+                length: half_length,
+                speed: pattern_speed,
+                ..Self::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use super::physics::{self, Forces};
+use super::{Body, SimulationState};
+
+/// Pair of bodies whose perihelion direction is tracked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrackedOrbit {
+    pub central: usize,
+    pub target: usize,
+}
+
+/// Per-orbit perihelion advance, in radians, published for the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PrecessionReadout {
+    pub orbits: u32,
+    /// Mean advance per orbit in the running simulation.
+    pub measured: f32,
+    /// Same orbit integrated without the post-Newtonian term.
+    pub newtonian: f32,
+    /// Analytic 1PN expectation, when the correction is enabled.
+    pub predicted: Option<f32>,
+}
+
+/// Detects perihelion passages and accumulates the turn of the eccentricity vector.
+#[derive(Clone, Copy, Debug, Default)]
+struct PerihelionTracker {
+    radial_speed: Option<f32>,
+    angle: Option<f32>,
+    orbits: u32,
+    total: f32,
+    semi_latus_rectum: f32,
+}
+
+impl PerihelionTracker {
+    fn observe(&mut self, central: &Body, body: &Body, gravitational_constant: f32) {
+        let r = body.position - central.position;
+        let v = body.velocity - central.velocity;
+        let radial_speed = r.dot(v);
+        let passed = self
+            .radial_speed
+            .is_some_and(|previous| previous < 0.0 && radial_speed >= 0.0);
+        self.radial_speed = Some(radial_speed);
+        if !passed {
+            return;
+        }
+
+        let mu = gravitational_constant * central.mass;
+        let h = r.cross(v);
+        let eccentricity = v.cross(h) / mu - r.normalize_or_zero();
+        // Orbits run counter-clockwise seen from +Y, from +X towards +Z.
+        let angle = eccentricity.z.atan2(eccentricity.x);
+        if let Some(previous) = self.angle.replace(angle) {
+            let advance = (angle - previous + std::f32::consts::PI)
+                .rem_euclid(std::f32::consts::TAU)
+                - std::f32::consts::PI;
+            self.total += advance;
+            self.orbits += 1;
+        }
+        self.semi_latus_rectum = h.length_squared() / mu;
+    }
+
+    fn mean(&self) -> f32 {
+        if self.orbits == 0 {
+            0.0
+        } else {
+            self.total / self.orbits as f32
+        }
+    }
+}
+
+/// Measures perihelion precession of a tracked orbit alongside a Newtonian
+/// two-body shadow integration of the same pair, which isolates the
+/// post-Newtonian share from integrator error.
+#[derive(Clone, Debug)]
+pub struct PrecessionProbe {
+    orbit: TrackedOrbit,
+    tracker: PerihelionTracker,
+    shadow: [Body; 2],
+    shadow_state: SimulationState,
+    shadow_tracker: PerihelionTracker,
+}
+
+impl PrecessionProbe {
+    pub fn new(orbit: TrackedOrbit, bodies: &[Body], state: &SimulationState) -> Option<Self> {
+        let shadow = [*bodies.get(orbit.central)?, *bodies.get(orbit.target)?];
+        Some(Self {
+            orbit,
+            tracker: PerihelionTracker::default(),
+            shadow,
+            shadow_state: SimulationState {
+                post_newtonian: None,
+                contact: None,
+                bounds: Default::default(),
+                ..*state
+            },
+            shadow_tracker: PerihelionTracker::default(),
+        })
+    }
+
+    /// Steps the shadow pair alongside the simulation and samples both orbits.
+    pub fn observe(&mut self, bodies: &[Body], state: &SimulationState, steps: u32) {
+        self.shadow_state.dt = state.dt;
+        self.shadow_state.time_scale = state.time_scale;
+        let g = state.gravitational_constant;
+        for _ in 0..steps {
+            physics::step(&mut self.shadow, &mut self.shadow_state, &Forces::default());
+        }
+        let [central, target] = &self.shadow;
+        self.shadow_tracker.observe(central, target, g);
+        if let (Some(central), Some(target)) = (
+            bodies.get(self.orbit.central),
+            bodies.get(self.orbit.target),
+        ) {
+            self.tracker.observe(central, target, g);
+        }
+    }
+
+    pub fn readout(&self, state: &SimulationState, bodies: &[Body]) -> PrecessionReadout {
+        let mu = bodies
+            .get(self.orbit.central)
+            .map_or(0.0, |central| state.gravitational_constant * central.mass);
+        PrecessionReadout {
+            orbits: self.tracker.orbits,
+            measured: self.tracker.mean(),
+            newtonian: self.shadow_tracker.mean(),
+            predicted: state
+                .post_newtonian
+                .filter(|_| self.tracker.semi_latus_rectum > 0.0)
+                .map(|pn| pn.predicted_precession(mu, self.tracker.semi_latus_rectum)),
+        }
+    }
+}
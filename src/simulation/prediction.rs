@@ -0,0 +1,42 @@
+use glam::Vec3;
+
+use super::physics::{self, Forces};
+use super::{Body, SimulationState};
+
+/// Integrates a massless test particle through the most massive bodies and
+/// returns its positions, for trajectory previews.
+///
+/// Only `max_attractors` bodies are simulated so the preview stays cheap in
+/// large scenarios; `stride` coarsens the step relative to the live `dt`.
+pub fn predict_trajectory(
+    bodies: &[Body],
+    position: Vec3,
+    velocity: Vec3,
+    state: &SimulationState,
+    steps: usize,
+    stride: f32,
+    max_attractors: usize,
+) -> Vec<Vec3> {
+    let mut attractors: Vec<Body> = bodies.to_vec();
+    attractors.sort_by(|a, b| b.mass.total_cmp(&a.mass));
+    attractors.truncate(max_attractors);
+    attractors.push(Body {
+        position,
+        velocity,
+        mass: 0.0,
+        ..Body::default()
+    });
+    let probe = attractors.len() - 1;
+
+    let mut state = SimulationState {
+        dt: state.dt * stride,
+        ..*state
+    };
+    let mut path = Vec::with_capacity(steps + 1);
+    path.push(position);
+    for _ in 0..steps {
+        physics::step(&mut attractors, &mut state, &Forces::default());
+        path.push(attractors[probe].position);
+    }
+    path
+}
@@ -0,0 +1,58 @@
+use glam::Vec3;
+
+use super::Body;
+
+/// First post-Newtonian (Schwarzschild, test-particle) correction for one body
+/// orbiting a central mass; the source of general-relativistic perihelion precession.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PostNewtonian {
+    /// In simulation units; lowering it exaggerates the effect.
+    pub speed_of_light: f32,
+    pub central: usize,
+    pub target: usize,
+}
+
+impl PostNewtonian {
+    /// Correction to the Newtonian acceleration of `body` around `central`.
+    pub fn acceleration(&self, central: &Body, body: &Body, gravitational_constant: f32) -> Vec3 {
+        let r = body.position - central.position;
+        let v = body.velocity - central.velocity;
+        let distance_sq = r.length_squared();
+        if distance_sq == 0.0 {
+            return Vec3::ZERO;
+        }
+        let distance = distance_sq.sqrt();
+        let mu = gravitational_constant * central.mass;
+        let c_sq = self.speed_of_light * self.speed_of_light;
+        mu / (c_sq * distance_sq * distance)
+            * ((4.0 * mu / distance - v.length_squared()) * r + 4.0 * r.dot(v) * v)
+    }
+
+    /// Expected 1PN advance per orbit, 6πμ / (c² a (1 − e²)), in radians.
+    pub fn predicted_precession(&self, mu: f32, semi_latus_rectum: f32) -> f32 {
+        6.0 * std::f32::consts::PI * mu
+            / (self.speed_of_light * self.speed_of_light * semi_latus_rectum)
+    }
+}
+
+/// Non-rotating black hole whose light bending the renderer fakes in screen
+/// space around the body's projected position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GravitationalLens {
+    pub body: usize,
+    /// 2GM/c² in simulation units.
+    pub schwarzschild_radius: f32,
+    /// Tint of the photon ring drawn at the edge of the shadow.
+    pub ring_color: Vec3,
+}
+
+impl GravitationalLens {
+    /// Schwarzschild radius of `mass` for the given constants.
+    pub fn schwarzschild_radius(
+        mass: f32,
+        gravitational_constant: f32,
+        speed_of_light: f32,
+    ) -> f32 {
+        2.0 * gravitational_constant * mass / (speed_of_light * speed_of_light)
+    }
+}
@@ -0,0 +1,160 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec3, Vec4};
+
+use super::potentials::GpuPotential;
+use super::supernova::{ImpulseUniforms, Supernova};
+use super::{Body, EscapePolicy, ExternalPotential, SimulationState, StarState};
+use crate::rendering::upload::{BufferId, UploadBelt};
+
+/// Uniform block mirrored by the compute shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct SimulationUniforms {
+    pub gravitational_constant: f32,
+    pub softening: f32,
+    pub dt: f32,
+    pub body_count: u32,
+    /// Radius checked by the escape-flag pass; zero disables it.
+    pub escape_radius: f32,
+    pub boundary_mode: u32,
+    pub potential_count: u32,
+    /// Simulated time, for time-dependent potentials.
+    pub time: f32,
+    pub bounds_half_extent: Vec3,
+    pub _padding_bounds: f32,
+    pub well_position: Vec3,
+    pub well_mass: f32,
+    pub well_softening: f32,
+    /// Soft-sphere contact coefficients; zero stiffness disables contacts.
+    pub contact_stiffness: f32,
+    pub contact_damping: f32,
+    /// 1/c² of the post-Newtonian term; zero disables it.
+    pub post_newtonian_inv_c2: f32,
+    pub post_newtonian_central: u32,
+    pub post_newtonian_target: u32,
+    /// `ForcePrecision` of the pairwise sum.
+    pub force_precision: u32,
+    /// Gyr of stellar age per unit of simulated time; zero disables `cs_evolve`.
+    pub stellar_gyr_per_time: f32,
+}
+
+/// GPU buffers holding the simulation; all writes go through the upload belt.
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationResources {
+    pub bodies_buffer: BufferId,
+    pub uniforms_buffer: BufferId,
+    pub potentials_buffer: BufferId,
+    /// Per-body input accelerations, parallel to the bodies.
+    pub accelerations_buffer: BufferId,
+    /// Parameters for the one-shot impulse pass.
+    pub impulse_buffer: BufferId,
+    /// Per-body spin and rotation phase, parallel to the bodies; read by the render pass.
+    pub spins_buffer: BufferId,
+    /// Per-body `StarState`, aged by `cs_evolve` and read back for the HR plot.
+    pub stars_buffer: BufferId,
+    pub body_count: u32,
+    pub potential_count: u32,
+}
+
+impl SimulationResources {
+    pub fn new(
+        bodies_buffer: BufferId,
+        uniforms_buffer: BufferId,
+        potentials_buffer: BufferId,
+        accelerations_buffer: BufferId,
+        impulse_buffer: BufferId,
+        spins_buffer: BufferId,
+        stars_buffer: BufferId,
+    ) -> Self {
+        Self {
+            bodies_buffer,
+            uniforms_buffer,
+            potentials_buffer,
+            accelerations_buffer,
+            impulse_buffer,
+            spins_buffer,
+            stars_buffer,
+            body_count: 0,
+            potential_count: 0,
+        }
+    }
+
+    pub fn update_potentials(&mut self, upload: &mut UploadBelt, potentials: &[ExternalPotential]) {
+        let gpu: Vec<GpuPotential> = potentials.iter().copied().map(GpuPotential::from).collect();
+        self.potential_count = gpu.len() as u32;
+        upload.write(self.potentials_buffer, 0, &gpu);
+    }
+
+    pub fn update_bodies(&mut self, upload: &mut UploadBelt, bodies: &[Body]) {
+        self.body_count = bodies.len() as u32;
+        upload.write(self.bodies_buffer, 0, bodies);
+    }
+
+    /// Rewrites one body in place, e.g. after an inspector edit, without
+    /// re-uploading the rest.
+    pub fn update_body(&self, upload: &mut UploadBelt, index: usize, body: &Body) {
+        let offset = (index * std::mem::size_of::<Body>()) as u64;
+        upload.write(self.bodies_buffer, offset, std::slice::from_ref(body));
+    }
+
+    pub fn update_accelerations(&mut self, upload: &mut UploadBelt, accelerations: &[Vec4]) {
+        upload.write(self.accelerations_buffer, 0, accelerations);
+    }
+
+    pub fn update_spins(&mut self, upload: &mut UploadBelt, spins: &[Vec4]) {
+        upload.write(self.spins_buffer, 0, spins);
+    }
+
+    pub fn update_stars(&mut self, upload: &mut UploadBelt, stars: &[StarState]) {
+        upload.write(self.stars_buffer, 0, stars);
+    }
+
+    /// Stages a supernova for `cs_impulse`, dispatched once over the bodies.
+    pub fn queue_impulse(&self, upload: &mut UploadBelt, supernova: Supernova) {
+        upload.write(self.impulse_buffer, 0, &[ImpulseUniforms::from(supernova)]);
+    }
+
+    pub fn update_simulation_state(
+        &self,
+        upload: &mut UploadBelt,
+        state: &SimulationState,
+        escape: &EscapePolicy,
+        gravity_well: Option<&ExternalPotential>,
+    ) {
+        let (well_position, well_mass, well_softening) = match gravity_well {
+            Some(&ExternalPotential::PointMass {
+                position,
+                mass,
+                softening,
+            }) => (position, mass, softening),
+            _ => (Vec3::ZERO, 0.0, 1.0),
+        };
+        let uniforms = SimulationUniforms {
+            gravitational_constant: state.gravitational_constant,
+            softening: state.softening,
+            dt: state.effective_dt(),
+            body_count: self.body_count,
+            escape_radius: escape.radius.unwrap_or(0.0),
+            boundary_mode: state.bounds.mode as u32,
+            potential_count: self.potential_count,
+            time: state.time as f32,
+            bounds_half_extent: state.bounds.half_extent,
+            _padding_bounds: 0.0,
+            well_position,
+            well_mass,
+            well_softening,
+            contact_stiffness: state.contact.map_or(0.0, |contact| contact.stiffness),
+            contact_damping: state.contact.map_or(0.0, |contact| contact.damping),
+            post_newtonian_inv_c2: state
+                .post_newtonian
+                .map_or(0.0, |pn| (pn.speed_of_light * pn.speed_of_light).recip()),
+            post_newtonian_central: state.post_newtonian.map_or(0, |pn| pn.central as u32),
+            post_newtonian_target: state.post_newtonian.map_or(0, |pn| pn.target as u32),
+            force_precision: state.force_precision as u32,
+            stellar_gyr_per_time: state
+                .stellar_evolution
+                .map_or(0.0, |evolution| evolution.gyr_per_time),
+        };
+        upload.write(self.uniforms_buffer, 0, &[uniforms]);
+    }
+}
@@ -0,0 +1,148 @@
+use glam::{Vec3, Vec4};
+
+use super::{
+    Body, BodyGroup, ParamDescriptor, Rng, Simulation, SimulationError, SimulationState,
+    SoftSphere, sanitize_parameter,
+};
+
+const PRIMARY_MASS: f32 = 1000.0;
+const PRIMARY_RADIUS: f32 = 2.0;
+const PILE_MASS: f32 = 1.0;
+const PILE_RADIUS: f32 = 0.5;
+
+/// A self-gravitating rubble pile held together by soft-sphere contacts, on an
+/// eccentric orbit whose pericenter can dip inside the primary's Roche limit
+/// (arbitrary units with G = 1).
+pub struct RubblePileSimulation {
+    /// Grain radius; the grain count follows from packing them into the pile.
+    pub grain_radius: f32,
+    /// Pericenter distance in units of the Roche limit.
+    pub pericenter: f32,
+    pub seed: u64,
+}
+
+impl Default for RubblePileSimulation {
+    fn default() -> Self {
+        Self {
+            grain_radius: 0.05,
+            pericenter: 0.4,
+            seed: 7,
+        }
+    }
+}
+
+impl RubblePileSimulation {
+    /// Fluid Roche limit, 2.44 R (ρ_primary / ρ_pile)^(1/3), in terms of the pile's bulk density.
+    pub fn roche_limit(&self) -> f32 {
+        2.44 * PILE_RADIUS * (PRIMARY_MASS / PILE_MASS).cbrt()
+    }
+
+    /// Grain centres on a jittered cubic lattice of touching grains inside the pile.
+    fn grain_offsets(&self) -> Vec<Vec3> {
+        let mut rng = Rng::new(self.seed);
+        let spacing = 2.0 * self.grain_radius;
+        let cells = (PILE_RADIUS / spacing).floor() as i32;
+        let mut offsets = Vec::new();
+        for x in -cells..=cells {
+            for y in -cells..=cells {
+                for z in -cells..=cells {
+                    let offset = Vec3::new(x as f32, y as f32, z as f32) * spacing;
+                    if offset.length() + self.grain_radius <= PILE_RADIUS {
+                        let jitter = Vec3::new(rng.gaussian(), rng.gaussian(), rng.gaussian());
+                        offsets.push(offset + jitter * 0.02 * self.grain_radius);
+                    }
+                }
+            }
+        }
+        offsets
+    }
+}
+
+impl Simulation for RubblePileSimulation {
+    fn name(&self) -> &str {
+        "Tidal Disruption"
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let roche = self.roche_limit();
+        let pericenter = (self.pericenter * roche).max(PRIMARY_RADIUS + 2.0 * PILE_RADIUS);
+        let apocenter = 2.0 * roche;
+        let semi_major_axis = 0.5 * (pericenter + apocenter);
+        let speed = (PRIMARY_MASS * (2.0 / apocenter - 1.0 / semi_major_axis)).sqrt();
+        let center = Vec3::new(apocenter, 0.0, 0.0);
+        let velocity = Vec3::new(0.0, 0.0, speed);
+
+        let offsets = self.grain_offsets();
+        let grain_mass = PILE_MASS / offsets.len() as f32;
+        let mut bodies = Vec::with_capacity(offsets.len() + 1);
+        bodies.push(Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            PRIMARY_MASS,
+            PRIMARY_RADIUS,
+            Vec4::new(0.9, 0.6, 0.3, 1.0),
+        ));
+        for offset in offsets {
+            let shade = 0.55 + 0.1 * (offset.y / PILE_RADIUS);
+            bodies.push(Body::new(
+                center + offset,
+                velocity,
+                grain_mass,
+                self.grain_radius,
+                Vec4::new(shade, shade, shade * 0.9, 1.0),
+            ));
+        }
+        bodies
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        if index == 0 {
+            BodyGroup::Planet
+        } else {
+            BodyGroup::Asteroid
+        }
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: self.grain_radius,
+            dt: 2.0e-3,
+            contact: Some(SoftSphere {
+                stiffness: 4.0e4,
+                damping: 100.0,
+            }),
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![
+            ParamDescriptor::new(
+                "grain_radius",
+                0.03,
+                0.15,
+                defaults.grain_radius,
+                self.grain_radius,
+            ),
+            ParamDescriptor::new("pericenter", 0.3, 2.0, defaults.pericenter, self.pericenter),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        match name {
+            "grain_radius" => self.grain_radius = value,
+            _ => self.pericenter = value,
+        }
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+
+use glam::Vec4;
+
+use super::{Body, SimulationState, StarState};
+
+/// Bodies, flags, spins, stellar ages and clock captured from a `BodyWorld`, for rolling back.
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    pub bodies: Vec<Body>,
+    pub flags: Vec<u32>,
+    pub spins: Vec<Vec4>,
+    pub stars: Vec<StarState>,
+    pub state: SimulationState,
+}
+
+/// The most recent snapshots, oldest first, dropping the oldest when full.
+#[derive(Clone, Debug)]
+pub struct SnapshotRing {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+}
+
+impl SnapshotRing {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Starts over from `snapshot`, e.g. after a scenario is (re)loaded.
+    pub fn reset(&mut self, snapshot: Snapshot) {
+        self.snapshots.clear();
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Records `snapshot`, dropping the oldest entry once full.
+    pub fn push(&mut self, snapshot: Snapshot) {
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    pub fn latest(&self) -> Option<&Snapshot> {
+        self.snapshots.back()
+    }
+
+    /// Steps one entry back: drops the newest snapshot unless it is the only
+    /// one left, and returns the one to restore.
+    pub fn rewind(&mut self) -> Option<&Snapshot> {
+        if self.snapshots.len() > 1 {
+            self.snapshots.pop_back();
+        }
+        self.snapshots.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
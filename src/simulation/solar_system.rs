@@ -0,0 +1,182 @@
+use glam::{Vec3, Vec4};
+
+use super::orbits::OrbitalElements;
+use super::units::G_ASTRONOMICAL;
+use super::{Body, BodyGroup, ForcePrecision, Simulation, SimulationState, SpacecraftSpec};
+use crate::rendering::config::{BackgroundConfig, BodyGeometry, RenderConfig, TrailConfig};
+
+struct Planet {
+    name: &'static str,
+    semi_major_axis: f32,
+    mass: f32,
+    radius: f32,
+    color: [f32; 3],
+}
+
+const PLANETS: [Planet; 8] = [
+    Planet {
+        name: "Mercury",
+        semi_major_axis: 0.387,
+        mass: 1.66e-7,
+        radius: 0.010,
+        color: [0.6, 0.6, 0.6],
+    },
+    Planet {
+        name: "Venus",
+        semi_major_axis: 0.723,
+        mass: 2.45e-6,
+        radius: 0.015,
+        color: [0.9, 0.8, 0.5],
+    },
+    Planet {
+        name: "Earth",
+        semi_major_axis: 1.000,
+        mass: 3.00e-6,
+        radius: 0.016,
+        color: [0.2, 0.5, 1.0],
+    },
+    Planet {
+        name: "Mars",
+        semi_major_axis: 1.524,
+        mass: 3.23e-7,
+        radius: 0.012,
+        color: [0.9, 0.4, 0.2],
+    },
+    Planet {
+        name: "Jupiter",
+        semi_major_axis: 5.203,
+        mass: 9.55e-4,
+        radius: 0.045,
+        color: [0.9, 0.7, 0.5],
+    },
+    Planet {
+        name: "Saturn",
+        semi_major_axis: 9.537,
+        mass: 2.86e-4,
+        radius: 0.040,
+        color: [0.9, 0.8, 0.6],
+    },
+    Planet {
+        name: "Uranus",
+        semi_major_axis: 19.19,
+        mass: 4.37e-5,
+        radius: 0.030,
+        color: [0.6, 0.9, 0.9],
+    },
+    Planet {
+        name: "Neptune",
+        semi_major_axis: 30.07,
+        mass: 5.15e-5,
+        radius: 0.030,
+        color: [0.3, 0.4, 1.0],
+    },
+];
+
+/// Index of Earth in the body list (after the Sun).
+const EARTH: usize = 3;
+
+/// Parking orbit radius of the spacecraft around Earth, well inside its Hill sphere.
+const PARKING_ORBIT: f32 = 0.004;
+
+/// Sun and the eight planets on circular, coplanar orbits (AU, years, solar masses),
+/// plus a steerable spacecraft parked around Earth for gravity-assist experiments.
+pub struct SolarSystemSimulation;
+
+impl Simulation for SolarSystemSimulation {
+    fn name(&self) -> &str {
+        "Solar System"
+    }
+
+    fn length_label(&self) -> &'static str {
+        "AU"
+    }
+
+    fn time_label(&self) -> &'static str {
+        "yr"
+    }
+
+    /// Nine large bodies: lit spheres show the planets' phases.
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            geometry: BodyGeometry::Icospheres,
+            background: BackgroundConfig {
+                starfield_density: 0.02,
+                ..BackgroundConfig::default()
+            },
+            trails: TrailConfig::planets_only(),
+            ..RenderConfig::default()
+        }
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut bodies = vec![Body::new(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            1.0,
+            0.08,
+            Vec4::new(1.0, 0.9, 0.4, 1.0),
+        )];
+
+        for (index, planet) in PLANETS.iter().enumerate() {
+            let (position, velocity) =
+                OrbitalElements::circular(planet.semi_major_axis, index as f32 * 2.4)
+                    .state_vectors(G_ASTRONOMICAL);
+            bodies.push(Body::new(
+                position,
+                velocity,
+                planet.mass,
+                planet.radius,
+                Vec3::from(planet.color).extend(1.0),
+            ));
+        }
+
+        let earth = bodies[EARTH];
+        let (offset, relative_velocity) =
+            OrbitalElements::circular(PARKING_ORBIT, earth.position.z.atan2(earth.position.x))
+                .state_vectors(G_ASTRONOMICAL * earth.mass);
+        bodies.push(Body::new(
+            earth.position + offset,
+            earth.velocity + relative_velocity,
+            1.0e-12,
+            0.004,
+            Vec4::new(0.4, 1.0, 0.4, 1.0),
+        ));
+
+        bodies
+    }
+
+    fn body_group(&self, index: usize) -> BodyGroup {
+        match index {
+            0 => BodyGroup::Star,
+            1..=8 => BodyGroup::Planet,
+            _ => BodyGroup::Other,
+        }
+    }
+
+    fn body_name(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("Sun"),
+            1..=8 => Some(PLANETS[index - 1].name),
+            _ => None,
+        }
+    }
+
+    /// Roughly enough delta-v for a Hohmann transfer to Jupiter and some margin.
+    fn spacecraft(&self) -> Option<SpacecraftSpec> {
+        Some(SpacecraftSpec {
+            index: PLANETS.len() + 1,
+            delta_v_budget: 3.0,
+            max_acceleration: 20.0,
+        })
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            gravitational_constant: G_ASTRONOMICAL,
+            force_precision: ForcePrecision::Compensated,
+            softening: 1.0e-4,
+            dt: 1.0e-4,
+            ..SimulationState::default()
+        }
+    }
+}
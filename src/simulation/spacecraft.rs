@@ -0,0 +1,65 @@
+use bevy_ecs::prelude::Component;
+use glam::Vec3;
+
+/// Marks a scenario body as the player-controlled spacecraft.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpacecraftSpec {
+    pub index: usize,
+    /// Total delta-v the craft can spend.
+    pub delta_v_budget: f32,
+    /// Acceleration at full throttle.
+    pub max_acceleration: f32,
+}
+
+/// Throttle along the craft's orbit: prograde/retrograde and radial out/in, each in `[-1, 1]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThrustInput {
+    pub prograde: f32,
+    pub radial: f32,
+}
+
+impl ThrustInput {
+    pub fn is_idle(&self) -> bool {
+        self.prograde == 0.0 && self.radial == 0.0
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Spacecraft {
+    pub fuel: f32,
+    pub capacity: f32,
+    pub max_acceleration: f32,
+    pub throttle: ThrustInput,
+}
+
+impl Spacecraft {
+    pub fn new(spec: &SpacecraftSpec) -> Self {
+        Self {
+            fuel: spec.delta_v_budget,
+            capacity: spec.delta_v_budget,
+            max_acceleration: spec.max_acceleration,
+            throttle: ThrustInput::default(),
+        }
+    }
+
+    /// Thrust acceleration for a craft moving with `velocity` at `position`
+    /// relative to the system origin; zero once the fuel is spent.
+    pub fn acceleration(&self, position: Vec3, velocity: Vec3) -> Vec3 {
+        if self.fuel <= 0.0 || self.throttle.is_idle() {
+            return Vec3::ZERO;
+        }
+        let prograde = velocity.normalize_or_zero();
+        let radial = (position - position.dot(prograde) * prograde).normalize_or_zero();
+        let direction = prograde * self.throttle.prograde + radial * self.throttle.radial;
+        direction.clamp_length_max(1.0) * self.max_acceleration
+    }
+}
+
+/// Fuel readout published with each frame for the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpacecraftStatus {
+    pub index: usize,
+    pub fuel: f32,
+    pub capacity: f32,
+    pub thrusting: bool,
+}
@@ -0,0 +1,59 @@
+use glam::{Vec3, Vec4};
+
+use super::Body;
+
+/// Constant-time-lag tidal torque on body spins: every pair drags each
+/// body's angular velocity toward their mutual orbital angular velocity at
+///
+/// `dω/dt = time_lag · G m_j² R_i³ / (m_i r⁶) · (n − ω)`,
+///
+/// so a moon spun up or down ends up tidally locked. Only the spins feel
+/// it; the orbits are not given the angular momentum back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TidalSpin {
+    /// `3 k₂ Δt / α` (time): Love number, tidal lag and moment-of-inertia factor.
+    pub time_lag: f32,
+}
+
+impl TidalSpin {
+    /// Relaxes every spin over `dt` toward the rate-weighted mean of its
+    /// orbital angular velocities, exactly, so long ticks cannot overshoot.
+    pub fn relax(&self, spins: &mut [Vec4], bodies: &[Body], gravitational_constant: f32, dt: f32) {
+        for (i, (spin, body)) in spins.iter_mut().zip(bodies).enumerate() {
+            if body.mass <= 0.0 || body.radius <= 0.0 {
+                continue;
+            }
+            let mut total_rate = 0.0;
+            let mut target = Vec3::ZERO;
+            for (j, other) in bodies.iter().enumerate() {
+                if i == j || other.mass <= 0.0 {
+                    continue;
+                }
+                let offset = body.position - other.position;
+                let distance_sq = offset.length_squared();
+                if distance_sq == 0.0 {
+                    continue;
+                }
+                // Grouped so SI-sized masses and radii stay inside f32 range.
+                let distance = distance_sq.sqrt();
+                let rate = self.time_lag * gravitational_constant * other.mass / distance.powi(3)
+                    * (other.mass / body.mass)
+                    * (body.radius / distance).powi(3);
+                let orbital = offset.cross(body.velocity - other.velocity) / distance_sq;
+                total_rate += rate;
+                target += orbital * rate;
+            }
+            if total_rate > 0.0 {
+                let target = target / total_rate;
+                let omega = target + (spin.truncate() - target) * (-total_rate * dt).exp();
+                *spin = omega.extend(spin.w);
+            }
+        }
+    }
+}
+
+/// Turns the rotation phase (`w`, radians about the spin axis) by `|ω| dt`.
+pub fn advance_phase(spin: &mut Vec4, dt: f32) {
+    let rate = spin.truncate().length();
+    spin.w = (spin.w + rate * dt).rem_euclid(std::f32::consts::TAU);
+}
@@ -0,0 +1,150 @@
+use glam::{Vec3, Vec4};
+
+use super::{
+    Body, BodyGroup, ClusterDiagnostics, ParamDescriptor, Rng, Simulation, SimulationError,
+    SimulationState, sanitize_parameter,
+};
+use crate::rendering::config::{BlendMode, HaloConfig, RenderConfig, ToneMapper};
+
+/// Plummer radii beyond this many scale lengths are redrawn.
+const MAX_RADIUS: f32 = 10.0;
+/// Plummer scale length in N-body units, 3π/16.
+const SCALE_LENGTH: f32 = 3.0 * std::f32::consts::PI / 16.0;
+
+/// Equal-mass Plummer sphere in virial equilibrium, sampled as in Aarseth,
+/// Hénon & Wielen (1974) directly in N-body units (G = M = 1, E = −1/4).
+/// With light softening the core contracts over a few relaxation times while
+/// the halo expands, which the Lagrangian radii make visible.
+pub struct StarClusterSimulation {
+    pub body_count: usize,
+    pub seed: u64,
+}
+
+impl Default for StarClusterSimulation {
+    fn default() -> Self {
+        Self {
+            body_count: 256,
+            seed: 3,
+        }
+    }
+}
+
+fn isotropic(rng: &mut Rng, length: f32) -> Vec3 {
+    let cos_theta = rng.range(-1.0, 1.0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+    let (sin_phi, cos_phi) = rng.range(0.0, std::f32::consts::TAU).sin_cos();
+    Vec3::new(sin_theta * cos_phi, cos_theta, sin_theta * sin_phi) * length
+}
+
+impl Simulation for StarClusterSimulation {
+    fn name(&self) -> &str {
+        "Star Cluster"
+    }
+
+    fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            blend_mode: BlendMode::Additive,
+            tone_mapper: ToneMapper::Aces,
+            // Equal masses: every star gets the same faint one-step halo.
+            halo: HaloConfig {
+                emissive_mass: 0.5 / self.body_count.max(1) as f32,
+                intensity: 0.3,
+                ..HaloConfig::default()
+            },
+            ..RenderConfig::default()
+        }
+    }
+
+    fn initialize_bodies(&self) -> Vec<Body> {
+        let mut rng = Rng::new(self.seed);
+        let mass = 1.0 / self.body_count as f32;
+        let mut bodies: Vec<Body> = (0..self.body_count)
+            .map(|_| {
+                let radius = loop {
+                    let mass_fraction = rng.next_f32().max(f32::MIN_POSITIVE);
+                    let radius = (mass_fraction.powf(-2.0 / 3.0) - 1.0)
+                        .max(0.0)
+                        .sqrt()
+                        .recip();
+                    if radius < MAX_RADIUS {
+                        break radius;
+                    }
+                };
+                // von Neumann rejection on g(q) = q² (1 − q²)^3.5, whose peak is below 0.1.
+                let q = loop {
+                    let q = rng.next_f32();
+                    if rng.range(0.0, 0.1) < q * q * (1.0 - q * q).powf(3.5) {
+                        break q;
+                    }
+                };
+                let escape_speed = std::f32::consts::SQRT_2 * (1.0 + radius * radius).powf(-0.25);
+                let warmth = (1.0 - radius / 3.0).clamp(0.0, 1.0);
+                Body::new(
+                    isotropic(&mut rng, radius * SCALE_LENGTH),
+                    isotropic(&mut rng, q * escape_speed / SCALE_LENGTH.sqrt()),
+                    mass,
+                    0.02,
+                    Vec4::new(
+                        0.7 + 0.3 * warmth,
+                        0.75 + 0.15 * warmth,
+                        1.0 - 0.2 * warmth,
+                        1.0,
+                    ),
+                )
+            })
+            .collect();
+
+        let center = bodies.iter().map(|body| body.position).sum::<Vec3>() / bodies.len() as f32;
+        let drift = bodies.iter().map(|body| body.velocity).sum::<Vec3>() / bodies.len() as f32;
+        for body in &mut bodies {
+            body.position -= center;
+            body.velocity -= drift;
+        }
+        bodies
+    }
+
+    fn body_group(&self, _index: usize) -> BodyGroup {
+        BodyGroup::Star
+    }
+
+    /// About one sample per second at the default time scale.
+    fn cluster_diagnostics(&self) -> Option<ClusterDiagnostics> {
+        Some(ClusterDiagnostics {
+            sample_interval: 2.0,
+        })
+    }
+
+    fn initial_state(&self) -> SimulationState {
+        SimulationState {
+            softening: 0.01,
+            dt: 2.0e-3,
+            substeps: 8,
+            ..SimulationState::default()
+        }
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        let defaults = Self::default();
+        vec![ParamDescriptor::integer(
+            "body_count",
+            16,
+            2048,
+            defaults.body_count as u32,
+            self.body_count as u32,
+        )]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), SimulationError> {
+        let value = sanitize_parameter(&self.parameters(), name, value)?;
+        self.body_count = value as usize;
+        Ok(())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+}
@@ -0,0 +1,77 @@
+use super::WorldBounds;
+use super::contact::SoftSphere;
+use super::evolution::StellarEvolution;
+use super::relativity::PostNewtonian;
+use super::spin::TidalSpin;
+use super::timesteps::BlockTimesteps;
+
+/// How the force kernel sums the pairwise terms.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ForcePrecision {
+    /// Plain f32 accumulation.
+    #[default]
+    Single,
+    /// Neumaier-compensated f32 accumulation, which keeps the small planetary
+    /// terms that plain summation loses next to the Sun's.
+    Compensated,
+}
+
+/// Physics constants and clock shared by the integrator and the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationState {
+    pub gravitational_constant: f32,
+    pub softening: f32,
+    pub dt: f32,
+    /// Integration steps per worker step, for scenarios that need a small `dt`
+    /// but long runs.
+    pub substeps: u32,
+    pub time_scale: f32,
+    pub paused: bool,
+    /// Integrate backwards in time; the leapfrog retraces its own steps.
+    pub reversed: bool,
+    pub time: f64,
+    pub step: u64,
+    pub bounds: WorldBounds,
+    /// Contact model between overlapping bodies; `None` lets them pass through.
+    pub contact: Option<SoftSphere>,
+    pub post_newtonian: Option<PostNewtonian>,
+    /// Tidal torques on body spins; `None` leaves every spin constant.
+    pub tides: Option<TidalSpin>,
+    /// Ageing of the bodies the scenario gives a stellar mass.
+    pub stellar_evolution: Option<StellarEvolution>,
+    pub force_precision: ForcePrecision,
+    /// Per-body power-of-two substeps; `None` steps every body by `dt`.
+    pub block_timesteps: Option<BlockTimesteps>,
+}
+
+impl Default for SimulationState {
+    fn default() -> Self {
+        Self {
+            gravitational_constant: 1.0,
+            softening: 0.01,
+            dt: 0.001,
+            substeps: 1,
+            time_scale: 1.0,
+            paused: false,
+            reversed: false,
+            time: 0.0,
+            step: 0,
+            bounds: WorldBounds::default(),
+            contact: None,
+            post_newtonian: None,
+            tides: None,
+            stellar_evolution: None,
+            force_precision: ForcePrecision::Single,
+            block_timesteps: None,
+        }
+    }
+}
+
+impl SimulationState {
+    /// Signed step: negative while running in reverse.
+    pub fn effective_dt(&self) -> f32 {
+        let dt = self.dt * self.time_scale;
+        if self.reversed { -dt } else { dt }
+    }
+}
@@ -0,0 +1,54 @@
+/// When a task paced by simulation steps, such as a video frame or a point
+/// cloud, is next due. Frames may skip or repeat steps, and a reset or rewind
+/// runs the step count backwards, which restarts the pacing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepCursor {
+    /// Step from which the task is next due.
+    next: Option<u64>,
+}
+
+impl StepCursor {
+    /// The step the task fell due at, if it is due by `step` when it runs
+    /// every `every` steps.
+    pub fn due(&self, step: u64, every: u64) -> Option<u64> {
+        let due = match self.next {
+            Some(next) if step + every >= next => next,
+            // First frame, or a reset or rewind ran time backwards.
+            _ => step,
+        };
+        (step >= due).then_some(due)
+    }
+
+    /// Makes the task due again from `next`.
+    pub fn schedule(&mut self, next: u64) {
+        self.next = Some(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_step_is_due_at_once() {
+        assert_eq!(StepCursor::default().due(7, 5), Some(7));
+    }
+
+    #[test]
+    fn steps_before_the_next_are_not_due() {
+        let mut cursor = StepCursor::default();
+        cursor.schedule(10);
+        assert_eq!(cursor.due(9, 5), None);
+        assert_eq!(cursor.due(10, 5), Some(10));
+        // Late frames are still due from the scheduled step.
+        assert_eq!(cursor.due(23, 5), Some(10));
+    }
+
+    #[test]
+    fn running_backwards_restarts_the_pacing() {
+        let mut cursor = StepCursor::default();
+        cursor.schedule(100);
+        assert_eq!(cursor.due(96, 5), None);
+        assert_eq!(cursor.due(3, 5), Some(3));
+    }
+}
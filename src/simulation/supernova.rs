@@ -0,0 +1,137 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec3;
+
+use super::Body;
+use super::guides::ring_vertices;
+
+/// Neighbours used to size the blast around the exploding body.
+const BLAST_NEIGHBOURS: usize = 8;
+
+/// Blast-wave extent in units of the blast radius.
+const BLAST_WAVE_EXTENT: f32 = 8.0;
+
+/// Shortest blast-wave lifetime in integration steps, so fast blasts stay visible.
+const BLAST_WAVE_MIN_STEPS: f32 = 240.0;
+
+/// A one-shot explosion: the progenitor is removed and every other body gets a
+/// radial velocity kick that falls off past `radius`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Supernova {
+    pub index: usize,
+    pub center: Vec3,
+    /// Kick at the center; roughly the progenitor's escape speed at `radius`.
+    pub speed: f32,
+    pub radius: f32,
+}
+
+impl Supernova {
+    /// Sizes the blast from the distance to the progenitor's nearest neighbours.
+    pub fn new(bodies: &[Body], index: usize, gravitational_constant: f32) -> Option<Self> {
+        let progenitor = bodies.get(index)?;
+        let mut distances: Vec<f32> = bodies
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != index)
+            .map(|(_, body)| body.position.distance(progenitor.position))
+            .collect();
+        if distances.is_empty() {
+            return None;
+        }
+        let nth = BLAST_NEIGHBOURS.min(distances.len()) - 1;
+        let (_, &mut radius, _) = distances.select_nth_unstable_by(nth, f32::total_cmp);
+        let radius = radius.max(f32::EPSILON);
+        Some(Self {
+            index,
+            center: progenitor.position,
+            speed: (2.0 * gravitational_constant * progenitor.mass / radius).sqrt(),
+            radius,
+        })
+    }
+
+    /// Picks the most massive body when nothing is selected.
+    pub fn most_massive(bodies: &[Body]) -> Option<usize> {
+        bodies
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.mass.total_cmp(&b.mass))
+            .map(|(index, _)| index)
+    }
+
+    pub fn impulse(&self, position: Vec3) -> Vec3 {
+        let offset = position - self.center;
+        let falloff = 1.0 / (1.0 + offset.length_squared() / (self.radius * self.radius));
+        offset.normalize_or_zero() * self.speed * falloff
+    }
+
+    /// Kicks every body but the progenitor, whose mass is zeroed until it is removed.
+    pub fn apply(&self, bodies: &mut [Body]) {
+        for (index, body) in bodies.iter_mut().enumerate() {
+            if index == self.index {
+                body.mass = 0.0;
+            } else {
+                body.velocity += self.impulse(body.position);
+            }
+        }
+    }
+
+    /// Shell expanding at the ejecta speed, slowed down if it would finish
+    /// within a few frames of `dt`.
+    pub fn blast_wave(&self, time: f64, dt: f32) -> BlastWave {
+        let max_radius = BLAST_WAVE_EXTENT * self.radius;
+        BlastWave {
+            center: self.center,
+            max_radius,
+            start_time: time,
+            duration: (max_radius / self.speed.max(f32::EPSILON)).max(BLAST_WAVE_MIN_STEPS * dt),
+        }
+    }
+}
+
+/// Parameters of the one-shot GPU impulse pass, mirrored by `cs_impulse`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ImpulseUniforms {
+    pub center: Vec3,
+    pub speed: f32,
+    pub radius: f32,
+    pub source: u32,
+    pub _padding: [f32; 2],
+}
+
+impl From<Supernova> for ImpulseUniforms {
+    fn from(supernova: Supernova) -> Self {
+        Self {
+            center: supernova.center,
+            speed: supernova.speed,
+            radius: supernova.radius,
+            source: supernova.index as u32,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+/// Expanding shell drawn after a supernova.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlastWave {
+    pub center: Vec3,
+    pub max_radius: f32,
+    pub start_time: f64,
+    /// Simulated time until the shell reaches `max_radius` and disappears.
+    pub duration: f32,
+}
+
+impl BlastWave {
+    pub fn is_active(&self, time: f64) -> bool {
+        time >= self.start_time && time - self.start_time < f64::from(self.duration)
+    }
+
+    pub fn radius_at(&self, time: f64) -> f32 {
+        let progress = (time - self.start_time).max(0.0) as f32 / self.duration;
+        progress.min(1.0) * self.max_radius
+    }
+
+    /// Line-list ring in the XZ plane at the shell's current radius.
+    pub fn ring_vertices(&self, time: f64, segments: usize) -> Vec<Vec3> {
+        ring_vertices(self.center, self.radius_at(time), segments)
+    }
+}
@@ -0,0 +1,151 @@
+use super::{Body, BodyKind};
+
+/// Hierarchical block timesteps: each step of `dt` is split into `2^max_level`
+/// substeps and every body is binned to the coarsest power-of-two fraction of
+/// `dt` that resolves its closest encounter, so a tight binary can take
+/// thousands of kicks while distant debris takes one.
+///
+/// Forces are only evaluated for the bodies whose bin ends at a substep; all
+/// bodies drift every substep, which is O(N) and keeps positions synchronised
+/// for the force sum. Bins are reassigned at the start of every step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockTimesteps {
+    max_level: u32,
+    accuracy: f32,
+}
+
+impl Default for BlockTimesteps {
+    fn default() -> Self {
+        Self {
+            max_level: 8,
+            accuracy: 0.02,
+        }
+    }
+}
+
+impl BlockTimesteps {
+    /// Deepest level `new` accepts: 65 536 substeps per step.
+    pub const MAX_LEVEL: u32 = 16;
+
+    /// `max_level` is clamped to `MAX_LEVEL`; an `accuracy` that is not a
+    /// positive number gives the default.
+    pub fn new(max_level: u32, accuracy: f32) -> Self {
+        Self {
+            max_level: max_level.min(Self::MAX_LEVEL),
+            accuracy: if accuracy.is_finite() && accuracy > 0.0 {
+                accuracy
+            } else {
+                Self::default().accuracy
+            },
+        }
+    }
+
+    /// Finest bin is `dt / 2^max_level`.
+    pub fn max_level(&self) -> u32 {
+        self.max_level
+    }
+
+    /// Fraction of the shortest pairwise free-fall time a body may step over.
+    pub fn accuracy(&self) -> f32 {
+        self.accuracy
+    }
+
+    /// Number of finest substeps in one step of `dt`.
+    pub fn substeps(&self) -> u32 {
+        1 << self.max_level
+    }
+
+    /// Substeps between two kicks of a body in `level`, at most `max_level`.
+    pub fn stride(&self, level: u32) -> u32 {
+        1 << self.max_level.saturating_sub(level)
+    }
+
+    /// Whether a body in `level` starts or ends a step at `substep`.
+    pub fn is_boundary(&self, level: u32, substep: u32) -> bool {
+        substep.is_multiple_of(self.stride(level))
+    }
+
+    /// Bin of every body for a step of `dt`, from its shortest free-fall time
+    /// `sqrt(r³ / G(m_i + m_j))` to any gravitating body.
+    pub fn levels(
+        &self,
+        bodies: &[Body],
+        flags: &[u32],
+        gravitational_constant: f32,
+        softening: f32,
+        dt: f32,
+    ) -> Vec<u32> {
+        let dt = dt.abs();
+        let softening_sq = softening * softening;
+        bodies
+            .iter()
+            .enumerate()
+            .map(|(i, body)| {
+                let mut shortest_sq = f32::INFINITY;
+                for (j, other) in bodies.iter().enumerate() {
+                    let is_source = flags
+                        .get(j)
+                        .is_none_or(|&flags| BodyKind::from_flags(flags).is_source());
+                    if i == j || other.mass == 0.0 || !is_source {
+                        continue;
+                    }
+                    let distance_sq = body.position.distance_squared(other.position) + softening_sq;
+                    let mu = gravitational_constant * (body.mass + other.mass);
+                    shortest_sq = shortest_sq.min(distance_sq * distance_sq.sqrt() / mu);
+                }
+                let allowed = self.accuracy * shortest_sq.sqrt();
+                if allowed >= dt {
+                    return 0;
+                }
+                // Smallest level whose step dt / 2^level fits inside the allowed one.
+                ((dt / allowed).log2().ceil() as u32).min(self.max_level)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Vec3, Vec4};
+
+    use super::*;
+
+    fn body(x: f32, mass: f32) -> Body {
+        Body::new(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, mass, 0.01, Vec4::ONE)
+    }
+
+    #[test]
+    fn levels_are_clamped_to_a_shiftable_depth() {
+        let blocks = BlockTimesteps::new(40, 0.02);
+        assert_eq!(blocks.max_level(), BlockTimesteps::MAX_LEVEL);
+        assert_eq!(blocks.substeps(), 1 << BlockTimesteps::MAX_LEVEL);
+        assert_eq!(blocks.stride(0), blocks.substeps());
+        assert_eq!(blocks.stride(BlockTimesteps::MAX_LEVEL + 3), 1);
+        assert_eq!(BlockTimesteps::new(4, f32::NAN).accuracy(), 0.02);
+    }
+
+    #[test]
+    fn close_pairs_take_finer_bins_than_distant_bodies() {
+        let blocks = BlockTimesteps::new(8, 0.02);
+        // A tight binary and a far, light body.
+        let bodies = [body(0.0, 1.0), body(0.01, 1.0), body(100.0, 1.0e-6)];
+        let levels = blocks.levels(&bodies, &[], 1.0, 0.0, 1.0e-3);
+        // Free fall over 0.01 is sqrt(1e-6 / 2) ≈ 7.1e-4; 2% of it, 1.4e-5,
+        // needs dt / 2^7.
+        assert_eq!(levels, [7, 7, 0]);
+        // A coarser step runs out of levels.
+        assert_eq!(blocks.levels(&bodies, &[], 1.0, 0.0, 1.0)[..2], [8, 8]);
+    }
+
+    #[test]
+    fn massless_and_non_source_bodies_do_not_shorten_steps() {
+        let blocks = BlockTimesteps::new(8, 0.02);
+        let bodies = [body(0.0, 1.0), body(1.0e-3, 0.0), body(2.0e-3, 1.0)];
+        let flags = [
+            BodyKind::Massive.bits(),
+            BodyKind::Massive.bits(),
+            BodyKind::TestParticle.bits(),
+        ];
+        assert_eq!(blocks.levels(&bodies, &flags, 1.0, 0.0, 1.0e-3)[0], 0);
+    }
+}
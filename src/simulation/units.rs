@@ -0,0 +1,172 @@
+//! Standard N-body (Hénon) units: G = 1, total mass M = 1 and, for a system in
+//! virial equilibrium, total energy E = −1/4.
+//!
+//! Scenarios are authored in whatever units suit them (SI for Earth-Moon,
+//! arbitrary ones for the galaxy). Those that opt in through
+//! [`Simulation::units`](super::Simulation::units) are rescaled on load so the
+//! integrator and shader see values of order one, and the frame carries a
+//! [`UnitConversion`] to turn them back into physical quantities for display.
+
+use glam::Vec4;
+
+use super::{Body, EscapePolicy, ExternalPotential, SimulationState};
+
+/// G in AU³ / (M☉ · yr²), the units of [`PhysicalUnits::ASTRONOMICAL`].
+pub const G_ASTRONOMICAL: f32 = 4.0 * std::f32::consts::PI * std::f32::consts::PI;
+
+/// A scenario's authoring units, as SI multiples plus labels for display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicalUnits {
+    pub length: f64,
+    pub mass: f64,
+    pub time: f64,
+    pub length_label: &'static str,
+    pub mass_label: &'static str,
+    pub time_label: &'static str,
+}
+
+impl PhysicalUnits {
+    pub const SI: Self = Self {
+        length: 1.0,
+        mass: 1.0,
+        time: 1.0,
+        length_label: "m",
+        mass_label: "kg",
+        time_label: "s",
+    };
+
+    /// AU, solar masses and Julian years, where G = 4π².
+    pub const ASTRONOMICAL: Self = Self {
+        length: 1.495_978_707e11,
+        mass: 1.988_47e30,
+        time: 3.155_76e7,
+        length_label: "AU",
+        mass_label: "M☉",
+        time_label: "yr",
+    };
+
+    /// Scenario units without a physical meaning.
+    pub const DIMENSIONLESS: Self = Self {
+        length: 1.0,
+        mass: 1.0,
+        time: 1.0,
+        length_label: "",
+        mass_label: "",
+        time_label: "",
+    };
+}
+
+/// One N-body unit of length, mass and time expressed in the scenario's units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitConversion {
+    pub length: f32,
+    pub mass: f32,
+    pub time: f32,
+    pub physical: PhysicalUnits,
+}
+
+impl UnitConversion {
+    /// Scales for `bodies` under the scenario's G: the mass unit is the total
+    /// mass and the length unit the virial radius G·M² / (2|W|), which gives
+    /// E = −1/4 for virialized systems and stays finite for unbound ones.
+    /// `None` without at least two massive bodies.
+    pub fn virial(
+        bodies: &[Body],
+        state: &SimulationState,
+        physical: PhysicalUnits,
+    ) -> Option<Self> {
+        let massive: Vec<&Body> = bodies.iter().filter(|body| body.mass > 0.0).collect();
+        let total_mass: f64 = massive.iter().map(|body| f64::from(body.mass)).sum();
+        let softening_sq = f64::from(state.softening).powi(2);
+        let mut binding = 0.0;
+        for (index, a) in massive.iter().enumerate() {
+            for b in &massive[index + 1..] {
+                let distance_sq = f64::from(a.position.distance_squared(b.position)) + softening_sq;
+                binding += f64::from(a.mass) * f64::from(b.mass) / distance_sq.sqrt();
+            }
+        }
+        if binding <= 0.0 {
+            return None;
+        }
+        // G cancels: R = G·M² / (2·G·Σ m_i m_j / r_ij).
+        let length = total_mass * total_mass / (2.0 * binding);
+        let time = (length.powi(3) / (f64::from(state.gravitational_constant) * total_mass)).sqrt();
+        Some(Self {
+            length: length as f32,
+            mass: total_mass as f32,
+            time: time as f32,
+            physical,
+        })
+    }
+
+    pub fn velocity(&self) -> f32 {
+        self.length / self.time
+    }
+
+    pub fn normalize_bodies(&self, bodies: &mut [Body]) {
+        let velocity = self.velocity();
+        for body in bodies {
+            body.position /= self.length;
+            body.velocity /= velocity;
+            body.mass /= self.mass;
+            body.radius /= self.length;
+        }
+    }
+
+    /// Angular velocities scale with time; rotation phases are unchanged.
+    pub fn normalize_spins(&self, spins: &mut [Vec4]) {
+        for spin in spins {
+            *spin = (spin.truncate() * self.time).extend(spin.w);
+        }
+    }
+
+    /// Rescales the constants and clock; G becomes one.
+    pub fn normalize_state(&self, state: &mut SimulationState) {
+        state.gravitational_constant = 1.0;
+        state.softening /= self.length;
+        state.dt /= self.time;
+        state.time /= f64::from(self.time);
+        state.bounds.half_extent /= self.length;
+        if let Some(contact) = &mut state.contact {
+            contact.stiffness *= self.time * self.time;
+            contact.damping *= self.time;
+        }
+        if let Some(post_newtonian) = &mut state.post_newtonian {
+            post_newtonian.speed_of_light /= self.velocity();
+        }
+        if let Some(tides) = &mut state.tides {
+            tides.time_lag /= self.time;
+        }
+        if let Some(evolution) = &mut state.stellar_evolution {
+            evolution.gyr_per_time *= self.time;
+        }
+    }
+
+    pub fn normalize_potential(&self, potential: ExternalPotential) -> ExternalPotential {
+        potential.rescaled(self.length, self.mass, self.time)
+    }
+
+    pub fn normalize_escape_policy(&self, policy: EscapePolicy) -> EscapePolicy {
+        EscapePolicy {
+            radius: policy.radius.map(|radius| radius / self.length),
+            respawn_radius: policy.respawn_radius.map(|radius| radius / self.length),
+        }
+    }
+
+    /// A normalized length in the scenario's physical units.
+    pub fn physical_length(&self, length: f32) -> f64 {
+        f64::from(length * self.length) * self.physical.length
+    }
+
+    pub fn physical_mass(&self, mass: f32) -> f64 {
+        f64::from(mass * self.mass) * self.physical.mass
+    }
+
+    pub fn physical_time(&self, time: f64) -> f64 {
+        time * f64::from(self.time) * self.physical.time
+    }
+
+    pub fn physical_velocity(&self, speed: f32) -> f64 {
+        f64::from(speed * self.velocity()) * self.physical.length / self.physical.time
+    }
+}
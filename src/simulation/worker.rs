@@ -0,0 +1,1023 @@
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TryRecvError, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use glam::{Vec3, Vec4};
+use tracing::{debug, error, info, trace_span, warn};
+
+use super::diagnostics::{ClusterHistory, EnergyMonitor, OrbitHistory};
+use super::divergence::first_non_finite;
+use super::ecs::EditTag;
+use super::precession::PrecessionProbe;
+use super::{
+    AccretionDisk, BlastWave, Body, BodyGroup, BodyKind, BodyProperties, BodyWorld, ClusterSample,
+    DiagnosticPlots, Divergence, Edit, EditHistory, EscapePolicy, ExternalPotential,
+    GravitationalLens, GuideCircle, Histogram, HistogramSpec, HrPoint, OrbitSample,
+    ParamDescriptor, PrecessionReadout, RecoveryPolicy, ResonanceAngle, RotatingFrame, Simulation,
+    SimulationError, SimulationManager, SimulationState, SnapshotRing, SpacecraftStatus,
+    ThrustInput, UnitConversion, WorldBounds,
+};
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::formats::ParticleSnapshot;
+use crate::rendering::config::RenderConfig;
+
+/// Requests from the render/event thread to the simulation thread.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationCommand {
+    /// Activates another scenario; `hot_swap` keeps pause state and time scale.
+    SwitchSimulation {
+        index: usize,
+        hot_swap: bool,
+    },
+    SetPaused(bool),
+    SetTimeScale(f32),
+    SetSoftening(f32),
+    SetReversed(bool),
+    SetDiagnostics(bool),
+    Reset,
+    SetEscapePolicy(EscapePolicy),
+    SetBounds(WorldBounds),
+    /// Temporary interactive attractor; `None` removes it.
+    SetGravityWell(Option<ExternalPotential>),
+    SetThrust(ThrustInput),
+    /// Pushes a history snapshot immediately.
+    SaveSnapshot,
+    /// Steps back to the previous history snapshot and pauses there.
+    Rewind,
+    /// Explodes a body; `None` picks the most massive one.
+    TriggerSupernova(Option<usize>),
+    /// Changes a scenario parameter and regenerates its bodies.
+    SetParameter(&'static str, f32),
+    /// Activates a scenario with the given seed and parameters, every other
+    /// parameter at its default, as encoded in a shared scenario code.
+    LoadScenario {
+        index: usize,
+        seed: Option<u64>,
+        parameters: Vec<(String, f32)>,
+    },
+    /// Replaces the scenario's bodies with imported ones.
+    LoadBodies(Arc<ParticleSnapshot>),
+    SpawnBody {
+        body: Body,
+        name: Option<String>,
+    },
+    DeleteBody(usize),
+    EditBody {
+        index: usize,
+        properties: BodyProperties,
+    },
+    MoveBody {
+        index: usize,
+        position: Vec3,
+    },
+    /// Records the moves since the last one as a single edit.
+    FinishMove,
+    /// Reverts the latest interactive edit.
+    Undo,
+    /// Reapplies the latest undone edit.
+    Redo,
+    Shutdown,
+}
+
+/// Snapshot of the simulation published once per tick.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationFrame {
+    pub simulation_index: usize,
+    pub bodies: Vec<Body>,
+    /// Per-body flag bits, parallel to `bodies`.
+    pub flags: Vec<u32>,
+    /// Angular velocity (xyz) and rotation phase (w), parallel to `bodies`.
+    pub spins: Vec<Vec4>,
+    /// Names of the bodies that have one, by index.
+    pub names: Vec<(usize, String)>,
+    /// Temperature and luminosity of every evolving star, for the
+    /// Hertzsprung–Russell inset; empty unless stellar evolution is on.
+    pub hr_diagram: Vec<HrPoint>,
+    pub state: SimulationState,
+    pub spacecraft: Option<SpacecraftStatus>,
+    /// Blast waves still expanding at `state.time`.
+    pub blast_waves: Vec<BlastWave>,
+    pub precession: Option<PrecessionReadout>,
+    /// Inner-orbit elements over time, oldest first.
+    pub orbit_history: Vec<OrbitSample>,
+    /// Virial ratio and Lagrangian radii over time, oldest first.
+    pub cluster_history: Vec<ClusterSample>,
+    /// Current parameter values of the scenario, and its seed if it has one.
+    pub parameters: Vec<ParamDescriptor>,
+    pub seed: Option<u64>,
+    /// The scenario's default look.
+    pub render_config: RenderConfig,
+    /// Frame the scenario offers as an alternative view.
+    pub rotating_frame: Option<RotatingFrame>,
+    /// Black hole to draw lensing around.
+    pub lens: Option<GravitationalLens>,
+    pub accretion_disk: Option<AccretionDisk>,
+    pub histogram: Option<Histogram>,
+    /// Filled while the diagnostics panel is open.
+    pub plots: Option<DiagnosticPlots>,
+    /// Label and current value (radians) of each resonant argument.
+    pub resonances: Vec<(&'static str, f32)>,
+    pub guide_circles: Vec<GuideCircle>,
+    /// Set when the scenario runs in N-body units; converts back for display.
+    pub units: Option<UnitConversion>,
+    /// The scenario's `Simulation::length_label` and `time_label`, for when
+    /// `units` is unset.
+    pub length_label: &'static str,
+    pub time_label: &'static str,
+    /// Latest NaN/Inf since the scenario was loaded, also once a rollback
+    /// has recovered from it.
+    pub divergence: Option<Divergence>,
+    /// Largest distance from the initial positions once a reversed run is back at t = 0.
+    pub return_error: Option<f32>,
+    /// Relative total-energy drift since load; `None` for very large systems.
+    pub energy_drift: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct WorkerConfig {
+    pub tick_interval: Duration,
+    pub steps_per_tick: u32,
+    pub recovery: RecoveryPolicy,
+    /// Ticks between the snapshots kept for rewinding and rollback.
+    pub snapshot_interval: u32,
+    /// Snapshots kept; rewinding reaches back `history_len * snapshot_interval` ticks.
+    pub history_len: usize,
+    /// Ticks between total-energy sums for the drift readout.
+    pub energy_interval: u32,
+    /// Interactive edits kept for undoing.
+    pub edit_history_len: usize,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval: Duration::from_secs_f64(1.0 / 120.0),
+            steps_per_tick: 1,
+            recovery: RecoveryPolicy::default(),
+            snapshot_interval: 30,
+            history_len: 40,
+            energy_interval: 30,
+            edit_history_len: 100,
+        }
+    }
+}
+
+/// Frames in flight between the threads; spent frames come back through the
+/// recycle channel so their body vectors are reused rather than reallocated.
+const FRAMES_IN_FLIGHT: usize = 2;
+
+/// Owns the simulation thread. Physics stepping and scenario generation happen
+/// there so the window stays responsive while large scenarios are rebuilt.
+pub struct SimulationWorker {
+    config: WorkerConfig,
+    commands: Sender<SimulationCommand>,
+    frames: Receiver<SimulationFrame>,
+    recycled: Sender<SimulationFrame>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SimulationWorker {
+    pub fn spawn(
+        manager: SimulationManager,
+        config: WorkerConfig,
+    ) -> Result<Self, SimulationError> {
+        let (command_sender, command_receiver) = mpsc::channel();
+        let (frame_sender, frame_receiver) = mpsc::sync_channel(FRAMES_IN_FLIGHT);
+        let (recycle_sender, recycle_receiver) = mpsc::channel();
+
+        let handle = thread::Builder::new()
+            .name("simulation".into())
+            .spawn(move || {
+                run(
+                    manager,
+                    config,
+                    command_receiver,
+                    frame_sender,
+                    recycle_receiver,
+                )
+            })
+            .map_err(SimulationError::SpawnThread)?;
+
+        Ok(Self {
+            config,
+            commands: command_sender,
+            frames: frame_receiver,
+            recycled: recycle_sender,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn config(&self) -> &WorkerConfig {
+        &self.config
+    }
+
+    pub fn send(&self, command: SimulationCommand) {
+        // The thread only exits on shutdown, after which commands are moot.
+        if let Err(error) = self.commands.send(command) {
+            debug!(command = ?error.0, "simulation thread has exited; dropping command");
+        }
+    }
+
+    /// Most recent published frame, if any arrived since the last call.
+    /// Older frames are handed straight back to the simulation thread.
+    pub fn latest_frame(&self) -> Option<SimulationFrame> {
+        let mut latest = None;
+        while let Ok(frame) = self.frames.try_recv() {
+            if let Some(stale) = latest.replace(frame) {
+                self.recycle(stale);
+            }
+        }
+        latest
+    }
+
+    /// Returns a consumed frame so its allocation can be reused.
+    pub fn recycle(&self, frame: SimulationFrame) {
+        let _ = self.recycled.send(frame);
+    }
+}
+
+impl EventHandler for SimulationWorker {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if let AppEvent::SharedScenarioLoaded {
+            index,
+            seed,
+            parameters,
+        } = event
+        {
+            self.send(SimulationCommand::LoadScenario {
+                index: *index,
+                seed: *seed,
+                parameters: parameters.clone(),
+            });
+            return;
+        }
+        if let AppEvent::SnapshotImported(snapshot) = event {
+            self.send(SimulationCommand::LoadBodies(Arc::clone(snapshot)));
+            return;
+        }
+        if let AppEvent::BodyPasted { body, name } = event {
+            self.send(SimulationCommand::SpawnBody {
+                body: *body,
+                name: name.clone(),
+            });
+            return;
+        }
+        let command = match *event {
+            AppEvent::SimulationSwitched { index, hot_swap } => {
+                SimulationCommand::SwitchSimulation { index, hot_swap }
+            }
+            AppEvent::PauseChanged(paused) => SimulationCommand::SetPaused(paused),
+            AppEvent::TimeScaleChanged(time_scale) => SimulationCommand::SetTimeScale(time_scale),
+            AppEvent::SofteningChanged(softening) => SimulationCommand::SetSoftening(softening),
+            AppEvent::ReverseChanged(reversed) => SimulationCommand::SetReversed(reversed),
+            AppEvent::DiagnosticsToggled(open) => SimulationCommand::SetDiagnostics(open),
+            AppEvent::ResetRequested => SimulationCommand::Reset,
+            AppEvent::GravityWellChanged(well) => SimulationCommand::SetGravityWell(well),
+            AppEvent::ThrustChanged(input) => SimulationCommand::SetThrust(input),
+            AppEvent::SnapshotRequested => SimulationCommand::SaveSnapshot,
+            AppEvent::RewindRequested => SimulationCommand::Rewind,
+            AppEvent::SupernovaTriggered(index) => SimulationCommand::TriggerSupernova(index),
+            AppEvent::ParameterChanged { name, value } => {
+                SimulationCommand::SetParameter(name, value)
+            }
+            AppEvent::BodySpawned(body) => SimulationCommand::SpawnBody { body, name: None },
+            AppEvent::BodyDeleted(index) => SimulationCommand::DeleteBody(index),
+            AppEvent::BodyEdited { index, properties } => {
+                SimulationCommand::EditBody { index, properties }
+            }
+            AppEvent::BodyMoved { index, position } => {
+                SimulationCommand::MoveBody { index, position }
+            }
+            AppEvent::BodyMoveFinished => SimulationCommand::FinishMove,
+            AppEvent::UndoRequested => SimulationCommand::Undo,
+            AppEvent::RedoRequested => SimulationCommand::Redo,
+            _ => return,
+        };
+        self.send(command);
+    }
+}
+
+impl Drop for SimulationWorker {
+    fn drop(&mut self) {
+        self.send(SimulationCommand::Shutdown);
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            warn!("simulation thread panicked");
+        }
+    }
+}
+
+fn run(
+    manager: SimulationManager,
+    config: WorkerConfig,
+    commands: Receiver<SimulationCommand>,
+    frames: SyncSender<SimulationFrame>,
+    recycled: Receiver<SimulationFrame>,
+) {
+    let mut worker = WorkerState::new(manager, config);
+
+    loop {
+        let tick_start = Instant::now();
+        let _tick = trace_span!("simulation_tick", step = worker.state.step).entered();
+
+        loop {
+            match commands.try_recv() {
+                Ok(SimulationCommand::Shutdown) | Err(TryRecvError::Disconnected) => return,
+                Ok(command) => worker.apply(command),
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+
+        worker.tick();
+        if !worker.publish(&frames, &recycled) {
+            return;
+        }
+        profiling::finish_frame!();
+
+        if let Some(remaining) = config.tick_interval.checked_sub(tick_start.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+}
+
+/// Rescales a scenario that opted into N-body units, before anything samples
+/// its bodies.
+pub(crate) fn normalize_units(
+    simulation: &dyn Simulation,
+    world: &mut BodyWorld,
+    state: &mut SimulationState,
+    potentials: &mut [ExternalPotential],
+) -> Option<UnitConversion> {
+    let units = UnitConversion::virial(world.bodies(), state, simulation.units()?)?;
+    units.normalize_bodies(world.bodies_mut());
+    units.normalize_spins(world.spins_mut());
+    units.normalize_state(state);
+    for potential in potentials {
+        *potential = units.normalize_potential(*potential);
+    }
+    world.set_escape_policy(units.normalize_escape_policy(simulation.escape_policy()));
+    debug!(
+        length = units.length,
+        mass = units.mass,
+        time = units.time,
+        "normalized to N-body units"
+    );
+    Some(units)
+}
+
+/// Everything owned by the simulation thread.
+struct WorkerState {
+    manager: SimulationManager,
+    config: WorkerConfig,
+    world: BodyWorld,
+    state: SimulationState,
+    potentials: Vec<ExternalPotential>,
+    gravity_well: Option<ExternalPotential>,
+    blast_waves: Vec<BlastWave>,
+    precession: Option<PrecessionProbe>,
+    orbit_history: Option<OrbitHistory>,
+    cluster_history: Option<ClusterHistory>,
+    histogram: Option<HistogramSpec>,
+    resonances: Vec<ResonanceAngle>,
+    guide_circles: Vec<GuideCircle>,
+    units: Option<UnitConversion>,
+    /// Recent finite states, taken every `snapshot_interval` ticks.
+    history: SnapshotRing,
+    ticks_since_snapshot: u32,
+    /// Set while the bodies are non-finite; stops snapshots and further checks.
+    divergence: Option<Divergence>,
+    /// Latest divergence, for the published frame.
+    reported_divergence: Option<Divergence>,
+    /// Bodies as loaded, for measuring how closely a reversed run returns.
+    initial_bodies: Vec<Body>,
+    return_error: Option<f32>,
+    energy: Option<EnergyMonitor>,
+    ticks_since_energy: u32,
+    diagnostics: bool,
+    /// Spawns, deletions and parameter changes made interactively.
+    edits: EditHistory,
+    /// Body being dragged and where the drag started.
+    move_start: Option<(EditTag, Vec3)>,
+    spare: Vec<SimulationFrame>,
+}
+
+impl WorkerState {
+    fn new(manager: SimulationManager, config: WorkerConfig) -> Self {
+        let mut world = BodyWorld::from_simulation(manager.current());
+        let mut state = manager.current().initial_state();
+        let mut potentials = manager.current().external_potentials();
+        let units = normalize_units(manager.current(), &mut world, &mut state, &mut potentials);
+        let initial_bodies = world.bodies().to_vec();
+        let energy = EnergyMonitor::new(world.bodies(), &state);
+        let mut history = SnapshotRing::new(config.history_len);
+        history.reset(world.snapshot(&state));
+        let precession = manager
+            .current()
+            .tracked_orbit()
+            .and_then(|orbit| PrecessionProbe::new(orbit, world.bodies(), &state));
+        let orbit_history = manager.current().inner_orbit().map(OrbitHistory::new);
+        let cluster_history = manager
+            .current()
+            .cluster_diagnostics()
+            .map(ClusterHistory::new);
+        let histogram = manager.current().histogram();
+        let resonances = manager.current().resonance_angles();
+        let guide_circles = manager.current().guide_circles();
+        Self {
+            manager,
+            config,
+            world,
+            state,
+            potentials,
+            gravity_well: None,
+            blast_waves: Vec::new(),
+            precession,
+            orbit_history,
+            cluster_history,
+            histogram,
+            resonances,
+            guide_circles,
+            units,
+            history,
+            ticks_since_snapshot: 0,
+            divergence: None,
+            reported_divergence: None,
+            initial_bodies,
+            return_error: None,
+            energy,
+            ticks_since_energy: 0,
+            diagnostics: false,
+            edits: EditHistory::new(config.edit_history_len),
+            move_start: None,
+            spare: Vec::with_capacity(FRAMES_IN_FLIGHT),
+        }
+    }
+
+    fn apply(&mut self, command: SimulationCommand) {
+        match command {
+            SimulationCommand::SwitchSimulation { index, hot_swap } => {
+                match self.manager.switch_simulation(index) {
+                    Ok(_) => {
+                        self.reset(hot_swap);
+                        self.edits.clear();
+                        info!(
+                            name = self.manager.current().name(),
+                            bodies = self.world.bodies().len(),
+                            hot_swap,
+                            "switched simulation"
+                        );
+                    }
+                    Err(error) => warn!(%error, "ignoring simulation switch"),
+                }
+            }
+            SimulationCommand::SetPaused(paused) => self.state.paused = paused,
+            SimulationCommand::SetTimeScale(time_scale) => self.state.time_scale = time_scale,
+            SimulationCommand::SetSoftening(softening) => self.state.softening = softening,
+            SimulationCommand::SetDiagnostics(open) => self.diagnostics = open,
+            SimulationCommand::SetReversed(reversed) => {
+                self.state.reversed = reversed;
+                self.return_error = None;
+            }
+            SimulationCommand::Reset => self.reset(true),
+            SimulationCommand::SetParameter(name, value) => {
+                let Some(before) = self
+                    .manager
+                    .current()
+                    .parameters()
+                    .iter()
+                    .find(|descriptor| descriptor.name == name)
+                    .map(|descriptor| descriptor.value)
+                else {
+                    warn!(name, "ignoring change to unknown parameter");
+                    return;
+                };
+                let edit = Edit::Parameter {
+                    name,
+                    before,
+                    after: value,
+                };
+                if let Some(edit) = self.perform(edit) {
+                    self.edits.record(edit);
+                }
+            }
+            SimulationCommand::LoadScenario {
+                index,
+                seed,
+                parameters,
+            } => {
+                if let Err(error) = self.manager.switch_simulation(index) {
+                    warn!(%error, "ignoring shared scenario");
+                    return;
+                }
+                let simulation = self.manager.current_mut();
+                if let Some(seed) = seed {
+                    simulation.set_seed(seed);
+                }
+                for descriptor in simulation.parameters() {
+                    let value = parameters
+                        .iter()
+                        .find(|(name, _)| name == descriptor.name)
+                        .map_or(descriptor.default, |&(_, value)| value);
+                    if let Err(error) = simulation.set_parameter(descriptor.name, value) {
+                        warn!(%error, "ignoring shared parameter");
+                    }
+                }
+                self.reset(false);
+                self.edits.clear();
+                info!(
+                    name = self.manager.current().name(),
+                    bodies = self.world.bodies().len(),
+                    "loaded shared scenario"
+                );
+            }
+            SimulationCommand::SetBounds(bounds) => self.state.bounds = bounds,
+            SimulationCommand::SetGravityWell(well) => self.gravity_well = well,
+            SimulationCommand::SetThrust(input) => self.world.set_thrust(input),
+            SimulationCommand::TriggerSupernova(index) => {
+                match self
+                    .world
+                    .detonate(index, self.state.gravitational_constant)
+                {
+                    Some(supernova) => {
+                        info!(
+                            index = supernova.index,
+                            radius = supernova.radius,
+                            "supernova"
+                        );
+                        self.blast_waves.push(
+                            supernova.blast_wave(self.state.time, self.state.effective_dt().abs()),
+                        );
+                    }
+                    None => warn!(?index, "no body to explode"),
+                }
+            }
+            SimulationCommand::SaveSnapshot => {
+                if self.divergence.is_none() {
+                    self.history.push(self.world.snapshot(&self.state));
+                    self.ticks_since_snapshot = 0;
+                }
+            }
+            SimulationCommand::Rewind => {
+                if let Some(snapshot) = self.history.rewind() {
+                    self.world.restore(snapshot);
+                    self.state = SimulationState {
+                        paused: true,
+                        time_scale: self.state.time_scale,
+                        ..snapshot.state
+                    };
+                    self.ticks_since_snapshot = 0;
+                    self.blast_waves.clear();
+                    info!(time = self.state.time, "rewound");
+                }
+            }
+            SimulationCommand::SetEscapePolicy(policy) => self.world.set_escape_policy(policy),
+            SimulationCommand::LoadBodies(snapshot) => {
+                self.load_bodies(&snapshot);
+                info!(bodies = self.world.bodies().len(), "imported snapshot");
+            }
+            SimulationCommand::SpawnBody { body, name } => {
+                let edit = Edit::Spawn {
+                    tag: self.edits.next_tag(),
+                    body,
+                    name,
+                    group: BodyGroup::Other,
+                };
+                if let Some(edit) = self.perform(edit) {
+                    self.edits.record(edit);
+                }
+            }
+            SimulationCommand::DeleteBody(index) => {
+                // The body keeps the tag earlier edits refer to, so undoing
+                // the delete restores it for them too.
+                let fresh = self.edits.next_tag();
+                let Some(tag) = self.world.ensure_tag(index, fresh) else {
+                    warn!(index, "no body to delete");
+                    return;
+                };
+                let Some((body, name, group)) = self.world.queue_removal(index) else {
+                    return;
+                };
+                self.edits.record(Edit::Delete {
+                    tag,
+                    body,
+                    name,
+                    group,
+                });
+            }
+            SimulationCommand::EditBody { index, properties } => {
+                let fresh = self.edits.next_tag();
+                let Some(tag) = self.world.ensure_tag(index, fresh) else {
+                    warn!(index, "no body to edit");
+                    return;
+                };
+                let edit = Edit::Properties {
+                    tag,
+                    before: properties,
+                    after: properties,
+                };
+                if let Some(edit) = self.perform(edit) {
+                    self.edits.record(edit);
+                }
+            }
+            SimulationCommand::MoveBody { index, position } => {
+                let fresh = self.edits.next_tag();
+                let Some(tag) = self.world.ensure_tag(index, fresh) else {
+                    warn!(index, "no body to move");
+                    return;
+                };
+                let Some(before) = self.world.move_body(index, position) else {
+                    return;
+                };
+                if self.move_start.is_none_or(|(moving, _)| moving != tag) {
+                    self.move_start = Some((tag, before));
+                }
+            }
+            SimulationCommand::FinishMove => {
+                let Some((tag, before)) = self.move_start.take() else {
+                    return;
+                };
+                if let Some(after) = self
+                    .world
+                    .find_tagged(tag)
+                    .map(|index| self.world.bodies()[index].position)
+                {
+                    self.edits.record(Edit::Move { tag, before, after });
+                }
+            }
+            SimulationCommand::Undo => {
+                let Some(edit) = self.edits.take_undo() else {
+                    return;
+                };
+                match self.perform(edit.clone().inverse()) {
+                    Some(undone) => self.edits.push_redo(undone.inverse()),
+                    None => warn!(?edit, "cannot undo; its body is gone"),
+                }
+            }
+            SimulationCommand::Redo => {
+                let Some(edit) = self.edits.take_redo() else {
+                    return;
+                };
+                match self.perform(edit.clone()) {
+                    Some(redone) => self.edits.push_undo(redone),
+                    None => warn!(?edit, "cannot redo; its body is gone"),
+                }
+            }
+            SimulationCommand::Shutdown => {}
+        }
+    }
+
+    /// Applies `edit` and returns it as performed, with a removed body's
+    /// current state in place of the recorded one; `None` if it no longer
+    /// applies.
+    fn perform(&mut self, edit: Edit) -> Option<Edit> {
+        match edit {
+            Edit::Spawn {
+                tag,
+                body,
+                ref name,
+                group,
+            } => {
+                self.world
+                    .queue_tagged_spawn(body, name.clone(), group, tag);
+                Some(edit)
+            }
+            Edit::Delete { tag, .. } => {
+                let index = self.world.find_tagged(tag)?;
+                let (body, name, group) = self.world.queue_removal(index)?;
+                Some(Edit::Delete {
+                    tag,
+                    body,
+                    name,
+                    group,
+                })
+            }
+            Edit::Properties { tag, after, .. } => {
+                let index = self.world.find_tagged(tag)?;
+                let before = self.world.edit_body(index, after)?;
+                Some(Edit::Properties { tag, before, after })
+            }
+            Edit::Move { tag, after, .. } => {
+                let index = self.world.find_tagged(tag)?;
+                let before = self.world.move_body(index, after)?;
+                Some(Edit::Move { tag, before, after })
+            }
+            Edit::Parameter { name, after, .. } => {
+                match self.manager.current_mut().set_parameter(name, after) {
+                    Ok(()) => {
+                        self.reset(true);
+                        Some(edit)
+                    }
+                    Err(error) => {
+                        warn!(%error, "ignoring parameter change");
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Regenerates the current scenario. With `keep_settings` the pause state and
+    /// time scale carry over; otherwise the scenario's own defaults apply.
+    fn reset(&mut self, keep_settings: bool) {
+        profiling::scope!("scenario_init");
+        let simulation = self.manager.current();
+        let _span = trace_span!("scenario_init", name = simulation.name()).entered();
+        self.world = BodyWorld::from_simulation(simulation);
+        self.blast_waves.clear();
+        self.potentials = simulation.external_potentials();
+        let initial = simulation.initial_state();
+        self.state = if keep_settings {
+            SimulationState {
+                paused: self.state.paused,
+                time_scale: self.state.time_scale,
+                ..initial
+            }
+        } else {
+            initial
+        };
+        self.units = normalize_units(
+            simulation,
+            &mut self.world,
+            &mut self.state,
+            &mut self.potentials,
+        );
+        self.history.reset(self.world.snapshot(&self.state));
+        self.ticks_since_snapshot = 0;
+        self.divergence = None;
+        self.reported_divergence = None;
+        self.initial_bodies.clear();
+        self.initial_bodies.extend_from_slice(self.world.bodies());
+        self.return_error = None;
+        self.energy = EnergyMonitor::new(self.world.bodies(), &self.state);
+        self.ticks_since_energy = 0;
+        self.precession = simulation
+            .tracked_orbit()
+            .and_then(|orbit| PrecessionProbe::new(orbit, self.world.bodies(), &self.state));
+        self.orbit_history = simulation.inner_orbit().map(OrbitHistory::new);
+        self.cluster_history = simulation.cluster_diagnostics().map(ClusterHistory::new);
+        self.histogram = simulation.histogram();
+        self.resonances = simulation.resonance_angles();
+        self.guide_circles = simulation.guide_circles();
+        self.edits.forget_bodies();
+        self.move_start = None;
+    }
+
+    /// Runs imported bodies in place of the scenario's, in the file's units
+    /// (G = 1) with the default softening and timestep. Pause state and time
+    /// scale carry over; the scenario's potentials and diagnostics do not.
+    fn load_bodies(&mut self, snapshot: &ParticleSnapshot) {
+        let groups = snapshot
+            .kinds
+            .iter()
+            .map(|kind| match kind {
+                BodyKind::Star => BodyGroup::Star,
+                BodyKind::Gas => BodyGroup::Gas,
+                _ => BodyGroup::Other,
+            })
+            .collect();
+        self.world = BodyWorld::new(snapshot.bodies.clone(), groups, snapshot.kinds.clone());
+        self.blast_waves.clear();
+        self.potentials.clear();
+        self.state = SimulationState {
+            time: snapshot.time,
+            paused: self.state.paused,
+            time_scale: self.state.time_scale,
+            ..SimulationState::default()
+        };
+        self.units = None;
+        self.history.reset(self.world.snapshot(&self.state));
+        self.ticks_since_snapshot = 0;
+        self.divergence = None;
+        self.reported_divergence = None;
+        self.initial_bodies.clear();
+        self.initial_bodies.extend_from_slice(self.world.bodies());
+        self.return_error = None;
+        self.energy = EnergyMonitor::new(self.world.bodies(), &self.state);
+        self.ticks_since_energy = 0;
+        self.precession = None;
+        self.orbit_history = None;
+        self.cluster_history = None;
+        self.histogram = None;
+        self.resonances.clear();
+        self.guide_circles.clear();
+        self.edits.clear();
+        self.move_start = None;
+    }
+
+    fn tick(&mut self) {
+        profiling::scope!("update");
+        let mut elapsed = 0.0;
+        if !self.state.paused {
+            let steps = self.config.steps_per_tick * self.state.substeps.max(1);
+            let _compute = trace_span!("compute", steps).entered();
+            let mut potentials = self.potentials.clone();
+            potentials.extend(self.gravity_well);
+            for _ in 0..steps {
+                // Lifetimes, emitters and fuel run on wall duration, whichever way time flows.
+                elapsed += self.state.effective_dt().abs();
+                self.world.step_physics(&mut self.state, &potentials);
+                if self.divergence.is_none()
+                    && let Some(body) = first_non_finite(self.world.bodies())
+                {
+                    self.diverged(body);
+                    if self.state.paused {
+                        return;
+                    }
+                }
+                if self.state.reversed
+                    && self.state.time <= 0.5 * f64::from(self.state.effective_dt().abs())
+                {
+                    self.returned_to_start();
+                    break;
+                }
+            }
+            self.ticks_since_snapshot += 1;
+            if self.divergence.is_none()
+                && self.ticks_since_snapshot >= self.config.snapshot_interval
+            {
+                self.history.push(self.world.snapshot(&self.state));
+                self.ticks_since_snapshot = 0;
+            }
+            if let Some(probe) = &mut self.precession {
+                probe.observe(self.world.bodies(), &self.state, steps);
+            }
+            if let Some(history) = &mut self.orbit_history {
+                history.record(
+                    self.world.bodies(),
+                    self.state.gravitational_constant,
+                    self.state.time,
+                );
+            }
+            if let Some(history) = &mut self.cluster_history {
+                history.record(self.world.bodies(), &self.state);
+            }
+            self.ticks_since_energy += 1;
+            if let Some(energy) = &mut self.energy
+                && self.ticks_since_energy >= self.config.energy_interval
+            {
+                energy.observe(self.world.bodies(), &self.state);
+                self.ticks_since_energy = 0;
+            }
+        }
+        self.world.run_systems(elapsed, &self.state);
+    }
+
+    /// Stops a reversed run at t = 0 and measures how far it is from the start.
+    fn returned_to_start(&mut self) {
+        self.state.paused = true;
+        self.state.reversed = false;
+        let bodies = self.world.bodies();
+        if bodies.len() != self.initial_bodies.len() {
+            info!("back at t = 0, but bodies were added or removed on the way");
+            return;
+        }
+        let error = bodies
+            .iter()
+            .zip(&self.initial_bodies)
+            .map(|(body, initial)| body.position.distance(initial.position))
+            .fold(0.0, f32::max);
+        info!(
+            error,
+            time = self.state.time,
+            "reversed run returned to t = 0"
+        );
+        self.return_error = Some(error);
+    }
+
+    /// Records the first non-finite body and applies the recovery policy.
+    fn diverged(&mut self, body: usize) {
+        let divergence = Divergence {
+            body,
+            step: self.state.step,
+            time: self.state.time,
+        };
+        error!(
+            body,
+            step = divergence.step,
+            time = divergence.time,
+            recovery = ?self.config.recovery,
+            "simulation diverged"
+        );
+        self.divergence = Some(divergence);
+        self.reported_divergence = Some(divergence);
+        match self.config.recovery {
+            RecoveryPolicy::Report => {}
+            RecoveryPolicy::Pause => self.state.paused = true,
+            RecoveryPolicy::Rollback => {
+                if let Some(snapshot) = self.history.latest() {
+                    self.world.restore(snapshot);
+                    self.state = SimulationState {
+                        paused: true,
+                        ..snapshot.state
+                    };
+                    // Finite again: keep recording and watching for the next blow-up.
+                    self.divergence = None;
+                    self.ticks_since_snapshot = 0;
+                }
+            }
+        }
+    }
+
+    /// Sends the current bodies to the render thread; `false` once it has gone away.
+    fn publish(
+        &mut self,
+        frames: &SyncSender<SimulationFrame>,
+        recycled: &Receiver<SimulationFrame>,
+    ) -> bool {
+        profiling::scope!("readback");
+        let _publish = trace_span!("publish_frame").entered();
+        self.spare.extend(recycled.try_iter());
+        let mut frame = self.spare.pop().unwrap_or_default();
+        frame.simulation_index = self.manager.current_index();
+        frame.bodies.clear();
+        frame.bodies.extend_from_slice(self.world.bodies());
+        frame.flags.clear();
+        frame.flags.extend_from_slice(self.world.flags());
+        frame.spins.clear();
+        frame.spins.extend_from_slice(self.world.spins());
+        frame.names = self.world.names();
+        frame.hr_diagram.clear();
+        if self.state.stellar_evolution.is_some() {
+            frame.hr_diagram.extend(
+                self.world
+                    .stars()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, star)| star.is_star())
+                    .map(|(body, star)| HrPoint {
+                        body,
+                        temperature: star.temperature(),
+                        luminosity: star.luminosity,
+                    }),
+            );
+        }
+        frame.state = self.state;
+        frame.spacecraft = self.world.spacecraft_status();
+        let time = self.state.time;
+        self.blast_waves.retain(|wave| wave.is_active(time));
+        frame.blast_waves.clear();
+        frame.blast_waves.extend_from_slice(&self.blast_waves);
+        frame.precession = self
+            .precession
+            .as_ref()
+            .map(|probe| probe.readout(&self.state, self.world.bodies()));
+        let simulation = self.manager.current();
+        frame.parameters.clear();
+        frame.parameters.extend(simulation.parameters());
+        frame.seed = simulation.seed();
+        frame.render_config = simulation.render_config();
+        frame.rotating_frame = simulation.rotating_frame();
+        frame.lens = simulation.gravitational_lens();
+        frame.accretion_disk = simulation.accretion_disk();
+        match &self.histogram {
+            Some(spec) => spec.fill(
+                self.world.bodies(),
+                self.state.gravitational_constant,
+                frame.histogram.get_or_insert_default(),
+            ),
+            None => frame.histogram = None,
+        }
+        if self.diagnostics {
+            frame
+                .plots
+                .get_or_insert_default()
+                .fill(self.world.bodies());
+        } else {
+            frame.plots = None;
+        }
+        frame.guide_circles.clear();
+        frame.guide_circles.extend_from_slice(&self.guide_circles);
+        frame.units = self.units;
+        frame.length_label = simulation.length_label();
+        frame.time_label = simulation.time_label();
+        frame.divergence = self.reported_divergence;
+        frame.return_error = self.return_error;
+        frame.energy_drift = self.energy.map(|energy| energy.drift());
+        frame.resonances.clear();
+        frame
+            .resonances
+            .extend(self.resonances.iter().filter_map(|resonance| {
+                resonance
+                    .evaluate(self.world.bodies(), self.state.gravitational_constant)
+                    .map(|angle| (resonance.label, angle))
+            }));
+        frame.orbit_history.clear();
+        if let Some(history) = &self.orbit_history {
+            frame.orbit_history.extend(history.samples());
+        }
+        frame.cluster_history.clear();
+        if let Some(history) = &self.cluster_history {
+            frame.cluster_history.extend(history.samples());
+        }
+        match frames.try_send(frame) {
+            Ok(()) => true,
+            Err(TrySendError::Full(frame)) => {
+                self.spare.push(frame);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use glam::{Vec2, Vec3};
+
+use crate::camera::Camera;
+use crate::simulation::ExternalPotential;
+
+/// Scroll steps change the well mass by this fraction of the system mass.
+const STRENGTH_STEP: f32 = 0.05;
+
+/// While active, the cursor drags a temporary point mass around the camera's
+/// focal plane. Negative strength turns it into a repulsor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GravityWellTool {
+    pub active: bool,
+    /// Well mass as a fraction of the total mass of the bodies.
+    pub strength: f32,
+    pub position: Vec3,
+}
+
+impl Default for GravityWellTool {
+    fn default() -> Self {
+        Self {
+            active: false,
+            strength: 0.25,
+            position: Vec3::ZERO,
+        }
+    }
+}
+
+impl GravityWellTool {
+    pub fn adjust_strength(&mut self, scroll_steps: f32) {
+        self.strength = (self.strength + scroll_steps * STRENGTH_STEP).clamp(-2.0, 2.0);
+    }
+
+    pub fn track_cursor(&mut self, camera: &Camera, cursor: Vec2, viewport: Vec2) {
+        if let Some(position) = camera.cursor_on_target_plane(cursor, viewport) {
+            self.position = position;
+        }
+    }
+
+    /// The well as a potential, scaled to the simulated system.
+    pub fn potential(&self, total_mass: f32, camera: &Camera) -> Option<ExternalPotential> {
+        self.active.then_some(ExternalPotential::PointMass {
+            position: self.position,
+            mass: self.strength * total_mass,
+            softening: camera.distance * 0.02,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::rendering::reference::LineVertex;
+use crate::simulation::Body;
+
+const LINE_COLOR: [f32; 4] = [1.0, 0.85, 0.3, 0.9];
+
+/// While enabled, left clicks on bodies pick the two ends of a live distance
+/// readout (`ui::measurement::Measurement`); a third click starts a new pair.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MeasureTool {
+    pub enabled: bool,
+    first: Option<usize>,
+    second: Option<usize>,
+}
+
+impl MeasureTool {
+    pub fn clear(&mut self) {
+        self.first = None;
+        self.second = None;
+    }
+
+    /// Takes the clicked body as the next end; clicking the first end again
+    /// does nothing.
+    pub fn pick(&mut self, index: usize) {
+        match (self.first, self.second) {
+            (Some(first), None) if first != index => self.second = Some(index),
+            (Some(_), None) => {}
+            _ => {
+                self.first = Some(index);
+                self.second = None;
+            }
+        }
+    }
+
+    /// The body picked first, while waiting for the second.
+    pub fn pending(&self) -> Option<usize> {
+        self.first.filter(|_| self.second.is_none())
+    }
+
+    /// Both ends, in the order they were picked.
+    pub fn pair(&self) -> Option<(usize, usize)> {
+        Some((self.first?, self.second?))
+    }
+
+    /// Line list of the segment between the two ends in `bodies`, the
+    /// bodies as shown, for the reference pass.
+    pub fn vertices(&self, bodies: &[Body]) -> Vec<LineVertex> {
+        let Some((from, to)) = self.pair() else {
+            return Vec::new();
+        };
+        let (Some(from), Some(to)) = (bodies.get(from), bodies.get(to)) else {
+            return Vec::new();
+        };
+        [from, to]
+            .map(|body| LineVertex {
+                position: body.position,
+                color: LINE_COLOR,
+            })
+            .to_vec()
+    }
+}
+
+impl EventHandler for MeasureTool {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if event.invalidates_bodies() {
+            self.clear();
+        }
+    }
+}
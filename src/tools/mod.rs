@@ -0,0 +1,7 @@
+pub mod gravity_well;
+pub mod measure;
+pub mod picking;
+pub mod point_cloud;
+pub mod spawn;
+pub mod trails;
+pub mod trajectory;
@@ -0,0 +1,34 @@
+use glam::Vec2;
+
+use crate::camera::Camera;
+use crate::simulation::Body;
+
+/// How far from a body's drawn disc the cursor may be and still pick it, in
+/// logical pixels; scaled by the UI scale so it feels the same on any screen.
+pub const PICK_RADIUS: f32 = 12.0;
+
+/// The body drawn nearest the cursor, if its disc comes within `radius`
+/// pixels of it. Among overlapping candidates the one closest on screen
+/// wins, then the one closest to the camera.
+pub fn pick_body(
+    camera: &Camera,
+    bodies: &[Body],
+    cursor: Vec2,
+    viewport: Vec2,
+    radius: f32,
+) -> Option<usize> {
+    // Pixels per world unit at unit depth.
+    let scale = 0.5 * viewport.y / (0.5 * camera.fovy).tan();
+    bodies
+        .iter()
+        .enumerate()
+        .filter(|(_, body)| body.position.is_finite())
+        .filter_map(|(index, body)| {
+            let (pixel, depth) = camera.project(body.position, viewport)?;
+            let disc = body.radius * scale / depth;
+            let gap = (pixel.distance(cursor) - disc).max(0.0);
+            (gap <= radius).then_some((index, gap, depth))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+        .map(|(index, _, _)| index)
+}
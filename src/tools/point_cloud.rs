@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+use crate::formats::ply;
+use crate::simulation::StepCursor;
+use crate::simulation::worker::SimulationFrame;
+
+/// Writes a PLY point cloud of the bodies every `every` simulation steps, as
+/// `frame_<step>.ply` in one directory, for inspecting a run offline.
+#[derive(Clone, Debug)]
+pub struct PointCloudSeries {
+    directory: PathBuf,
+    every: u64,
+    cursor: StepCursor,
+    written: usize,
+}
+
+impl PointCloudSeries {
+    /// Creates `directory` if needed.
+    pub fn new(directory: &Path, every: u64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(directory)?;
+        Ok(Self {
+            directory: directory.to_owned(),
+            every: every.max(1),
+            cursor: StepCursor::default(),
+            written: 0,
+        })
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Clouds written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Writes the frame's cloud if one is due, returning its path. Frames land
+    /// on multiples of `every` when the worker steps one at a time; otherwise
+    /// on the first frame past each multiple.
+    pub fn observe(&mut self, frame: &SimulationFrame) -> std::io::Result<Option<PathBuf>> {
+        let step = frame.state.step;
+        if self.cursor.due(step, self.every).is_none() {
+            return Ok(None);
+        }
+        let path = self.directory.join(format!("frame_{step:010}.ply"));
+        std::fs::write(&path, ply::encode(&frame.bodies))?;
+        self.cursor.schedule((step / self.every + 1) * self.every);
+        self.written += 1;
+        Ok(Some(path))
+    }
+}
@@ -0,0 +1,116 @@
+use glam::{Vec2, Vec3, Vec4};
+
+use crate::camera::Camera;
+use crate::simulation::prediction::predict_trajectory;
+use crate::simulation::{Body, SimulationState};
+
+const PREDICTION_STEPS: usize = 400;
+const PREDICTION_STRIDE: f32 = 4.0;
+const PREDICTION_ATTRACTORS: usize = 16;
+/// Dragging across the whole view distance gives this many steps' worth of travel.
+const THROW_STEPS: f32 = 200.0;
+
+/// Press to place a body, drag to aim its initial velocity, release to commit.
+#[derive(Clone, Debug, Default)]
+pub struct SpawnTool {
+    pub enabled: bool,
+    anchor: Option<Vec3>,
+    aim: Vec3,
+    preview: Vec<Vec3>,
+}
+
+impl SpawnTool {
+    pub fn is_dragging(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    pub fn begin(&mut self, camera: &Camera, cursor: Vec2, viewport: Vec2) {
+        match camera.cursor_on_target_plane(cursor, viewport) {
+            Some(anchor) => self.begin_at(anchor),
+            None => self.cancel(),
+        }
+    }
+
+    /// Places the body at a world point, e.g. along a VR controller's ray.
+    pub fn begin_at(&mut self, anchor: Vec3) {
+        if !self.enabled {
+            return;
+        }
+        self.anchor = Some(anchor);
+        self.aim = anchor;
+        self.preview.clear();
+    }
+
+    /// Updates the aim and recomputes the predicted arc.
+    pub fn drag(
+        &mut self,
+        camera: &Camera,
+        cursor: Vec2,
+        viewport: Vec2,
+        bodies: &[Body],
+        state: &SimulationState,
+    ) {
+        let aim = camera
+            .cursor_on_target_plane(cursor, viewport)
+            .unwrap_or(self.aim);
+        self.aim_at(aim, bodies, state);
+    }
+
+    /// Aims the initial velocity towards a world point.
+    pub fn aim_at(&mut self, aim: Vec3, bodies: &[Body], state: &SimulationState) {
+        let Some(anchor) = self.anchor else {
+            return;
+        };
+        self.aim = aim;
+        self.preview = predict_trajectory(
+            bodies,
+            anchor,
+            self.velocity(state),
+            state,
+            PREDICTION_STEPS,
+            PREDICTION_STRIDE,
+            PREDICTION_ATTRACTORS,
+        );
+    }
+
+    /// Finishes the drag and returns the body to spawn.
+    pub fn release(
+        &mut self,
+        camera: &Camera,
+        bodies: &[Body],
+        state: &SimulationState,
+    ) -> Option<Body> {
+        let anchor = self.anchor.take()?;
+        self.preview.clear();
+        let total_mass: f32 = bodies.iter().map(|body| body.mass).sum();
+        Some(Body::new(
+            anchor,
+            self.velocity(state),
+            total_mass * 1e-6,
+            camera.distance * 0.005,
+            Vec4::ONE,
+        ))
+    }
+
+    pub fn cancel(&mut self) {
+        self.anchor = None;
+        self.preview.clear();
+    }
+
+    fn velocity(&self, state: &SimulationState) -> Vec3 {
+        let drag = self.aim - self.anchor.unwrap_or(self.aim);
+        drag / (THROW_STEPS * state.effective_dt().abs().max(f32::EPSILON))
+    }
+
+    /// Line-list vertices: the velocity arrow followed by the predicted arc.
+    pub fn preview_lines(&self) -> Vec<Vec3> {
+        let Some(anchor) = self.anchor else {
+            return Vec::new();
+        };
+        let mut lines = vec![anchor, self.aim];
+        for pair in self.preview.windows(2) {
+            lines.extend_from_slice(pair);
+        }
+        lines
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use glam::Vec3;
+
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::rendering::config::TrailConfig;
+use crate::simulation::Body;
+
+/// Bodies whose trails are kept; large particle scenarios only keep their first ones.
+const MAX_BODIES: usize = 1024;
+
+/// Recent positions of every body, one sample per new simulation frame, in
+/// simulation units and the inertial frame, for export as orbit trails.
+/// Restarts whenever the body indices may have shifted.
+#[derive(Clone, Debug)]
+pub struct TrailHistory {
+    length: usize,
+    time: Option<f64>,
+    trails: Vec<VecDeque<Vec3>>,
+}
+
+impl Default for TrailHistory {
+    fn default() -> Self {
+        Self::new(TrailConfig::default().length as usize)
+    }
+}
+
+impl TrailHistory {
+    /// Keeps the last `length` positions of each body.
+    pub fn new(length: usize) -> Self {
+        Self {
+            length: length.max(2),
+            time: None,
+            trails: Vec::new(),
+        }
+    }
+
+    /// Keeps the last `length` positions from now on, dropping the oldest
+    /// ones if there are more.
+    pub fn set_length(&mut self, length: usize) {
+        self.length = length.max(2);
+        for trail in &mut self.trails {
+            let excess = trail.len().saturating_sub(self.length);
+            trail.drain(..excess);
+        }
+    }
+
+    /// Oldest position first, parallel to the frame's bodies.
+    pub fn trails(&self) -> &[VecDeque<Vec3>] {
+        &self.trails
+    }
+
+    pub fn clear(&mut self) {
+        self.time = None;
+        self.trails.clear();
+    }
+
+    /// Appends every body's position at `time`, unless the frame is the one
+    /// already recorded.
+    pub fn record(&mut self, bodies: &[Body], time: f64) {
+        if self.time == Some(time) {
+            return;
+        }
+        self.time = Some(time);
+        let count = bodies.len().min(MAX_BODIES);
+        // Spawns and removals shift indices too.
+        if count != self.trails.len() {
+            self.trails.clear();
+            self.trails.resize_with(count, VecDeque::new);
+        }
+        for (trail, body) in self.trails.iter_mut().zip(bodies) {
+            if trail.len() == self.length {
+                trail.pop_front();
+            }
+            trail.push_back(body.position);
+        }
+    }
+}
+
+impl EventHandler for TrailHistory {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if event.invalidates_bodies() {
+            self.clear();
+        }
+    }
+}
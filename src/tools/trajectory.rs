@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use glam::Vec3;
+
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::simulation::Body;
+
+/// Samples kept before the oldest are dropped; several orbits of any scenario.
+const MAX_SAMPLES: usize = 20_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrajectorySample {
+    pub time: f64,
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+/// File layouts `TrajectoryRecorder::export` can write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// `{"body": i, "samples": [{"t", "x", "y", "z", "vx", "vy", "vz"}, ...]}`.
+    Json,
+    /// Header row `t,x,y,z,vx,vy,vz`, then one sample per row.
+    Csv,
+}
+
+impl ExportFormat {
+    /// CSV for a `.csv` extension, JSON otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Trail of the selected (followed) body, one sample per new simulation frame,
+/// in simulation units and the inertial frame. Restarts when the selection
+/// changes or the body indices may have shifted.
+#[derive(Clone, Debug, Default)]
+pub struct TrajectoryRecorder {
+    body: Option<usize>,
+    samples: VecDeque<TrajectorySample>,
+}
+
+impl TrajectoryRecorder {
+    pub fn body(&self) -> Option<usize> {
+        self.body
+    }
+
+    /// Oldest first.
+    pub fn samples(&self) -> impl ExactSizeIterator<Item = &TrajectorySample> {
+        self.samples.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.body = None;
+        self.samples.clear();
+    }
+
+    /// Appends the selected body's state at `time`, unless the frame is the
+    /// one already recorded.
+    pub fn record(&mut self, selected: Option<usize>, bodies: &[Body], time: f64) {
+        if selected != self.body {
+            self.samples.clear();
+            self.body = selected;
+        }
+        let Some(body) = selected.and_then(|index| bodies.get(index)) else {
+            return;
+        };
+        if self.samples.back().is_some_and(|last| last.time == time) {
+            return;
+        }
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(TrajectorySample {
+            time,
+            position: body.position,
+            velocity: body.velocity,
+        });
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"body\": ");
+        match self.body {
+            Some(body) => write!(json, "{body}").unwrap(),
+            None => json.push_str("null"),
+        }
+        json.push_str(", \"samples\": [");
+        for (index, sample) in self.samples.iter().enumerate() {
+            let separator = if index == 0 { "\n" } else { ",\n" };
+            let (p, v) = (sample.position, sample.velocity);
+            write!(
+                json,
+                "{separator}  {{\"t\": {}, \"x\": {}, \"y\": {}, \"z\": {}, \"vx\": {}, \"vy\": {}, \"vz\": {}}}",
+                sample.time,
+                number(p.x),
+                number(p.y),
+                number(p.z),
+                number(v.x),
+                number(v.y),
+                number(v.z),
+            )
+            .unwrap();
+        }
+        json.push_str("\n]}\n");
+        json
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("t,x,y,z,vx,vy,vz\n");
+        for sample in &self.samples {
+            let (p, v) = (sample.position, sample.velocity);
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                sample.time, p.x, p.y, p.z, v.x, v.y, v.z
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    pub fn export(&self, path: &Path) -> std::io::Result<()> {
+        let contents = match ExportFormat::from_path(path) {
+            ExportFormat::Json => self.to_json(),
+            ExportFormat::Csv => self.to_csv(),
+        };
+        std::fs::write(path, contents)
+    }
+}
+
+/// JSON has no NaN or infinity, so a diverged sample is written as `null`.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
+impl EventHandler for TrajectoryRecorder {
+    fn handle(&mut self, event: &AppEvent, _events: &mut EventQueue) {
+        if event.invalidates_bodies() {
+            self.clear();
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Guided tours: narrated walkthroughs scripted as TOML, such as "from
+//! Earth-Moon to galaxy collisions". Each `[[step]]` may switch simulation,
+//! set its parameters, place the camera and set the clock, and shows a
+//! caption until the presenter presses Enter or its `wait` runs out:
+//!
+//! ```toml
+//! title = "From Earth-Moon to galaxy collisions"
+//!
+//! [[step]]
+//! simulation = "Earth-Moon"
+//! caption = "The Moon pulls on the Earth as much as the Earth on the Moon."
+//! camera = { target = [0, 0, 0], distance = 3, yaw = 0, pitch = 20 }
+//! time_scale = 0.5
+//!
+//! [[step]]
+//! caption = "Ten times heavier, the Moon drags the Earth around."
+//! parameters = { moon_mass = 10 }
+//! wait = 8
+//! ```
+//!
+//! Camera angles are in degrees; a step without `camera` refits the view to
+//! the bodies when it loads a simulation and leaves it alone otherwise.
+//! `seed` and `parameters` reload the simulation, the previous step's one if
+//! the step names none. `wait` is `"key"` (the default) or a number of
+//! seconds.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use glam::Vec3;
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::share::CameraPose;
+
+#[derive(Debug, Error)]
+pub enum TourError {
+    #[error("could not read tour: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("tour is not valid TOML: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("tour step {step}: {message}")]
+    Invalid { step: usize, message: String },
+    #[error("tour has no steps")]
+    Empty,
+    #[error("tour step {step}: no simulation named `{name}`")]
+    UnknownSimulation { step: usize, name: String },
+}
+
+/// When a step gives way to the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Advance {
+    Key,
+    After(Duration),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TourStep {
+    /// Simulation to (re)load, `None` to keep the running one as it is.
+    pub simulation: Option<String>,
+    pub seed: Option<u64>,
+    pub parameters: Vec<(String, f32)>,
+    pub camera: Option<CameraPose>,
+    pub time_scale: Option<f32>,
+    pub paused: Option<bool>,
+    pub caption: String,
+    pub advance: Advance,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tour {
+    pub title: Option<String>,
+    pub steps: Vec<TourStep>,
+}
+
+impl Tour {
+    pub fn load(path: &Path) -> Result<Self, TourError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, TourError> {
+        let table: Table = text.parse()?;
+        let title = table
+            .get("title")
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+        let Some(Value::Array(entries)) = table.get("step") else {
+            return Err(TourError::Empty);
+        };
+        let mut steps = Vec::with_capacity(entries.len());
+        let mut current: Option<String> = None;
+        for (index, entry) in entries.iter().enumerate() {
+            // Numbered from 1, as a teacher counts them in the file.
+            let number = index + 1;
+            let invalid = |message: String| TourError::Invalid {
+                step: number,
+                message,
+            };
+            let Value::Table(entry) = entry else {
+                return Err(invalid("`step` entries must be tables".to_owned()));
+            };
+            let mut step = parse_step(entry).map_err(invalid)?;
+            if step.simulation.is_some() {
+                current = step.simulation.clone();
+            } else if step.seed.is_some() || !step.parameters.is_empty() {
+                step.simulation = Some(current.clone().ok_or_else(|| {
+                    invalid("`seed` and `parameters` need a `simulation`".to_owned())
+                })?);
+            }
+            steps.push(step);
+        }
+        if steps.is_empty() {
+            return Err(TourError::Empty);
+        }
+        Ok(Self { title, steps })
+    }
+
+    /// Checks every step's simulation against the available ones.
+    pub fn check_simulations(&self, names: &[String]) -> Result<(), TourError> {
+        for (index, step) in self.steps.iter().enumerate() {
+            if let Some(name) = &step.simulation
+                && !names.contains(name)
+            {
+                return Err(TourError::UnknownSimulation {
+                    step: index + 1,
+                    name: name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_step(entry: &Table) -> Result<TourStep, String> {
+    let simulation = match entry.get("simulation") {
+        None => None,
+        Some(Value::String(name)) => Some(name.clone()),
+        Some(_) => return Err("`simulation` must be a name".to_owned()),
+    };
+    let seed = match entry.get("seed") {
+        None => None,
+        Some(Value::Integer(seed)) => {
+            Some(u64::try_from(*seed).map_err(|_| "`seed` must not be negative".to_owned())?)
+        }
+        Some(_) => return Err("`seed` must be an integer".to_owned()),
+    };
+    let parameters = match entry.get("parameters") {
+        None => Vec::new(),
+        Some(Value::Table(parameters)) => parameters
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), number(value, name)?)))
+            .collect::<Result<_, String>>()?,
+        Some(_) => return Err("`parameters` must be a table".to_owned()),
+    };
+    let camera = match entry.get("camera") {
+        None => None,
+        Some(Value::Table(camera)) => Some(parse_camera(camera)?),
+        Some(_) => return Err("`camera` must be a table".to_owned()),
+    };
+    let time_scale = entry
+        .get("time_scale")
+        .map(|value| number(value, "time_scale"))
+        .transpose()?;
+    let paused = match entry.get("paused") {
+        None => None,
+        Some(Value::Boolean(paused)) => Some(*paused),
+        Some(_) => return Err("`paused` must be true or false".to_owned()),
+    };
+    let caption = match entry.get("caption") {
+        None => String::new(),
+        Some(Value::String(caption)) => caption.trim().to_owned(),
+        Some(_) => return Err("`caption` must be text".to_owned()),
+    };
+    let advance = match entry.get("wait") {
+        None => Advance::Key,
+        Some(Value::String(key)) if key == "key" => Advance::Key,
+        Some(value) => {
+            let seconds = number(value, "wait")
+                .map_err(|_| "`wait` must be \"key\" or a number of seconds".to_owned())?;
+            Advance::After(
+                Duration::try_from_secs_f32(seconds)
+                    .map_err(|_| "`wait` must not be negative".to_owned())?,
+            )
+        }
+    };
+    Ok(TourStep {
+        simulation,
+        seed,
+        parameters,
+        camera,
+        time_scale,
+        paused,
+        caption,
+        advance,
+    })
+}
+
+fn parse_camera(camera: &Table) -> Result<CameraPose, String> {
+    let target = match camera.get("target") {
+        None => Vec3::ZERO,
+        Some(Value::Array(values)) => {
+            let values: Vec<f32> = values
+                .iter()
+                .map(|value| number(value, "camera.target"))
+                .collect::<Result<_, _>>()?;
+            let values: [f32; 3] = values
+                .try_into()
+                .map_err(|_| "`camera.target` must be 3 numbers".to_owned())?;
+            Vec3::from_array(values)
+        }
+        Some(_) => return Err("`camera.target` must be 3 numbers".to_owned()),
+    };
+    let field = |key: &str| -> Result<f32, String> {
+        camera
+            .get(key)
+            .ok_or_else(|| format!("`camera` has no `{key}`"))
+            .and_then(|value| number(value, &format!("camera.{key}")))
+    };
+    let distance = field("distance")?;
+    if distance <= 0.0 {
+        return Err("`camera.distance` must be positive".to_owned());
+    }
+    Ok(CameraPose {
+        target,
+        distance,
+        yaw: field("yaw")?.to_radians(),
+        pitch: field("pitch")?.to_radians(),
+    })
+}
+
+fn number(value: &Value, key: &str) -> Result<f32, String> {
+    let number = match value {
+        Value::Integer(value) => *value as f32,
+        Value::Float(value) => *value as f32,
+        _ => return Err(format!("`{key}` must be a number")),
+    };
+    if number.is_finite() {
+        Ok(number)
+    } else {
+        Err(format!("`{key}` must be finite"))
+    }
+}
+
+/// What the UI layer shows while a tour plays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TourCaption<'a> {
+    pub title: Option<&'a str>,
+    pub text: &'a str,
+    /// Numbered from 1.
+    pub step: usize,
+    pub steps: usize,
+    /// Whether the presenter has to press Enter to go on.
+    pub waits_for_key: bool,
+}
+
+/// A tour being played: which step is on screen and since when.
+#[derive(Clone, Debug)]
+pub struct TourPlayer {
+    tour: Tour,
+    step: usize,
+    shown_at: Instant,
+}
+
+impl TourPlayer {
+    pub fn new(tour: Tour, now: Instant) -> Self {
+        Self {
+            tour,
+            step: 0,
+            shown_at: now,
+        }
+    }
+
+    pub fn step(&self) -> &TourStep {
+        &self.tour.steps[self.step]
+    }
+
+    /// Moves to the next step; `false` past the last one.
+    pub fn next(&mut self, now: Instant) -> bool {
+        if self.step + 1 == self.tour.steps.len() {
+            return false;
+        }
+        self.step += 1;
+        self.shown_at = now;
+        true
+    }
+
+    /// Whether a timed step has been shown for long enough.
+    pub fn is_due(&self, now: Instant) -> bool {
+        match self.step().advance {
+            Advance::Key => false,
+            Advance::After(wait) => now.duration_since(self.shown_at) >= wait,
+        }
+    }
+
+    pub fn caption(&self) -> TourCaption<'_> {
+        let step = self.step();
+        TourCaption {
+            title: self.tour.title.as_deref(),
+            text: &step.caption,
+            step: self.step + 1,
+            steps: self.tour.steps.len(),
+            waits_for_key: step.advance == Advance::Key,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use glam::Vec2;
+
+use super::orbit_hud::quantity;
+use super::tooltip::BodyTooltip;
+use crate::localization::Localizer;
+use crate::simulation::Body;
+use crate::simulation::worker::SimulationFrame;
+
+/// Sentences for a screen reader about what changed on screen: the
+/// simulation, whether it runs and the selected body. Diffs each frame
+/// against the last one instead of listening for events, so changes made
+/// over the control server or by the worker itself are announced too.
+#[derive(Clone, Debug, Default)]
+pub struct Announcer {
+    simulation: Option<usize>,
+    paused: Option<bool>,
+    selected: Option<usize>,
+    pending: Vec<String>,
+}
+
+impl Announcer {
+    /// `bodies` are the bodies as shown, as for `BodyTooltip`.
+    pub fn observe(
+        &mut self,
+        frame: &SimulationFrame,
+        bodies: &[Body],
+        selected: Option<usize>,
+        simulation_names: &[String],
+        locale: &Localizer,
+    ) {
+        if self.simulation != Some(frame.simulation_index) {
+            self.simulation = Some(frame.simulation_index);
+            if let Some(name) = simulation_names.get(frame.simulation_index) {
+                self.pending
+                    .push(locale.format("announcement-switched", &[("simulation", name.into())]));
+            }
+        }
+        let paused = frame.state.paused;
+        if self.paused.is_some_and(|was| was != paused) {
+            self.pending.push(locale.text(if paused {
+                "announcement-paused"
+            } else {
+                "announcement-running"
+            }));
+        }
+        self.paused = Some(paused);
+        if self.selected != selected {
+            self.selected = selected;
+            let text = match selected.and_then(|index| selected_body(frame, bodies, index, locale))
+            {
+                Some(text) => text,
+                None => locale.text("announcement-selection-cleared"),
+            };
+            self.pending.push(text);
+        }
+    }
+
+    /// The announcements since the last call, oldest first.
+    pub fn take(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+fn selected_body(
+    frame: &SimulationFrame,
+    bodies: &[Body],
+    index: usize,
+    locale: &Localizer,
+) -> Option<String> {
+    let tooltip = BodyTooltip::new(frame, bodies, index, Vec2::ZERO)?;
+    let (mass_per_unit, mass_label) = frame.units.map_or((1.0, ""), |units| {
+        (f64::from(units.mass), units.physical.mass_label)
+    });
+    let speed_label = format!("{}/{}", tooltip.length.label, tooltip.time.label);
+    Some(
+        locale.format(
+            "announcement-selected",
+            &[
+                ("body", tooltip.title(locale).into()),
+                (
+                    "mass",
+                    quantity(f64::from(bodies[index].mass) * mass_per_unit, mass_label).into(),
+                ),
+                (
+                    "distance",
+                    quantity(
+                        f64::from(tooltip.distance) * tooltip.length.per_unit,
+                        tooltip.length.label,
+                    )
+                    .into(),
+                ),
+                (
+                    "speed",
+                    quantity(
+                        f64::from(tooltip.speed) * tooltip.length.per_unit / tooltip.time.per_unit,
+                        &speed_label,
+                    )
+                    .into(),
+                ),
+            ],
+        ),
+    )
+}
@@ -0,0 +1,54 @@
+use crate::events::{AppEvent, EventQueue};
+use crate::localization::Localizer;
+use crate::simulation::{Body, BodyProperties};
+
+/// Mass, velocity and color of the selected (followed) body, for the UI
+/// layer to edit in place. `commit` sends the values to the simulation; the
+/// app also writes just that body to the GPU buffer, so the change shows
+/// before the next frame arrives.
+#[derive(Clone, Debug, Default)]
+pub struct BodyInspector {
+    pub index: Option<usize>,
+    pub properties: Option<BodyProperties>,
+}
+
+impl BodyInspector {
+    /// Loads `index` from `bodies` when the selection changed; otherwise the
+    /// fields keep whatever the user is typing.
+    pub fn select(&mut self, index: Option<usize>, bodies: &[Body]) {
+        if index == self.index && self.properties.is_some() == index.is_some() {
+            return;
+        }
+        self.index = index;
+        self.properties = index
+            .and_then(|index| bodies.get(index))
+            .map(BodyProperties::of);
+    }
+
+    /// Reloads the fields from the running simulation, discarding edits.
+    pub fn refresh(&mut self, bodies: &[Body]) {
+        self.properties = self
+            .index
+            .and_then(|index| bodies.get(index))
+            .map(BodyProperties::of);
+    }
+
+    /// Applies the edited values and returns them with the body's index.
+    /// Invalid ones (negative mass, NaN) are refused with a toast.
+    pub fn commit(
+        &mut self,
+        events: &mut EventQueue,
+        locale: &Localizer,
+    ) -> Option<(usize, BodyProperties)> {
+        let (index, properties) = (self.index?, self.properties?);
+        if !properties.is_valid() {
+            events.push(AppEvent::ToastRequested {
+                message: locale.text("toast-invalid-properties"),
+                duration: None,
+            });
+            return None;
+        }
+        events.push(AppEvent::BodyEdited { index, properties });
+        Some((index, properties))
+    }
+}
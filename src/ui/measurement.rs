@@ -0,0 +1,101 @@
+use glam::Vec2;
+
+use super::orbit_hud::quantity;
+use crate::camera::Camera;
+use crate::localization::Localizer;
+use crate::simulation::Body;
+use crate::simulation::units::{PhysicalUnits, UnitConversion};
+use crate::simulation::worker::SimulationFrame;
+
+const METRES_PER_AU: f64 = PhysicalUnits::ASTRONOMICAL.length;
+
+/// Live distance between the two bodies picked with `tools::measure`, in
+/// simulation units and, when the scenario's length scale is known, in AU
+/// and km. Frame-independent, so the shown bodies measure the same as the
+/// simulated ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub from: usize,
+    pub to: usize,
+    pub from_name: Option<String>,
+    pub to_name: Option<String>,
+    /// In simulation units.
+    pub distance: f32,
+    /// Metres per simulation length unit, if the scenario has a physical scale.
+    pub metres_per_unit: Option<f64>,
+    /// Midpoint of the connecting line in physical pixels, where the UI draws
+    /// the readout; `None` when it is behind the camera.
+    pub anchor: Option<Vec2>,
+}
+
+impl Measurement {
+    /// `bodies` are the bodies as shown. `None` if either end is gone.
+    pub fn new(
+        frame: &SimulationFrame,
+        bodies: &[Body],
+        (from, to): (usize, usize),
+        camera: &Camera,
+        viewport: Vec2,
+    ) -> Option<Self> {
+        let (a, b) = (bodies.get(from)?.position, bodies.get(to)?.position);
+        let name = |index: usize| {
+            frame
+                .names
+                .iter()
+                .find(|(named, _)| *named == index)
+                .map(|(_, name)| name.clone())
+        };
+        Some(Self {
+            from,
+            to,
+            from_name: name(from),
+            to_name: name(to),
+            distance: a.distance(b),
+            metres_per_unit: metres_per_unit(frame.units.as_ref(), frame.length_label),
+            anchor: camera
+                .project((a + b) * 0.5, viewport)
+                .map(|(position, _)| position),
+        })
+    }
+
+    pub fn lines(&self, locale: &Localizer) -> Vec<String> {
+        let body = |name: &Option<String>, index: usize| match name {
+            Some(name) => name.clone(),
+            None => locale.format("measure-body", &[("index", index.into())]),
+        };
+        let mut lines = vec![
+            locale.format(
+                "measure-title",
+                &[
+                    ("from", body(&self.from_name, self.from).into()),
+                    ("to", body(&self.to_name, self.to).into()),
+                ],
+            ),
+            locale.format(
+                "measure-simulation-units",
+                &[("distance", quantity(f64::from(self.distance), "").into())],
+            ),
+        ];
+        if let Some(metres_per_unit) = self.metres_per_unit {
+            let metres = f64::from(self.distance) * metres_per_unit;
+            lines.push(quantity(metres / METRES_PER_AU, "AU"));
+            lines.push(quantity(metres / 1000.0, "km"));
+        }
+        lines
+    }
+}
+
+/// Metres per simulation length unit: from the unit conversion when the
+/// scenario runs in N-body units, else from the unit it is authored in.
+fn metres_per_unit(units: Option<&UnitConversion>, length_label: &str) -> Option<f64> {
+    match units {
+        Some(units) if units.physical.length_label.is_empty() => None,
+        Some(units) => Some(f64::from(units.length) * units.physical.length),
+        None => match length_label {
+            "m" => Some(1.0),
+            "km" => Some(1000.0),
+            "AU" => Some(METRES_PER_AU),
+            _ => None,
+        },
+    }
+}
@@ -0,0 +1,9 @@
+pub mod announcements;
+pub mod inspector;
+pub mod measurement;
+pub mod orbit_hud;
+pub mod shader_editor;
+pub mod simulation_palette;
+pub mod theme;
+pub mod toasts;
+pub mod tooltip;
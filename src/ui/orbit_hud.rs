@@ -0,0 +1,125 @@
+use crate::localization::Localizer;
+use crate::rendering::reference::LengthUnit;
+use crate::simulation::Body;
+use crate::simulation::orbits::OrbitalElements;
+use crate::simulation::units::UnitConversion;
+use crate::simulation::worker::SimulationFrame;
+
+/// Display unit for times, the counterpart of `LengthUnit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeUnit {
+    pub label: &'static str,
+    /// Display units per simulation time unit.
+    pub per_unit: f64,
+}
+
+impl TimeUnit {
+    pub fn new(units: Option<&UnitConversion>, time_label: &'static str) -> Self {
+        match units {
+            // Orbital periods in seconds run to seven digits.
+            Some(units) if units.physical.time_label == "s" => Self {
+                label: "d",
+                per_unit: f64::from(units.time) * units.physical.time / 86_400.0,
+            },
+            Some(units) => Self {
+                label: units.physical.time_label,
+                per_unit: f64::from(units.time),
+            },
+            None => Self {
+                label: time_label,
+                per_unit: 1.0,
+            },
+        }
+    }
+}
+
+/// The body whose pull on `index` is strongest, which the HUD takes as the
+/// center of its orbit: the Sun for a planet, the planet for its moons.
+pub fn dominant_body(bodies: &[Body], index: usize) -> Option<usize> {
+    let position = bodies.get(index)?.position;
+    bodies
+        .iter()
+        .enumerate()
+        .filter(|&(other, body)| other != index && body.mass > 0.0)
+        .map(|(other, body)| (other, body.mass / body.position.distance_squared(position)))
+        .filter(|(_, pull)| pull.is_finite())
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(other, _)| other)
+}
+
+/// Live osculating elements of the followed body about its dominant mass,
+/// recomputed from the state vectors every frame so perturbations show up as
+/// drifting numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrbitHud {
+    pub body: usize,
+    pub central: usize,
+    pub elements: OrbitalElements,
+    /// G·(M + m), for the period.
+    pub mu: f32,
+    pub length: LengthUnit,
+    pub time: TimeUnit,
+}
+
+impl OrbitHud {
+    /// `None` if `body` is out of range, has nothing to orbit or moves on a
+    /// degenerate (radial or parabolic) path.
+    pub fn new(frame: &SimulationFrame, body: usize) -> Option<Self> {
+        let central = dominant_body(&frame.bodies, body)?;
+        let (orbiter, primary) = (&frame.bodies[body], &frame.bodies[central]);
+        let mu = frame.state.gravitational_constant * (primary.mass + orbiter.mass);
+        let elements = OrbitalElements::from_state_vectors(
+            orbiter.position - primary.position,
+            orbiter.velocity - primary.velocity,
+            mu,
+        )?;
+        Some(Self {
+            body,
+            central,
+            elements,
+            mu,
+            length: LengthUnit::new(frame.units.as_ref(), frame.length_label),
+            time: TimeUnit::new(frame.units.as_ref(), frame.time_label),
+        })
+    }
+
+    /// One `name value` line per element, for the UI to draw as a block.
+    pub fn lines(&self, locale: &Localizer) -> Vec<String> {
+        let elements = &self.elements;
+        let length =
+            |value: f32| quantity(f64::from(value) * self.length.per_unit, self.length.label);
+        let mut lines = vec![
+            locale.format(
+                "orbit-hud-title",
+                &[("body", self.body.into()), ("central", self.central.into())],
+            ),
+            format!("a  {}", length(elements.semi_major_axis)),
+            format!("e  {:.4}", elements.eccentricity),
+            format!("i  {:.2}°", elements.inclination.to_degrees()),
+        ];
+        if let Some(period) = elements.period(self.mu) {
+            lines.push(format!(
+                "T  {}",
+                quantity(f64::from(period) * self.time.per_unit, self.time.label)
+            ));
+        }
+        lines.push(format!("q  {}", length(elements.periapsis())));
+        if let Some(apoapsis) = elements.apoapsis() {
+            lines.push(format!("Q  {}", length(apoapsis)));
+        }
+        lines
+    }
+}
+
+/// Four significant digits, in scientific notation outside 0.01..10⁵.
+pub(super) fn quantity(value: f64, label: &str) -> String {
+    let number = if value == 0.0 {
+        "0".to_owned()
+    } else if (1e-2..1e5).contains(&value.abs()) {
+        let decimals = (3 - value.abs().log10().floor() as i32).clamp(0, 5) as usize;
+        format!("{value:.decimals$}")
+    } else {
+        format!("{value:.3e}")
+    };
+    format!("{number} {label}").trim_end().to_owned()
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::events::{AppEvent, EventQueue};
+use crate::localization::Localizer;
+use crate::rendering::shaders::{self, ShaderDiagnostic, ShaderId, ShaderSources};
+
+/// Editor panel over the root file of one shader, `#include`s and all. The UI
+/// layer edits `text` in place; `compile` validates it with naga and, on
+/// success, swaps it in through `AppEvent::ShaderChanged` so the affected
+/// pipelines are rebuilt. Errors stay in `diagnostic` for the panel to show
+/// next to the offending line.
+#[derive(Clone, Debug)]
+pub struct ShaderEditor {
+    pub open: bool,
+    pub shader: ShaderId,
+    pub text: String,
+    pub diagnostic: Option<ShaderDiagnostic>,
+}
+
+impl Default for ShaderEditor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            shader: ShaderId::Bodies,
+            text: ShaderId::Bodies.root_source().to_owned(),
+            diagnostic: None,
+        }
+    }
+}
+
+impl ShaderEditor {
+    /// Shows the active source of `shader`, discarding uncompiled edits.
+    pub fn select(&mut self, shader: ShaderId, sources: &ShaderSources) {
+        self.shader = shader;
+        self.text = sources.text(shader).to_owned();
+        self.diagnostic = None;
+    }
+
+    /// Whether `text` differs from what the pipelines currently run.
+    pub fn has_unapplied_edits(&self, sources: &ShaderSources) -> bool {
+        self.text != sources.text(self.shader)
+    }
+
+    pub fn compile(&mut self, events: &mut EventQueue, locale: &Localizer) {
+        match shaders::validate(self.shader, &self.text) {
+            Ok(()) => {
+                self.diagnostic = None;
+                events.push(AppEvent::ShaderChanged {
+                    shader: self.shader,
+                    text: Some(Arc::from(self.text.as_str())),
+                });
+                events.push(AppEvent::ToastRequested {
+                    message: locale.format(
+                        "toast-rebuilt-shader",
+                        &[("shader", self.shader.label().into())],
+                    ),
+                    duration: None,
+                });
+            }
+            Err(diagnostic) => self.diagnostic = Some(diagnostic),
+        }
+    }
+
+    /// Puts the built-in source back, both in the editor and on the GPU.
+    pub fn revert(&mut self, events: &mut EventQueue) {
+        self.text = self.shader.root_source().to_owned();
+        self.diagnostic = None;
+        events.push(AppEvent::ShaderChanged {
+            shader: self.shader,
+            text: None,
+        });
+    }
+}
@@ -0,0 +1,55 @@
+/// Searchable simulation list, opened with Ctrl+K. Like the shader editor,
+/// the UI layer edits `query` in place; the arrow keys move `selected`
+/// through `matches` and Enter switches to it.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationPalette {
+    pub open: bool,
+    pub query: String,
+    /// Index into `matches`, not into the simulation list.
+    pub selected: usize,
+}
+
+impl SimulationPalette {
+    /// Opens with an empty query, or closes.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Simulations whose name contains the query's characters in order,
+    /// ignoring case: names containing it as a whole first, then the looser
+    /// matches, each in list order. An empty query lists everything.
+    pub fn matches<'a>(&self, names: &'a [String]) -> Vec<(usize, &'a str)> {
+        let query = self.query.to_lowercase();
+        let (mut exact, loose): (Vec<_>, Vec<_>) = names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| is_subsequence(&query, &name.to_lowercase()))
+            .map(|(index, name)| (index, name.as_str()))
+            .partition(|(_, name)| name.to_lowercase().contains(&query));
+        exact.extend(loose);
+        exact
+    }
+
+    /// Moves the selection by `delta` matches, wrapping around the ends.
+    pub fn move_selection(&mut self, delta: isize, names: &[String]) {
+        let count = self.matches(names).len();
+        if count > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(count as isize) as usize;
+        }
+    }
+
+    /// Simulation index of the selected match. The selection is clamped, so
+    /// narrowing the query never leaves it past the end.
+    pub fn choice(&self, names: &[String]) -> Option<usize> {
+        let matches = self.matches(names);
+        let last = matches.len().checked_sub(1)?;
+        Some(matches[self.selected.min(last)].0)
+    }
+}
+
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text = text.chars();
+    query.chars().all(|wanted| text.any(|c| c == wanted))
+}
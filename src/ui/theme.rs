@@ -0,0 +1,72 @@
+use glam::Vec4;
+
+use crate::rendering::palette::ColorPalette;
+
+/// Colors the UI layer draws panels, text and highlights with, in linear
+/// RGBA. Follows `AppSettings::color_palette`, so a colorblind-safe palette
+/// also keeps warnings and highlights apart without relying on red–green.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiTheme {
+    pub text: Vec4,
+    /// Labels, units and hints drawn under the main text.
+    pub secondary_text: Vec4,
+    pub panel: Vec4,
+    /// Selection, focus and the palette's current match.
+    pub accent: Vec4,
+    /// Shader diagnostics and refused edits.
+    pub warning: Vec4,
+}
+
+impl UiTheme {
+    pub fn new(palette: ColorPalette) -> Self {
+        match palette {
+            ColorPalette::Standard => Self {
+                text: Vec4::new(0.92, 0.92, 0.92, 1.0),
+                secondary_text: Vec4::new(0.6, 0.6, 0.65, 1.0),
+                panel: Vec4::new(0.05, 0.05, 0.08, 0.8),
+                accent: Vec4::new(0.4, 0.7, 1.0, 1.0),
+                warning: Vec4::new(1.0, 0.35, 0.3, 1.0),
+            },
+            ColorPalette::Deuteranopia | ColorPalette::Protanopia => Self {
+                text: Vec4::new(0.92, 0.92, 0.92, 1.0),
+                secondary_text: Vec4::new(0.65, 0.65, 0.65, 1.0),
+                panel: Vec4::new(0.05, 0.05, 0.08, 0.85),
+                accent: Vec4::new(0.337, 0.706, 0.914, 1.0),
+                warning: Vec4::new(0.902, 0.624, 0.0, 1.0),
+            },
+            ColorPalette::HighContrast => Self {
+                text: Vec4::ONE,
+                secondary_text: Vec4::ONE,
+                panel: Vec4::new(0.0, 0.0, 0.0, 1.0),
+                accent: Vec4::new(1.0, 0.85, 0.0, 1.0),
+                warning: Vec4::new(0.0, 0.9, 1.0, 1.0),
+            },
+        }
+    }
+}
+
+/// Size of UI labels relative to the platform's scale factor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextSize {
+    #[default]
+    Normal,
+    Large,
+    Largest,
+}
+
+impl TextSize {
+    pub const ALL: [Self; 3] = [Self::Normal, Self::Large, Self::Largest];
+
+    /// The size after this one, wrapping back to `Normal`.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn scale(self) -> f32 {
+        match self {
+            Self::Normal => 1.0,
+            Self::Large => 1.25,
+            Self::Largest => 1.5,
+        }
+    }
+}
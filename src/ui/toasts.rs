@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::events::{AppEvent, EventHandler, EventQueue};
+use crate::localization::Localizer;
+
+/// Toasts fade out over this final stretch of their lifetime.
+const FADE_OUT: Duration = Duration::from_millis(300);
+
+/// Short on-screen message confirming a user action.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    pub shown_at: Instant,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.shown_at) >= self.duration
+    }
+
+    /// Opacity at `now`: one until the last `FADE_OUT`, then linearly to zero.
+    pub fn opacity(&self, now: Instant) -> f32 {
+        let remaining = self
+            .duration
+            .saturating_sub(now.saturating_duration_since(self.shown_at));
+        (remaining.as_secs_f32() / FADE_OUT.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Queue of transient messages drawn by the UI layer, newest last. Fed by
+/// `AppEvent::ToastRequested`; also announces scenario switches by name.
+#[derive(Clone, Debug)]
+pub struct Toasts {
+    /// Lifetime of toasts that do not ask for their own.
+    pub duration: Duration,
+    /// Older toasts are dropped once more than this many are showing.
+    pub max_visible: usize,
+    active: VecDeque<Toast>,
+    simulation_names: Vec<String>,
+    pub(crate) locale: Arc<Localizer>,
+}
+
+impl Toasts {
+    pub fn new(simulation_names: Vec<String>, locale: Arc<Localizer>) -> Self {
+        Self {
+            duration: Duration::from_secs(3),
+            max_visible: 4,
+            active: VecDeque::new(),
+            simulation_names,
+            locale,
+        }
+    }
+
+    pub fn push(&mut self, message: String, duration: Option<Duration>, now: Instant) {
+        self.active.push_back(Toast {
+            message,
+            shown_at: now,
+            duration: duration.unwrap_or(self.duration),
+        });
+        while self.active.len() > self.max_visible {
+            self.active.pop_front();
+        }
+    }
+
+    /// Drops the toasts whose time is up.
+    pub fn expire(&mut self, now: Instant) {
+        self.active.retain(|toast| !toast.is_expired(now));
+    }
+
+    /// Toasts to draw, oldest first.
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.active.iter()
+    }
+}
+
+impl EventHandler for Toasts {
+    fn handle(&mut self, event: &AppEvent, events: &mut EventQueue) {
+        match event {
+            AppEvent::ToastRequested { message, duration } => {
+                self.push(message.clone(), *duration, Instant::now());
+            }
+            AppEvent::SimulationSwitched { index, .. } => {
+                if let Some(name) = self.simulation_names.get(*index) {
+                    events.push(AppEvent::ToastRequested {
+                        message: self
+                            .locale
+                            .format("toast-switched", &[("simulation", name.into())]),
+                        duration: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use glam::Vec2;
+
+use super::orbit_hud::{TimeUnit, quantity};
+use crate::localization::Localizer;
+use crate::rendering::reference::LengthUnit;
+use crate::simulation::Body;
+use crate::simulation::worker::SimulationFrame;
+
+/// What the UI shows next to the cursor while it hovers over a body: its
+/// name or index, distance from the origin and speed, in the scenario's
+/// display units and in the shown reference frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BodyTooltip {
+    pub index: usize,
+    pub name: Option<String>,
+    pub distance: f32,
+    pub speed: f32,
+    /// Cursor position the tooltip is drawn beside, in physical pixels.
+    pub anchor: Vec2,
+    pub length: LengthUnit,
+    pub time: TimeUnit,
+}
+
+impl BodyTooltip {
+    /// `bodies` are the bodies as shown, which may differ from
+    /// `frame.bodies` in a moving reference frame.
+    pub fn new(
+        frame: &SimulationFrame,
+        bodies: &[Body],
+        index: usize,
+        anchor: Vec2,
+    ) -> Option<Self> {
+        let body = bodies.get(index)?;
+        Some(Self {
+            index,
+            name: frame
+                .names
+                .iter()
+                .find(|(named, _)| *named == index)
+                .map(|(_, name)| name.clone()),
+            distance: body.position.length(),
+            speed: body.velocity.length(),
+            anchor,
+            length: LengthUnit::new(frame.units.as_ref(), frame.length_label),
+            time: TimeUnit::new(frame.units.as_ref(), frame.time_label),
+        })
+    }
+
+    /// The body's name and index, or just the index.
+    pub fn title(&self, locale: &Localizer) -> String {
+        match &self.name {
+            Some(name) => locale.format(
+                "tooltip-title-named",
+                &[("name", name.into()), ("index", self.index.into())],
+            ),
+            None => locale.format("tooltip-title", &[("index", self.index.into())]),
+        }
+    }
+
+    pub fn lines(&self, locale: &Localizer) -> Vec<String> {
+        let speed_label = format!("{}/{}", self.length.label, self.time.label);
+        vec![
+            self.title(locale),
+            format!(
+                "r  {}",
+                quantity(
+                    f64::from(self.distance) * self.length.per_unit,
+                    self.length.label
+                )
+            ),
+            format!(
+                "v  {}",
+                quantity(
+                    f64::from(self.speed) * self.length.per_unit / self.time.per_unit,
+                    &speed_label
+                )
+            ),
+        ]
+    }
+}
@@ -0,0 +1,251 @@
+//! Which monitor the window opens on and how big it is. The host lists the
+//! monitors (e.g. winit's `available_monitors`), and this module picks one —
+//! from `--monitor <n>`, else the one used last, else the primary — and sizes
+//! and centers the window on it, or makes it fullscreen there.
+
+use std::path::Path;
+
+use thiserror::Error;
+use tracing::warn;
+
+use crate::rendering::resize::{DEFAULT_WINDOW_SIZE, LogicalSize, SurfaceSize};
+
+/// Largest fraction of the monitor a windowed window takes, so its
+/// decorations stay on screen.
+const MAX_SCREEN_FRACTION: f64 = 0.9;
+
+#[derive(Debug, Error)]
+pub enum PlacementError {
+    #[error("--monitor needs a monitor number")]
+    MissingMonitor,
+    #[error("invalid monitor number `{0}`")]
+    InvalidMonitor(String),
+}
+
+/// One display as the window system reports it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// Stable across runs, unlike the index; used to recognize the monitor
+    /// the user last used.
+    pub name: String,
+    /// Top-left corner in the desktop's physical coordinates.
+    pub position: (i32, i32),
+    pub size: SurfaceSize,
+    pub scale_factor: f64,
+}
+
+/// Where the window ends up: position in physical desktop coordinates and
+/// size in logical pixels, or exclusive of everything when fullscreen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placement {
+    /// Index into the monitor list.
+    pub monitor: usize,
+    pub fullscreen: bool,
+    pub position: (i32, i32),
+    pub size: LogicalSize,
+}
+
+/// The user's placement choices, with the last-used monitor remembered
+/// between runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindowPlacement {
+    /// From `--monitor`, overriding the remembered one.
+    pub requested_monitor: Option<usize>,
+    /// Name of the monitor the window was last on.
+    pub last_monitor: Option<String>,
+    pub fullscreen: bool,
+}
+
+impl WindowPlacement {
+    /// Reads the remembered state; missing or unreadable files give defaults.
+    pub fn load(path: &Path) -> Self {
+        let mut placement = Self::default();
+        let Ok(text) = std::fs::read_to_string(path) else {
+            return placement;
+        };
+        for line in text.lines() {
+            match line.split_once('=') {
+                Some(("monitor", name)) if !name.is_empty() => {
+                    placement.last_monitor = Some(name.to_owned());
+                }
+                Some(("fullscreen", value)) => placement.fullscreen = value == "true",
+                _ => {}
+            }
+        }
+        placement
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let monitor = self.last_monitor.as_deref().unwrap_or_default();
+        std::fs::write(
+            path,
+            format!("monitor={monitor}\nfullscreen={}\n", self.fullscreen),
+        )
+    }
+
+    /// Records the monitor the window is on, e.g. after the user drags it
+    /// across or toggles fullscreen there.
+    pub fn remember(&mut self, monitor: &MonitorInfo) {
+        self.last_monitor = Some(monitor.name.clone());
+    }
+
+    /// Index of the monitor to use: the requested one if it exists, else the
+    /// remembered one if still connected, else `primary`. A requested monitor
+    /// that does not exist is logged.
+    pub fn choose_monitor(&self, monitors: &[MonitorInfo], primary: usize) -> usize {
+        if let Some(index) = self.requested_monitor {
+            if index < monitors.len() {
+                return index;
+            }
+            warn!(
+                monitor = index,
+                monitors = monitors.len(),
+                "requested monitor does not exist, using the remembered or primary one"
+            );
+        }
+        self.last_monitor
+            .as_deref()
+            .and_then(|name| monitors.iter().position(|monitor| monitor.name == name))
+            .unwrap_or(primary)
+    }
+
+    /// Placement on the chosen monitor, `None` without any monitors.
+    pub fn place(&self, monitors: &[MonitorInfo], primary: usize) -> Option<Placement> {
+        let index = self.choose_monitor(monitors, primary.min(monitors.len().checked_sub(1)?));
+        let monitor = &monitors[index];
+        if self.fullscreen {
+            return Some(Placement {
+                monitor: index,
+                fullscreen: true,
+                position: monitor.position,
+                size: monitor.size.to_logical(monitor.scale_factor),
+            });
+        }
+        let size = compute_screen_size(monitor);
+        let physical = size.to_physical(monitor.scale_factor);
+        let centered = |origin: i32, extent: u32, window: u32| {
+            origin + (extent.saturating_sub(window) / 2) as i32
+        };
+        Some(Placement {
+            monitor: index,
+            fullscreen: false,
+            position: (
+                centered(monitor.position.0, monitor.size.width, physical.width),
+                centered(monitor.position.1, monitor.size.height, physical.height),
+            ),
+            size,
+        })
+    }
+}
+
+/// Windowed size for a monitor: `DEFAULT_WINDOW_SIZE`, shrunk to fit
+/// smaller screens with the aspect ratio kept.
+pub fn compute_screen_size(monitor: &MonitorInfo) -> LogicalSize {
+    let screen = monitor.size.to_logical(monitor.scale_factor);
+    let fit = (MAX_SCREEN_FRACTION * screen.width / DEFAULT_WINDOW_SIZE.width)
+        .min(MAX_SCREEN_FRACTION * screen.height / DEFAULT_WINDOW_SIZE.height)
+        .min(1.0);
+    LogicalSize {
+        width: (DEFAULT_WINDOW_SIZE.width * fit).floor(),
+        height: (DEFAULT_WINDOW_SIZE.height * fit).floor(),
+    }
+}
+
+/// Finds `--monitor <n>` or `--monitor=<n>` among the command-line
+/// arguments; other arguments are left for the host.
+pub fn parse_monitor_arg(
+    args: impl IntoIterator<Item = String>,
+) -> Result<Option<usize>, PlacementError> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == "--monitor" {
+            args.next().ok_or(PlacementError::MissingMonitor)?
+        } else if let Some(value) = arg.strip_prefix("--monitor=") {
+            value.to_owned()
+        } else {
+            continue;
+        };
+        return value
+            .parse()
+            .map(Some)
+            .map_err(|_| PlacementError::InvalidMonitor(value));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| arg.to_owned()).collect()
+    }
+
+    fn monitor(name: &str) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_owned(),
+            position: (0, 0),
+            size: SurfaceSize {
+                width: 1920,
+                height: 1080,
+            },
+            scale_factor: 1.0,
+        }
+    }
+
+    #[test]
+    fn monitor_is_read_from_either_form() {
+        let parse = |list: &[&str]| parse_monitor_arg(args(list)).unwrap();
+        assert_eq!(parse(&["--fullscreen", "--monitor", "2"]), Some(2));
+        assert_eq!(parse(&["--monitor=1", "--other"]), Some(1));
+        assert_eq!(parse(&["--monitors", "3"]), None);
+        assert_eq!(parse(&[]), None);
+    }
+
+    #[test]
+    fn monitor_needs_a_number() {
+        assert!(matches!(
+            parse_monitor_arg(args(&["--monitor"])),
+            Err(PlacementError::MissingMonitor)
+        ));
+        assert!(matches!(
+            parse_monitor_arg(args(&["--monitor", "left"])),
+            Err(PlacementError::InvalidMonitor(value)) if value == "left"
+        ));
+        assert!(matches!(
+            parse_monitor_arg(args(&["--monitor=-1"])),
+            Err(PlacementError::InvalidMonitor(value)) if value == "-1"
+        ));
+    }
+
+    #[test]
+    fn requested_then_remembered_then_primary_monitor() {
+        let monitors = [monitor("A"), monitor("B"), monitor("C")];
+        let mut placement = WindowPlacement {
+            requested_monitor: Some(2),
+            last_monitor: Some("B".to_owned()),
+            fullscreen: false,
+        };
+        assert_eq!(placement.choose_monitor(&monitors, 0), 2);
+        placement.requested_monitor = Some(7);
+        assert_eq!(placement.choose_monitor(&monitors, 0), 1);
+        placement.last_monitor = Some("unplugged".to_owned());
+        assert_eq!(placement.choose_monitor(&monitors, 0), 0);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("window-placement-{}.txt", std::process::id()));
+        let placement = WindowPlacement {
+            requested_monitor: None,
+            last_monitor: Some("DP-1".to_owned()),
+            fullscreen: true,
+        };
+        placement.save(&path).unwrap();
+        let loaded = WindowPlacement::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, placement);
+        assert_eq!(WindowPlacement::load(&path), WindowPlacement::default());
+    }
+}
@@ -0,0 +1,173 @@
+//! Head-mounted viewing through OpenXR. The host owns the OpenXR session and
+//! swapchains and, each frame, hands over the located views and controller
+//! aim poses in stage space (metres, +Y up, −Z forward). This module maps the
+//! stage onto the simulation, builds the per-eye matrices for the body
+//! passes (replacing the desktop camera) and turns controller rays into
+//! selections and spawned bodies.
+
+use glam::{Mat4, Quat, Vec3, Vec4};
+
+use crate::camera::Camera;
+use crate::rendering::palette::ColorPalette;
+use crate::rendering::uniforms::{ColorMode, RenderUniforms};
+use crate::simulation::{Body, GroupMask};
+
+/// How far in front of the user the simulation is centered, in metres.
+const VIEW_DISTANCE: f32 = 1.0;
+/// Height of that center above the stage floor, in metres.
+const VIEW_HEIGHT: f32 = 1.2;
+/// Size the fitted system spans, in metres: about arm's reach.
+const VIEW_SPAN: f32 = 1.0;
+/// Bodies are easier to hit than their radius when tiny: the ray may miss by
+/// up to this angle.
+const PICK_TOLERANCE: f32 = 0.01;
+
+/// `XrPosef`: an orientation and position in stage space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pose {
+    pub orientation: Quat,
+    pub position: Vec3,
+}
+
+/// `XrFovf`: the four half-angles of an eye's (usually asymmetric) frustum,
+/// in radians; `angle_left` and `angle_down` are negative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fov {
+    pub angle_left: f32,
+    pub angle_right: f32,
+    pub angle_up: f32,
+    pub angle_down: f32,
+}
+
+impl Fov {
+    /// Right-handed projection with wgpu's 0..1 depth range.
+    pub fn projection(&self, znear: f32, zfar: f32) -> Mat4 {
+        let left = self.angle_left.tan();
+        let right = self.angle_right.tan();
+        let up = self.angle_up.tan();
+        let down = self.angle_down.tan();
+        let width = right - left;
+        let height = up - down;
+        let depth = zfar / (znear - zfar);
+        Mat4::from_cols(
+            Vec4::new(2.0 / width, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 2.0 / height, 0.0, 0.0),
+            Vec4::new((right + left) / width, (up + down) / height, depth, -1.0),
+            Vec4::new(0.0, 0.0, znear * depth, 0.0),
+        )
+    }
+}
+
+/// `XrView`: one eye as located by the runtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EyeView {
+    pub pose: Pose,
+    pub fov: Fov,
+}
+
+/// Places the simulation in the room: `center` sits `VIEW_DISTANCE` in front
+/// of the stage origin at `VIEW_HEIGHT`, and one simulation unit spans
+/// `metres_per_unit`. Rotation is left to the user walking around.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StageMapping {
+    pub center: Vec3,
+    pub metres_per_unit: f32,
+}
+
+impl StageMapping {
+    /// Centered on the desktop camera's target, scaled so its view distance
+    /// spans `VIEW_SPAN`, so entering VR keeps what was on screen in front.
+    pub fn from_camera(camera: &Camera) -> Self {
+        Self {
+            center: camera.target,
+            metres_per_unit: VIEW_SPAN / camera.distance.max(f32::EPSILON),
+        }
+    }
+
+    fn anchor() -> Vec3 {
+        Vec3::new(0.0, VIEW_HEIGHT, -VIEW_DISTANCE)
+    }
+
+    /// Simulation space to stage space.
+    pub fn to_stage(&self) -> Mat4 {
+        Mat4::from_translation(Self::anchor())
+            * Mat4::from_scale(Vec3::splat(self.metres_per_unit))
+            * Mat4::from_translation(-self.center)
+    }
+
+    pub fn stage_to_simulation(&self, point: Vec3) -> Vec3 {
+        (point - Self::anchor()) / self.metres_per_unit + self.center
+    }
+
+    /// Zooms about the point in front of the user, e.g. from a thumbstick.
+    pub fn zoom(&mut self, factor: f32) {
+        if factor.is_finite() && factor > 0.0 {
+            self.metres_per_unit *= factor;
+        }
+    }
+}
+
+/// Body-pass uniforms for one eye. `znear`/`zfar` are in metres.
+pub fn eye_uniforms(
+    eye: &EyeView,
+    mapping: &StageMapping,
+    (znear, zfar): (f32, f32),
+    visible_groups: GroupMask,
+    color_mode: ColorMode,
+    palette: ColorPalette,
+    viewport_size: glam::Vec2,
+) -> RenderUniforms {
+    let eye_from_stage =
+        Mat4::from_rotation_translation(eye.pose.orientation, eye.pose.position).inverse();
+    RenderUniforms::from_matrices(
+        eye_from_stage * mapping.to_stage(),
+        eye.fov.projection(znear, zfar),
+        visible_groups,
+        color_mode,
+        palette,
+        viewport_size,
+    )
+}
+
+/// A controller's pointing ray in simulation space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ControllerRay {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl ControllerRay {
+    /// From an aim pose, which points along its local −Z.
+    pub fn from_aim(aim: &Pose, mapping: &StageMapping) -> Self {
+        let tip = aim.position + aim.orientation * Vec3::NEG_Z;
+        let origin = mapping.stage_to_simulation(aim.position);
+        Self {
+            origin,
+            direction: (mapping.stage_to_simulation(tip) - origin).normalize(),
+        }
+    }
+
+    /// Nearest body the ray passes through, counting a small angular
+    /// tolerance around each so distant specks can still be selected.
+    pub fn pick(&self, bodies: &[Body]) -> Option<usize> {
+        bodies
+            .iter()
+            .enumerate()
+            .filter(|(_, body)| body.position.is_finite())
+            .filter_map(|(index, body)| {
+                let offset = body.position - self.origin;
+                let along = offset.dot(self.direction);
+                let miss = (offset - self.direction * along).length();
+                let reach = body.radius + PICK_TOLERANCE * along;
+                (along > 0.0 && miss <= reach).then_some((index, along))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// Point `distance` simulation units along the ray, where a trigger
+    /// press places a new body (see `tools::spawn`).
+    pub fn point_at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+}